@@ -0,0 +1,224 @@
+//! Generates `src/gl/gl_signatures.rs` from the source of `gl46`.
+//!
+//! Run it again after updating `gl46`:
+//!
+//! ```txt
+//! cargo run --example gl_signatures > src/gl/gl_signatures.rs
+//! ```
+//!
+//! The `gl46` source is found with `cargo metadata`, or you can pass the path
+//! of its `src/` folder as the argument.
+
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+fn main() -> Result<(), String> {
+  let src = match std::env::args().nth(1) {
+    Some(path) => PathBuf::from(path),
+    None => find_gl46_src()?,
+  };
+  let read = |name: &str| {
+    let path = src.join(name);
+    std::fs::read_to_string(&path)
+      .map_err(|e| format!("Couldn't read `{}`: {}", path.display(), e))
+  };
+  let (core, types) = (read("gl_core_types.rs")?, read("gl_command_types.rs")?);
+  let loader = read("struct_loader.rs")?;
+  let aliases = private_aliases(&core);
+  let signatures = signatures(&types);
+
+  println!("//! Every GL function that `gl46` loads, with its signature.");
+  println!("//!");
+  println!(
+    "//! Generated by `examples/gl_signatures.rs` from the `gl46` source,"
+  );
+  println!("//! so don't edit it by hand. The `gl46` type aliases that aren't");
+  println!("//! public are written out as the types they stand for.");
+  println!();
+  println!(
+    "/// Calls the macro `$m` with every GL function that `gl46` loads, as"
+  );
+  println!("/// `fn Name(arg: Type, ...) -> Ret;` items.");
+  println!("///");
+  println!(
+    "/// The names are the `GlFns` method names (without the `gl` prefix)."
+  );
+  println!("macro_rules! gl_signatures {{");
+  println!("  ($m:ident) => {{");
+  println!("    $m! {{");
+  for name in loaded_names(&loader) {
+    let (params, ret) = signatures
+      .get(name)
+      .ok_or_else(|| format!("No signature for `gl{}`.", name))?;
+    let params: Vec<String> = params
+      .iter()
+      .map(|(param, ty)| {
+        format!("{}: {}", snake_case(param), resolve_type(ty, &aliases))
+      })
+      .collect();
+    let ret = match ret {
+      Some(ret) => format!(" -> {}", resolve_type(ret, &aliases)),
+      None => String::new(),
+    };
+    for line in wrap_fn(name, &params, &ret) {
+      println!("      {}", line);
+    }
+  }
+  println!("    }}");
+  println!("  }};");
+  println!("}}");
+  Ok(())
+}
+
+/// The `src/` folder of the `gl46` that this crate uses.
+fn find_gl46_src() -> Result<PathBuf, String> {
+  let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+  let output = Command::new(cargo)
+    .args(["metadata", "--format-version", "1"])
+    .output()
+    .map_err(|e| format!("Couldn't run `cargo metadata`: {}", e))?;
+  let json = String::from_utf8_lossy(&output.stdout);
+  // just enough of the JSON to find the package's manifest.
+  let package = json
+    .find("\"name\":\"gl46\"")
+    .ok_or("`cargo metadata` doesn't list gl46, pass its path instead.")?;
+  let key = "\"manifest_path\":\"";
+  let start = json[package..]
+    .find(key)
+    .map(|i| package + i + key.len())
+    .ok_or("`cargo metadata` has no gl46 manifest, pass its path instead.")?;
+  let end = start + json[start..].find('"').unwrap_or(0);
+  let manifest = json[start..end].replace("\\\\", "\\");
+  Ok(Path::new(&manifest).with_file_name("src"))
+}
+
+/// The names (without the `gl` prefix) in the order that the loader loads
+/// them, which is the required functions and then the nullable ones.
+fn loaded_names(loader: &str) -> Vec<&str> {
+  loader
+    .lines()
+    .filter_map(|line| line.trim().strip_prefix("let gl"))
+    .filter_map(|rest| rest.find("_p = transmute").map(|end| &rest[..end]))
+    .collect()
+}
+
+/// The `(name, type)` of each param, and the return type.
+type Signature<'a> = (Vec<(&'a str, &'a str)>, Option<&'a str>);
+
+/// Each `glName_t` type, by `Name`.
+fn signatures(types: &str) -> HashMap<&str, Signature<'_>> {
+  let mut out = HashMap::new();
+  for line in types.lines() {
+    let rest = match line.strip_prefix("pub(crate) type ") {
+      Some(rest) => rest,
+      None => continue,
+    };
+    let name = &rest["gl".len()..rest.find("_t = ").unwrap()];
+    let open = rest.find("fn(").unwrap() + "fn(".len();
+    let close = open + rest[open..].find(')').unwrap();
+    let params = rest[open..close]
+      .split(", ")
+      .filter(|p| !p.is_empty())
+      .map(|p| {
+        let colon = p.find(": ").unwrap();
+        (&p[..colon], &p[colon + 2..])
+      })
+      .collect();
+    let ret =
+      rest[close..].strip_prefix(") -> ").map(|r| r.trim_end_matches(';'));
+    out.insert(name, (params, ret));
+  }
+  out
+}
+
+/// The `pub(crate)` type aliases of `gl46`, fully resolved.
+fn private_aliases(core: &str) -> HashMap<String, String> {
+  let mut out: HashMap<String, String> = core
+    .lines()
+    .filter_map(|line| line.strip_prefix("pub(crate) type "))
+    .filter_map(|rest| {
+      let eq = rest.find(" = ")?;
+      Some((rest[..eq].to_string(), rest[eq + 3..].trim_end_matches(';')))
+    })
+    .map(|(name, ty)| {
+      let ty = match ty {
+        "c_uchar" => "u8",
+        "c_ushort" => "u16",
+        "c_int" => "i32",
+        "c_uint" => "u32",
+        "c_float" => "f32",
+        "c_double" => "f64",
+        "core::ffi::c_void" => "c_void",
+        ty => ty,
+      };
+      (name, ty.to_string())
+    })
+    .collect();
+  let names: Vec<String> = out.keys().cloned().collect();
+  for name in names {
+    let mut ty = out[&name].clone();
+    while let Some(next) = out.get(&ty) {
+      ty = next.clone();
+    }
+    out.insert(name, ty);
+  }
+  out
+}
+
+/// Replaces each private alias within a type.
+fn resolve_type(ty: &str, aliases: &HashMap<String, String>) -> String {
+  let mut out = String::new();
+  let mut word = String::new();
+  for c in ty.chars().chain(core::iter::once(' ')) {
+    if c.is_alphanumeric() || c == '_' {
+      word.push(c);
+    } else {
+      out.push_str(aliases.get(&word).unwrap_or(&word));
+      word.clear();
+      out.push(c);
+    }
+  }
+  out.pop();
+  out
+}
+
+/// `camelCase` to `snake_case`, so that the names pass the style lints.
+fn snake_case(name: &str) -> String {
+  let mut out = String::new();
+  let mut prev_lower = false;
+  for c in name.chars() {
+    if c.is_ascii_uppercase() && prev_lower {
+      out.push('_');
+    }
+    prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+    out.push(c.to_ascii_lowercase());
+  }
+  out
+}
+
+/// Lays out one item in 80 columns, like rustfmt would (at this indent).
+fn wrap_fn(name: &str, params: &[String], ret: &str) -> Vec<String> {
+  let one_line = format!("fn {}({}){};", name, params.join(", "), ret);
+  if one_line.len() + 6 <= 80 {
+    return vec![one_line];
+  }
+  let mut lines = vec![format!("fn {}(", name)];
+  let mut row = String::new();
+  for param in params {
+    if !row.is_empty() && 8 + row.len() + 1 + param.len() + 1 > 80 {
+      lines.push(format!("  {}", row));
+      row.clear();
+    }
+    if !row.is_empty() {
+      row.push(' ');
+    }
+    row.push_str(param);
+    row.push(',');
+  }
+  lines.push(format!("  {}", row));
+  lines.push(format!("){};", ret));
+  lines
+}
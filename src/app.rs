@@ -0,0 +1,281 @@
+use super::*;
+use std::{fs::File, io::BufWriter, path::PathBuf, time::Instant};
+
+/// Settings for [`run`].
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+  /// The window title.
+  pub title: String,
+  /// The initial width of the window.
+  pub width: i32,
+  /// The initial height of the window.
+  pub height: i32,
+  /// The swap interval to request (1 is normal vsync, 0 is no vsync).
+  ///
+  /// If the backend can't set the swap interval the request is ignored.
+  pub swap_interval: i32,
+  /// If set, the app stops after rendering this many frames.
+  pub frame_limit: Option<u64>,
+  /// If set, GL calls are traced into this file (see [`GlTracer`]).
+  pub trace_path: Option<PathBuf>,
+}
+impl Default for AppConfig {
+  fn default() -> Self {
+    Self {
+      title: String::from("SuperBible7"),
+      width: 800,
+      height: 600,
+      swap_interval: 1,
+      frame_limit: None,
+      trace_path: None,
+    }
+  }
+}
+
+/// A sample application, in the style of the book's `sb7::application`.
+///
+/// The [runner](run) owns the window and the GL context, and calls these
+/// methods as needed. In debug builds the runner also turns on GL debug
+/// output (using [`println_debug_message_callback`]) before `startup`.
+pub trait App: Sized {
+  /// Does any one-time GL setup, and makes the app's state.
+  ///
+  /// The viewport is already set to the full window when this is called.
+  fn startup(gl: &GlFnsRusty) -> Self;
+
+  /// Draws one frame.
+  ///
+  /// * `current_time`: seconds since the app started.
+  fn render(&mut self, gl: &GlFnsRusty, current_time: f64);
+
+  /// Cleans up any GL objects before the context is destroyed.
+  fn shutdown(&mut self, _gl: &GlFnsRusty) {}
+
+  /// Called after the window is resized.
+  ///
+  /// The viewport has already been updated to the new size.
+  fn on_resize(&mut self, _gl: &GlFnsRusty, _width: i32, _height: i32) {}
+
+  /// Called when a key is pressed (`pressed` is `true`) or released.
+  fn on_key(&mut self, _gl: &GlFnsRusty, _key: Key, _pressed: bool) {}
+}
+
+/// An object-safe version of [`App`], so that different apps can be kept
+/// together (eg: in a list of samples).
+///
+/// Use [`start_boxed_app`] to start any `App` as a `Box<dyn DynApp>`.
+pub trait DynApp {
+  /// As [`App::render`]
+  fn render(&mut self, gl: &GlFnsRusty, current_time: f64);
+  /// As [`App::shutdown`]
+  fn shutdown(&mut self, gl: &GlFnsRusty);
+  /// As [`App::on_resize`]
+  fn on_resize(&mut self, gl: &GlFnsRusty, width: i32, height: i32);
+  /// As [`App::on_key`]
+  fn on_key(&mut self, gl: &GlFnsRusty, key: Key, pressed: bool);
+}
+
+// Note: This is a wrapper rather than a blanket impl so that the methods of
+// `App` and `DynApp` are never ambiguous with each other.
+struct BoxedApp<A>(A);
+impl<A: App> DynApp for BoxedApp<A> {
+  fn render(&mut self, gl: &GlFnsRusty, current_time: f64) {
+    self.0.render(gl, current_time)
+  }
+  fn shutdown(&mut self, gl: &GlFnsRusty) {
+    self.0.shutdown(gl)
+  }
+  fn on_resize(&mut self, gl: &GlFnsRusty, width: i32, height: i32) {
+    self.0.on_resize(gl, width, height)
+  }
+  fn on_key(&mut self, gl: &GlFnsRusty, key: Key, pressed: bool) {
+    self.0.on_key(gl, key, pressed)
+  }
+}
+
+/// Starts an [`App`], boxed up as a [`DynApp`].
+pub fn start_boxed_app<A: App + 'static>(gl: &GlFnsRusty) -> Box<dyn DynApp> {
+  Box::new(BoxedApp(A::startup(gl)))
+}
+
+/// Runs an [`App`] until its window is closed.
+///
+/// * On Windows this opens a `WglWindow`.
+/// * On Linux there's no window backend yet, so this renders off-screen with a
+///   [`HeadlessContext`]. Nothing will ever close a headless context, so it
+///   stops after `config.frame_limit` frames (or after 1 frame if there's no
+///   limit set).
+///
+/// ## Failure
+/// * If the context can't be made, or GL can't be loaded, or a buffer swap
+///   fails, or the trace can't be written, you get a description of the
+///   problem.
+pub fn run<A: App>(config: AppConfig) -> Result<(), String> {
+  run_with(config, A::startup)
+}
+
+/// As [`run`], but the app is made by the `startup` function given rather
+/// than by [`App::startup`].
+///
+/// This lets you pass extra arguments along to the app.
+pub fn run_with<A: App>(
+  config: AppConfig, startup: impl FnOnce(&GlFnsRusty) -> A,
+) -> Result<(), String> {
+  #[cfg(windows)]
+  {
+    let window = WglWindow::new(&config.title, config.width, config.height)
+      .map_err(|e| e.to_string())?;
+    run_with_context(&window, &config, startup, || window.poll_events())
+  }
+  #[cfg(target_os = "linux")]
+  {
+    let ctx = HeadlessContext::new(config.width, config.height)
+      .map_err(|e| e.to_string())?;
+    let config = AppConfig {
+      frame_limit: Some(config.frame_limit.unwrap_or(1)),
+      ..config
+    };
+    run_with_context(&ctx, &config, startup, Vec::new)
+  }
+  #[cfg(not(any(windows, target_os = "linux")))]
+  {
+    let _ = (config, startup);
+    Err(String::from("There's no GL context backend for this platform."))
+  }
+}
+
+/// Runs an [`App`] with a context that you've already made.
+///
+/// * `ctx`: The context to draw with. It must be current in this thread.
+/// * `config`: Only the `swap_interval`, `frame_limit`, and `trace_path` are
+///   used.
+/// * `startup`: Makes the app, see [`run_with`].
+/// * `poll_events`: Gets the window events since the last frame.
+///
+/// This is what [`run_with`] uses once it has a context. The app stops when a
+/// [`WindowEvent::CloseRequested`] arrives, or when the frame limit is reached.
+pub fn run_with_context<A: App, C: GlContext>(
+  ctx: &C, config: &AppConfig, startup: impl FnOnce(&GlFnsRusty) -> A,
+  mut poll_events: impl FnMut() -> Vec<WindowEvent>,
+) -> Result<(), String> {
+  let (tracer, gl) = match &config.trace_path {
+    Some(path) => {
+      let file = File::create(path)
+        .map_err(|e| format!("Couldn't create `{}`: {}", path.display(), e))?;
+      let (tracer, gl) = GlTracer::load(ctx, BufWriter::new(file))?;
+      (Some(tracer), gl)
+    }
+    None => (None, ctx.load_gl_functions()?),
+  };
+  let _i_dont_care = ctx.set_swap_interval(config.swap_interval);
+
+  #[cfg(debug_assertions)]
+  {
+    unsafe {
+      gl.Enable(GL_DEBUG_OUTPUT);
+      gl.Enable(GL_DEBUG_OUTPUT_SYNCHRONOUS);
+      gl.DebugMessageCallback(
+        Some(println_debug_message_callback),
+        core::ptr::null_mut(),
+      )
+    };
+  }
+
+  let (width, height) = ctx.size();
+  gl.viewport(0, 0, width, height);
+
+  let mut app = startup(&gl);
+  let start = Instant::now();
+  let mut frames_rendered = 0_u64;
+  'running: loop {
+    for event in poll_events() {
+      match event {
+        WindowEvent::Resized(width, height) => {
+          gl.viewport(0, 0, width, height);
+          app.on_resize(&gl, width, height);
+        }
+        WindowEvent::KeyDown(key) => app.on_key(&gl, key, true),
+        WindowEvent::KeyUp(key) => app.on_key(&gl, key, false),
+        WindowEvent::CloseRequested => break 'running,
+      }
+    }
+    app.render(&gl, start.elapsed().as_secs_f64());
+    ctx.swap_buffers().map_err(|e| e.to_string())?;
+    if let Some(tracer) = &tracer {
+      tracer.end_frame()?;
+    }
+    frames_rendered += 1;
+    if config.frame_limit.is_some_and(|limit| frames_rendered >= limit) {
+      break 'running;
+    }
+  }
+  app.shutdown(&gl);
+  match tracer {
+    Some(tracer) => tracer.finish(),
+    None => Ok(()),
+  }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_run_with_context_dispatches_events() {
+  use std::cell::RefCell;
+  thread_local! {
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+  }
+  fn log(s: String) {
+    LOG.with(|log| log.borrow_mut().push(s));
+  }
+  struct Logger;
+  impl App for Logger {
+    fn startup(_gl: &GlFnsRusty) -> Self {
+      log(String::from("startup"));
+      Logger
+    }
+    fn render(&mut self, _gl: &GlFnsRusty, _current_time: f64) {
+      log(String::from("render"));
+    }
+    fn shutdown(&mut self, _gl: &GlFnsRusty) {
+      log(String::from("shutdown"));
+    }
+    fn on_resize(&mut self, _gl: &GlFnsRusty, width: i32, height: i32) {
+      log(format!("resize {}x{}", width, height));
+    }
+    fn on_key(&mut self, _gl: &GlFnsRusty, key: Key, pressed: bool) {
+      log(format!("key {:?} {}", key, pressed));
+    }
+  }
+
+  let ctx = match HeadlessContext::new(32, 32) {
+    Ok(ctx) => ctx,
+    Err(e) => {
+      println!("skipping, no headless EGL available: {}", e);
+      return;
+    }
+  };
+  let mut frames = vec![
+    vec![WindowEvent::Resized(16, 8), WindowEvent::KeyDown(Key::Space)],
+    vec![WindowEvent::KeyUp(Key::Space)],
+    vec![WindowEvent::CloseRequested],
+  ]
+  .into_iter();
+  let config = AppConfig { frame_limit: Some(10), ..AppConfig::default() };
+  run_with_context(&ctx, &config, Logger::startup, || {
+    frames.next().unwrap_or_default()
+  })
+  .unwrap();
+  LOG.with(|log| {
+    assert_eq!(
+      *log.borrow(),
+      vec![
+        "startup",
+        "resize 16x8",
+        "key Space true",
+        "render",
+        "key Space false",
+        "render",
+        "shutdown",
+      ]
+    )
+  });
+}
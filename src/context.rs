@@ -0,0 +1,92 @@
+use super::*;
+use core::ptr::{null, NonNull};
+
+/// An OpenGL context along with the surface that it draws to.
+///
+/// Each platform backend (a WGL window, a headless EGL context, etc) implements
+/// this trait, so that sample code can be written once against any
+/// `GlContext` and then run with whatever backend is available.
+pub trait GlContext {
+  /// The error type of the backend.
+  type Error: core::fmt::Debug + core::fmt::Display;
+
+  /// Makes this context current in this thread.
+  ///
+  /// A context is already current in the thread that made it, so you only
+  /// need this if you've switched to another context since then.
+  fn make_current(&self) -> Result<(), Self::Error>;
+
+  /// Posts the back buffer of the surface.
+  fn swap_buffers(&self) -> Result<(), Self::Error>;
+
+  /// Looks up a GL function.
+  ///
+  /// * `name` should be a byte slice with the desired function's name,
+  ///   *including* the terminating `0`. Use the [`c_str!`] macro for
+  ///   assistance.
+  ///
+  /// ## Failure
+  /// * If the slice doesn't end with `0`, or the function isn't available, you
+  ///   get `None`.
+  fn get_proc_address(&self, name: &[u8]) -> Option<NonNull<c_void>>;
+
+  /// The current `(width, height)` of the surface, in pixels.
+  fn size(&self) -> (i32, i32);
+
+  /// Sets the minimum number of video frame periods per buffer swap.
+  ///
+  /// * 0 means that swaps are not synchronized to the display at all.
+  /// * Negative values request adaptive vsync, when the backend supports it.
+  fn set_swap_interval(&self, interval: i32) -> Result<(), Self::Error>;
+
+  /// Loads all GL functions for this context.
+  ///
+  /// ## Failure
+  /// * The name of the first GL function that couldn't be loaded.
+  fn load_gl_functions(&self) -> Result<GlFnsRusty, &'static str> {
+    unsafe {
+      GlFns::load_from(&|name_ptr| {
+        let name = std::ffi::CStr::from_ptr(name_ptr.cast());
+        self
+          .get_proc_address(name.to_bytes_with_nul())
+          .map(|nn| nn.as_ptr() as *const c_void)
+          .unwrap_or(null())
+      })
+    }
+    .map(GlFnsRusty)
+  }
+}
+
+/// Something that happened to a window since events were last polled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowEvent {
+  /// The client area is now this `(width, height)`.
+  Resized(i32, i32),
+  /// The user asked for the window to close (eg: they clicked the X).
+  CloseRequested,
+  /// A key was pressed. Holding a key down sends this repeatedly.
+  KeyDown(Key),
+  /// A key was released.
+  KeyUp(Key),
+}
+
+/// A keyboard key, independent of the windowing backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+  /// A letter key (always uppercase) or a digit key.
+  Char(char),
+  /// A function key, `F(1)` through `F(24)`.
+  F(u8),
+  Escape,
+  Space,
+  Enter,
+  Tab,
+  Left,
+  Right,
+  Up,
+  Down,
+  PageUp,
+  PageDown,
+  /// Any other key, holding the backend's own key code.
+  Other(u32),
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+// Note: we don't need a `#[link]` attribute here, the standard library
+// already links in whatever the target needs for the dynamic loader.
+extern "C" {
+  /// [dlopen](https://man7.org/linux/man-pages/man3/dlopen.3.html)
+  fn dlopen(filename: *const u8, flags: c_int) -> *mut c_void;
+
+  /// [dlsym](https://man7.org/linux/man-pages/man3/dlsym.3.html)
+  fn dlsym(handle: *mut c_void, symbol: *const u8) -> *mut c_void;
+
+  /// [dlclose](https://man7.org/linux/man-pages/man3/dlclose.3.html)
+  fn dlclose(handle: *mut c_void) -> c_int;
+}
+
+const RTLD_NOW: c_int = 0x00002;
+const RTLD_LOCAL: c_int = 0;
+
+/// Loads a shared library.
+///
+/// * `name` should be a byte slice with the library's name, *including* the
+///   terminating `0`. Use the [`c_str!`] macro for assistance.
+///
+/// ## Failure
+/// * If the slice doesn't end with `0`, or the library can't be found, you get
+///   `None`.
+pub fn load_library(name: &[u8]) -> Option<LibHandle> {
+  match name.last() {
+    Some(0) => {
+      let p = unsafe { dlopen(name.as_ptr().cast(), RTLD_NOW | RTLD_LOCAL) };
+      if p.is_null() {
+        None
+      } else {
+        Some(LibHandle(p))
+      }
+    }
+    _ => None,
+  }
+}
+
+/// Closes a shared library opened with [`load_library`].
+///
+/// ## Safety
+/// * Any function pointers obtained from the library become dangling.
+pub unsafe fn free_library(lib: LibHandle) {
+  dlclose(lib.0);
+}
+
+/// Looks up a symbol's address within a shared library.
+///
+/// * `name` should be a byte slice with the symbol's name, *including* the
+///   terminating `0`.
+///
+/// ## Failure
+/// * If the slice doesn't end with `0`, or the symbol isn't found, you get
+///   `None`.
+pub fn get_proc_address(
+  lib: LibHandle, name: &[u8],
+) -> Option<NonNull<c_void>> {
+  match name.last() {
+    Some(0) => NonNull::new(unsafe { dlsym(lib.0, name.as_ptr().cast()) }),
+    _ => None,
+  }
+}
@@ -0,0 +1,433 @@
+use super::*;
+
+type eglGetProcAddress_t =
+  unsafe extern "C" fn(procname: *const u8) -> *mut c_void;
+type eglGetError_t = unsafe extern "C" fn() -> EGLint;
+type eglQueryString_t =
+  unsafe extern "C" fn(dpy: EGLDisplay, name: EGLint) -> *const u8;
+type eglGetDisplay_t =
+  unsafe extern "C" fn(display_id: EGLNativeDisplayType) -> EGLDisplay;
+type eglGetPlatformDisplay_t = unsafe extern "C" fn(
+  platform: EGLenum,
+  native_display: *mut c_void,
+  attrib_list: *const EGLAttrib,
+) -> EGLDisplay;
+type eglInitialize_t = unsafe extern "C" fn(
+  dpy: EGLDisplay,
+  major: *mut EGLint,
+  minor: *mut EGLint,
+) -> EGLBoolean;
+type eglTerminate_t = unsafe extern "C" fn(dpy: EGLDisplay) -> EGLBoolean;
+type eglBindAPI_t = unsafe extern "C" fn(api: EGLenum) -> EGLBoolean;
+type eglChooseConfig_t = unsafe extern "C" fn(
+  dpy: EGLDisplay,
+  attrib_list: *const EGLint,
+  configs: *mut EGLConfig,
+  config_size: EGLint,
+  num_config: *mut EGLint,
+) -> EGLBoolean;
+type eglCreateContext_t = unsafe extern "C" fn(
+  dpy: EGLDisplay,
+  config: EGLConfig,
+  share_context: EGLContext,
+  attrib_list: *const EGLint,
+) -> EGLContext;
+type eglDestroyContext_t =
+  unsafe extern "C" fn(dpy: EGLDisplay, ctx: EGLContext) -> EGLBoolean;
+type eglCreatePbufferSurface_t = unsafe extern "C" fn(
+  dpy: EGLDisplay,
+  config: EGLConfig,
+  attrib_list: *const EGLint,
+) -> EGLSurface;
+type eglDestroySurface_t =
+  unsafe extern "C" fn(dpy: EGLDisplay, surface: EGLSurface) -> EGLBoolean;
+type eglMakeCurrent_t = unsafe extern "C" fn(
+  dpy: EGLDisplay,
+  draw: EGLSurface,
+  read: EGLSurface,
+  ctx: EGLContext,
+) -> EGLBoolean;
+type eglSwapBuffers_t =
+  unsafe extern "C" fn(dpy: EGLDisplay, surface: EGLSurface) -> EGLBoolean;
+type eglSwapInterval_t =
+  unsafe extern "C" fn(dpy: EGLDisplay, interval: EGLint) -> EGLBoolean;
+
+/// This holds the function pointers of the system's EGL library.
+///
+/// EGL is loaded at runtime with `dlopen` rather than linked, so that the
+/// crate still builds (and non-GL tests still run) on a machine without any
+/// EGL installed.
+///
+/// The library is never closed, because any GL function pointers that were
+/// looked up through it would dangle.
+pub struct EglFns {
+  eglGetProcAddress_p: eglGetProcAddress_t,
+  eglGetError_p: eglGetError_t,
+  eglQueryString_p: eglQueryString_t,
+  eglGetDisplay_p: eglGetDisplay_t,
+  // EGL 1.5, or EGL_EXT_platform_base
+  eglGetPlatformDisplay_p: Option<eglGetPlatformDisplay_t>,
+  eglInitialize_p: eglInitialize_t,
+  eglTerminate_p: eglTerminate_t,
+  eglBindAPI_p: eglBindAPI_t,
+  eglChooseConfig_p: eglChooseConfig_t,
+  eglCreateContext_p: eglCreateContext_t,
+  eglDestroyContext_p: eglDestroyContext_t,
+  eglCreatePbufferSurface_p: eglCreatePbufferSurface_t,
+  eglDestroySurface_p: eglDestroySurface_t,
+  eglMakeCurrent_p: eglMakeCurrent_t,
+  eglSwapBuffers_p: eglSwapBuffers_t,
+  eglSwapInterval_p: eglSwapInterval_t,
+}
+
+// constructor
+
+impl EglFns {
+  /// Loads `libEGL.so.1` and looks up all the functions.
+  ///
+  /// ## Failure
+  /// * If the library can't be loaded, or any required function is missing,
+  ///   you get an Application error.
+  pub fn new() -> EglResult<Self> {
+    use core::mem::transmute;
+    let lib = load_library(c_str!("libEGL.so.1"))
+      .or_else(|| load_library(c_str!("libEGL.so")))
+      .ok_or(EglError::APP)?;
+    macro_rules! required {
+      ($t:ty, $name:literal) => {
+        unsafe {
+          transmute::<NonNull<c_void>, $t>(
+            get_proc_address(lib, c_str!($name)).ok_or(EglError::APP)?,
+          )
+        }
+      };
+    }
+    let eglGetProcAddress_p =
+      required!(eglGetProcAddress_t, "eglGetProcAddress");
+    let eglGetPlatformDisplay_p = unsafe {
+      transmute::<Option<NonNull<c_void>>, Option<eglGetPlatformDisplay_t>>(
+        get_proc_address(lib, c_str!("eglGetPlatformDisplay")).or_else(|| {
+          NonNull::new(eglGetProcAddress_p(
+            c_str!("eglGetPlatformDisplayEXT").as_ptr(),
+          ))
+        }),
+      )
+    };
+    Ok(Self {
+      eglGetProcAddress_p,
+      eglGetError_p: required!(eglGetError_t, "eglGetError"),
+      eglQueryString_p: required!(eglQueryString_t, "eglQueryString"),
+      eglGetDisplay_p: required!(eglGetDisplay_t, "eglGetDisplay"),
+      eglGetPlatformDisplay_p,
+      eglInitialize_p: required!(eglInitialize_t, "eglInitialize"),
+      eglTerminate_p: required!(eglTerminate_t, "eglTerminate"),
+      eglBindAPI_p: required!(eglBindAPI_t, "eglBindAPI"),
+      eglChooseConfig_p: required!(eglChooseConfig_t, "eglChooseConfig"),
+      eglCreateContext_p: required!(eglCreateContext_t, "eglCreateContext"),
+      eglDestroyContext_p: required!(eglDestroyContext_t, "eglDestroyContext"),
+      eglCreatePbufferSurface_p: required!(
+        eglCreatePbufferSurface_t,
+        "eglCreatePbufferSurface"
+      ),
+      eglDestroySurface_p: required!(eglDestroySurface_t, "eglDestroySurface"),
+      eglMakeCurrent_p: required!(eglMakeCurrent_t, "eglMakeCurrent"),
+      eglSwapBuffers_p: required!(eglSwapBuffers_t, "eglSwapBuffers"),
+      eglSwapInterval_p: required!(eglSwapInterval_t, "eglSwapInterval"),
+    })
+  }
+}
+
+/// Checks that an attribute list is either empty or ends with `EGL_NONE`.
+fn attrib_list_ptr<T: PartialEq>(
+  list: &[[T; 2]], none: T,
+) -> EglResult<*const T> {
+  match list.last() {
+    Some([k, _v]) if *k == none => Ok(list.as_ptr().cast()),
+    Some(_) => Err(EglError::APP),
+    None => Ok(null()),
+  }
+}
+
+impl EglFns {
+  /// Gets the thread-local error code of the last EGL call.
+  ///
+  /// See [eglGetError](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglGetError.xhtml)
+  pub fn get_error(&self) -> EglError {
+    EglError(unsafe { (self.eglGetError_p)() })
+  }
+
+  /// Looks up a client API function (eg: a GL function).
+  ///
+  /// * `name` should be a byte slice with the desired function's name,
+  ///   *including* the terminating `0`. Use the [`c_str!`] macro for
+  ///   assistance.
+  ///
+  /// See [eglGetProcAddress](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglGetProcAddress.xhtml)
+  pub fn get_proc_address(&self, name: &[u8]) -> Option<NonNull<c_void>> {
+    match name.last() {
+      Some(0) => unsafe { self.get_proc_address_raw(name.as_ptr()) },
+      _ => None,
+    }
+  }
+
+  /// As [`get_proc_address`](Self::get_proc_address), but with a raw pointer
+  /// for use with [`GlFns::load_from`].
+  ///
+  /// ## Safety
+  /// * `name_ptr` must point to a null-terminated string.
+  pub(crate) unsafe fn get_proc_address_raw(
+    &self, name_ptr: *const u8,
+  ) -> Option<NonNull<c_void>> {
+    NonNull::new((self.eglGetProcAddress_p)(name_ptr.cast()))
+  }
+
+  /// Queries a string about the display (or the client library, if the
+  /// display is null and `name` is `EGL_EXTENSIONS`).
+  ///
+  /// See [eglQueryString](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglQueryString.xhtml)
+  pub fn query_string(
+    &self, display: EGLDisplay, name: EGLint,
+  ) -> EglResult<String> {
+    let p = unsafe { (self.eglQueryString_p)(display, name) };
+    if p.is_null() {
+      Err(self.get_error())
+    } else {
+      Ok(min_alloc_lossy_into_string(unsafe {
+        gather_null_terminated_bytes(p)
+      }))
+    }
+  }
+
+  /// Gets the display connection for a native display.
+  ///
+  /// See [eglGetDisplay](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglGetDisplay.xhtml)
+  ///
+  /// ## Safety
+  /// * `native_display` must be [`EGL_DEFAULT_DISPLAY`] or a valid native
+  ///   display connection.
+  pub unsafe fn get_display(
+    &self, native_display: EGLNativeDisplayType,
+  ) -> EglResult<EGLDisplay> {
+    let display = (self.eglGetDisplay_p)(native_display);
+    if display.is_null() {
+      Err(self.get_error())
+    } else {
+      Ok(display)
+    }
+  }
+
+  /// Gets the display connection for a particular platform.
+  ///
+  /// * `attrib_list` must be empty or have a key of `EGL_NONE` as the last
+  ///   [key, value] pair.
+  ///
+  /// See [eglGetPlatformDisplay](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglGetPlatformDisplay.xhtml)
+  ///
+  /// ## Failure
+  /// * If neither EGL 1.5 nor `EGL_EXT_platform_base` is available you get an
+  ///   Application error.
+  ///
+  /// ## Safety
+  /// * `native_display` must be null or a valid native display of the
+  ///   `platform` given.
+  pub unsafe fn get_platform_display(
+    &self, platform: EGLenum, native_display: *mut c_void,
+    attrib_list: &[[EGLAttrib; 2]],
+  ) -> EglResult<EGLDisplay> {
+    match self.eglGetPlatformDisplay_p {
+      None => Err(EglError::APP),
+      Some(f) => {
+        let a_ptr = attrib_list_ptr(attrib_list, EGL_NONE as EGLAttrib)?;
+        let display = f(platform, native_display, a_ptr);
+        if display.is_null() {
+          Err(self.get_error())
+        } else {
+          Ok(display)
+        }
+      }
+    }
+  }
+
+  /// Initializes the display, returning the `(major, minor)` EGL version.
+  ///
+  /// See [eglInitialize](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglInitialize.xhtml)
+  pub fn initialize(&self, display: EGLDisplay) -> EglResult<(EGLint, EGLint)> {
+    let mut major = 0;
+    let mut minor = 0;
+    let it_worked =
+      unsafe { (self.eglInitialize_p)(display, &mut major, &mut minor) };
+    if it_worked != EGL_FALSE {
+      Ok((major, minor))
+    } else {
+      Err(self.get_error())
+    }
+  }
+
+  /// Releases the resources of a display.
+  ///
+  /// See [eglTerminate](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglTerminate.xhtml)
+  pub fn terminate(&self, display: EGLDisplay) -> EglResult<()> {
+    if unsafe { (self.eglTerminate_p)(display) } != EGL_FALSE {
+      Ok(())
+    } else {
+      Err(self.get_error())
+    }
+  }
+
+  /// Sets the current rendering API for this thread.
+  ///
+  /// See [eglBindAPI](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglBindAPI.xhtml)
+  pub fn bind_api(&self, api: EGLenum) -> EglResult<()> {
+    if unsafe { (self.eglBindAPI_p)(api) } != EGL_FALSE {
+      Ok(())
+    } else {
+      Err(self.get_error())
+    }
+  }
+
+  /// Selects configs that match your requested criteria.
+  ///
+  /// * `attrib_list` must be empty or have a key of `EGL_NONE` as the last
+  ///   [key, value] pair.
+  ///
+  /// **Output:** The initial sub-slice of `out_slice` that holds the matching
+  /// configs, best matches first. This might be empty even on success.
+  ///
+  /// See [eglChooseConfig](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglChooseConfig.xhtml)
+  pub fn choose_config<'out>(
+    &self, display: EGLDisplay, attrib_list: &[[EGLint; 2]],
+    out_slice: &'out mut [EGLConfig],
+  ) -> EglResult<&'out mut [EGLConfig]> {
+    let a_ptr = attrib_list_ptr(attrib_list, EGL_NONE)?;
+    let mut num_configs = 0;
+    let it_worked = unsafe {
+      (self.eglChooseConfig_p)(
+        display,
+        a_ptr,
+        out_slice.as_mut_ptr(),
+        out_slice.len().try_into().unwrap(),
+        &mut num_configs,
+      )
+    };
+    if it_worked != EGL_FALSE {
+      Ok(&mut out_slice[..num_configs as usize])
+    } else {
+      Err(self.get_error())
+    }
+  }
+
+  /// Makes a rendering context.
+  ///
+  /// * `attrib_list` must be empty or have a key of `EGL_NONE` as the last
+  ///   [key, value] pair.
+  /// * The new context is **not** automatically made current.
+  ///
+  /// See [eglCreateContext](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglCreateContext.xhtml)
+  pub fn create_context(
+    &self, display: EGLDisplay, config: EGLConfig, share_context: EGLContext,
+    attrib_list: &[[EGLint; 2]],
+  ) -> EglResult<EGLContext> {
+    let a_ptr = attrib_list_ptr(attrib_list, EGL_NONE)?;
+    let context = unsafe {
+      (self.eglCreateContext_p)(display, config, share_context, a_ptr)
+    };
+    if context.is_null() {
+      Err(self.get_error())
+    } else {
+      Ok(context)
+    }
+  }
+
+  /// Destroys a rendering context.
+  ///
+  /// See [eglDestroyContext](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglDestroyContext.xhtml)
+  pub fn destroy_context(
+    &self, display: EGLDisplay, context: EGLContext,
+  ) -> EglResult<()> {
+    if unsafe { (self.eglDestroyContext_p)(display, context) } != EGL_FALSE {
+      Ok(())
+    } else {
+      Err(self.get_error())
+    }
+  }
+
+  /// Makes an off-screen pixel buffer surface.
+  ///
+  /// * `attrib_list` must be empty or have a key of `EGL_NONE` as the last
+  ///   [key, value] pair.
+  ///
+  /// See [eglCreatePbufferSurface](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglCreatePbufferSurface.xhtml)
+  pub fn create_pbuffer_surface(
+    &self, display: EGLDisplay, config: EGLConfig, attrib_list: &[[EGLint; 2]],
+  ) -> EglResult<EGLSurface> {
+    let a_ptr = attrib_list_ptr(attrib_list, EGL_NONE)?;
+    let surface =
+      unsafe { (self.eglCreatePbufferSurface_p)(display, config, a_ptr) };
+    if surface.is_null() {
+      Err(self.get_error())
+    } else {
+      Ok(surface)
+    }
+  }
+
+  /// Destroys a surface.
+  ///
+  /// See [eglDestroySurface](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglDestroySurface.xhtml)
+  pub fn destroy_surface(
+    &self, display: EGLDisplay, surface: EGLSurface,
+  ) -> EglResult<()> {
+    if unsafe { (self.eglDestroySurface_p)(display, surface) } != EGL_FALSE {
+      Ok(())
+    } else {
+      Err(self.get_error())
+    }
+  }
+
+  /// Makes a context current in this thread, bound to the surfaces given.
+  ///
+  /// * Pass null surfaces and a null context to release the current context.
+  /// * Null surfaces with a non-null context requires
+  ///   `EGL_KHR_surfaceless_context`.
+  ///
+  /// ## Safety
+  /// * Any GL function pointers you have will now act on the new context.
+  ///
+  /// See [eglMakeCurrent](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglMakeCurrent.xhtml)
+  pub unsafe fn make_current(
+    &self, display: EGLDisplay, draw: EGLSurface, read: EGLSurface,
+    context: EGLContext,
+  ) -> EglResult<()> {
+    if (self.eglMakeCurrent_p)(display, draw, read, context) != EGL_FALSE {
+      Ok(())
+    } else {
+      Err(self.get_error())
+    }
+  }
+
+  /// Posts the surface's back buffer.
+  ///
+  /// See [eglSwapBuffers](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglSwapBuffers.xhtml)
+  pub fn swap_buffers(
+    &self, display: EGLDisplay, surface: EGLSurface,
+  ) -> EglResult<()> {
+    if unsafe { (self.eglSwapBuffers_p)(display, surface) } != EGL_FALSE {
+      Ok(())
+    } else {
+      Err(self.get_error())
+    }
+  }
+
+  /// Sets the minimum number of video frame periods per buffer swap for the
+  /// surface bound to the current context.
+  ///
+  /// See [eglSwapInterval](https://www.khronos.org/registry/EGL/sdk/docs/man/html/eglSwapInterval.xhtml)
+  pub fn swap_interval(
+    &self, display: EGLDisplay, interval: EGLint,
+  ) -> EglResult<()> {
+    if unsafe { (self.eglSwapInterval_p)(display, interval) } != EGL_FALSE {
+      Ok(())
+    } else {
+      Err(self.get_error())
+    }
+  }
+}
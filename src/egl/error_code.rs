@@ -0,0 +1,74 @@
+use super::*;
+
+pub type EglResult<T> = Result<T, EglError>;
+
+/// Newtype wrapper for an EGL error code.
+///
+/// EGL's own codes are all in the `0x3000` range, so a code of 0 is used for
+/// application errors (eg: the library couldn't be loaded, or an attribute
+/// list wasn't terminated).
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct EglError(pub EGLint);
+
+impl EglError {
+  /// Shorthand for an application error.
+  pub const APP: Self = Self(0);
+
+  /// The name of the error code, if it's one of the standard EGL codes.
+  pub fn name(self) -> Option<&'static str> {
+    Some(match self.0 {
+      EGL_SUCCESS => "EGL_SUCCESS",
+      EGL_NOT_INITIALIZED => "EGL_NOT_INITIALIZED",
+      EGL_BAD_ACCESS => "EGL_BAD_ACCESS",
+      EGL_BAD_ALLOC => "EGL_BAD_ALLOC",
+      EGL_BAD_ATTRIBUTE => "EGL_BAD_ATTRIBUTE",
+      EGL_BAD_CONFIG => "EGL_BAD_CONFIG",
+      EGL_BAD_CONTEXT => "EGL_BAD_CONTEXT",
+      EGL_BAD_CURRENT_SURFACE => "EGL_BAD_CURRENT_SURFACE",
+      EGL_BAD_DISPLAY => "EGL_BAD_DISPLAY",
+      EGL_BAD_MATCH => "EGL_BAD_MATCH",
+      EGL_BAD_NATIVE_PIXMAP => "EGL_BAD_NATIVE_PIXMAP",
+      EGL_BAD_NATIVE_WINDOW => "EGL_BAD_NATIVE_WINDOW",
+      EGL_BAD_PARAMETER => "EGL_BAD_PARAMETER",
+      EGL_BAD_SURFACE => "EGL_BAD_SURFACE",
+      EGL_CONTEXT_LOST => "EGL_CONTEXT_LOST",
+      _ => return None,
+    })
+  }
+}
+
+impl std::error::Error for EglError {}
+
+impl core::fmt::Debug for EglError {
+  /// Formats the error code.
+  ///
+  /// * Application errors are "EglError(Application)".
+  /// * Known codes are "EglError(0x3001 EGL_NOT_INITIALIZED)".
+  /// * Unknown codes are just "EglError(0x1234)".
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if *self == Self::APP {
+      write!(f, "EglError(Application)")
+    } else if let Some(name) = self.name() {
+      write!(f, "EglError(0x{:04X} {})", self.0, name)
+    } else {
+      write!(f, "EglError(0x{:04X})", self.0)
+    }
+  }
+}
+
+impl core::fmt::Display for EglError {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    core::fmt::Debug::fmt(self, f)
+  }
+}
+
+#[test]
+fn test_EglError_formatting() {
+  assert_eq!("EglError(Application)", format!("{}", EglError::APP));
+  assert_eq!(
+    "EglError(0x3001 EGL_NOT_INITIALIZED)",
+    format!("{:?}", EglError(EGL_NOT_INITIALIZED))
+  );
+  assert_eq!("EglError(0x1234)", format!("{}", EglError(0x1234)));
+}
@@ -0,0 +1,255 @@
+use super::*;
+
+/// An off-screen OpenGL core context, made current on the thread that
+/// created it.
+///
+/// The display is the `EGL_MESA_platform_surfaceless` platform when that's
+/// available (so no X11 or Wayland server is needed), otherwise it's the
+/// default display. The context renders into a pbuffer of the requested size
+/// when the driver offers pbuffer configs, and into no surface at all
+/// otherwise (in which case you must render to your own framebuffer objects).
+///
+/// All resources are released on drop. Any [`GlFnsRusty`] loaded from this
+/// context must not be used after that.
+pub struct HeadlessContext {
+  egl: EglFns,
+  display: EGLDisplay,
+  context: EGLContext,
+  surface: EGLSurface,
+  width: i32,
+  height: i32,
+}
+
+impl HeadlessContext {
+  /// Makes a new headless GL 4.6 core context (or 4.5, if 4.6 isn't
+  /// available), and makes it current in this thread.
+  ///
+  /// In debug builds the context is also a debug context.
+  pub fn new(width: i32, height: i32) -> EglResult<Self> {
+    let egl = EglFns::new()?;
+    // Note: if there's an error part way through, the `Drop` impl will clean
+    // up whatever we've made so far.
+    let mut out = Self {
+      egl,
+      display: EGLDisplay::null(),
+      context: EGLContext::null(),
+      surface: EGLSurface::null(),
+      width,
+      height,
+    };
+
+    let client_extensions = out
+      .egl
+      .query_string(EGLDisplay::null(), EGL_EXTENSIONS)
+      .unwrap_or_default();
+    out.display = if client_extensions.contains("EGL_MESA_platform_surfaceless")
+    {
+      unsafe {
+        out.egl.get_platform_display(
+          EGL_PLATFORM_SURFACELESS_MESA,
+          EGL_DEFAULT_DISPLAY,
+          &[],
+        )
+      }?
+    } else {
+      unsafe { out.egl.get_display(EGL_DEFAULT_DISPLAY) }?
+    };
+    out.egl.initialize(out.display)?;
+    out.egl.bind_api(EGL_OPENGL_API)?;
+
+    // base criteria
+    let mut config_attribs = vec![
+      [EGL_RENDERABLE_TYPE, EGL_OPENGL_BIT],
+      [EGL_CONFORMANT, EGL_OPENGL_BIT],
+      [EGL_RED_SIZE, 8],
+      [EGL_GREEN_SIZE, 8],
+      [EGL_BLUE_SIZE, 8],
+      [EGL_ALPHA_SIZE, 8],
+      [EGL_DEPTH_SIZE, 24],
+      [EGL_STENCIL_SIZE, 8],
+      [EGL_SURFACE_TYPE, EGL_PBUFFER_BIT],
+      [EGL_NONE, 0],
+    ];
+    let mut config_buffer = [EGLConfig::null()];
+    let mut use_pbuffer = true;
+    let mut configs = out.egl.choose_config(
+      out.display,
+      &config_attribs,
+      &mut config_buffer,
+    )?;
+    if configs.is_empty() {
+      // no pbuffer support, so try again for a surfaceless config.
+      use_pbuffer = false;
+      config_attribs.retain(|[k, _v]| *k != EGL_SURFACE_TYPE);
+      configs = out.egl.choose_config(
+        out.display,
+        &config_attribs,
+        &mut config_buffer,
+      )?;
+    }
+    let config = *configs.first().ok_or(EglError(EGL_BAD_CONFIG))?;
+
+    let debug = if cfg!(debug_assertions) { EGL_TRUE } else { EGL_FALSE };
+    let context_attribs = |minor: EGLint| {
+      [
+        [EGL_CONTEXT_MAJOR_VERSION, 4],
+        [EGL_CONTEXT_MINOR_VERSION, minor],
+        [EGL_CONTEXT_OPENGL_PROFILE_MASK, EGL_CONTEXT_OPENGL_CORE_PROFILE_BIT],
+        [EGL_CONTEXT_OPENGL_DEBUG, debug as EGLint],
+        [EGL_NONE, 0],
+      ]
+    };
+    out.context = out
+      .egl
+      .create_context(
+        out.display,
+        config,
+        EGLContext::null(),
+        &context_attribs(6),
+      )
+      .or_else(|_| {
+        out.egl.create_context(
+          out.display,
+          config,
+          EGLContext::null(),
+          &context_attribs(5),
+        )
+      })?;
+
+    if use_pbuffer {
+      out.surface = out.egl.create_pbuffer_surface(
+        out.display,
+        config,
+        &[[EGL_WIDTH, width], [EGL_HEIGHT, height], [EGL_NONE, 0]],
+      )?;
+    }
+    unsafe {
+      out.egl.make_current(out.display, out.surface, out.surface, out.context)
+    }?;
+    Ok(out)
+  }
+
+  /// The EGL functions backing this context.
+  pub fn egl(&self) -> &EglFns {
+    &self.egl
+  }
+
+  /// The display connection of this context.
+  pub fn display(&self) -> EGLDisplay {
+    self.display
+  }
+
+  /// The surface of this context (null if the context is surfaceless).
+  pub fn surface(&self) -> EGLSurface {
+    self.surface
+  }
+
+  /// If the context renders into a pbuffer (rather than no surface at all).
+  pub fn has_surface(&self) -> bool {
+    self.surface.is_not_null()
+  }
+}
+
+impl GlContext for HeadlessContext {
+  type Error = EglError;
+
+  fn make_current(&self) -> EglResult<()> {
+    unsafe {
+      self.egl.make_current(
+        self.display,
+        self.surface,
+        self.surface,
+        self.context,
+      )
+    }
+  }
+
+  /// Posts the pbuffer's back buffer. This does nothing if the context is
+  /// surfaceless.
+  fn swap_buffers(&self) -> EglResult<()> {
+    if self.has_surface() {
+      self.egl.swap_buffers(self.display, self.surface)
+    } else {
+      Ok(())
+    }
+  }
+
+  fn get_proc_address(&self, name: &[u8]) -> Option<NonNull<c_void>> {
+    self.egl.get_proc_address(name)
+  }
+
+  /// The `(width, height)` requested when the context was made.
+  fn size(&self) -> (i32, i32) {
+    (self.width, self.height)
+  }
+
+  fn set_swap_interval(&self, interval: i32) -> EglResult<()> {
+    self.egl.swap_interval(self.display, interval)
+  }
+}
+
+impl Drop for HeadlessContext {
+  fn drop(&mut self) {
+    if self.display.is_not_null() {
+      let _i_dont_care = unsafe {
+        self.egl.make_current(
+          self.display,
+          EGLSurface::null(),
+          EGLSurface::null(),
+          EGLContext::null(),
+        )
+      };
+      if self.surface.is_not_null() {
+        let _i_dont_care = self.egl.destroy_surface(self.display, self.surface);
+      }
+      if self.context.is_not_null() {
+        let _i_dont_care = self.egl.destroy_context(self.display, self.context);
+      }
+      let _i_dont_care = self.egl.terminate(self.display);
+    }
+  }
+}
+
+/// Makes a small headless context (and its GL functions) for a test.
+///
+/// If there's no headless EGL on this machine this prints why and gives
+/// `None`, and the test should just return.
+#[cfg(test)]
+pub(crate) fn test_context() -> Option<(HeadlessContext, GlFnsRusty)> {
+  let ctx = match HeadlessContext::new(32, 32) {
+    Ok(ctx) => ctx,
+    Err(e) => {
+      println!("skipping, no headless EGL available: {}", e);
+      return None;
+    }
+  };
+  let gl = ctx.load_gl_functions().unwrap();
+  Some((ctx, gl))
+}
+
+#[test]
+fn test_HeadlessContext_clear_and_read_back() {
+  let (ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  if !ctx.has_surface() {
+    println!("skipping, the EGL context is surfaceless");
+    return;
+  }
+  let mut pixel = [0_u8; 4];
+  unsafe {
+    gl.ClearColor(1.0, 0.0, 1.0, 1.0);
+    gl.Clear(GL_COLOR_BUFFER_BIT);
+    gl.ReadPixels(
+      0,
+      0,
+      1,
+      1,
+      GL_RGBA,
+      GL_UNSIGNED_BYTE,
+      pixel.as_mut_ptr().cast(),
+    )
+  };
+  assert_eq!(pixel, [255, 0, 255, 255]);
+}
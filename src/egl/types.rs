@@ -0,0 +1,47 @@
+use super::*;
+
+pub type EGLint = i32;
+pub type EGLenum = c_uint;
+pub type EGLBoolean = c_uint;
+pub type EGLAttrib = isize;
+pub type EGLNativeDisplayType = *mut c_void;
+
+/// The only `false` value for `EGLBoolean`.
+pub const EGL_FALSE: EGLBoolean = 0;
+
+/// The canonical `true` value for `EGLBoolean`.
+pub const EGL_TRUE: EGLBoolean = 1;
+
+macro_rules! make_handle {
+  ($new:ident) => {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
+    pub struct $new(pub *mut c_void);
+    impl Default for $new {
+      fn default() -> Self {
+        Self::null()
+      }
+    }
+    impl $new {
+      pub const fn null() -> Self {
+        Self(null_mut())
+      }
+      pub fn is_null(self) -> bool {
+        self.0.is_null()
+      }
+      pub fn is_not_null(self) -> bool {
+        !self.0.is_null()
+      }
+    }
+  };
+}
+
+make_handle!(EGLDisplay);
+make_handle!(EGLConfig);
+make_handle!(EGLContext);
+make_handle!(EGLSurface);
+
+/// An open handle from `dlopen`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct LibHandle(pub *mut c_void);
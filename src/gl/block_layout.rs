@@ -0,0 +1,826 @@
+use super::*;
+
+/// The memory layout rules of uniform and shader storage blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockLayoutEnum {
+  /// The rules of `layout(std140)`, usable with any block.
+  Std140,
+  /// The rules of `layout(std430)`, usable with shader storage blocks.
+  ///
+  /// This is like std140, but arrays and structs aren't padded out to 16
+  /// bytes.
+  Std430,
+}
+
+/// One member (or struct field, or struct array element's field) of a
+/// [`BlockLayout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockMember {
+  /// The GLSL name, with struct fields given as `outer.inner` and elements of
+  /// struct arrays given as `outer[i].inner`.
+  pub name: String,
+  pub ty: UniformTypeEnum,
+  /// The byte offset from the start of the block.
+  pub offset: usize,
+  /// The number of array elements (1 if it's not an array, 0 for an unsized
+  /// array).
+  pub array_len: usize,
+  /// The bytes between array elements (0 if it's not an array).
+  pub array_stride: usize,
+  /// The bytes between matrix columns (0 if it's not a matrix).
+  pub matrix_stride: usize,
+}
+
+/// The offsets of every member of a block, computed on the CPU.
+///
+/// Make one with [`BlockLayout::builder`], listing the members in the same
+/// order as the GLSL block declares them:
+///
+/// ```
+/// # use superbible7::*;
+/// // layout(std140) uniform Camera { mat4 view; vec3 eye; float exposure; };
+/// let camera = BlockLayout::builder(BlockLayoutEnum::Std140)
+///   .field("view", UniformTypeEnum::Mat4)
+///   .field("eye", UniformTypeEnum::Vec3)
+///   .field("exposure", UniformTypeEnum::Float)
+///   .build();
+/// assert_eq!(camera.member("exposure").unwrap().offset, 76);
+/// let mut bytes = camera.new_buffer();
+/// camera.write(&mut bytes, "eye", [0.0_f32, 1.0, 5.0]).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockLayout {
+  rule: BlockLayoutEnum,
+  members: Vec<BlockMember>,
+  size: usize,
+  alignment: usize,
+}
+
+/// Builds a [`BlockLayout`], see [`BlockLayout::builder`].
+#[derive(Debug, Clone)]
+pub struct BlockLayoutBuilder {
+  layout: BlockLayout,
+  has_unsized_array: bool,
+}
+
+const fn round_up(x: usize, align: usize) -> usize {
+  x.div_ceil(align) * align
+}
+
+/// The `(component_bytes, components, columns)` of a type.
+pub(crate) fn type_shape(ty: UniformTypeEnum) -> Option<(usize, usize, usize)> {
+  use UniformTypeEnum::*;
+  let (components, columns) = match ty {
+    Float | Int | UInt | Bool => (1, 1),
+    Vec2 | IVec2 | UVec2 | BVec2 => (2, 1),
+    Vec3 | IVec3 | UVec3 | BVec3 => (3, 1),
+    Vec4 | IVec4 | UVec4 | BVec4 => (4, 1),
+    Mat2 => (2, 2),
+    Mat3 => (3, 3),
+    Mat4 => (4, 4),
+    Mat2x3 => (3, 2),
+    Mat2x4 => (4, 2),
+    Mat3x2 => (2, 3),
+    Mat3x4 => (4, 3),
+    Mat4x2 => (2, 4),
+    Mat4x3 => (3, 4),
+    Other(ty) => return double_shape(GLenum(ty)).map(|(c, m)| (8, c, m)),
+    Sampler(_) | Image(_) => return None,
+  };
+  Some((4, components, columns))
+}
+
+/// The `(components, columns)` of the double types, which are
+/// [`Other`](UniformTypeEnum::Other) types.
+#[allow(non_upper_case_globals)]
+fn double_shape(ty: GLenum) -> Option<(usize, usize)> {
+  Some(match ty {
+    GL_DOUBLE => (1, 1),
+    GL_DOUBLE_VEC2 => (2, 1),
+    GL_DOUBLE_VEC3 => (3, 1),
+    GL_DOUBLE_VEC4 => (4, 1),
+    GL_DOUBLE_MAT2 => (2, 2),
+    GL_DOUBLE_MAT3 => (3, 3),
+    GL_DOUBLE_MAT4 => (4, 4),
+    GL_DOUBLE_MAT2x3 => (3, 2),
+    GL_DOUBLE_MAT2x4 => (4, 2),
+    GL_DOUBLE_MAT3x2 => (2, 3),
+    GL_DOUBLE_MAT3x4 => (4, 3),
+    GL_DOUBLE_MAT4x2 => (2, 4),
+    GL_DOUBLE_MAT4x3 => (3, 4),
+    _ => return None,
+  })
+}
+
+impl BlockLayout {
+  /// Starts building a layout that follows the rules given.
+  pub fn builder(rule: BlockLayoutEnum) -> BlockLayoutBuilder {
+    BlockLayoutBuilder {
+      layout: BlockLayout { rule, members: Vec::new(), size: 0, alignment: 4 },
+      has_unsized_array: false,
+    }
+  }
+
+  /// The layout rules that were used.
+  pub fn rule(&self) -> BlockLayoutEnum {
+    self.rule
+  }
+
+  /// All the members, in offset order.
+  pub fn members(&self) -> &[BlockMember] {
+    &self.members
+  }
+
+  /// Finds a member by name.
+  pub fn member(&self, name: &str) -> Option<&BlockMember> {
+    self.members.iter().find(|m| m.name == name)
+  }
+
+  /// The size of the block in bytes (not counting any unsized array at the
+  /// end).
+  ///
+  /// When this layout is used as a struct, this includes the padding at the
+  /// end of the struct.
+  pub fn size(&self) -> usize {
+    self.size
+  }
+
+  /// The alignment of the block when it's used as a struct.
+  pub fn alignment(&self) -> usize {
+    self.alignment
+  }
+
+  /// Makes a zeroed byte buffer of the block's size.
+  pub fn new_buffer(&self) -> Vec<u8> {
+    vec![0; self.size]
+  }
+
+  /// Writes a value into a member's place within `buffer`.
+  ///
+  /// Use `name[i]` to write to element `i` of an array member.
+  ///
+  /// ## Failure
+  /// * If there's no member with that name, the value doesn't match the
+  ///   member's type, or the buffer is too short.
+  pub fn write<T: BlockValue>(
+    &self, buffer: &mut [u8], name: &str, value: T,
+  ) -> Result<(), String> {
+    self.write_array(buffer, name, &[value])
+  }
+
+  /// Writes values into consecutive elements of an array member.
+  ///
+  /// Use `name[i]` to start at element `i`.
+  ///
+  /// ## Failure
+  /// * As [`write`](Self::write), and also if there are more values than
+  ///   there are elements (unsized arrays can be written to up to the end of
+  ///   the buffer).
+  pub fn write_array<T: BlockValue>(
+    &self, buffer: &mut [u8], name: &str, values: &[T],
+  ) -> Result<(), String> {
+    let (member, first) = self.find_element(name)?;
+    if !T::accepts(member.ty) {
+      return Err(format!(
+        "Member `{}` is a {:?}, which can't be written from a `{}`.",
+        member.name,
+        member.ty,
+        core::any::type_name::<T>()
+      ));
+    }
+    let end_element = first.checked_add(values.len());
+    if member.array_len != 0
+      && end_element.is_none_or(|end| end > member.array_len)
+    {
+      return Err(format!(
+        "Member `{}` has {} elements, but {} values were given starting at \
+        element {}.",
+        member.name,
+        member.array_len,
+        values.len(),
+        first
+      ));
+    }
+    let buffer_len = buffer.len();
+    for (i, value) in values.iter().enumerate() {
+      let element = first.saturating_add(i);
+      let range = element
+        .checked_mul(member.array_stride)
+        .and_then(|o| o.checked_add(member.offset))
+        .and_then(|o| Some(o..o.checked_add(value_size::<T>(member))?));
+      let out = range.and_then(|r| buffer.get_mut(r)).ok_or_else(|| {
+        format!(
+          "Member `{}` element {} is past the end of the buffer, which is {} \
+          bytes.",
+          member.name, element, buffer_len
+        )
+      })?;
+      value.write_block_bytes(out, member.ty, member.matrix_stride);
+    }
+    Ok(())
+  }
+
+  /// Finds a member, and the array element that `name` starts at.
+  fn find_element(&self, name: &str) -> Result<(&BlockMember, usize), String> {
+    if let Some(member) = self.member(name) {
+      return Ok((member, 0));
+    }
+    let not_found = || format!("There's no member named `{}`.", name);
+    let (base, index) = name
+      .strip_suffix(']')
+      .and_then(|s| s.rsplit_once('['))
+      .ok_or_else(not_found)?;
+    let index: usize = index.parse().map_err(|_| not_found())?;
+    let member = self
+      .member(base)
+      .filter(|m| m.array_stride != 0)
+      .ok_or_else(not_found)?;
+    Ok((member, index))
+  }
+
+  /// Checks this layout against the offsets that GL reports for a block.
+  ///
+  /// * `block_name`: The name of a uniform block or shader storage block
+  ///   within the program.
+  ///
+  /// Members are matched by name, or by `block_name.name` for blocks with an
+  /// instance name. Arrays are matched by their first element's name (eg:
+  /// `lights[0]`). GL only has to list the first element of a struct array
+  /// at the top of a shader storage block, so struct array elements after
+  /// the first are skipped if GL doesn't list them.
+  ///
+  /// ## Failure
+  /// * If there's no such block, a member is missing, or any offset or stride
+  ///   is different, you get a description of every problem found.
+  pub fn check_against(
+    &self, reflection: &ProgramReflection, block_name: &str,
+  ) -> Result<(), String> {
+    // (name, offset, array_stride, matrix_stride)
+    let gl_vars: Vec<(&str, i32, i32, i32)> =
+      if let Some(block) = reflection.uniform_block(block_name) {
+        block
+          .variables
+          .iter()
+          .map(|&i| &reflection.uniforms[i])
+          .map(|v| (&v.name[..], v.offset, v.array_stride, v.matrix_stride))
+          .collect()
+      } else if let Some(block) = reflection.shader_storage_block(block_name) {
+        block
+          .variables
+          .iter()
+          .map(|&i| &reflection.buffer_variables[i])
+          .map(|v| (&v.name[..], v.offset, v.array_stride, v.matrix_stride))
+          .collect()
+      } else {
+        return Err(format!("There's no block named `{}`.", block_name));
+      };
+
+    let mut problems = Vec::new();
+    for member in self.members.iter() {
+      let gl_name = if member.array_stride != 0 {
+        format!("{}[0]", member.name)
+      } else {
+        member.name.clone()
+      };
+      let prefixed = format!("{}.{}", block_name, gl_name);
+      let found =
+        gl_vars.iter().find(|(name, ..)| *name == gl_name || *name == prefixed);
+      let (_, offset, array_stride, matrix_stride) = match found {
+        Some(v) => *v,
+        None if is_later_struct_array_element(&member.name) => continue,
+        None => {
+          problems.push(format!("`{}` isn't in the block.", member.name));
+          continue;
+        }
+      };
+      let expected = [
+        ("offset", member.offset, offset),
+        ("array stride", member.array_stride, array_stride),
+        ("matrix stride", member.matrix_stride, matrix_stride),
+      ];
+      for (what, ours, theirs) in expected.iter() {
+        if *ours as i32 != *theirs {
+          problems.push(format!(
+            "`{}` has {} {}, but GL says {}.",
+            member.name, what, ours, theirs
+          ));
+        }
+      }
+    }
+    if problems.is_empty() {
+      Ok(())
+    } else {
+      Err(problems.join("\n"))
+    }
+  }
+}
+
+/// If the name has an array index other than `[0]` in it.
+fn is_later_struct_array_element(name: &str) -> bool {
+  name.split('[').skip(1).any(|s| !s.starts_with("0]"))
+}
+
+/// The bytes taken up by one `T` in a member.
+fn value_size<T: BlockValue>(member: &BlockMember) -> usize {
+  match type_shape(member.ty) {
+    Some((_, _, columns)) if columns > 1 => {
+      (columns - 1) * member.matrix_stride + T::COLUMN_SIZE
+    }
+    _ => T::COLUMN_SIZE,
+  }
+}
+
+impl BlockLayoutBuilder {
+  /// The `(alignment, size, matrix_stride)` of a type.
+  fn type_layout(&self, ty: UniformTypeEnum) -> (usize, usize, usize) {
+    let (component_bytes, components, columns) =
+      type_shape(ty).unwrap_or_else(|| {
+        panic!("{:?} can't be used in a block.", ty);
+      });
+    let vec_align = match components {
+      1 => component_bytes,
+      2 => component_bytes * 2,
+      _ => component_bytes * 4,
+    };
+    if columns == 1 {
+      (vec_align, components * component_bytes, 0)
+    } else {
+      let column_stride = match self.layout.rule {
+        BlockLayoutEnum::Std140 => round_up(vec_align, 16),
+        BlockLayoutEnum::Std430 => vec_align,
+      };
+      (column_stride, columns * column_stride, column_stride)
+    }
+  }
+
+  /// The alignment of an array of elements with the alignment given.
+  fn array_alignment(&self, element_alignment: usize) -> usize {
+    match self.layout.rule {
+      BlockLayoutEnum::Std140 => round_up(element_alignment, 16),
+      BlockLayoutEnum::Std430 => element_alignment,
+    }
+  }
+
+  /// Places something with the alignment and size given after the members
+  /// so far, returning its offset.
+  fn place(&mut self, alignment: usize, size: usize) -> usize {
+    assert!(
+      !self.has_unsized_array,
+      "An unsized array must be the last member of a block."
+    );
+    let offset = round_up(self.layout.size, alignment);
+    self.layout.size = offset + size;
+    self.layout.alignment = self.layout.alignment.max(alignment);
+    offset
+  }
+
+  /// Adds a scalar, vector, or matrix member.
+  ///
+  /// The double types can be laid out too (eg:
+  /// `UniformTypeEnum::from_enum(GL_DOUBLE_VEC3)`), but there are no
+  /// [`BlockValue`]s for them, so they can't be written.
+  ///
+  /// ## Panics
+  /// * If the type is a sampler, image, or other opaque type.
+  /// * If an unsized array was already added (it must be the last member).
+  pub fn field(mut self, name: &str, ty: UniformTypeEnum) -> Self {
+    let (alignment, size, matrix_stride) = self.type_layout(ty);
+    let offset = self.place(alignment, size);
+    self.layout.members.push(BlockMember {
+      name: String::from(name),
+      ty,
+      offset,
+      array_len: 1,
+      array_stride: 0,
+      matrix_stride,
+    });
+    self
+  }
+
+  /// Adds an array of scalars, vectors, or matrices.
+  ///
+  /// A `len` of 0 makes an unsized array, which must be the last member.
+  ///
+  /// ## Panics
+  /// * If the type is a sampler, image, or other opaque type.
+  /// * If an unsized array was already added.
+  pub fn array(mut self, name: &str, ty: UniformTypeEnum, len: usize) -> Self {
+    let (alignment, size, matrix_stride) = self.type_layout(ty);
+    let alignment = self.array_alignment(alignment);
+    let array_stride = round_up(size, alignment);
+    let offset = self.place(alignment, array_stride * len);
+    self.layout.members.push(BlockMember {
+      name: String::from(name),
+      ty,
+      offset,
+      array_len: len,
+      array_stride,
+      matrix_stride,
+    });
+    self.has_unsized_array = len == 0;
+    self
+  }
+
+  /// Adds a struct member, with the struct's own layout.
+  ///
+  /// ## Panics
+  /// * If the struct's layout uses different rules.
+  /// * If an unsized array was already added.
+  pub fn struct_field(self, name: &str, layout: &BlockLayout) -> Self {
+    self.struct_fields(name, layout, None)
+  }
+
+  /// Adds a sized array of structs.
+  ///
+  /// Every element's fields are listed as members (eg: `lights[2].color`).
+  ///
+  /// ## Panics
+  /// * If the struct's layout uses different rules.
+  /// * If an unsized array was already added.
+  pub fn struct_array(
+    self, name: &str, layout: &BlockLayout, len: usize,
+  ) -> Self {
+    self.struct_fields(name, layout, Some(len))
+  }
+
+  fn struct_fields(
+    mut self, name: &str, layout: &BlockLayout, opt_len: Option<usize>,
+  ) -> Self {
+    assert_eq!(
+      self.layout.rule, layout.rule,
+      "A struct must use the same layout rules as its block."
+    );
+    let alignment = self.array_alignment(layout.alignment);
+    let stride = round_up(layout.size, alignment);
+    let len = opt_len.unwrap_or(1);
+    let base = self.place(alignment, stride * len);
+    for i in 0..len {
+      let prefix = match opt_len {
+        Some(_) => format!("{}[{}]", name, i),
+        None => String::from(name),
+      };
+      for field in layout.members.iter() {
+        self.layout.members.push(BlockMember {
+          name: format!("{}.{}", prefix, field.name),
+          offset: base + i * stride + field.offset,
+          ..field.clone()
+        });
+      }
+    }
+    self
+  }
+
+  /// Finishes the layout.
+  ///
+  /// The size is padded out to a multiple of the alignment, so that the
+  /// layout can also be used as a struct.
+  pub fn build(mut self) -> BlockLayout {
+    self.layout.alignment = self.array_alignment(self.layout.alignment);
+    self.layout.size = round_up(self.layout.size, self.layout.alignment);
+    self.layout
+  }
+}
+
+/// A Rust value that can be written into a block member.
+///
+/// This is implemented for all the [`UniformValue`] types.
+pub trait BlockValue: UniformValue {
+  /// The bytes of one column (or the whole value, if it's not a matrix).
+  const COLUMN_SIZE: usize;
+
+  /// Writes the value's bytes to the start of `out`, as the member type `ty`
+  /// (which it [accepts](UniformValue::accepts)), with matrix columns
+  /// `matrix_stride` bytes apart.
+  ///
+  /// `out` must be at least as long as the value's data.
+  fn write_block_bytes(
+    &self, out: &mut [u8], ty: UniformTypeEnum, matrix_stride: usize,
+  );
+}
+macro_rules! impl_block_value {
+  ($($t:ty),*) => {
+    $(
+      impl BlockValue for $t {
+        const COLUMN_SIZE: usize = core::mem::size_of::<$t>();
+        fn write_block_bytes(
+          &self, out: &mut [u8], _ty: UniformTypeEnum, _matrix_stride: usize,
+        ) {
+          out[..Self::COLUMN_SIZE].copy_from_slice(bytemuck::bytes_of(self))
+        }
+      }
+    )*
+  };
+}
+macro_rules! impl_block_value_float {
+  ($($t:ty),*) => {
+    $(
+      impl BlockValue for $t {
+        const COLUMN_SIZE: usize = core::mem::size_of::<$t>();
+        fn write_block_bytes(
+          &self, out: &mut [u8], ty: UniformTypeEnum, _matrix_stride: usize,
+        ) {
+          use UniformTypeEnum::*;
+          let floats: &[f32] = bytemuck::cast_slice(bytemuck::bytes_of(self));
+          let is_bool = matches!(ty, Bool | BVec2 | BVec3 | BVec4);
+          for (f, chunk) in floats.iter().zip(out.chunks_exact_mut(4)) {
+            // Note: a bool is 0 or 1, not the bits of a float.
+            let bits = if is_bool { (*f != 0.0) as u32 } else { f.to_bits() };
+            chunk.copy_from_slice(&bits.to_ne_bytes());
+          }
+        }
+      }
+    )*
+  };
+}
+impl_block_value_float!(f32, [f32; 2], [f32; 3], [f32; 4]);
+impl_block_value!(i32, [i32; 2], [i32; 3], [i32; 4]);
+impl_block_value!(u32, [u32; 2], [u32; 3], [u32; 4]);
+macro_rules! impl_block_value_bool {
+  ($($n:literal),*) => {
+    $(
+      impl BlockValue for [bool; $n] {
+        const COLUMN_SIZE: usize = $n * 4;
+        fn write_block_bytes(
+          &self, out: &mut [u8], _ty: UniformTypeEnum, _matrix_stride: usize,
+        ) {
+          for (b, chunk) in self.iter().zip(out.chunks_exact_mut(4)) {
+            chunk.copy_from_slice(&(*b as u32).to_ne_bytes());
+          }
+        }
+      }
+    )*
+  };
+}
+impl_block_value_bool!(2, 3, 4);
+impl BlockValue for bool {
+  const COLUMN_SIZE: usize = 4;
+  fn write_block_bytes(
+    &self, out: &mut [u8], _ty: UniformTypeEnum, _matrix_stride: usize,
+  ) {
+    out[..4].copy_from_slice(&(*self as u32).to_ne_bytes());
+  }
+}
+macro_rules! impl_block_value_matrix {
+  ($(($cols:literal, $rows:literal)),*) => {
+    $(
+      impl BlockValue for [[f32; $rows]; $cols] {
+        const COLUMN_SIZE: usize = $rows * 4;
+        fn write_block_bytes(
+          &self, out: &mut [u8], _ty: UniformTypeEnum, matrix_stride: usize,
+        ) {
+          for (i, column) in self.iter().enumerate() {
+            let start = i * matrix_stride;
+            out[start..start + Self::COLUMN_SIZE]
+              .copy_from_slice(bytemuck::bytes_of(column));
+          }
+        }
+      }
+    )*
+  };
+}
+impl_block_value_matrix!(
+  (2, 2),
+  (3, 3),
+  (4, 4),
+  (2, 3),
+  (2, 4),
+  (3, 2),
+  (3, 4),
+  (4, 2),
+  (4, 3)
+);
+
+#[test]
+fn test_std140_offsets() {
+  use UniformTypeEnum::*;
+  let light = BlockLayout::builder(BlockLayoutEnum::Std140)
+    .field("position", Vec3)
+    .field("intensity", Float)
+    .field("color", Vec2)
+    .build();
+  assert_eq!(light.size(), 32);
+  assert_eq!(light.alignment(), 16);
+
+  let block = BlockLayout::builder(BlockLayoutEnum::Std140)
+    .field("a", Float)
+    .field("b", Vec3)
+    .field("c", Float)
+    .array("d", Float, 3)
+    .field("e", Mat3)
+    .field("f", Vec2)
+    .struct_array("lights", &light, 2)
+    .field("g", Mat2x3)
+    .field("h", Bool)
+    .build();
+  let offset = |name| block.member(name).unwrap().offset;
+  assert_eq!(offset("a"), 0);
+  assert_eq!(offset("b"), 16);
+  assert_eq!(offset("c"), 28);
+  assert_eq!(offset("d"), 32);
+  assert_eq!(block.member("d").unwrap().array_stride, 16);
+  assert_eq!(offset("e"), 80);
+  assert_eq!(block.member("e").unwrap().matrix_stride, 16);
+  assert_eq!(offset("f"), 128);
+  assert_eq!(offset("lights[0].position"), 144);
+  assert_eq!(offset("lights[0].intensity"), 156);
+  assert_eq!(offset("lights[1].color"), 192);
+  assert_eq!(offset("g"), 208);
+  assert_eq!(offset("h"), 240);
+  assert_eq!(block.size(), 256);
+}
+
+#[test]
+fn test_std430_offsets() {
+  use UniformTypeEnum::*;
+  let particle = BlockLayout::builder(BlockLayoutEnum::Std430)
+    .field("velocity", Vec2)
+    .field("mass", Float)
+    .build();
+  assert_eq!((particle.size(), particle.alignment()), (16, 8));
+
+  let block = BlockLayout::builder(BlockLayoutEnum::Std430)
+    .array("weights", Float, 3)
+    .field("m", Mat3x2)
+    .struct_array("particles", &particle, 2)
+    .array("rest", Vec3, 0)
+    .build();
+  let member = |name| block.member(name).unwrap();
+  assert_eq!(
+    (member("weights").offset, member("weights").array_stride),
+    (0, 4)
+  );
+  assert_eq!((member("m").offset, member("m").matrix_stride), (16, 8));
+  assert_eq!(member("particles[1].mass").offset, 64);
+  assert_eq!((member("rest").offset, member("rest").array_stride), (80, 16));
+  assert_eq!(block.size(), 80);
+}
+
+#[test]
+fn test_double_offsets() {
+  use UniformTypeEnum::*;
+  let from_enum = UniformTypeEnum::from_enum;
+  let block = BlockLayout::builder(BlockLayoutEnum::Std140)
+    .field("a", Float)
+    .field("b", from_enum(GL_DOUBLE_VEC3))
+    .field("c", from_enum(GL_DOUBLE))
+    .field("d", from_enum(GL_DOUBLE_MAT2x3))
+    .array("e", from_enum(GL_DOUBLE), 2)
+    .field("f", from_enum(GL_DOUBLE_VEC2))
+    .build();
+  let member = |name| block.member(name).unwrap();
+  assert_eq!(member("b").offset, 32);
+  assert_eq!(member("c").offset, 56);
+  assert_eq!((member("d").offset, member("d").matrix_stride), (64, 32));
+  assert_eq!((member("e").offset, member("e").array_stride), (128, 16));
+  assert_eq!(member("f").offset, 160);
+  assert_eq!((block.size(), block.alignment()), (192, 32));
+
+  let block = BlockLayout::builder(BlockLayoutEnum::Std430)
+    .array("e", from_enum(GL_DOUBLE), 3)
+    .field("m", from_enum(GL_DOUBLE_MAT3x2))
+    .build();
+  let member = |name| block.member(name).unwrap();
+  assert_eq!(member("e").array_stride, 8);
+  assert_eq!((member("m").offset, member("m").matrix_stride), (32, 16));
+  assert_eq!(block.size(), 80);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_BlockLayout_write() {
+  use UniformTypeEnum::*;
+  let block = BlockLayout::builder(BlockLayoutEnum::Std140)
+    .field("flag", Bool)
+    .array("values", Float, 2)
+    .field("m", Mat2)
+    .build();
+  let mut bytes = block.new_buffer();
+  block.write(&mut bytes, "flag", true).unwrap();
+  block.write(&mut bytes, "values[1]", 2.0_f32).unwrap();
+  block.write(&mut bytes, "m", [[1.0_f32, 2.0], [3.0, 4.0]]).unwrap();
+  let floats: &[f32] = bytemuck::cast_slice(&bytes);
+  assert_eq!(floats[4..9], [0.0, 0.0, 0.0, 0.0, 2.0]);
+  assert_eq!(floats[12..18], [1.0, 2.0, 0.0, 0.0, 3.0, 4.0]);
+  assert_eq!(bytemuck::cast_slice::<u8, u32>(&bytes)[0], 1);
+
+  assert!(block.write(&mut bytes, "flag", [1.0_f32; 2]).is_err());
+  assert!(block.write(&mut bytes, "values[2]", 1.0_f32).is_err());
+  assert!(block.write_array(&mut bytes, "values", &[1.0_f32; 3]).is_err());
+  assert!(block.write(&mut bytes, "nope", 1.0_f32).is_err());
+  assert!(block.write(&mut bytes[..8], "m", [[0.0_f32; 2]; 2]).is_err());
+
+  // floats written to a bool are 0 or 1 (even -0.0, which isn't all zero).
+  block.write(&mut bytes, "flag", 0.5_f32).unwrap();
+  assert_eq!(bytemuck::cast_slice::<u8, u32>(&bytes)[0], 1);
+  block.write(&mut bytes, "flag", -0.0_f32).unwrap();
+  assert_eq!(bytemuck::cast_slice::<u8, u32>(&bytes)[0], 0);
+
+  // indexes so big that the offset overflows are just out of bounds.
+  let rest = BlockLayout::builder(BlockLayoutEnum::Std430)
+    .array("rest", Float, 0)
+    .build();
+  let huge = format!("rest[{}]", usize::MAX / 2);
+  assert!(rest.write(&mut bytes, &huge, 1.0_f32).is_err());
+  let last = format!("rest[{}]", usize::MAX);
+  assert!(rest.write_array(&mut bytes, &last, &[1.0_f32; 2]).is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+#[allow(non_snake_case)]
+fn test_BlockLayout_check_against_gl() {
+  use UniformTypeEnum::*;
+  let from_enum = UniformTypeEnum::from_enum;
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let shader = gl
+    .create_compiled_shader(
+      ShaderEnum::Compute,
+      "#version 450 core
+      layout(local_size_x = 1) in;
+      struct Light { vec3 position; float intensity; vec2 color; };
+      layout(binding = 0, std140) uniform Scene {
+        float a; vec3 b; float d[3]; mat3 e; Light lights[2]; mat2x3 g;
+        dvec3 h; dmat2x3 i;
+      } scene;
+      struct Particle { vec2 velocity; float mass; };
+      layout(binding = 1, std430) buffer Particles {
+        float weights[3]; mat3x2 m; Particle particles[2]; vec3 rest[];
+      };
+      void main() {
+        particles[0].mass = scene.a + scene.b.x + scene.d[2] + scene.e[1].x
+          + scene.lights[1].color.y + scene.g[0].z + weights[1] + m[2].y
+          + rest[3].z;
+      }",
+    )
+    .unwrap();
+  let program = gl.create_linked_program(&[shader]).unwrap();
+  let reflection = gl.get_program_reflection(program);
+
+  let light = BlockLayout::builder(BlockLayoutEnum::Std140)
+    .field("position", Vec3)
+    .field("intensity", Float)
+    .field("color", Vec2)
+    .build();
+  let scene = BlockLayout::builder(BlockLayoutEnum::Std140)
+    .field("a", Float)
+    .field("b", Vec3)
+    .array("d", Float, 3)
+    .field("e", Mat3)
+    .struct_array("lights", &light, 2)
+    .field("g", Mat2x3)
+    .field("h", from_enum(GL_DOUBLE_VEC3))
+    .field("i", from_enum(GL_DOUBLE_MAT2x3))
+    .build();
+  assert_eq!(scene.check_against(&reflection, "Scene"), Ok(()));
+  assert_eq!(
+    scene.size() as i32,
+    reflection.uniform_block("Scene").unwrap().data_size
+  );
+
+  let particle = BlockLayout::builder(BlockLayoutEnum::Std430)
+    .field("velocity", Vec2)
+    .field("mass", Float)
+    .build();
+  let particles = BlockLayout::builder(BlockLayoutEnum::Std430)
+    .array("weights", Float, 3)
+    .field("m", Mat3x2)
+    .struct_array("particles", &particle, 2)
+    .array("rest", Vec3, 0)
+    .build();
+  assert_eq!(particles.check_against(&reflection, "Particles"), Ok(()));
+
+  // the std140 rules give different array strides
+  let wrong = BlockLayout::builder(BlockLayoutEnum::Std140)
+    .array("weights", Float, 3)
+    .build();
+  assert!(wrong.check_against(&reflection, "Particles").is_err());
+  assert!(wrong.check_against(&reflection, "NotABlock").is_err());
+
+  let mut bytes = scene.new_buffer();
+  scene.write(&mut bytes, "lights[1].intensity", 2.5_f32).unwrap();
+  let buffer = gl.create_buffers::<1>()[0].unwrap();
+  gl.named_buffer_storage(buffer, &bytes, BufferStorageFlags::empty());
+  gl.bind_buffer_base(IndexedBufferTargetEnum::Uniform, 0, Some(buffer));
+  gl.bind_buffer_range(
+    IndexedBufferTargetEnum::ShaderStorage,
+    1,
+    buffer,
+    0,
+    64,
+  );
+  let mut bound = [0; 2];
+  unsafe {
+    gl.GetIntegeri_v(GL_UNIFORM_BUFFER_BINDING, 0, &mut bound[0]);
+    gl.GetIntegeri_v(GL_SHADER_STORAGE_BUFFER_SIZE, 1, &mut bound[1]);
+  }
+  assert_eq!(bound, [buffer.0.get() as i32, 64]);
+  gl.bind_buffer_base(IndexedBufferTargetEnum::Uniform, 0, None);
+
+  gl.delete_buffers([Some(buffer)]);
+  gl.delete_program(program);
+  gl.delete_shader(shader);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
@@ -0,0 +1,488 @@
+use super::*;
+use bytemuck::Pod;
+use core::{
+  marker::PhantomData,
+  mem::{align_of, size_of, ManuallyDrop},
+  ptr::NonNull,
+};
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct BufferID(pub(crate) NonZeroU32);
+
+macro_rules! impl_bit_ops_for_flags {
+  ($t:ty) => {
+    impl $t {
+      /// No flags set.
+      pub const fn empty() -> Self {
+        Self(0)
+      }
+      /// If all the bits of `other` are also set in `self`.
+      pub const fn contains(self, other: Self) -> bool {
+        (self.0 & other.0) == other.0
+      }
+      /// The flags as a GL bitfield.
+      pub fn as_bitfield(self) -> GLbitfield {
+        GLbitfield(self.0)
+      }
+    }
+    impl core::ops::BitOr for $t {
+      type Output = Self;
+      fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+      }
+    }
+    impl core::ops::BitOrAssign for $t {
+      fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+      }
+    }
+  };
+}
+
+/// How a buffer's immutable storage can be used.
+///
+/// Combine flags with `|`. See
+/// [glBufferStorage](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBufferStorage.xhtml)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct BufferStorageFlags(pub(crate) u32);
+impl_bit_ops_for_flags!(BufferStorageFlags);
+impl BufferStorageFlags {
+  /// The contents can be updated with
+  /// [`named_buffer_sub_data`](GlFnsRusty::named_buffer_sub_data).
+  pub const DYNAMIC_STORAGE: Self = Self(GL_DYNAMIC_STORAGE_BIT.0);
+  /// The buffer can be mapped for reading.
+  pub const MAP_READ: Self = Self(GL_MAP_READ_BIT.0);
+  /// The buffer can be mapped for writing.
+  pub const MAP_WRITE: Self = Self(GL_MAP_WRITE_BIT.0);
+  /// The buffer can stay mapped while GL uses it.
+  pub const MAP_PERSISTENT: Self = Self(GL_MAP_PERSISTENT_BIT.0);
+  /// Persistent mappings are kept coherent with GL automatically.
+  pub const MAP_COHERENT: Self = Self(GL_MAP_COHERENT_BIT.0);
+  /// A hint that the storage should be in client memory.
+  pub const CLIENT_STORAGE: Self = Self(GL_CLIENT_STORAGE_BIT.0);
+}
+
+/// How a buffer mapping will be accessed.
+///
+/// Combine flags with `|`. See
+/// [glMapBufferRange](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glMapBufferRange.xhtml)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct MapAccessFlags(pub(crate) u32);
+impl_bit_ops_for_flags!(MapAccessFlags);
+impl MapAccessFlags {
+  /// The mapping can be read.
+  pub const READ: Self = Self(GL_MAP_READ_BIT.0);
+  /// The mapping can be written.
+  pub const WRITE: Self = Self(GL_MAP_WRITE_BIT.0);
+  /// The old contents of the mapped range can be discarded.
+  pub const INVALIDATE_RANGE: Self = Self(GL_MAP_INVALIDATE_RANGE_BIT.0);
+  /// The old contents of the whole buffer can be discarded.
+  pub const INVALIDATE_BUFFER: Self = Self(GL_MAP_INVALIDATE_BUFFER_BIT.0);
+  /// Writes are only visible after you [flush](BufferMapping::flush) them.
+  pub const FLUSH_EXPLICIT: Self = Self(GL_MAP_FLUSH_EXPLICIT_BIT.0);
+  /// GL won't wait for pending operations on the buffer before mapping it.
+  pub const UNSYNCHRONIZED: Self = Self(GL_MAP_UNSYNCHRONIZED_BIT.0);
+  /// The mapping can stay active while GL uses the buffer.
+  pub const PERSISTENT: Self = Self(GL_MAP_PERSISTENT_BIT.0);
+  /// A persistent mapping is kept coherent with GL automatically.
+  pub const COHERENT: Self = Self(GL_MAP_COHERENT_BIT.0);
+}
+
+/// The buffer targets that have indexed binding points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum IndexedBufferTargetEnum {
+  Uniform = GL_UNIFORM_BUFFER.0,
+  ShaderStorage = GL_SHADER_STORAGE_BUFFER.0,
+  AtomicCounter = GL_ATOMIC_COUNTER_BUFFER.0,
+  TransformFeedback = GL_TRANSFORM_FEEDBACK_BUFFER.0,
+}
+impl IndexedBufferTargetEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+}
+
+impl GlFnsRusty {
+  /// Attempts to create a given number of buffer objects.
+  pub fn create_buffers<const X: usize>(&self) -> [Option<BufferID>; X] {
+    let mut out = [None; X];
+    unsafe {
+      self.CreateBuffers(X.try_into().unwrap(), out.as_mut_ptr().cast())
+    };
+    out
+  }
+
+  /// Deletes the given list of buffer objects.
+  ///
+  /// Any mapping of a deleted buffer is unmapped, so no [`BufferMapping`] of
+  /// them may still be in use.
+  pub fn delete_buffers<const X: usize>(&self, buffers: [Option<BufferID>; X]) {
+    unsafe {
+      self.DeleteBuffers(X.try_into().unwrap(), buffers.as_ptr().cast())
+    };
+  }
+
+  /// Gives a buffer immutable storage, initialized with the data given.
+  ///
+  /// The size of the storage is the size of the slice, and can't be changed
+  /// later. To change the contents later, include
+  /// [`DYNAMIC_STORAGE`](BufferStorageFlags::DYNAMIC_STORAGE) or one of the
+  /// mapping flags.
+  ///
+  /// See [glNamedBufferStorage](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBufferStorage.xhtml)
+  pub fn named_buffer_storage<T: Pod>(
+    &self, buffer: BufferID, data: &[T], flags: BufferStorageFlags,
+  ) {
+    unsafe {
+      self.NamedBufferStorage(
+        buffer.0.get(),
+        core::mem::size_of_val(data).try_into().unwrap(),
+        data.as_ptr().cast(),
+        flags.as_bitfield(),
+      )
+    };
+  }
+
+  /// Gives a buffer immutable storage of `byte_size` bytes, with undefined
+  /// initial contents.
+  ///
+  /// See [`named_buffer_storage`](Self::named_buffer_storage)
+  pub fn named_buffer_storage_uninit(
+    &self, buffer: BufferID, byte_size: usize, flags: BufferStorageFlags,
+  ) {
+    unsafe {
+      self.NamedBufferStorage(
+        buffer.0.get(),
+        byte_size.try_into().unwrap(),
+        core::ptr::null(),
+        flags.as_bitfield(),
+      )
+    };
+  }
+
+  /// Replaces part of a buffer's contents, starting at `byte_offset`.
+  ///
+  /// The buffer's storage must have been made with
+  /// [`DYNAMIC_STORAGE`](BufferStorageFlags::DYNAMIC_STORAGE).
+  ///
+  /// See [glNamedBufferSubData](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBufferSubData.xhtml)
+  pub fn named_buffer_sub_data<T: Pod>(
+    &self, buffer: BufferID, byte_offset: usize, data: &[T],
+  ) {
+    unsafe {
+      self.NamedBufferSubData(
+        buffer.0.get(),
+        byte_offset.try_into().unwrap(),
+        core::mem::size_of_val(data).try_into().unwrap(),
+        data.as_ptr().cast(),
+      )
+    };
+  }
+
+  /// Copies part of a buffer's contents, starting at `byte_offset`, into the
+  /// slice given.
+  ///
+  /// See [glGetNamedBufferSubData](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetBufferSubData.xhtml)
+  pub fn get_named_buffer_sub_data<T: Pod>(
+    &self, buffer: BufferID, byte_offset: usize, out: &mut [T],
+  ) {
+    unsafe {
+      self.GetNamedBufferSubData(
+        buffer.0.get(),
+        byte_offset.try_into().unwrap(),
+        core::mem::size_of_val(out).try_into().unwrap(),
+        out.as_mut_ptr().cast(),
+      )
+    };
+  }
+
+  /// Copies `byte_count` bytes from one buffer to another (or within one
+  /// buffer, as long as the ranges don't overlap).
+  ///
+  /// See [glCopyNamedBufferSubData](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glCopyBufferSubData.xhtml)
+  pub fn copy_named_buffer_sub_data(
+    &self, read_buffer: BufferID, write_buffer: BufferID,
+    read_byte_offset: usize, write_byte_offset: usize, byte_count: usize,
+  ) {
+    unsafe {
+      self.CopyNamedBufferSubData(
+        read_buffer.0.get(),
+        write_buffer.0.get(),
+        read_byte_offset.try_into().unwrap(),
+        write_byte_offset.try_into().unwrap(),
+        byte_count.try_into().unwrap(),
+      )
+    };
+  }
+
+  /// Discards the entire contents of a buffer.
+  ///
+  /// See [glInvalidateBufferData](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glInvalidateBufferData.xhtml)
+  pub fn invalidate_buffer_data(&self, buffer: BufferID) {
+    unsafe { self.InvalidateBufferData(buffer.0.get()) };
+  }
+
+  /// Discards `byte_count` bytes of a buffer's contents, starting at
+  /// `byte_offset`.
+  ///
+  /// See [glInvalidateBufferSubData](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glInvalidateBufferSubData.xhtml)
+  pub fn invalidate_buffer_sub_data(
+    &self, buffer: BufferID, byte_offset: usize, byte_count: usize,
+  ) {
+    unsafe {
+      self.InvalidateBufferSubData(
+        buffer.0.get(),
+        byte_offset.try_into().unwrap(),
+        byte_count.try_into().unwrap(),
+      )
+    };
+  }
+
+  /// Maps `count` elements of a buffer, starting at `byte_offset`, into
+  /// client memory.
+  ///
+  /// The mapping is a slice of `T`, and is unmapped when it drops.
+  ///
+  /// See [glMapNamedBufferRange](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glMapBufferRange.xhtml)
+  ///
+  /// ## Failure
+  /// * If `byte_offset` isn't aligned for `T`, or if the mapping fails (eg:
+  ///   the range is out of bounds, or the buffer's storage flags don't allow
+  ///   the access), you get `None`.
+  ///
+  /// ## Safety
+  /// The slice points at driver memory that other GL calls can still free or
+  /// change, so while the mapping is alive:
+  /// * The buffer must not be deleted (which unmaps it, leaving the slice
+  ///   dangling), or have its storage respecified.
+  /// * The mapped range must not be changed through GL (eg:
+  ///   [`named_buffer_sub_data`](Self::named_buffer_sub_data), a copy into
+  ///   it, or shader writes).
+  /// * With [`PERSISTENT`](MapAccessFlags::PERSISTENT) access, GL must not
+  ///   read or write the mapped range (eg: a draw using it as a vertex
+  ///   buffer) while you access the slice, so you have to synchronize with
+  ///   fences yourself.
+  /// * Without [`READ`](MapAccessFlags::READ) access, the slice must only be
+  ///   written, never read (its contents are undefined, and reading them can
+  ///   even fault). Reading through `&` panics, but the `&mut [T]` that you
+  ///   write through can't stop reads (eg: `mapping[0] += 1`).
+  pub unsafe fn map_named_buffer_range<T: Pod>(
+    &self, buffer: BufferID, byte_offset: usize, count: usize,
+    access: MapAccessFlags,
+  ) -> Option<BufferMapping<'_, T>> {
+    if !byte_offset.is_multiple_of(align_of::<T>()) {
+      return None;
+    }
+    let byte_count = count.checked_mul(size_of::<T>())?;
+    let p = self.MapNamedBufferRange(
+      buffer.0.get(),
+      byte_offset.try_into().unwrap(),
+      byte_count.try_into().unwrap(),
+      access.as_bitfield(),
+    );
+    let ptr = NonNull::new(p.cast::<T>())?;
+    let mapping = BufferMapping {
+      gl: self,
+      buffer,
+      ptr,
+      len: count,
+      access,
+      _marker: PhantomData,
+    };
+    // GL promises at least 64 byte alignment of the buffer's start, but we
+    // don't want to rely on that.
+    if !(ptr.as_ptr() as usize).is_multiple_of(align_of::<T>()) {
+      return None;
+    }
+    Some(mapping)
+  }
+
+  /// Binds a whole buffer (`Some`) to an indexed binding point, or clears the
+  /// binding point (`None`).
+  ///
+  /// This is how a buffer is connected to the block with `layout(binding =
+  /// index)` in a shader.
+  ///
+  /// See [glBindBufferBase](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBindBufferBase.xhtml)
+  pub fn bind_buffer_base(
+    &self, target: IndexedBufferTargetEnum, index: u32,
+    opt_buffer: Option<BufferID>,
+  ) {
+    unsafe {
+      self.BindBufferBase(
+        target.as_enum(),
+        index,
+        core::mem::transmute::<Option<BufferID>, u32>(opt_buffer),
+      )
+    }
+  }
+
+  /// Binds `byte_count` bytes of a buffer, starting at `byte_offset`, to an
+  /// indexed binding point.
+  ///
+  /// The offset must be a multiple of the target's offset alignment (eg:
+  /// `GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT`).
+  ///
+  /// See [glBindBufferRange](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBindBufferRange.xhtml)
+  pub fn bind_buffer_range(
+    &self, target: IndexedBufferTargetEnum, index: u32, buffer: BufferID,
+    byte_offset: usize, byte_count: usize,
+  ) {
+    unsafe {
+      self.BindBufferRange(
+        target.as_enum(),
+        index,
+        buffer.0.get(),
+        byte_offset.try_into().unwrap(),
+        byte_count.try_into().unwrap(),
+      )
+    }
+  }
+}
+
+/// A mapped range of a buffer, accessed as a slice.
+///
+/// The buffer is unmapped when this drops (or when you call
+/// [`unmap`](Self::unmap)).
+pub struct BufferMapping<'gl, T: Pod> {
+  gl: &'gl GlFnsRusty,
+  buffer: BufferID,
+  ptr: NonNull<T>,
+  len: usize,
+  access: MapAccessFlags,
+  _marker: PhantomData<&'gl mut [T]>,
+}
+impl<'gl, T: Pod> BufferMapping<'gl, T> {
+  /// The buffer that's mapped.
+  pub fn buffer(&self) -> BufferID {
+    self.buffer
+  }
+
+  /// The access flags the buffer was mapped with.
+  pub fn access(&self) -> MapAccessFlags {
+    self.access
+  }
+
+  /// The number of elements mapped (even without read access).
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// If no elements are mapped.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Makes writes to `count` elements, starting at element `start`, visible
+  /// to GL.
+  ///
+  /// Only needed when the buffer was mapped with
+  /// [`FLUSH_EXPLICIT`](MapAccessFlags::FLUSH_EXPLICIT). The range is relative
+  /// to the start of the mapping.
+  ///
+  /// ## Panics
+  /// * If the range goes past the end of the mapping.
+  pub fn flush(&self, start: usize, count: usize) {
+    assert!(start.checked_add(count).is_some_and(|end| end <= self.len));
+    unsafe {
+      self.gl.FlushMappedNamedBufferRange(
+        self.buffer.0.get(),
+        (start * size_of::<T>()).try_into().unwrap(),
+        (count * size_of::<T>()).try_into().unwrap(),
+      )
+    };
+  }
+
+  /// Unmaps the buffer.
+  ///
+  /// **Returns:** `false` if the buffer's contents became corrupt while it
+  /// was mapped (eg: a screen mode change), in which case you must upload
+  /// the data again.
+  pub fn unmap(self) -> bool {
+    let this = ManuallyDrop::new(self);
+    let it_was_fine = unsafe { this.gl.UnmapNamedBuffer(this.buffer.0.get()) };
+    it_was_fine != 0
+  }
+}
+impl<'gl, T: Pod> core::ops::Deref for BufferMapping<'gl, T> {
+  type Target = [T];
+  /// ## Panics
+  /// * If the buffer wasn't mapped with [`READ`](MapAccessFlags::READ)
+  ///   access.
+  fn deref(&self) -> &[T] {
+    assert!(
+      self.access.contains(MapAccessFlags::READ),
+      "the buffer wasn't mapped for reading"
+    );
+    unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+  }
+}
+impl<'gl, T: Pod> core::ops::DerefMut for BufferMapping<'gl, T> {
+  /// ## Panics
+  /// * If the buffer wasn't mapped with [`WRITE`](MapAccessFlags::WRITE)
+  ///   access.
+  fn deref_mut(&mut self) -> &mut [T] {
+    assert!(
+      self.access.contains(MapAccessFlags::WRITE),
+      "the buffer wasn't mapped for writing"
+    );
+    unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+  }
+}
+impl<'gl, T: Pod> Drop for BufferMapping<'gl, T> {
+  fn drop(&mut self) {
+    let _i_dont_care = unsafe { self.gl.UnmapNamedBuffer(self.buffer.0.get()) };
+  }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_buffer_upload_map_and_copy() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let [a, b] = gl.create_buffers::<2>();
+  let (a, b) = (a.unwrap(), b.unwrap());
+  gl.named_buffer_storage(
+    a,
+    &[1_u32, 2, 3, 4],
+    BufferStorageFlags::DYNAMIC_STORAGE | BufferStorageFlags::MAP_READ,
+  );
+  gl.named_buffer_sub_data(a, 4, &[20_u32, 30]);
+  {
+    let mapping = unsafe {
+      gl.map_named_buffer_range::<u32>(a, 0, 4, MapAccessFlags::READ)
+    }
+    .unwrap();
+    assert_eq!(&mapping[..], &[1, 20, 30, 4]);
+  }
+  // misaligned offsets are rejected before calling GL.
+  assert!(unsafe {
+    gl.map_named_buffer_range::<u32>(a, 2, 1, MapAccessFlags::READ)
+  }
+  .is_none());
+
+  gl.named_buffer_storage_uninit(b, 16, BufferStorageFlags::MAP_WRITE);
+  {
+    let mut mapping = unsafe {
+      gl.map_named_buffer_range::<u32>(b, 0, 4, MapAccessFlags::WRITE)
+    }
+    .unwrap();
+    assert_eq!(mapping.len(), 4);
+    mapping.copy_from_slice(&[5, 6, 7, 8]);
+    assert!(mapping.unmap());
+  }
+  gl.copy_named_buffer_sub_data(a, b, 8, 0, 8);
+  let mut out = [0_u32; 4];
+  gl.get_named_buffer_sub_data(b, 0, &mut out);
+  assert_eq!(out, [30, 4, 7, 8]);
+
+  gl.invalidate_buffer_data(a);
+  gl.delete_buffers([Some(a), Some(b)]);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
@@ -0,0 +1,496 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct FramebufferID(pub(crate) NonZeroU32);
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct RenderbufferID(pub(crate) NonZeroU32);
+
+/// The points that images can be attached to within a framebuffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentEnum {
+  /// `GL_COLOR_ATTACHMENTi`
+  Color(u32),
+  Depth,
+  Stencil,
+  DepthStencil,
+}
+impl AttachmentEnum {
+  pub fn as_enum(self) -> GLenum {
+    match self {
+      Self::Color(i) => GLenum(GL_COLOR_ATTACHMENT0.0 + i),
+      Self::Depth => GL_DEPTH_ATTACHMENT,
+      Self::Stencil => GL_STENCIL_ATTACHMENT,
+      Self::DepthStencil => GL_DEPTH_STENCIL_ATTACHMENT,
+    }
+  }
+}
+
+/// The framebuffer binding points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum FramebufferTargetEnum {
+  /// Both of the other targets at once.
+  Framebuffer = GL_FRAMEBUFFER.0,
+  Draw = GL_DRAW_FRAMEBUFFER.0,
+  Read = GL_READ_FRAMEBUFFER.0,
+}
+impl FramebufferTargetEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+}
+
+/// Which buffers a blit copies.
+///
+/// Combine flags with `|`. See
+/// [glBlitFramebuffer](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBlitFramebuffer.xhtml)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct BlitMask(pub(crate) u32);
+impl_bit_ops_for_flags!(BlitMask);
+impl BlitMask {
+  pub const COLOR: Self = Self(GL_COLOR_BUFFER_BIT.0);
+  pub const DEPTH: Self = Self(GL_DEPTH_BUFFER_BIT.0);
+  pub const STENCIL: Self = Self(GL_STENCIL_BUFFER_BIT.0);
+}
+
+/// The result of checking a framebuffer's completeness.
+///
+/// The `Display` impl gives a short explanation of the problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramebufferStatusEnum {
+  Complete,
+  Undefined,
+  IncompleteAttachment,
+  IncompleteMissingAttachment,
+  IncompleteDrawBuffer,
+  IncompleteReadBuffer,
+  Unsupported,
+  IncompleteMultisample,
+  IncompleteLayerTargets,
+  /// The status couldn't be checked (the check itself was a GL error).
+  CheckFailed,
+  /// A status value that isn't listed in the GL spec.
+  Unknown(u32),
+}
+impl FramebufferStatusEnum {
+  /// Converts a value from `glCheckNamedFramebufferStatus`.
+  pub fn from_enum(status: GLenum) -> Self {
+    match status {
+      GL_FRAMEBUFFER_COMPLETE => Self::Complete,
+      GL_FRAMEBUFFER_UNDEFINED => Self::Undefined,
+      GL_FRAMEBUFFER_INCOMPLETE_ATTACHMENT => Self::IncompleteAttachment,
+      GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => {
+        Self::IncompleteMissingAttachment
+      }
+      GL_FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER => Self::IncompleteDrawBuffer,
+      GL_FRAMEBUFFER_INCOMPLETE_READ_BUFFER => Self::IncompleteReadBuffer,
+      GL_FRAMEBUFFER_UNSUPPORTED => Self::Unsupported,
+      GL_FRAMEBUFFER_INCOMPLETE_MULTISAMPLE => Self::IncompleteMultisample,
+      GL_FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS => Self::IncompleteLayerTargets,
+      GLenum(0) => Self::CheckFailed,
+      GLenum(other) => Self::Unknown(other),
+    }
+  }
+
+  /// If the framebuffer can be used.
+  pub fn is_complete(self) -> bool {
+    self == Self::Complete
+  }
+
+  /// A sentence explaining what the status means.
+  pub fn explanation(self) -> &'static str {
+    match self {
+      Self::Complete => "The framebuffer is complete.",
+      Self::Undefined => {
+        "The default framebuffer was checked, but there isn't one."
+      }
+      Self::IncompleteAttachment => {
+        "An attachment is incomplete: it has a zero size, or its format can't \
+        be rendered to at that attachment point."
+      }
+      Self::IncompleteMissingAttachment => {
+        "There are no images attached to the framebuffer."
+      }
+      Self::IncompleteDrawBuffer => {
+        "A draw buffer names a color attachment that has no image."
+      }
+      Self::IncompleteReadBuffer => {
+        "The read buffer names a color attachment that has no image."
+      }
+      Self::Unsupported => {
+        "This combination of attachment formats isn't supported by the \
+        implementation."
+      }
+      Self::IncompleteMultisample => {
+        "The attachments don't all have the same number of samples (or fixed \
+        sample locations setting)."
+      }
+      Self::IncompleteLayerTargets => {
+        "Some attachments are layered and others aren't, or the layered ones \
+        have different targets."
+      }
+      Self::CheckFailed => "The status check itself generated a GL error.",
+      Self::Unknown(_) => "GL returned an unknown framebuffer status.",
+    }
+  }
+}
+impl core::fmt::Display for FramebufferStatusEnum {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    match self {
+      Self::Unknown(status) => {
+        write!(f, "{} (0x{:04X})", self.explanation(), status)
+      }
+      _ => write!(f, "{:?}: {}", self, self.explanation()),
+    }
+  }
+}
+
+impl GlFnsRusty {
+  /// Attempts to create a given number of framebuffer objects.
+  pub fn create_framebuffers<const X: usize>(
+    &self,
+  ) -> [Option<FramebufferID>; X] {
+    let mut out = [None; X];
+    unsafe {
+      self.CreateFramebuffers(X.try_into().unwrap(), out.as_mut_ptr().cast())
+    };
+    out
+  }
+
+  /// Deletes the given list of framebuffer objects.
+  pub fn delete_framebuffers<const X: usize>(
+    &self, framebuffers: [Option<FramebufferID>; X],
+  ) {
+    unsafe {
+      self
+        .DeleteFramebuffers(X.try_into().unwrap(), framebuffers.as_ptr().cast())
+    };
+  }
+
+  /// Attempts to create a given number of renderbuffer objects.
+  pub fn create_renderbuffers<const X: usize>(
+    &self,
+  ) -> [Option<RenderbufferID>; X] {
+    let mut out = [None; X];
+    unsafe {
+      self.CreateRenderbuffers(X.try_into().unwrap(), out.as_mut_ptr().cast())
+    };
+    out
+  }
+
+  /// Deletes the given list of renderbuffer objects.
+  pub fn delete_renderbuffers<const X: usize>(
+    &self, renderbuffers: [Option<RenderbufferID>; X],
+  ) {
+    unsafe {
+      self.DeleteRenderbuffers(
+        X.try_into().unwrap(),
+        renderbuffers.as_ptr().cast(),
+      )
+    };
+  }
+
+  /// Gives storage to a renderbuffer.
+  ///
+  /// * `samples`: 0 for a normal renderbuffer, or the number of samples for
+  ///   a multisample one.
+  ///
+  /// See [glNamedRenderbufferStorageMultisample](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glRenderbufferStorageMultisample.xhtml)
+  pub fn named_renderbuffer_storage(
+    &self, renderbuffer: RenderbufferID, samples: i32,
+    internal_format: InternalFormatEnum, width: i32, height: i32,
+  ) {
+    unsafe {
+      self.NamedRenderbufferStorageMultisample(
+        renderbuffer.0.get(),
+        samples,
+        internal_format.as_enum(),
+        width,
+        height,
+      )
+    }
+  }
+
+  /// Binds a framebuffer (`Some`), or the default framebuffer (`None`).
+  pub fn bind_framebuffer(
+    &self, target: FramebufferTargetEnum,
+    opt_framebuffer: Option<FramebufferID>,
+  ) {
+    unsafe {
+      self.BindFramebuffer(
+        target.as_enum(),
+        core::mem::transmute::<Option<FramebufferID>, u32>(opt_framebuffer),
+      )
+    }
+  }
+
+  /// Attaches a level of a texture to a framebuffer (`Some`), or detaches
+  /// whatever is at that attachment point (`None`).
+  ///
+  /// Layered textures (3D, arrays, cube maps) are attached as layered images.
+  ///
+  /// See [glNamedFramebufferTexture](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glFramebufferTexture.xhtml)
+  pub fn named_framebuffer_texture(
+    &self, framebuffer: FramebufferID, attachment: AttachmentEnum,
+    opt_texture: Option<TextureID>, level: i32,
+  ) {
+    unsafe {
+      self.NamedFramebufferTexture(
+        framebuffer.0.get(),
+        attachment.as_enum(),
+        core::mem::transmute::<Option<TextureID>, u32>(opt_texture),
+        level,
+      )
+    }
+  }
+
+  /// Attaches one layer of a level of a layered texture to a framebuffer.
+  ///
+  /// For cube maps the layer is the face index (see
+  /// [`texture_cube_face_image`](Self::texture_cube_face_image)).
+  ///
+  /// See [glNamedFramebufferTextureLayer](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glFramebufferTextureLayer.xhtml)
+  pub fn named_framebuffer_texture_layer(
+    &self, framebuffer: FramebufferID, attachment: AttachmentEnum,
+    texture: TextureID, level: i32, layer: i32,
+  ) {
+    unsafe {
+      self.NamedFramebufferTextureLayer(
+        framebuffer.0.get(),
+        attachment.as_enum(),
+        texture.0.get(),
+        level,
+        layer,
+      )
+    }
+  }
+
+  /// Attaches a renderbuffer to a framebuffer (`Some`), or detaches whatever
+  /// is at that attachment point (`None`).
+  ///
+  /// See [glNamedFramebufferRenderbuffer](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glFramebufferRenderbuffer.xhtml)
+  pub fn named_framebuffer_renderbuffer(
+    &self, framebuffer: FramebufferID, attachment: AttachmentEnum,
+    opt_renderbuffer: Option<RenderbufferID>,
+  ) {
+    unsafe {
+      self.NamedFramebufferRenderbuffer(
+        framebuffer.0.get(),
+        attachment.as_enum(),
+        GL_RENDERBUFFER,
+        core::mem::transmute::<Option<RenderbufferID>, u32>(opt_renderbuffer),
+      )
+    }
+  }
+
+  /// Sets which color attachments the fragment shader outputs go to.
+  ///
+  /// Output `i` goes to `GL_COLOR_ATTACHMENTn` when `draw_buffers[i]` is
+  /// `Some(n)`, and is discarded when it's `None`.
+  ///
+  /// See [glNamedFramebufferDrawBuffers](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glDrawBuffers.xhtml)
+  pub fn named_framebuffer_draw_buffers(
+    &self, framebuffer: FramebufferID, draw_buffers: &[Option<u32>],
+  ) {
+    let buffers: Vec<GLenum> = draw_buffers
+      .iter()
+      .map(|opt_n| match opt_n {
+        Some(n) => AttachmentEnum::Color(*n).as_enum(),
+        None => GL_NONE,
+      })
+      .collect();
+    unsafe {
+      self.NamedFramebufferDrawBuffers(
+        framebuffer.0.get(),
+        buffers.len().try_into().unwrap(),
+        buffers.as_ptr(),
+      )
+    }
+  }
+
+  /// Sets which color attachment is read from by blits and pixel reads
+  /// (`None` turns reading off).
+  pub fn named_framebuffer_read_buffer(
+    &self, framebuffer: FramebufferID, opt_color: Option<u32>,
+  ) {
+    let mode = match opt_color {
+      Some(n) => AttachmentEnum::Color(n).as_enum(),
+      None => GL_NONE,
+    };
+    unsafe { self.NamedFramebufferReadBuffer(framebuffer.0.get(), mode) }
+  }
+
+  /// Copies a rectangle of pixels from one framebuffer to another.
+  ///
+  /// * `None` for either framebuffer means the default framebuffer.
+  /// * `src` and `dst` are `[x0, y0, x1, y1]` rectangles. If the sizes differ
+  ///   the image is scaled with `filter` (which must be `Nearest` when
+  ///   copying depth or stencil).
+  ///
+  /// See [glBlitNamedFramebuffer](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBlitFramebuffer.xhtml)
+  pub fn blit_named_framebuffer(
+    &self, opt_read: Option<FramebufferID>, opt_draw: Option<FramebufferID>,
+    src: [i32; 4], dst: [i32; 4], mask: BlitMask, filter: MagFilterEnum,
+  ) {
+    let [src_x0, src_y0, src_x1, src_y1] = src;
+    let [dst_x0, dst_y0, dst_x1, dst_y1] = dst;
+    unsafe {
+      self.BlitNamedFramebuffer(
+        core::mem::transmute::<Option<FramebufferID>, u32>(opt_read),
+        core::mem::transmute::<Option<FramebufferID>, u32>(opt_draw),
+        src_x0,
+        src_y0,
+        src_x1,
+        src_y1,
+        dst_x0,
+        dst_y0,
+        dst_x1,
+        dst_y1,
+        mask.as_bitfield(),
+        filter.as_enum(),
+      )
+    }
+  }
+
+  /// Checks if a framebuffer (`Some`), or the default framebuffer (`None`),
+  /// is complete when bound to the given target.
+  ///
+  /// See [glCheckNamedFramebufferStatus](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glCheckFramebufferStatus.xhtml)
+  pub fn check_named_framebuffer_status(
+    &self, opt_framebuffer: Option<FramebufferID>,
+    target: FramebufferTargetEnum,
+  ) -> FramebufferStatusEnum {
+    FramebufferStatusEnum::from_enum(unsafe {
+      self.CheckNamedFramebufferStatus(
+        core::mem::transmute::<Option<FramebufferID>, u32>(opt_framebuffer),
+        target.as_enum(),
+      )
+    })
+  }
+
+  /// Clears a color draw buffer of a framebuffer (`Some`), or of the default
+  /// framebuffer (`None`).
+  ///
+  /// See [`clear_color_draw_buffer`](Self::clear_color_draw_buffer).
+  pub fn clear_named_framebuffer_color(
+    &self, opt_framebuffer: Option<FramebufferID>, draw_buffer: i32,
+    color: [f32; 4],
+  ) {
+    unsafe {
+      self.ClearNamedFramebufferfv(
+        core::mem::transmute::<Option<FramebufferID>, u32>(opt_framebuffer),
+        GL_COLOR,
+        draw_buffer,
+        color.as_ptr(),
+      )
+    }
+  }
+
+  /// Clears the depth buffer of a framebuffer (`Some`), or of the default
+  /// framebuffer (`None`).
+  pub fn clear_named_framebuffer_depth(
+    &self, opt_framebuffer: Option<FramebufferID>, depth: f32,
+  ) {
+    unsafe {
+      self.ClearNamedFramebufferfv(
+        core::mem::transmute::<Option<FramebufferID>, u32>(opt_framebuffer),
+        GL_DEPTH,
+        0,
+        &depth,
+      )
+    }
+  }
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_FramebufferStatusEnum_from_enum() {
+  assert!(
+    FramebufferStatusEnum::from_enum(GL_FRAMEBUFFER_COMPLETE).is_complete()
+  );
+  let missing = FramebufferStatusEnum::from_enum(
+    GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT,
+  );
+  assert_eq!(missing, FramebufferStatusEnum::IncompleteMissingAttachment);
+  assert!(missing.to_string().starts_with("IncompleteMissingAttachment: "));
+  assert_eq!(
+    FramebufferStatusEnum::from_enum(GLenum(0)),
+    FramebufferStatusEnum::CheckFailed
+  );
+  assert_eq!(
+    FramebufferStatusEnum::from_enum(GLenum(0x1234)),
+    FramebufferStatusEnum::Unknown(0x1234)
+  );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_framebuffer_render_and_blit() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let [fbo_a, fbo_b] = gl.create_framebuffers::<2>();
+  let (fbo_a, fbo_b) = (fbo_a.unwrap(), fbo_b.unwrap());
+  assert_eq!(
+    gl.check_named_framebuffer_status(Some(fbo_a), FramebufferTargetEnum::Draw),
+    FramebufferStatusEnum::IncompleteMissingAttachment
+  );
+
+  let [tex_a, tex_b] = gl.create_textures::<2>(TextureTargetEnum::Texture2D);
+  let (tex_a, tex_b) = (tex_a.unwrap(), tex_b.unwrap());
+  gl.texture_storage_2d(tex_a, 1, InternalFormatEnum::RGBA8, 4, 4);
+  gl.texture_storage_2d(tex_b, 1, InternalFormatEnum::RGBA8, 2, 2);
+  let depth = gl.create_renderbuffers::<1>()[0].unwrap();
+  gl.named_renderbuffer_storage(
+    depth,
+    0,
+    InternalFormatEnum::DepthComponent24,
+    4,
+    4,
+  );
+  gl.named_framebuffer_texture(fbo_a, AttachmentEnum::Color(1), Some(tex_a), 0);
+  gl.named_framebuffer_renderbuffer(fbo_a, AttachmentEnum::Depth, Some(depth));
+  gl.named_framebuffer_draw_buffers(fbo_a, &[None, Some(1)]);
+  gl.named_framebuffer_read_buffer(fbo_a, Some(1));
+  gl.named_framebuffer_texture(fbo_b, AttachmentEnum::Color(0), Some(tex_b), 0);
+  for fbo in [fbo_a, fbo_b] {
+    let status =
+      gl.check_named_framebuffer_status(Some(fbo), FramebufferTargetEnum::Draw);
+    assert!(status.is_complete(), "{}", status);
+  }
+
+  gl.clear_named_framebuffer_color(Some(fbo_a), 1, [1.0, 0.0, 1.0, 1.0]);
+  gl.clear_named_framebuffer_depth(Some(fbo_a), 1.0);
+  gl.blit_named_framebuffer(
+    Some(fbo_a),
+    Some(fbo_b),
+    [0, 0, 4, 4],
+    [0, 0, 2, 2],
+    BlitMask::COLOR,
+    MagFilterEnum::Linear,
+  );
+  let mut out = [[0_u8; 4]; 4];
+  unsafe {
+    gl.GetTextureImage(
+      tex_b.0.get(),
+      0,
+      GL_RGBA,
+      GL_UNSIGNED_BYTE,
+      core::mem::size_of_val(&out) as i32,
+      out.as_mut_ptr().cast(),
+    )
+  };
+  assert_eq!(out, [[255, 0, 255, 255]; 4]);
+
+  gl.bind_framebuffer(FramebufferTargetEnum::Framebuffer, Some(fbo_a));
+  gl.bind_framebuffer(FramebufferTargetEnum::Framebuffer, None);
+  gl.delete_framebuffers([Some(fbo_a), Some(fbo_b)]);
+  gl.delete_renderbuffers([Some(depth)]);
+  gl.delete_textures([Some(tex_a), Some(tex_b)]);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
@@ -0,0 +1,1585 @@
+//! Every GL function that `gl46` loads, with its signature.
+//!
+//! Generated by `examples/gl_signatures.rs` from the `gl46` source,
+//! so don't edit it by hand. The `gl46` type aliases that aren't
+//! public are written out as the types they stand for.
+
+/// Calls the macro `$m` with every GL function that `gl46` loads, as
+/// `fn Name(arg: Type, ...) -> Ret;` items.
+///
+/// The names are the `GlFns` method names (without the `gl` prefix).
+macro_rules! gl_signatures {
+  ($m:ident) => {
+    $m! {
+      fn ActiveShaderProgram(pipeline: u32, program: u32);
+      fn ActiveTexture(texture: TextureUnit);
+      fn AttachShader(program: u32, shader: u32);
+      fn BeginConditionalRender(id: u32, mode: ConditionalRenderMode);
+      fn BeginQuery(target: QueryTarget, id: u32);
+      fn BeginQueryIndexed(target: QueryTarget, index: u32, id: u32);
+      fn BeginTransformFeedback(primitive_mode: PrimitiveType);
+      fn BindAttribLocation(program: u32, index: u32, name: *const u8);
+      fn BindBuffer(target: BufferTargetARB, buffer: u32);
+      fn BindBufferBase(target: BufferTargetARB, index: u32, buffer: u32);
+      fn BindBufferRange(
+        target: BufferTargetARB, index: u32, buffer: u32, offset: isize,
+        size: isize,
+      );
+      fn BindBuffersBase(
+        target: BufferTargetARB, first: u32, count: i32, buffers: *const u32,
+      );
+      fn BindBuffersRange(
+        target: BufferTargetARB, first: u32, count: i32, buffers: *const u32,
+        offsets: *const isize, sizes: *const isize,
+      );
+      fn BindFragDataLocation(program: u32, color: u32, name: *const u8);
+      fn BindFragDataLocationIndexed(
+        program: u32, color_number: u32, index: u32, name: *const u8,
+      );
+      fn BindFramebuffer(target: FramebufferTarget, framebuffer: u32);
+      fn BindImageTexture(
+        unit: u32, texture: u32, level: i32, layered: u8, layer: i32,
+        access: BufferAccessARB, format: InternalFormat,
+      );
+      fn BindImageTextures(first: u32, count: i32, textures: *const u32);
+      fn BindProgramPipeline(pipeline: u32);
+      fn BindRenderbuffer(target: RenderbufferTarget, renderbuffer: u32);
+      fn BindSampler(unit: u32, sampler: u32);
+      fn BindSamplers(first: u32, count: i32, samplers: *const u32);
+      fn BindTexture(target: TextureTarget, texture: u32);
+      fn BindTextureUnit(unit: u32, texture: u32);
+      fn BindTextures(first: u32, count: i32, textures: *const u32);
+      fn BindTransformFeedback(target: BindTransformFeedbackTarget, id: u32);
+      fn BindVertexArray(array: u32);
+      fn BindVertexBuffer(
+        bindingindex: u32, buffer: u32, offset: isize, stride: i32,
+      );
+      fn BindVertexBuffers(
+        first: u32, count: i32, buffers: *const u32, offsets: *const isize,
+        strides: *const i32,
+      );
+      fn BlendColor(red: f32, green: f32, blue: f32, alpha: f32);
+      fn BlendEquation(mode: BlendEquationModeEXT);
+      fn BlendEquationSeparate(
+        mode_rgb: BlendEquationModeEXT, mode_alpha: BlendEquationModeEXT,
+      );
+      fn BlendEquationSeparatei(
+        buf: u32, mode_rgb: BlendEquationModeEXT,
+        mode_alpha: BlendEquationModeEXT,
+      );
+      fn BlendEquationi(buf: u32, mode: BlendEquationModeEXT);
+      fn BlendFunc(sfactor: BlendingFactor, dfactor: BlendingFactor);
+      fn BlendFuncSeparate(
+        sfactor_rgb: BlendingFactor, dfactor_rgb: BlendingFactor,
+        sfactor_alpha: BlendingFactor, dfactor_alpha: BlendingFactor,
+      );
+      fn BlendFuncSeparatei(
+        buf: u32, src_rgb: BlendingFactor, dst_rgb: BlendingFactor,
+        src_alpha: BlendingFactor, dst_alpha: BlendingFactor,
+      );
+      fn BlendFunci(buf: u32, src: BlendingFactor, dst: BlendingFactor);
+      fn BlitFramebuffer(
+        src_x0: i32, src_y0: i32, src_x1: i32, src_y1: i32, dst_x0: i32,
+        dst_y0: i32, dst_x1: i32, dst_y1: i32, mask: GLbitfield,
+        filter: BlitFramebufferFilter,
+      );
+      fn BlitNamedFramebuffer(
+        read_framebuffer: u32, draw_framebuffer: u32, src_x0: i32, src_y0: i32,
+        src_x1: i32, src_y1: i32, dst_x0: i32, dst_y0: i32, dst_x1: i32,
+        dst_y1: i32, mask: GLbitfield, filter: BlitFramebufferFilter,
+      );
+      fn BufferData(
+        target: BufferTargetARB, size: isize, data: *const c_void,
+        usage: BufferUsageARB,
+      );
+      fn BufferStorage(
+        target: BufferStorageTarget, size: isize, data: *const c_void,
+        flags: GLbitfield,
+      );
+      fn BufferSubData(
+        target: BufferTargetARB, offset: isize, size: isize,
+        data: *const c_void,
+      );
+      fn CheckFramebufferStatus(target: FramebufferTarget) -> FramebufferStatus;
+      fn CheckNamedFramebufferStatus(
+        framebuffer: u32, target: FramebufferTarget,
+      ) -> FramebufferStatus;
+      fn ClampColor(target: ClampColorTargetARB, clamp: ClampColorModeARB);
+      fn Clear(mask: GLbitfield);
+      fn ClearBufferData(
+        target: BufferStorageTarget, internalformat: InternalFormat,
+        format: PixelFormat, type_: PixelType, data: *const c_void,
+      );
+      fn ClearBufferSubData(
+        target: BufferTargetARB, internalformat: InternalFormat, offset: isize,
+        size: isize, format: PixelFormat, type_: PixelType, data: *const c_void,
+      );
+      fn ClearBufferfi(
+        buffer: Buffer, drawbuffer: i32, depth: f32, stencil: i32,
+      );
+      fn ClearBufferfv(buffer: Buffer, drawbuffer: i32, value: *const f32);
+      fn ClearBufferiv(buffer: Buffer, drawbuffer: i32, value: *const i32);
+      fn ClearBufferuiv(buffer: Buffer, drawbuffer: i32, value: *const u32);
+      fn ClearColor(red: f32, green: f32, blue: f32, alpha: f32);
+      fn ClearDepth(depth: f64);
+      fn ClearDepthf(d: f32);
+      fn ClearNamedBufferData(
+        buffer: u32, internalformat: InternalFormat, format: PixelFormat,
+        type_: PixelType, data: *const c_void,
+      );
+      fn ClearNamedBufferSubData(
+        buffer: u32, internalformat: InternalFormat, offset: isize, size: isize,
+        format: PixelFormat, type_: PixelType, data: *const c_void,
+      );
+      fn ClearNamedFramebufferfi(
+        framebuffer: u32, buffer: Buffer, drawbuffer: i32, depth: f32,
+        stencil: i32,
+      );
+      fn ClearNamedFramebufferfv(
+        framebuffer: u32, buffer: Buffer, drawbuffer: i32, value: *const f32,
+      );
+      fn ClearNamedFramebufferiv(
+        framebuffer: u32, buffer: Buffer, drawbuffer: i32, value: *const i32,
+      );
+      fn ClearNamedFramebufferuiv(
+        framebuffer: u32, buffer: Buffer, drawbuffer: i32, value: *const u32,
+      );
+      fn ClearStencil(s: i32);
+      fn ClearTexImage(
+        texture: u32, level: i32, format: PixelFormat, type_: PixelType,
+        data: *const c_void,
+      );
+      fn ClearTexSubImage(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, zoffset: i32,
+        width: i32, height: i32, depth: i32, format: PixelFormat,
+        type_: PixelType, data: *const c_void,
+      );
+      fn ClientWaitSync(
+        sync: GLsync, flags: GLbitfield, timeout: u64,
+      ) -> SyncStatus;
+      fn ClipControl(origin: ClipControlOrigin, depth: ClipControlDepth);
+      fn ColorMask(red: u8, green: u8, blue: u8, alpha: u8);
+      fn ColorMaski(index: u32, r: u8, g: u8, b: u8, a: u8);
+      fn CompileShader(shader: u32);
+      fn CompressedTexImage1D(
+        target: TextureTarget, level: i32, internalformat: InternalFormat,
+        width: i32, border: i32, image_size: i32, data: *const c_void,
+      );
+      fn CompressedTexImage2D(
+        target: TextureTarget, level: i32, internalformat: InternalFormat,
+        width: i32, height: i32, border: i32, image_size: i32,
+        data: *const c_void,
+      );
+      fn CompressedTexImage3D(
+        target: TextureTarget, level: i32, internalformat: InternalFormat,
+        width: i32, height: i32, depth: i32, border: i32, image_size: i32,
+        data: *const c_void,
+      );
+      fn CompressedTexSubImage1D(
+        target: TextureTarget, level: i32, xoffset: i32, width: i32,
+        format: PixelFormat, image_size: i32, data: *const c_void,
+      );
+      fn CompressedTexSubImage2D(
+        target: TextureTarget, level: i32, xoffset: i32, yoffset: i32,
+        width: i32, height: i32, format: PixelFormat, image_size: i32,
+        data: *const c_void,
+      );
+      fn CompressedTexSubImage3D(
+        target: TextureTarget, level: i32, xoffset: i32, yoffset: i32,
+        zoffset: i32, width: i32, height: i32, depth: i32, format: PixelFormat,
+        image_size: i32, data: *const c_void,
+      );
+      fn CompressedTextureSubImage1D(
+        texture: u32, level: i32, xoffset: i32, width: i32, format: PixelFormat,
+        image_size: i32, data: *const c_void,
+      );
+      fn CompressedTextureSubImage2D(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, width: i32,
+        height: i32, format: PixelFormat, image_size: i32, data: *const c_void,
+      );
+      fn CompressedTextureSubImage3D(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, zoffset: i32,
+        width: i32, height: i32, depth: i32, format: PixelFormat,
+        image_size: i32, data: *const c_void,
+      );
+      fn CopyBufferSubData(
+        read_target: CopyBufferSubDataTarget,
+        write_target: CopyBufferSubDataTarget, read_offset: isize,
+        write_offset: isize, size: isize,
+      );
+      fn CopyImageSubData(
+        src_name: u32, src_target: CopyImageSubDataTarget, src_level: i32,
+        src_x: i32, src_y: i32, src_z: i32, dst_name: u32,
+        dst_target: CopyImageSubDataTarget, dst_level: i32, dst_x: i32,
+        dst_y: i32, dst_z: i32, src_width: i32, src_height: i32, src_depth: i32,
+      );
+      fn CopyNamedBufferSubData(
+        read_buffer: u32, write_buffer: u32, read_offset: isize,
+        write_offset: isize, size: isize,
+      );
+      fn CopyTexImage1D(
+        target: TextureTarget, level: i32, internalformat: InternalFormat,
+        x: i32, y: i32, width: i32, border: i32,
+      );
+      fn CopyTexImage2D(
+        target: TextureTarget, level: i32, internalformat: InternalFormat,
+        x: i32, y: i32, width: i32, height: i32, border: i32,
+      );
+      fn CopyTexSubImage1D(
+        target: TextureTarget, level: i32, xoffset: i32, x: i32, y: i32,
+        width: i32,
+      );
+      fn CopyTexSubImage2D(
+        target: TextureTarget, level: i32, xoffset: i32, yoffset: i32, x: i32,
+        y: i32, width: i32, height: i32,
+      );
+      fn CopyTexSubImage3D(
+        target: TextureTarget, level: i32, xoffset: i32, yoffset: i32,
+        zoffset: i32, x: i32, y: i32, width: i32, height: i32,
+      );
+      fn CopyTextureSubImage1D(
+        texture: u32, level: i32, xoffset: i32, x: i32, y: i32, width: i32,
+      );
+      fn CopyTextureSubImage2D(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, x: i32, y: i32,
+        width: i32, height: i32,
+      );
+      fn CopyTextureSubImage3D(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, zoffset: i32,
+        x: i32, y: i32, width: i32, height: i32,
+      );
+      fn CreateBuffers(n: i32, buffers: *mut u32);
+      fn CreateFramebuffers(n: i32, framebuffers: *mut u32);
+      fn CreateProgram() -> u32;
+      fn CreateProgramPipelines(n: i32, pipelines: *mut u32);
+      fn CreateQueries(target: QueryTarget, n: i32, ids: *mut u32);
+      fn CreateRenderbuffers(n: i32, renderbuffers: *mut u32);
+      fn CreateSamplers(n: i32, samplers: *mut u32);
+      fn CreateShader(type_: ShaderType) -> u32;
+      fn CreateShaderProgramv(
+        type_: ShaderType, count: i32, strings: *const *const u8,
+      ) -> u32;
+      fn CreateTextures(target: TextureTarget, n: i32, textures: *mut u32);
+      fn CreateTransformFeedbacks(n: i32, ids: *mut u32);
+      fn CreateVertexArrays(n: i32, arrays: *mut u32);
+      fn CullFace(mode: CullFaceMode);
+      fn DebugMessageCallback(callback: GLDEBUGPROC, user_param: *const c_void);
+      fn DebugMessageControl(
+        source: DebugSource, type_: DebugType, severity: DebugSeverity,
+        count: i32, ids: *const u32, enabled: u8,
+      );
+      fn DebugMessageInsert(
+        source: DebugSource, type_: DebugType, id: u32, severity: DebugSeverity,
+        length: i32, buf: *const u8,
+      );
+      fn DeleteBuffers(n: i32, buffers: *const u32);
+      fn DeleteFramebuffers(n: i32, framebuffers: *const u32);
+      fn DeleteProgram(program: u32);
+      fn DeleteProgramPipelines(n: i32, pipelines: *const u32);
+      fn DeleteQueries(n: i32, ids: *const u32);
+      fn DeleteRenderbuffers(n: i32, renderbuffers: *const u32);
+      fn DeleteSamplers(count: i32, samplers: *const u32);
+      fn DeleteShader(shader: u32);
+      fn DeleteSync(sync: GLsync);
+      fn DeleteTextures(n: i32, textures: *const u32);
+      fn DeleteTransformFeedbacks(n: i32, ids: *const u32);
+      fn DeleteVertexArrays(n: i32, arrays: *const u32);
+      fn DepthFunc(func: DepthFunction);
+      fn DepthMask(flag: u8);
+      fn DepthRange(n: f64, f: f64);
+      fn DepthRangeArrayv(first: u32, count: i32, v: *const f64);
+      fn DepthRangeIndexed(index: u32, n: f64, f: f64);
+      fn DepthRangef(n: f32, f: f32);
+      fn DetachShader(program: u32, shader: u32);
+      fn Disable(cap: EnableCap);
+      fn DisableVertexArrayAttrib(vaobj: u32, index: u32);
+      fn DisableVertexAttribArray(index: u32);
+      fn Disablei(target: EnableCap, index: u32);
+      fn DispatchCompute(
+        num_groups_x: u32, num_groups_y: u32, num_groups_z: u32,
+      );
+      fn DispatchComputeIndirect(indirect: isize);
+      fn DrawArrays(mode: PrimitiveType, first: i32, count: i32);
+      fn DrawArraysIndirect(mode: PrimitiveType, indirect: *const c_void);
+      fn DrawArraysInstanced(
+        mode: PrimitiveType, first: i32, count: i32, instancecount: i32,
+      );
+      fn DrawArraysInstancedBaseInstance(
+        mode: PrimitiveType, first: i32, count: i32, instancecount: i32,
+        baseinstance: u32,
+      );
+      fn DrawBuffer(buf: DrawBufferMode);
+      fn DrawBuffers(n: i32, bufs: *const DrawBufferMode);
+      fn DrawElements(
+        mode: PrimitiveType, count: i32, type_: DrawElementsType,
+        indices: *const c_void,
+      );
+      fn DrawElementsBaseVertex(
+        mode: PrimitiveType, count: i32, type_: DrawElementsType,
+        indices: *const c_void, basevertex: i32,
+      );
+      fn DrawElementsIndirect(
+        mode: PrimitiveType, type_: DrawElementsType, indirect: *const c_void,
+      );
+      fn DrawElementsInstanced(
+        mode: PrimitiveType, count: i32, type_: DrawElementsType,
+        indices: *const c_void, instancecount: i32,
+      );
+      fn DrawElementsInstancedBaseInstance(
+        mode: PrimitiveType, count: i32, type_: PrimitiveType,
+        indices: *const c_void, instancecount: i32, baseinstance: u32,
+      );
+      fn DrawElementsInstancedBaseVertex(
+        mode: PrimitiveType, count: i32, type_: DrawElementsType,
+        indices: *const c_void, instancecount: i32, basevertex: i32,
+      );
+      fn DrawElementsInstancedBaseVertexBaseInstance(
+        mode: PrimitiveType, count: i32, type_: DrawElementsType,
+        indices: *const c_void, instancecount: i32, basevertex: i32,
+        baseinstance: u32,
+      );
+      fn DrawRangeElements(
+        mode: PrimitiveType, start: u32, end: u32, count: i32,
+        type_: DrawElementsType, indices: *const c_void,
+      );
+      fn DrawRangeElementsBaseVertex(
+        mode: PrimitiveType, start: u32, end: u32, count: i32,
+        type_: DrawElementsType, indices: *const c_void, basevertex: i32,
+      );
+      fn DrawTransformFeedback(mode: PrimitiveType, id: u32);
+      fn DrawTransformFeedbackInstanced(
+        mode: PrimitiveType, id: u32, instancecount: i32,
+      );
+      fn DrawTransformFeedbackStream(mode: PrimitiveType, id: u32, stream: u32);
+      fn DrawTransformFeedbackStreamInstanced(
+        mode: PrimitiveType, id: u32, stream: u32, instancecount: i32,
+      );
+      fn Enable(cap: EnableCap);
+      fn EnableVertexArrayAttrib(vaobj: u32, index: u32);
+      fn EnableVertexAttribArray(index: u32);
+      fn Enablei(target: EnableCap, index: u32);
+      fn EndConditionalRender();
+      fn EndQuery(target: QueryTarget);
+      fn EndQueryIndexed(target: QueryTarget, index: u32);
+      fn EndTransformFeedback();
+      fn FenceSync(condition: SyncCondition, flags: GLbitfield) -> GLsync;
+      fn Finish();
+      fn Flush();
+      fn FlushMappedBufferRange(
+        target: BufferTargetARB, offset: isize, length: isize,
+      );
+      fn FlushMappedNamedBufferRange(buffer: u32, offset: isize, length: isize);
+      fn FramebufferParameteri(
+        target: FramebufferTarget, pname: FramebufferParameterName, param: i32,
+      );
+      fn FramebufferRenderbuffer(
+        target: FramebufferTarget, attachment: FramebufferAttachment,
+        renderbuffertarget: RenderbufferTarget, renderbuffer: u32,
+      );
+      fn FramebufferTexture(
+        target: FramebufferTarget, attachment: FramebufferAttachment,
+        texture: u32, level: i32,
+      );
+      fn FramebufferTexture1D(
+        target: FramebufferTarget, attachment: FramebufferAttachment,
+        textarget: TextureTarget, texture: u32, level: i32,
+      );
+      fn FramebufferTexture2D(
+        target: FramebufferTarget, attachment: FramebufferAttachment,
+        textarget: TextureTarget, texture: u32, level: i32,
+      );
+      fn FramebufferTexture3D(
+        target: FramebufferTarget, attachment: FramebufferAttachment,
+        textarget: TextureTarget, texture: u32, level: i32, zoffset: i32,
+      );
+      fn FramebufferTextureLayer(
+        target: FramebufferTarget, attachment: FramebufferAttachment,
+        texture: u32, level: i32, layer: i32,
+      );
+      fn FrontFace(mode: FrontFaceDirection);
+      fn GenBuffers(n: i32, buffers: *mut u32);
+      fn GenFramebuffers(n: i32, framebuffers: *mut u32);
+      fn GenProgramPipelines(n: i32, pipelines: *mut u32);
+      fn GenQueries(n: i32, ids: *mut u32);
+      fn GenRenderbuffers(n: i32, renderbuffers: *mut u32);
+      fn GenSamplers(count: i32, samplers: *mut u32);
+      fn GenTextures(n: i32, textures: *mut u32);
+      fn GenTransformFeedbacks(n: i32, ids: *mut u32);
+      fn GenVertexArrays(n: i32, arrays: *mut u32);
+      fn GenerateMipmap(target: TextureTarget);
+      fn GenerateTextureMipmap(texture: u32);
+      fn GetActiveAtomicCounterBufferiv(
+        program: u32, buffer_index: u32, pname: AtomicCounterBufferPName,
+        params: *mut i32,
+      );
+      fn GetActiveAttrib(
+        program: u32, index: u32, buf_size: i32, length: *mut i32,
+        size: *mut i32, type_: *mut AttributeType, name: *mut u8,
+      );
+      fn GetActiveSubroutineName(
+        program: u32, shadertype: ShaderType, index: u32, buf_size: i32,
+        length: *mut i32, name: *mut u8,
+      );
+      fn GetActiveSubroutineUniformName(
+        program: u32, shadertype: ShaderType, index: u32, buf_size: i32,
+        length: *mut i32, name: *mut u8,
+      );
+      fn GetActiveSubroutineUniformiv(
+        program: u32, shadertype: ShaderType, index: u32,
+        pname: SubroutineParameterName, values: *mut i32,
+      );
+      fn GetActiveUniform(
+        program: u32, index: u32, buf_size: i32, length: *mut i32,
+        size: *mut i32, type_: *mut UniformType, name: *mut u8,
+      );
+      fn GetActiveUniformBlockName(
+        program: u32, uniform_block_index: u32, buf_size: i32, length: *mut i32,
+        uniform_block_name: *mut u8,
+      );
+      fn GetActiveUniformBlockiv(
+        program: u32, uniform_block_index: u32, pname: UniformBlockPName,
+        params: *mut i32,
+      );
+      fn GetActiveUniformName(
+        program: u32, uniform_index: u32, buf_size: i32, length: *mut i32,
+        uniform_name: *mut u8,
+      );
+      fn GetActiveUniformsiv(
+        program: u32, uniform_count: i32, uniform_indices: *const u32,
+        pname: UniformPName, params: *mut i32,
+      );
+      fn GetAttachedShaders(
+        program: u32, max_count: i32, count: *mut i32, shaders: *mut u32,
+      );
+      fn GetAttribLocation(program: u32, name: *const u8) -> i32;
+      fn GetBooleani_v(target: BufferTargetARB, index: u32, data: *mut u8);
+      fn GetBooleanv(pname: GetPName, data: *mut u8);
+      fn GetBufferParameteri64v(
+        target: BufferTargetARB, pname: BufferPNameARB, params: *mut i64,
+      );
+      fn GetBufferParameteriv(
+        target: BufferTargetARB, pname: BufferPNameARB, params: *mut i32,
+      );
+      fn GetBufferPointerv(
+        target: BufferTargetARB, pname: BufferPointerNameARB,
+        params: *mut *mut c_void,
+      );
+      fn GetBufferSubData(
+        target: BufferTargetARB, offset: isize, size: isize, data: *mut c_void,
+      );
+      fn GetCompressedTexImage(
+        target: TextureTarget, level: i32, img: *mut c_void,
+      );
+      fn GetCompressedTextureImage(
+        texture: u32, level: i32, buf_size: i32, pixels: *mut c_void,
+      );
+      fn GetCompressedTextureSubImage(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, zoffset: i32,
+        width: i32, height: i32, depth: i32, buf_size: i32, pixels: *mut c_void,
+      );
+      fn GetDebugMessageLog(
+        count: u32, buf_size: i32, sources: *mut DebugSource,
+        types: *mut DebugType, ids: *mut u32, severities: *mut DebugSeverity,
+        lengths: *mut i32, message_log: *mut u8,
+      ) -> u32;
+      fn GetDoublei_v(target: GetPName, index: u32, data: *mut f64);
+      fn GetDoublev(pname: GetPName, data: *mut f64);
+      fn GetError() -> ErrorCode;
+      fn GetFloati_v(target: GetPName, index: u32, data: *mut f32);
+      fn GetFloatv(pname: GetPName, data: *mut f32);
+      fn GetFragDataIndex(program: u32, name: *const u8) -> i32;
+      fn GetFragDataLocation(program: u32, name: *const u8) -> i32;
+      fn GetFramebufferAttachmentParameteriv(
+        target: FramebufferTarget, attachment: FramebufferAttachment,
+        pname: FramebufferAttachmentParameterName, params: *mut i32,
+      );
+      fn GetFramebufferParameteriv(
+        target: FramebufferTarget, pname: FramebufferAttachmentParameterName,
+        params: *mut i32,
+      );
+      fn GetGraphicsResetStatus() -> GraphicsResetStatus;
+      fn GetInteger64i_v(target: GetPName, index: u32, data: *mut i64);
+      fn GetInteger64v(pname: GetPName, data: *mut i64);
+      fn GetIntegeri_v(target: GetPName, index: u32, data: *mut i32);
+      fn GetIntegerv(pname: GetPName, data: *mut i32);
+      fn GetInternalformati64v(
+        target: TextureTarget, internalformat: InternalFormat,
+        pname: InternalFormatPName, count: i32, params: *mut i64,
+      );
+      fn GetInternalformativ(
+        target: TextureTarget, internalformat: InternalFormat,
+        pname: InternalFormatPName, count: i32, params: *mut i32,
+      );
+      fn GetMultisamplefv(
+        pname: GetMultisamplePNameNV, index: u32, val: *mut f32,
+      );
+      fn GetNamedBufferParameteri64v(
+        buffer: u32, pname: BufferPNameARB, params: *mut i64,
+      );
+      fn GetNamedBufferParameteriv(
+        buffer: u32, pname: BufferPNameARB, params: *mut i32,
+      );
+      fn GetNamedBufferPointerv(
+        buffer: u32, pname: BufferPointerNameARB, params: *mut *mut c_void,
+      );
+      fn GetNamedBufferSubData(
+        buffer: u32, offset: isize, size: isize, data: *mut c_void,
+      );
+      fn GetNamedFramebufferAttachmentParameteriv(
+        framebuffer: u32, attachment: FramebufferAttachment,
+        pname: FramebufferAttachmentParameterName, params: *mut i32,
+      );
+      fn GetNamedFramebufferParameteriv(
+        framebuffer: u32, pname: GetFramebufferParameter, param: *mut i32,
+      );
+      fn GetNamedRenderbufferParameteriv(
+        renderbuffer: u32, pname: RenderbufferParameterName, params: *mut i32,
+      );
+      fn GetObjectLabel(
+        identifier: ObjectIdentifier, name: u32, buf_size: i32,
+        length: *mut i32, label: *mut u8,
+      );
+      fn GetObjectPtrLabel(
+        ptr: *const c_void, buf_size: i32, length: *mut i32, label: *mut u8,
+      );
+      fn GetPointerv(pname: GetPointervPName, params: *mut *mut c_void);
+      fn GetProgramBinary(
+        program: u32, buf_size: i32, length: *mut i32,
+        binary_format: *mut GLenum, binary: *mut c_void,
+      );
+      fn GetProgramInfoLog(
+        program: u32, buf_size: i32, length: *mut i32, info_log: *mut u8,
+      );
+      fn GetProgramInterfaceiv(
+        program: u32, program_interface: ProgramInterface,
+        pname: ProgramInterfacePName, params: *mut i32,
+      );
+      fn GetProgramPipelineInfoLog(
+        pipeline: u32, buf_size: i32, length: *mut i32, info_log: *mut u8,
+      );
+      fn GetProgramPipelineiv(
+        pipeline: u32, pname: PipelineParameterName, params: *mut i32,
+      );
+      fn GetProgramResourceIndex(
+        program: u32, program_interface: ProgramInterface, name: *const u8,
+      ) -> u32;
+      fn GetProgramResourceLocation(
+        program: u32, program_interface: ProgramInterface, name: *const u8,
+      ) -> i32;
+      fn GetProgramResourceLocationIndex(
+        program: u32, program_interface: ProgramInterface, name: *const u8,
+      ) -> i32;
+      fn GetProgramResourceName(
+        program: u32, program_interface: ProgramInterface, index: u32,
+        buf_size: i32, length: *mut i32, name: *mut u8,
+      );
+      fn GetProgramResourceiv(
+        program: u32, program_interface: ProgramInterface, index: u32,
+        prop_count: i32, props: *const ProgramResourceProperty, count: i32,
+        length: *mut i32, params: *mut i32,
+      );
+      fn GetProgramStageiv(
+        program: u32, shadertype: ShaderType, pname: ProgramStagePName,
+        values: *mut i32,
+      );
+      fn GetProgramiv(
+        program: u32, pname: ProgramPropertyARB, params: *mut i32,
+      );
+      fn GetQueryBufferObjecti64v(
+        id: u32, buffer: u32, pname: QueryObjectParameterName, offset: isize,
+      );
+      fn GetQueryBufferObjectiv(
+        id: u32, buffer: u32, pname: QueryObjectParameterName, offset: isize,
+      );
+      fn GetQueryBufferObjectui64v(
+        id: u32, buffer: u32, pname: QueryObjectParameterName, offset: isize,
+      );
+      fn GetQueryBufferObjectuiv(
+        id: u32, buffer: u32, pname: QueryObjectParameterName, offset: isize,
+      );
+      fn GetQueryIndexediv(
+        target: QueryTarget, index: u32, pname: QueryParameterName,
+        params: *mut i32,
+      );
+      fn GetQueryObjecti64v(
+        id: u32, pname: QueryObjectParameterName, params: *mut i64,
+      );
+      fn GetQueryObjectiv(
+        id: u32, pname: QueryObjectParameterName, params: *mut i32,
+      );
+      fn GetQueryObjectui64v(
+        id: u32, pname: QueryObjectParameterName, params: *mut u64,
+      );
+      fn GetQueryObjectuiv(
+        id: u32, pname: QueryObjectParameterName, params: *mut u32,
+      );
+      fn GetQueryiv(
+        target: QueryTarget, pname: QueryParameterName, params: *mut i32,
+      );
+      fn GetRenderbufferParameteriv(
+        target: RenderbufferTarget, pname: RenderbufferParameterName,
+        params: *mut i32,
+      );
+      fn GetSamplerParameterIiv(
+        sampler: u32, pname: SamplerParameterI, params: *mut i32,
+      );
+      fn GetSamplerParameterIuiv(
+        sampler: u32, pname: SamplerParameterI, params: *mut u32,
+      );
+      fn GetSamplerParameterfv(
+        sampler: u32, pname: SamplerParameterF, params: *mut f32,
+      );
+      fn GetSamplerParameteriv(
+        sampler: u32, pname: SamplerParameterI, params: *mut i32,
+      );
+      fn GetShaderInfoLog(
+        shader: u32, buf_size: i32, length: *mut i32, info_log: *mut u8,
+      );
+      fn GetShaderPrecisionFormat(
+        shadertype: ShaderType, precisiontype: PrecisionType,
+        range: *mut [i32; 2], precision: *mut i32,
+      );
+      fn GetShaderSource(
+        shader: u32, buf_size: i32, length: *mut i32, source: *mut u8,
+      );
+      fn GetShaderiv(shader: u32, pname: ShaderParameterName, params: *mut i32);
+      fn GetString(name: StringName) -> *const u8;
+      fn GetStringi(name: StringName, index: u32) -> *const u8;
+      fn GetSubroutineIndex(
+        program: u32, shadertype: ShaderType, name: *const u8,
+      ) -> u32;
+      fn GetSubroutineUniformLocation(
+        program: u32, shadertype: ShaderType, name: *const u8,
+      ) -> i32;
+      fn GetSynciv(
+        sync: GLsync, pname: SyncParameterName, count: i32, length: *mut i32,
+        values: *mut i32,
+      );
+      fn GetTexImage(
+        target: TextureTarget, level: i32, format: PixelFormat,
+        type_: PixelType, pixels: *mut c_void,
+      );
+      fn GetTexLevelParameterfv(
+        target: TextureTarget, level: i32, pname: GetTextureParameter,
+        params: *mut f32,
+      );
+      fn GetTexLevelParameteriv(
+        target: TextureTarget, level: i32, pname: GetTextureParameter,
+        params: *mut i32,
+      );
+      fn GetTexParameterIiv(
+        target: TextureTarget, pname: GetTextureParameter, params: *mut i32,
+      );
+      fn GetTexParameterIuiv(
+        target: TextureTarget, pname: GetTextureParameter, params: *mut u32,
+      );
+      fn GetTexParameterfv(
+        target: TextureTarget, pname: GetTextureParameter, params: *mut f32,
+      );
+      fn GetTexParameteriv(
+        target: TextureTarget, pname: GetTextureParameter, params: *mut i32,
+      );
+      fn GetTextureImage(
+        texture: u32, level: i32, format: PixelFormat, type_: PixelType,
+        buf_size: i32, pixels: *mut c_void,
+      );
+      fn GetTextureLevelParameterfv(
+        texture: u32, level: i32, pname: GetTextureParameter, params: *mut f32,
+      );
+      fn GetTextureLevelParameteriv(
+        texture: u32, level: i32, pname: GetTextureParameter, params: *mut i32,
+      );
+      fn GetTextureParameterIiv(
+        texture: u32, pname: GetTextureParameter, params: *mut i32,
+      );
+      fn GetTextureParameterIuiv(
+        texture: u32, pname: GetTextureParameter, params: *mut u32,
+      );
+      fn GetTextureParameterfv(
+        texture: u32, pname: GetTextureParameter, params: *mut f32,
+      );
+      fn GetTextureParameteriv(
+        texture: u32, pname: GetTextureParameter, params: *mut i32,
+      );
+      fn GetTextureSubImage(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, zoffset: i32,
+        width: i32, height: i32, depth: i32, format: PixelFormat,
+        type_: PixelType, buf_size: i32, pixels: *mut c_void,
+      );
+      fn GetTransformFeedbackVarying(
+        program: u32, index: u32, buf_size: i32, length: *mut i32,
+        size: *mut i32, type_: *mut AttributeType, name: *mut u8,
+      );
+      fn GetTransformFeedbacki64_v(
+        xfb: u32, pname: TransformFeedbackPName, index: u32, param: *mut i64,
+      );
+      fn GetTransformFeedbacki_v(
+        xfb: u32, pname: TransformFeedbackPName, index: u32, param: *mut i32,
+      );
+      fn GetTransformFeedbackiv(
+        xfb: u32, pname: TransformFeedbackPName, param: *mut i32,
+      );
+      fn GetUniformBlockIndex(
+        program: u32, uniform_block_name: *const u8,
+      ) -> u32;
+      fn GetUniformIndices(
+        program: u32, uniform_count: i32, uniform_names: *const *const u8,
+        uniform_indices: *mut u32,
+      );
+      fn GetUniformLocation(program: u32, name: *const u8) -> i32;
+      fn GetUniformSubroutineuiv(
+        shadertype: ShaderType, location: i32, params: *mut u32,
+      );
+      fn GetUniformdv(program: u32, location: i32, params: *mut f64);
+      fn GetUniformfv(program: u32, location: i32, params: *mut f32);
+      fn GetUniformiv(program: u32, location: i32, params: *mut i32);
+      fn GetUniformuiv(program: u32, location: i32, params: *mut u32);
+      fn GetVertexArrayIndexed64iv(
+        vaobj: u32, index: u32, pname: VertexArrayPName, param: *mut i64,
+      );
+      fn GetVertexArrayIndexediv(
+        vaobj: u32, index: u32, pname: VertexArrayPName, param: *mut i32,
+      );
+      fn GetVertexArrayiv(vaobj: u32, pname: VertexArrayPName, param: *mut i32);
+      fn GetVertexAttribIiv(
+        index: u32, pname: VertexAttribEnum, params: *mut i32,
+      );
+      fn GetVertexAttribIuiv(
+        index: u32, pname: VertexAttribEnum, params: *mut u32,
+      );
+      fn GetVertexAttribLdv(
+        index: u32, pname: VertexAttribEnum, params: *mut f64,
+      );
+      fn GetVertexAttribPointerv(
+        index: u32, pname: VertexAttribPointerPropertyARB,
+        pointer: *mut *mut c_void,
+      );
+      fn GetVertexAttribdv(
+        index: u32, pname: VertexAttribPropertyARB, params: *mut [f64; 4],
+      );
+      fn GetVertexAttribfv(
+        index: u32, pname: VertexAttribPropertyARB, params: *mut [f32; 4],
+      );
+      fn GetVertexAttribiv(
+        index: u32, pname: VertexAttribPropertyARB, params: *mut [i32; 4],
+      );
+      fn GetnCompressedTexImage(
+        target: TextureTarget, lod: i32, buf_size: i32, pixels: *mut c_void,
+      );
+      fn GetnTexImage(
+        target: TextureTarget, level: i32, format: PixelFormat,
+        type_: PixelType, buf_size: i32, pixels: *mut c_void,
+      );
+      fn GetnUniformdv(
+        program: u32, location: i32, buf_size: i32, params: *mut f64,
+      );
+      fn GetnUniformfv(
+        program: u32, location: i32, buf_size: i32, params: *mut f32,
+      );
+      fn GetnUniformiv(
+        program: u32, location: i32, buf_size: i32, params: *mut i32,
+      );
+      fn GetnUniformuiv(
+        program: u32, location: i32, buf_size: i32, params: *mut u32,
+      );
+      fn Hint(target: HintTarget, mode: HintMode);
+      fn InvalidateBufferData(buffer: u32);
+      fn InvalidateBufferSubData(buffer: u32, offset: isize, length: isize);
+      fn InvalidateFramebuffer(
+        target: FramebufferTarget, num_attachments: i32,
+        attachments: *const InvalidateFramebufferAttachment,
+      );
+      fn InvalidateNamedFramebufferData(
+        framebuffer: u32, num_attachments: i32,
+        attachments: *const FramebufferAttachment,
+      );
+      fn InvalidateNamedFramebufferSubData(
+        framebuffer: u32, num_attachments: i32,
+        attachments: *const FramebufferAttachment, x: i32, y: i32, width: i32,
+        height: i32,
+      );
+      fn InvalidateSubFramebuffer(
+        target: FramebufferTarget, num_attachments: i32,
+        attachments: *const InvalidateFramebufferAttachment, x: i32, y: i32,
+        width: i32, height: i32,
+      );
+      fn InvalidateTexImage(texture: u32, level: i32);
+      fn InvalidateTexSubImage(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, zoffset: i32,
+        width: i32, height: i32, depth: i32,
+      );
+      fn IsBuffer(buffer: u32) -> u8;
+      fn IsEnabled(cap: EnableCap) -> u8;
+      fn IsEnabledi(target: EnableCap, index: u32) -> u8;
+      fn IsFramebuffer(framebuffer: u32) -> u8;
+      fn IsProgram(program: u32) -> u8;
+      fn IsProgramPipeline(pipeline: u32) -> u8;
+      fn IsQuery(id: u32) -> u8;
+      fn IsRenderbuffer(renderbuffer: u32) -> u8;
+      fn IsSampler(sampler: u32) -> u8;
+      fn IsShader(shader: u32) -> u8;
+      fn IsSync(sync: GLsync) -> u8;
+      fn IsTexture(texture: u32) -> u8;
+      fn IsTransformFeedback(id: u32) -> u8;
+      fn IsVertexArray(array: u32) -> u8;
+      fn LineWidth(width: f32);
+      fn LinkProgram(program: u32);
+      fn LogicOp(opcode: LogicOp);
+      fn MapBuffer(
+        target: BufferTargetARB, access: BufferAccessARB,
+      ) -> *mut c_void;
+      fn MapBufferRange(
+        target: BufferTargetARB, offset: isize, length: isize,
+        access: GLbitfield,
+      ) -> *mut c_void;
+      fn MapNamedBuffer(buffer: u32, access: BufferAccessARB) -> *mut c_void;
+      fn MapNamedBufferRange(
+        buffer: u32, offset: isize, length: isize, access: GLbitfield,
+      ) -> *mut c_void;
+      fn MemoryBarrier(barriers: GLbitfield);
+      fn MemoryBarrierByRegion(barriers: GLbitfield);
+      fn MinSampleShading(value: f32);
+      fn MultiDrawArrays(
+        mode: PrimitiveType, first: *const i32, count: *const i32,
+        drawcount: i32,
+      );
+      fn MultiDrawArraysIndirect(
+        mode: PrimitiveType, indirect: *const c_void, drawcount: i32,
+        stride: i32,
+      );
+      fn MultiDrawArraysIndirectCount(
+        mode: PrimitiveType, indirect: *const c_void, drawcount: isize,
+        maxdrawcount: i32, stride: i32,
+      );
+      fn MultiDrawElements(
+        mode: PrimitiveType, count: *const i32, type_: DrawElementsType,
+        indices: *const *const c_void, drawcount: i32,
+      );
+      fn MultiDrawElementsBaseVertex(
+        mode: PrimitiveType, count: *const i32, type_: DrawElementsType,
+        indices: *const *const c_void, drawcount: i32, basevertex: *const i32,
+      );
+      fn MultiDrawElementsIndirect(
+        mode: PrimitiveType, type_: DrawElementsType, indirect: *const c_void,
+        drawcount: i32, stride: i32,
+      );
+      fn MultiDrawElementsIndirectCount(
+        mode: PrimitiveType, type_: DrawElementsType, indirect: *const c_void,
+        drawcount: isize, maxdrawcount: i32, stride: i32,
+      );
+      fn NamedBufferData(
+        buffer: u32, size: isize, data: *const c_void,
+        usage: VertexBufferObjectUsage,
+      );
+      fn NamedBufferStorage(
+        buffer: u32, size: isize, data: *const c_void, flags: GLbitfield,
+      );
+      fn NamedBufferSubData(
+        buffer: u32, offset: isize, size: isize, data: *const c_void,
+      );
+      fn NamedFramebufferDrawBuffer(framebuffer: u32, buf: ColorBuffer);
+      fn NamedFramebufferDrawBuffers(
+        framebuffer: u32, n: i32, bufs: *const ColorBuffer,
+      );
+      fn NamedFramebufferParameteri(
+        framebuffer: u32, pname: FramebufferParameterName, param: i32,
+      );
+      fn NamedFramebufferReadBuffer(framebuffer: u32, src: ColorBuffer);
+      fn NamedFramebufferRenderbuffer(
+        framebuffer: u32, attachment: FramebufferAttachment,
+        renderbuffertarget: RenderbufferTarget, renderbuffer: u32,
+      );
+      fn NamedFramebufferTexture(
+        framebuffer: u32, attachment: FramebufferAttachment, texture: u32,
+        level: i32,
+      );
+      fn NamedFramebufferTextureLayer(
+        framebuffer: u32, attachment: FramebufferAttachment, texture: u32,
+        level: i32, layer: i32,
+      );
+      fn NamedRenderbufferStorage(
+        renderbuffer: u32, internalformat: InternalFormat, width: i32,
+        height: i32,
+      );
+      fn NamedRenderbufferStorageMultisample(
+        renderbuffer: u32, samples: i32, internalformat: InternalFormat,
+        width: i32, height: i32,
+      );
+      fn ObjectLabel(
+        identifier: ObjectIdentifier, name: u32, length: i32, label: *const u8,
+      );
+      fn ObjectPtrLabel(ptr: *const c_void, length: i32, label: *const u8);
+      fn PatchParameterfv(pname: PatchParameterName, values: *const f32);
+      fn PatchParameteri(pname: PatchParameterName, value: i32);
+      fn PauseTransformFeedback();
+      fn PixelStoref(pname: PixelStoreParameter, param: f32);
+      fn PixelStorei(pname: PixelStoreParameter, param: i32);
+      fn PointParameterf(pname: PointParameterNameARB, param: f32);
+      fn PointParameterfv(pname: PointParameterNameARB, params: *const f32);
+      fn PointParameteri(pname: PointParameterNameARB, param: i32);
+      fn PointParameteriv(pname: PointParameterNameARB, params: *const i32);
+      fn PointSize(size: f32);
+      fn PolygonMode(face: MaterialFace, mode: PolygonMode);
+      fn PolygonOffset(factor: f32, units: f32);
+      fn PolygonOffsetClamp(factor: f32, units: f32, clamp: f32);
+      fn PopDebugGroup();
+      fn PrimitiveRestartIndex(index: u32);
+      fn ProgramBinary(
+        program: u32, binary_format: GLenum, binary: *const c_void, length: i32,
+      );
+      fn ProgramParameteri(
+        program: u32, pname: ProgramParameterPName, value: i32,
+      );
+      fn ProgramUniform1d(program: u32, location: i32, v0: f64);
+      fn ProgramUniform1dv(
+        program: u32, location: i32, count: i32, value: *const f64,
+      );
+      fn ProgramUniform1f(program: u32, location: i32, v0: f32);
+      fn ProgramUniform1fv(
+        program: u32, location: i32, count: i32, value: *const f32,
+      );
+      fn ProgramUniform1i(program: u32, location: i32, v0: i32);
+      fn ProgramUniform1iv(
+        program: u32, location: i32, count: i32, value: *const i32,
+      );
+      fn ProgramUniform1ui(program: u32, location: i32, v0: u32);
+      fn ProgramUniform1uiv(
+        program: u32, location: i32, count: i32, value: *const u32,
+      );
+      fn ProgramUniform2d(program: u32, location: i32, v0: f64, v1: f64);
+      fn ProgramUniform2dv(
+        program: u32, location: i32, count: i32, value: *const f64,
+      );
+      fn ProgramUniform2f(program: u32, location: i32, v0: f32, v1: f32);
+      fn ProgramUniform2fv(
+        program: u32, location: i32, count: i32, value: *const f32,
+      );
+      fn ProgramUniform2i(program: u32, location: i32, v0: i32, v1: i32);
+      fn ProgramUniform2iv(
+        program: u32, location: i32, count: i32, value: *const i32,
+      );
+      fn ProgramUniform2ui(program: u32, location: i32, v0: u32, v1: u32);
+      fn ProgramUniform2uiv(
+        program: u32, location: i32, count: i32, value: *const u32,
+      );
+      fn ProgramUniform3d(
+        program: u32, location: i32, v0: f64, v1: f64, v2: f64,
+      );
+      fn ProgramUniform3dv(
+        program: u32, location: i32, count: i32, value: *const f64,
+      );
+      fn ProgramUniform3f(
+        program: u32, location: i32, v0: f32, v1: f32, v2: f32,
+      );
+      fn ProgramUniform3fv(
+        program: u32, location: i32, count: i32, value: *const f32,
+      );
+      fn ProgramUniform3i(
+        program: u32, location: i32, v0: i32, v1: i32, v2: i32,
+      );
+      fn ProgramUniform3iv(
+        program: u32, location: i32, count: i32, value: *const i32,
+      );
+      fn ProgramUniform3ui(
+        program: u32, location: i32, v0: u32, v1: u32, v2: u32,
+      );
+      fn ProgramUniform3uiv(
+        program: u32, location: i32, count: i32, value: *const u32,
+      );
+      fn ProgramUniform4d(
+        program: u32, location: i32, v0: f64, v1: f64, v2: f64, v3: f64,
+      );
+      fn ProgramUniform4dv(
+        program: u32, location: i32, count: i32, value: *const f64,
+      );
+      fn ProgramUniform4f(
+        program: u32, location: i32, v0: f32, v1: f32, v2: f32, v3: f32,
+      );
+      fn ProgramUniform4fv(
+        program: u32, location: i32, count: i32, value: *const f32,
+      );
+      fn ProgramUniform4i(
+        program: u32, location: i32, v0: i32, v1: i32, v2: i32, v3: i32,
+      );
+      fn ProgramUniform4iv(
+        program: u32, location: i32, count: i32, value: *const i32,
+      );
+      fn ProgramUniform4ui(
+        program: u32, location: i32, v0: u32, v1: u32, v2: u32, v3: u32,
+      );
+      fn ProgramUniform4uiv(
+        program: u32, location: i32, count: i32, value: *const u32,
+      );
+      fn ProgramUniformMatrix2dv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f64,
+      );
+      fn ProgramUniformMatrix2fv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f32,
+      );
+      fn ProgramUniformMatrix2x3dv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f64,
+      );
+      fn ProgramUniformMatrix2x3fv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f32,
+      );
+      fn ProgramUniformMatrix2x4dv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f64,
+      );
+      fn ProgramUniformMatrix2x4fv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f32,
+      );
+      fn ProgramUniformMatrix3dv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f64,
+      );
+      fn ProgramUniformMatrix3fv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f32,
+      );
+      fn ProgramUniformMatrix3x2dv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f64,
+      );
+      fn ProgramUniformMatrix3x2fv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f32,
+      );
+      fn ProgramUniformMatrix3x4dv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f64,
+      );
+      fn ProgramUniformMatrix3x4fv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f32,
+      );
+      fn ProgramUniformMatrix4dv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f64,
+      );
+      fn ProgramUniformMatrix4fv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f32,
+      );
+      fn ProgramUniformMatrix4x2dv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f64,
+      );
+      fn ProgramUniformMatrix4x2fv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f32,
+      );
+      fn ProgramUniformMatrix4x3dv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f64,
+      );
+      fn ProgramUniformMatrix4x3fv(
+        program: u32, location: i32, count: i32, transpose: u8,
+        value: *const f32,
+      );
+      fn ProvokingVertex(mode: VertexProvokingMode);
+      fn PushDebugGroup(
+        source: DebugSource, id: u32, length: i32, message: *const u8,
+      );
+      fn QueryCounter(id: u32, target: QueryCounterTarget);
+      fn ReadBuffer(src: ReadBufferMode);
+      fn ReadPixels(
+        x: i32, y: i32, width: i32, height: i32, format: PixelFormat,
+        type_: PixelType, pixels: *mut c_void,
+      );
+      fn ReadnPixels(
+        x: i32, y: i32, width: i32, height: i32, format: PixelFormat,
+        type_: PixelType, buf_size: i32, data: *mut c_void,
+      );
+      fn ReleaseShaderCompiler();
+      fn RenderbufferStorage(
+        target: RenderbufferTarget, internalformat: InternalFormat, width: i32,
+        height: i32,
+      );
+      fn RenderbufferStorageMultisample(
+        target: RenderbufferTarget, samples: i32,
+        internalformat: InternalFormat, width: i32, height: i32,
+      );
+      fn ResumeTransformFeedback();
+      fn SampleCoverage(value: f32, invert: u8);
+      fn SampleMaski(mask_number: u32, mask: GLbitfield);
+      fn SamplerParameterIiv(
+        sampler: u32, pname: SamplerParameterI, param: *const i32,
+      );
+      fn SamplerParameterIuiv(
+        sampler: u32, pname: SamplerParameterI, param: *const u32,
+      );
+      fn SamplerParameterf(sampler: u32, pname: SamplerParameterF, param: f32);
+      fn SamplerParameterfv(
+        sampler: u32, pname: SamplerParameterF, param: *const f32,
+      );
+      fn SamplerParameteri(sampler: u32, pname: SamplerParameterI, param: i32);
+      fn SamplerParameteriv(
+        sampler: u32, pname: SamplerParameterI, param: *const i32,
+      );
+      fn Scissor(x: i32, y: i32, width: i32, height: i32);
+      fn ScissorArrayv(first: u32, count: i32, v: *const i32);
+      fn ScissorIndexed(
+        index: u32, left: i32, bottom: i32, width: i32, height: i32,
+      );
+      fn ScissorIndexedv(index: u32, v: *const [i32; 4]);
+      fn ShaderBinary(
+        count: i32, shaders: *const u32, binary_format: ShaderBinaryFormat,
+        binary: *const c_void, length: i32,
+      );
+      fn ShaderSource(
+        shader: u32, count: i32, string: *const *const u8, length: *const i32,
+      );
+      fn ShaderStorageBlockBinding(
+        program: u32, storage_block_index: u32, storage_block_binding: u32,
+      );
+      fn SpecializeShader(
+        shader: u32, p_entry_point: *const u8,
+        num_specialization_constants: u32, p_constant_index: *const u32,
+        p_constant_value: *const u32,
+      );
+      fn StencilFunc(func: StencilFunction, ref_: i32, mask: u32);
+      fn StencilFuncSeparate(
+        face: StencilFaceDirection, func: StencilFunction, ref_: i32, mask: u32,
+      );
+      fn StencilMask(mask: u32);
+      fn StencilMaskSeparate(face: StencilFaceDirection, mask: u32);
+      fn StencilOp(fail: StencilOp, zfail: StencilOp, zpass: StencilOp);
+      fn StencilOpSeparate(
+        face: StencilFaceDirection, sfail: StencilOp, dpfail: StencilOp,
+        dppass: StencilOp,
+      );
+      fn TexBuffer(
+        target: TextureTarget, internalformat: InternalFormat, buffer: u32,
+      );
+      fn TexBufferRange(
+        target: TextureTarget, internalformat: InternalFormat, buffer: u32,
+        offset: isize, size: isize,
+      );
+      fn TexImage1D(
+        target: TextureTarget, level: i32, internalformat: i32, width: i32,
+        border: i32, format: PixelFormat, type_: PixelType,
+        pixels: *const c_void,
+      );
+      fn TexImage2D(
+        target: TextureTarget, level: i32, internalformat: i32, width: i32,
+        height: i32, border: i32, format: PixelFormat, type_: PixelType,
+        pixels: *const c_void,
+      );
+      fn TexImage2DMultisample(
+        target: TextureTarget, samples: i32, internalformat: InternalFormat,
+        width: i32, height: i32, fixedsamplelocations: u8,
+      );
+      fn TexImage3D(
+        target: TextureTarget, level: i32, internalformat: i32, width: i32,
+        height: i32, depth: i32, border: i32, format: PixelFormat,
+        type_: PixelType, pixels: *const c_void,
+      );
+      fn TexImage3DMultisample(
+        target: TextureTarget, samples: i32, internalformat: InternalFormat,
+        width: i32, height: i32, depth: i32, fixedsamplelocations: u8,
+      );
+      fn TexParameterIiv(
+        target: TextureTarget, pname: TextureParameterName, params: *const i32,
+      );
+      fn TexParameterIuiv(
+        target: TextureTarget, pname: TextureParameterName, params: *const u32,
+      );
+      fn TexParameterf(
+        target: TextureTarget, pname: TextureParameterName, param: f32,
+      );
+      fn TexParameterfv(
+        target: TextureTarget, pname: TextureParameterName, params: *const f32,
+      );
+      fn TexParameteri(
+        target: TextureTarget, pname: TextureParameterName, param: i32,
+      );
+      fn TexParameteriv(
+        target: TextureTarget, pname: TextureParameterName, params: *const i32,
+      );
+      fn TexStorage1D(
+        target: TextureTarget, levels: i32, internalformat: InternalFormat,
+        width: i32,
+      );
+      fn TexStorage2D(
+        target: TextureTarget, levels: i32, internalformat: InternalFormat,
+        width: i32, height: i32,
+      );
+      fn TexStorage2DMultisample(
+        target: TextureTarget, samples: i32, internalformat: InternalFormat,
+        width: i32, height: i32, fixedsamplelocations: u8,
+      );
+      fn TexStorage3D(
+        target: TextureTarget, levels: i32, internalformat: InternalFormat,
+        width: i32, height: i32, depth: i32,
+      );
+      fn TexStorage3DMultisample(
+        target: TextureTarget, samples: i32, internalformat: InternalFormat,
+        width: i32, height: i32, depth: i32, fixedsamplelocations: u8,
+      );
+      fn TexSubImage1D(
+        target: TextureTarget, level: i32, xoffset: i32, width: i32,
+        format: PixelFormat, type_: PixelType, pixels: *const c_void,
+      );
+      fn TexSubImage2D(
+        target: TextureTarget, level: i32, xoffset: i32, yoffset: i32,
+        width: i32, height: i32, format: PixelFormat, type_: PixelType,
+        pixels: *const c_void,
+      );
+      fn TexSubImage3D(
+        target: TextureTarget, level: i32, xoffset: i32, yoffset: i32,
+        zoffset: i32, width: i32, height: i32, depth: i32, format: PixelFormat,
+        type_: PixelType, pixels: *const c_void,
+      );
+      fn TextureBarrier();
+      fn TextureBuffer(
+        texture: u32, internalformat: InternalFormat, buffer: u32,
+      );
+      fn TextureBufferRange(
+        texture: u32, internalformat: InternalFormat, buffer: u32,
+        offset: isize, size: isize,
+      );
+      fn TextureParameterIiv(
+        texture: u32, pname: TextureParameterName, params: *const i32,
+      );
+      fn TextureParameterIuiv(
+        texture: u32, pname: TextureParameterName, params: *const u32,
+      );
+      fn TextureParameterf(
+        texture: u32, pname: TextureParameterName, param: f32,
+      );
+      fn TextureParameterfv(
+        texture: u32, pname: TextureParameterName, param: *const f32,
+      );
+      fn TextureParameteri(
+        texture: u32, pname: TextureParameterName, param: i32,
+      );
+      fn TextureParameteriv(
+        texture: u32, pname: TextureParameterName, param: *const i32,
+      );
+      fn TextureStorage1D(
+        texture: u32, levels: i32, internalformat: InternalFormat, width: i32,
+      );
+      fn TextureStorage2D(
+        texture: u32, levels: i32, internalformat: InternalFormat, width: i32,
+        height: i32,
+      );
+      fn TextureStorage2DMultisample(
+        texture: u32, samples: i32, internalformat: InternalFormat, width: i32,
+        height: i32, fixedsamplelocations: u8,
+      );
+      fn TextureStorage3D(
+        texture: u32, levels: i32, internalformat: InternalFormat, width: i32,
+        height: i32, depth: i32,
+      );
+      fn TextureStorage3DMultisample(
+        texture: u32, samples: i32, internalformat: InternalFormat, width: i32,
+        height: i32, depth: i32, fixedsamplelocations: u8,
+      );
+      fn TextureSubImage1D(
+        texture: u32, level: i32, xoffset: i32, width: i32, format: PixelFormat,
+        type_: PixelType, pixels: *const c_void,
+      );
+      fn TextureSubImage2D(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, width: i32,
+        height: i32, format: PixelFormat, type_: PixelType,
+        pixels: *const c_void,
+      );
+      fn TextureSubImage3D(
+        texture: u32, level: i32, xoffset: i32, yoffset: i32, zoffset: i32,
+        width: i32, height: i32, depth: i32, format: PixelFormat,
+        type_: PixelType, pixels: *const c_void,
+      );
+      fn TextureView(
+        texture: u32, target: TextureTarget, origtexture: u32,
+        internalformat: InternalFormat, minlevel: u32, numlevels: u32,
+        minlayer: u32, numlayers: u32,
+      );
+      fn TransformFeedbackBufferBase(xfb: u32, index: u32, buffer: u32);
+      fn TransformFeedbackBufferRange(
+        xfb: u32, index: u32, buffer: u32, offset: isize, size: isize,
+      );
+      fn TransformFeedbackVaryings(
+        program: u32, count: i32, varyings: *const *const u8,
+        buffer_mode: TransformFeedbackBufferMode,
+      );
+      fn Uniform1d(location: i32, x: f64);
+      fn Uniform1dv(location: i32, count: i32, value: *const f64);
+      fn Uniform1f(location: i32, v0: f32);
+      fn Uniform1fv(location: i32, count: i32, value: *const f32);
+      fn Uniform1i(location: i32, v0: i32);
+      fn Uniform1iv(location: i32, count: i32, value: *const i32);
+      fn Uniform1ui(location: i32, v0: u32);
+      fn Uniform1uiv(location: i32, count: i32, value: *const u32);
+      fn Uniform2d(location: i32, x: f64, y: f64);
+      fn Uniform2dv(location: i32, count: i32, value: *const f64);
+      fn Uniform2f(location: i32, v0: f32, v1: f32);
+      fn Uniform2fv(location: i32, count: i32, value: *const f32);
+      fn Uniform2i(location: i32, v0: i32, v1: i32);
+      fn Uniform2iv(location: i32, count: i32, value: *const i32);
+      fn Uniform2ui(location: i32, v0: u32, v1: u32);
+      fn Uniform2uiv(location: i32, count: i32, value: *const u32);
+      fn Uniform3d(location: i32, x: f64, y: f64, z: f64);
+      fn Uniform3dv(location: i32, count: i32, value: *const f64);
+      fn Uniform3f(location: i32, v0: f32, v1: f32, v2: f32);
+      fn Uniform3fv(location: i32, count: i32, value: *const f32);
+      fn Uniform3i(location: i32, v0: i32, v1: i32, v2: i32);
+      fn Uniform3iv(location: i32, count: i32, value: *const i32);
+      fn Uniform3ui(location: i32, v0: u32, v1: u32, v2: u32);
+      fn Uniform3uiv(location: i32, count: i32, value: *const u32);
+      fn Uniform4d(location: i32, x: f64, y: f64, z: f64, w: f64);
+      fn Uniform4dv(location: i32, count: i32, value: *const f64);
+      fn Uniform4f(location: i32, v0: f32, v1: f32, v2: f32, v3: f32);
+      fn Uniform4fv(location: i32, count: i32, value: *const f32);
+      fn Uniform4i(location: i32, v0: i32, v1: i32, v2: i32, v3: i32);
+      fn Uniform4iv(location: i32, count: i32, value: *const i32);
+      fn Uniform4ui(location: i32, v0: u32, v1: u32, v2: u32, v3: u32);
+      fn Uniform4uiv(location: i32, count: i32, value: *const u32);
+      fn UniformBlockBinding(
+        program: u32, uniform_block_index: u32, uniform_block_binding: u32,
+      );
+      fn UniformMatrix2dv(
+        location: i32, count: i32, transpose: u8, value: *const f64,
+      );
+      fn UniformMatrix2fv(
+        location: i32, count: i32, transpose: u8, value: *const f32,
+      );
+      fn UniformMatrix2x3dv(
+        location: i32, count: i32, transpose: u8, value: *const f64,
+      );
+      fn UniformMatrix2x3fv(
+        location: i32, count: i32, transpose: u8, value: *const f32,
+      );
+      fn UniformMatrix2x4dv(
+        location: i32, count: i32, transpose: u8, value: *const f64,
+      );
+      fn UniformMatrix2x4fv(
+        location: i32, count: i32, transpose: u8, value: *const f32,
+      );
+      fn UniformMatrix3dv(
+        location: i32, count: i32, transpose: u8, value: *const f64,
+      );
+      fn UniformMatrix3fv(
+        location: i32, count: i32, transpose: u8, value: *const f32,
+      );
+      fn UniformMatrix3x2dv(
+        location: i32, count: i32, transpose: u8, value: *const f64,
+      );
+      fn UniformMatrix3x2fv(
+        location: i32, count: i32, transpose: u8, value: *const f32,
+      );
+      fn UniformMatrix3x4dv(
+        location: i32, count: i32, transpose: u8, value: *const f64,
+      );
+      fn UniformMatrix3x4fv(
+        location: i32, count: i32, transpose: u8, value: *const f32,
+      );
+      fn UniformMatrix4dv(
+        location: i32, count: i32, transpose: u8, value: *const f64,
+      );
+      fn UniformMatrix4fv(
+        location: i32, count: i32, transpose: u8, value: *const f32,
+      );
+      fn UniformMatrix4x2dv(
+        location: i32, count: i32, transpose: u8, value: *const f64,
+      );
+      fn UniformMatrix4x2fv(
+        location: i32, count: i32, transpose: u8, value: *const f32,
+      );
+      fn UniformMatrix4x3dv(
+        location: i32, count: i32, transpose: u8, value: *const f64,
+      );
+      fn UniformMatrix4x3fv(
+        location: i32, count: i32, transpose: u8, value: *const f32,
+      );
+      fn UniformSubroutinesuiv(
+        shadertype: ShaderType, count: i32, indices: *const u32,
+      );
+      fn UnmapBuffer(target: BufferTargetARB) -> u8;
+      fn UnmapNamedBuffer(buffer: u32) -> u8;
+      fn UseProgram(program: u32);
+      fn UseProgramStages(pipeline: u32, stages: GLbitfield, program: u32);
+      fn ValidateProgram(program: u32);
+      fn ValidateProgramPipeline(pipeline: u32);
+      fn VertexArrayAttribBinding(
+        vaobj: u32, attribindex: u32, bindingindex: u32,
+      );
+      fn VertexArrayAttribFormat(
+        vaobj: u32, attribindex: u32, size: i32, type_: VertexAttribType,
+        normalized: u8, relativeoffset: u32,
+      );
+      fn VertexArrayAttribIFormat(
+        vaobj: u32, attribindex: u32, size: i32, type_: VertexAttribIType,
+        relativeoffset: u32,
+      );
+      fn VertexArrayAttribLFormat(
+        vaobj: u32, attribindex: u32, size: i32, type_: VertexAttribLType,
+        relativeoffset: u32,
+      );
+      fn VertexArrayBindingDivisor(vaobj: u32, bindingindex: u32, divisor: u32);
+      fn VertexArrayElementBuffer(vaobj: u32, buffer: u32);
+      fn VertexArrayVertexBuffer(
+        vaobj: u32, bindingindex: u32, buffer: u32, offset: isize, stride: i32,
+      );
+      fn VertexArrayVertexBuffers(
+        vaobj: u32, first: u32, count: i32, buffers: *const u32,
+        offsets: *const isize, strides: *const i32,
+      );
+      fn VertexAttrib1d(index: u32, x: f64);
+      fn VertexAttrib1dv(index: u32, v: *const f64);
+      fn VertexAttrib1f(index: u32, x: f32);
+      fn VertexAttrib1fv(index: u32, v: *const f32);
+      fn VertexAttrib1s(index: u32, x: i16);
+      fn VertexAttrib1sv(index: u32, v: *const i16);
+      fn VertexAttrib2d(index: u32, x: f64, y: f64);
+      fn VertexAttrib2dv(index: u32, v: *const [f64; 2]);
+      fn VertexAttrib2f(index: u32, x: f32, y: f32);
+      fn VertexAttrib2fv(index: u32, v: *const [f32; 2]);
+      fn VertexAttrib2s(index: u32, x: i16, y: i16);
+      fn VertexAttrib2sv(index: u32, v: *const [i16; 2]);
+      fn VertexAttrib3d(index: u32, x: f64, y: f64, z: f64);
+      fn VertexAttrib3dv(index: u32, v: *const [f64; 3]);
+      fn VertexAttrib3f(index: u32, x: f32, y: f32, z: f32);
+      fn VertexAttrib3fv(index: u32, v: *const [f32; 3]);
+      fn VertexAttrib3s(index: u32, x: i16, y: i16, z: i16);
+      fn VertexAttrib3sv(index: u32, v: *const [i16; 3]);
+      fn VertexAttrib4Nbv(index: u32, v: *const [i8; 4]);
+      fn VertexAttrib4Niv(index: u32, v: *const [i32; 4]);
+      fn VertexAttrib4Nsv(index: u32, v: *const [i16; 4]);
+      fn VertexAttrib4Nub(index: u32, x: u8, y: u8, z: u8, w: u8);
+      fn VertexAttrib4Nubv(index: u32, v: *const [u8; 4]);
+      fn VertexAttrib4Nuiv(index: u32, v: *const [u32; 4]);
+      fn VertexAttrib4Nusv(index: u32, v: *const [u16; 4]);
+      fn VertexAttrib4bv(index: u32, v: *const [i8; 4]);
+      fn VertexAttrib4d(index: u32, x: f64, y: f64, z: f64, w: f64);
+      fn VertexAttrib4dv(index: u32, v: *const [f64; 4]);
+      fn VertexAttrib4f(index: u32, x: f32, y: f32, z: f32, w: f32);
+      fn VertexAttrib4fv(index: u32, v: *const [f32; 4]);
+      fn VertexAttrib4iv(index: u32, v: *const [i32; 4]);
+      fn VertexAttrib4s(index: u32, x: i16, y: i16, z: i16, w: i16);
+      fn VertexAttrib4sv(index: u32, v: *const [i16; 4]);
+      fn VertexAttrib4ubv(index: u32, v: *const [u8; 4]);
+      fn VertexAttrib4uiv(index: u32, v: *const [u32; 4]);
+      fn VertexAttrib4usv(index: u32, v: *const [u16; 4]);
+      fn VertexAttribBinding(attribindex: u32, bindingindex: u32);
+      fn VertexAttribDivisor(index: u32, divisor: u32);
+      fn VertexAttribFormat(
+        attribindex: u32, size: i32, type_: VertexAttribType, normalized: u8,
+        relativeoffset: u32,
+      );
+      fn VertexAttribI1i(index: u32, x: i32);
+      fn VertexAttribI1iv(index: u32, v: *const i32);
+      fn VertexAttribI1ui(index: u32, x: u32);
+      fn VertexAttribI1uiv(index: u32, v: *const u32);
+      fn VertexAttribI2i(index: u32, x: i32, y: i32);
+      fn VertexAttribI2iv(index: u32, v: *const [i32; 2]);
+      fn VertexAttribI2ui(index: u32, x: u32, y: u32);
+      fn VertexAttribI2uiv(index: u32, v: *const [u32; 2]);
+      fn VertexAttribI3i(index: u32, x: i32, y: i32, z: i32);
+      fn VertexAttribI3iv(index: u32, v: *const [i32; 3]);
+      fn VertexAttribI3ui(index: u32, x: u32, y: u32, z: u32);
+      fn VertexAttribI3uiv(index: u32, v: *const [u32; 3]);
+      fn VertexAttribI4bv(index: u32, v: *const [i8; 4]);
+      fn VertexAttribI4i(index: u32, x: i32, y: i32, z: i32, w: i32);
+      fn VertexAttribI4iv(index: u32, v: *const [i32; 4]);
+      fn VertexAttribI4sv(index: u32, v: *const [i16; 4]);
+      fn VertexAttribI4ubv(index: u32, v: *const [u8; 4]);
+      fn VertexAttribI4ui(index: u32, x: u32, y: u32, z: u32, w: u32);
+      fn VertexAttribI4uiv(index: u32, v: *const [u32; 4]);
+      fn VertexAttribI4usv(index: u32, v: *const [u16; 4]);
+      fn VertexAttribIFormat(
+        attribindex: u32, size: i32, type_: VertexAttribIType,
+        relativeoffset: u32,
+      );
+      fn VertexAttribIPointer(
+        index: u32, size: i32, type_: VertexAttribIType, stride: i32,
+        pointer: *const c_void,
+      );
+      fn VertexAttribL1d(index: u32, x: f64);
+      fn VertexAttribL1dv(index: u32, v: *const f64);
+      fn VertexAttribL2d(index: u32, x: f64, y: f64);
+      fn VertexAttribL2dv(index: u32, v: *const [f64; 2]);
+      fn VertexAttribL3d(index: u32, x: f64, y: f64, z: f64);
+      fn VertexAttribL3dv(index: u32, v: *const [f64; 3]);
+      fn VertexAttribL4d(index: u32, x: f64, y: f64, z: f64, w: f64);
+      fn VertexAttribL4dv(index: u32, v: *const [f64; 4]);
+      fn VertexAttribLFormat(
+        attribindex: u32, size: i32, type_: VertexAttribLType,
+        relativeoffset: u32,
+      );
+      fn VertexAttribLPointer(
+        index: u32, size: i32, type_: VertexAttribLType, stride: i32,
+        pointer: *const c_void,
+      );
+      fn VertexAttribP1ui(
+        index: u32, type_: VertexAttribPointerType, normalized: u8, value: u32,
+      );
+      fn VertexAttribP1uiv(
+        index: u32, type_: VertexAttribPointerType, normalized: u8,
+        value: *const u32,
+      );
+      fn VertexAttribP2ui(
+        index: u32, type_: VertexAttribPointerType, normalized: u8, value: u32,
+      );
+      fn VertexAttribP2uiv(
+        index: u32, type_: VertexAttribPointerType, normalized: u8,
+        value: *const u32,
+      );
+      fn VertexAttribP3ui(
+        index: u32, type_: VertexAttribPointerType, normalized: u8, value: u32,
+      );
+      fn VertexAttribP3uiv(
+        index: u32, type_: VertexAttribPointerType, normalized: u8,
+        value: *const u32,
+      );
+      fn VertexAttribP4ui(
+        index: u32, type_: VertexAttribPointerType, normalized: u8, value: u32,
+      );
+      fn VertexAttribP4uiv(
+        index: u32, type_: VertexAttribPointerType, normalized: u8,
+        value: *const u32,
+      );
+      fn VertexAttribPointer(
+        index: u32, size: i32, type_: VertexAttribPointerType, normalized: u8,
+        stride: i32, pointer: *const c_void,
+      );
+      fn VertexBindingDivisor(bindingindex: u32, divisor: u32);
+      fn Viewport(x: i32, y: i32, width: i32, height: i32);
+      fn ViewportArrayv(first: u32, count: i32, v: *const f32);
+      fn ViewportIndexedf(index: u32, x: f32, y: f32, w: f32, h: f32);
+      fn ViewportIndexedfv(index: u32, v: *const [f32; 4]);
+      fn WaitSync(sync: GLsync, flags: GLbitfield, timeout: u64);
+      fn GetImageHandleARB(
+        texture: u32, level: i32, layered: u8, layer: i32, format: PixelFormat,
+      ) -> u64;
+      fn GetTextureHandleARB(texture: u32) -> u64;
+      fn GetTextureSamplerHandleARB(texture: u32, sampler: u32) -> u64;
+      fn GetVertexAttribLui64vARB(
+        index: u32, pname: VertexAttribEnum, params: *mut u64,
+      );
+      fn IsImageHandleResidentARB(handle: u64) -> u8;
+      fn IsTextureHandleResidentARB(handle: u64) -> u8;
+      fn MakeImageHandleNonResidentARB(handle: u64);
+      fn MakeImageHandleResidentARB(handle: u64, access: GLenum);
+      fn MakeTextureHandleNonResidentARB(handle: u64);
+      fn MakeTextureHandleResidentARB(handle: u64);
+      fn ProgramUniformHandleui64ARB(program: u32, location: i32, value: u64);
+      fn ProgramUniformHandleui64vARB(
+        program: u32, location: i32, count: i32, values: *const u64,
+      );
+      fn TexPageCommitmentARB(
+        target: GLenum, level: i32, xoffset: i32, yoffset: i32, zoffset: i32,
+        width: i32, height: i32, depth: i32, commit: u8,
+      );
+      fn UniformHandleui64ARB(location: i32, value: u64);
+      fn UniformHandleui64vARB(location: i32, count: i32, value: *const u64);
+      fn VertexAttribL1ui64ARB(index: u32, x: u64);
+      fn VertexAttribL1ui64vARB(index: u32, v: *const u64);
+    }
+  };
+}
@@ -0,0 +1,112 @@
+use super::*;
+use naga::{
+  front::glsl::{Frontend, Options},
+  valid::{Capabilities, ValidationFlags, Validator},
+  ShaderStage, Span,
+};
+
+/// What [`validate_glsl`] did with a shader that it didn't reject.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlslValidation {
+  /// The shader was parsed and validated.
+  Validated,
+  /// naga can't check this stage, so the shader wasn't checked at all.
+  Skipped,
+}
+
+/// Parses and validates GLSL source on the CPU, using [naga](https://docs.rs/naga).
+///
+/// This needs the `naga` feature. No GL context (or GPU) is used, so it's
+/// good for tests. naga isn't a full GLSL compiler though: a shader that it
+/// rejects might still work with a real driver, and the other way around.
+///
+/// Any `#include` needs to be resolved first (eg: with a
+/// [`ShaderPreprocessor`]). naga expects Vulkan style GLSL, but
+/// `gl_VertexID` and `gl_InstanceID` are accepted too.
+///
+/// naga only understands vertex, fragment, and compute shaders, so other
+/// stages (eg: geometry or tessellation) are
+/// [`Skipped`](GlslValidation::Skipped).
+///
+/// ## Failure
+/// * Each problem is a [`ShaderDiagnostic`] for source string 0.
+pub fn validate_glsl(
+  t: ShaderEnum, src: &str,
+) -> Result<GlslValidation, Vec<ShaderDiagnostic>> {
+  let stage = match t {
+    ShaderEnum::Vertex => ShaderStage::Vertex,
+    ShaderEnum::Fragment => ShaderStage::Fragment,
+    ShaderEnum::Compute => ShaderStage::Compute,
+    _ => return Ok(GlslValidation::Skipped),
+  };
+  // naga follows the Vulkan flavor of GLSL, so the GL names for these are
+  // mapped over.
+  let mut options = Options::from(stage);
+  for (gl_name, vk_name) in
+    [("gl_VertexID", "gl_VertexIndex"), ("gl_InstanceID", "gl_InstanceIndex")]
+  {
+    options.defines.insert(String::from(gl_name), String::from(vk_name));
+  }
+  let module = Frontend::default().parse(&options, src).map_err(|errors| {
+    errors
+      .errors
+      .iter()
+      .map(|e| naga_diagnostic(src, e.meta, e.kind.to_string()))
+      .collect::<Vec<_>>()
+  })?;
+  Validator::new(ValidationFlags::all(), Capabilities::all())
+    .validate(&module)
+    .map_err(|e| {
+      let span = e.spans().next().map(|(span, _)| *span);
+      // the error's own message is often just "function x is invalid", so
+      // the chain of causes is included too.
+      let mut message = e.as_inner().to_string();
+      let mut source = std::error::Error::source(e.as_inner());
+      while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+      }
+      vec![naga_diagnostic(src, span.unwrap_or_default(), message)]
+    })?;
+  Ok(GlslValidation::Validated)
+}
+
+fn naga_diagnostic(src: &str, span: Span, message: String) -> ShaderDiagnostic {
+  let location =
+    if span.is_defined() { Some(span.location(src)) } else { None };
+  ShaderDiagnostic {
+    file: Some(0),
+    line: location.map(|l| l.line_number),
+    column: location.map(|l| l.line_position),
+    severity: DiagnosticSeverity::Error,
+    message,
+  }
+}
+
+#[test]
+fn test_validate_glsl() {
+  assert_eq!(
+    validate_glsl(
+      ShaderEnum::Fragment,
+      "#version 450 core\nlayout(location = 0) out vec4 c;\n\
+       void main() { c = vec4(1.0); }",
+    ),
+    Ok(GlslValidation::Validated)
+  );
+  let errors = validate_glsl(
+    ShaderEnum::Fragment,
+    "#version 450 core\nlayout(location = 0) out vec4 c;\n\
+     void main() {\n  c = vec4(nope);\n}",
+  )
+  .unwrap_err();
+  assert_eq!(errors[0].severity, DiagnosticSeverity::Error);
+  assert_eq!((errors[0].file, errors[0].line), (Some(0), Some(4)));
+  assert!(errors[0].message.contains("nope"), "{:?}", errors);
+
+  // stages that naga can't check aren't failures, but aren't passes either.
+  assert_eq!(
+    validate_glsl(ShaderEnum::Geometry, "not checked"),
+    Ok(GlslValidation::Skipped)
+  );
+}
@@ -0,0 +1,871 @@
+use super::*;
+use std::{fs, path::Path};
+
+/// The first 12 bytes of a KTX 1.1 file.
+const KTX1_IDENTIFIER: [u8; 12] =
+  [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+/// The first 12 bytes of a KTX2 file.
+const KTX2_IDENTIFIER: [u8; 12] =
+  [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+const KTX1_HEADER_SIZE: usize = 64;
+/// The header and index, up to the start of the level index.
+const KTX2_HEADER_SIZE: usize = 80;
+
+/// How the image data of a [`KtxTexture`] is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KtxFormat {
+  /// Pixels, uploaded with the client `format` and `data_type` given.
+  Uncompressed {
+    internal_format: InternalFormatEnum,
+    /// The client pixel format (eg: `GL_RGBA`).
+    format: GLenum,
+    /// The client data type (eg: `GL_UNSIGNED_BYTE`).
+    data_type: GLenum,
+    /// The size of the data type (1, 2, or 4), which is the unit of byte
+    /// swapping.
+    type_size: u32,
+  },
+  /// Blocks of compressed texels.
+  Compressed(CompressedFormatEnum),
+}
+impl KtxFormat {
+  /// The internal format to give the texture's storage.
+  pub fn internal_format_enum(self) -> GLenum {
+    match self {
+      Self::Uncompressed { internal_format, .. } => internal_format.as_enum(),
+      Self::Compressed(compressed) => compressed.as_enum(),
+    }
+  }
+
+  /// The tightly packed size of a `width` by `height` by `depth` image, or
+  /// `None` if it doesn't fit in a `usize`.
+  fn image_size(self, width: u32, height: u32, depth: u32) -> Option<usize> {
+    let (width, height, unit) = match self {
+      Self::Uncompressed { format, data_type, .. } => {
+        (width, height, pixel_size(format, data_type)?)
+      }
+      Self::Compressed(compressed) => {
+        (width.div_ceil(4), height.div_ceil(4), compressed.block_size())
+      }
+    };
+    (width as usize)
+      .checked_mul(height as usize)?
+      .checked_mul(depth as usize)?
+      .checked_mul(unit as usize)
+  }
+}
+
+/// The size of one pixel of client data, or `None` if GL doesn't allow the
+/// combination.
+fn pixel_size(format: GLenum, data_type: GLenum) -> Option<u32> {
+  let components = match format {
+    GL_RED | GL_RED_INTEGER | GL_DEPTH_COMPONENT | GL_STENCIL_INDEX => 1,
+    GL_RG | GL_RG_INTEGER | GL_DEPTH_STENCIL => 2,
+    GL_RGB | GL_BGR | GL_RGB_INTEGER | GL_BGR_INTEGER => 3,
+    GL_RGBA | GL_BGRA | GL_RGBA_INTEGER | GL_BGRA_INTEGER => 4,
+    _ => return None,
+  };
+  Some(match data_type {
+    GL_BYTE | GL_UNSIGNED_BYTE => components,
+    GL_SHORT | GL_UNSIGNED_SHORT | GL_HALF_FLOAT => components * 2,
+    GL_INT | GL_UNSIGNED_INT | GL_FLOAT => components * 4,
+    // packed types hold a whole pixel.
+    GL_UNSIGNED_BYTE_3_3_2 | GL_UNSIGNED_BYTE_2_3_3_REV => 1,
+    GL_UNSIGNED_SHORT_5_6_5
+    | GL_UNSIGNED_SHORT_5_6_5_REV
+    | GL_UNSIGNED_SHORT_4_4_4_4
+    | GL_UNSIGNED_SHORT_4_4_4_4_REV
+    | GL_UNSIGNED_SHORT_5_5_5_1
+    | GL_UNSIGNED_SHORT_1_5_5_5_REV => 2,
+    GL_UNSIGNED_INT_8_8_8_8
+    | GL_UNSIGNED_INT_8_8_8_8_REV
+    | GL_UNSIGNED_INT_10_10_10_2
+    | GL_UNSIGNED_INT_2_10_10_10_REV
+    | GL_UNSIGNED_INT_10F_11F_11F_REV
+    | GL_UNSIGNED_INT_5_9_9_9_REV
+    | GL_UNSIGNED_INT_24_8 => 4,
+    GL_FLOAT_32_UNSIGNED_INT_24_8_REV => 8,
+    _ => return None,
+  })
+}
+
+const fn uncompressed(
+  internal_format: InternalFormatEnum, format: GLenum, data_type: GLenum,
+  type_size: u32,
+) -> KtxFormat {
+  KtxFormat::Uncompressed { internal_format, format, data_type, type_size }
+}
+
+/// The `VkFormat` values that a KTX2 file can use, and their GL equivalents.
+const VK_FORMATS: &[(u32, KtxFormat)] = {
+  use CompressedFormatEnum as C;
+  use InternalFormatEnum as I;
+  &[
+    (9, uncompressed(I::R8, GL_RED, GL_UNSIGNED_BYTE, 1)),
+    (13, uncompressed(I::R8UI, GL_RED_INTEGER, GL_UNSIGNED_BYTE, 1)),
+    (16, uncompressed(I::RG8, GL_RG, GL_UNSIGNED_BYTE, 1)),
+    (23, uncompressed(I::RGB8, GL_RGB, GL_UNSIGNED_BYTE, 1)),
+    (29, uncompressed(I::SRGB8, GL_RGB, GL_UNSIGNED_BYTE, 1)),
+    (37, uncompressed(I::RGBA8, GL_RGBA, GL_UNSIGNED_BYTE, 1)),
+    (41, uncompressed(I::RGBA8UI, GL_RGBA_INTEGER, GL_UNSIGNED_BYTE, 1)),
+    (43, uncompressed(I::SRGB8Alpha8, GL_RGBA, GL_UNSIGNED_BYTE, 1)),
+    (70, uncompressed(I::R16, GL_RED, GL_UNSIGNED_SHORT, 2)),
+    (74, uncompressed(I::R16UI, GL_RED_INTEGER, GL_UNSIGNED_SHORT, 2)),
+    (76, uncompressed(I::R16F, GL_RED, GL_HALF_FLOAT, 2)),
+    (77, uncompressed(I::RG16, GL_RG, GL_UNSIGNED_SHORT, 2)),
+    (83, uncompressed(I::RG16F, GL_RG, GL_HALF_FLOAT, 2)),
+    (90, uncompressed(I::RGB16F, GL_RGB, GL_HALF_FLOAT, 2)),
+    (91, uncompressed(I::RGBA16, GL_RGBA, GL_UNSIGNED_SHORT, 2)),
+    (97, uncompressed(I::RGBA16F, GL_RGBA, GL_HALF_FLOAT, 2)),
+    (98, uncompressed(I::R32UI, GL_RED_INTEGER, GL_UNSIGNED_INT, 4)),
+    (99, uncompressed(I::R32I, GL_RED_INTEGER, GL_INT, 4)),
+    (100, uncompressed(I::R32F, GL_RED, GL_FLOAT, 4)),
+    (101, uncompressed(I::RG32UI, GL_RG_INTEGER, GL_UNSIGNED_INT, 4)),
+    (103, uncompressed(I::RG32F, GL_RG, GL_FLOAT, 4)),
+    (106, uncompressed(I::RGB32F, GL_RGB, GL_FLOAT, 4)),
+    (107, uncompressed(I::RGBA32UI, GL_RGBA_INTEGER, GL_UNSIGNED_INT, 4)),
+    (108, uncompressed(I::RGBA32I, GL_RGBA_INTEGER, GL_INT, 4)),
+    (109, uncompressed(I::RGBA32F, GL_RGBA, GL_FLOAT, 4)),
+    (
+      122,
+      uncompressed(I::R11FG11FB10F, GL_RGB, GL_UNSIGNED_INT_10F_11F_11F_REV, 4),
+    ),
+    (
+      124,
+      uncompressed(
+        I::DepthComponent16,
+        GL_DEPTH_COMPONENT,
+        GL_UNSIGNED_SHORT,
+        2,
+      ),
+    ),
+    (126, uncompressed(I::DepthComponent32F, GL_DEPTH_COMPONENT, GL_FLOAT, 4)),
+    (139, KtxFormat::Compressed(C::RedRGTC1)),
+    (140, KtxFormat::Compressed(C::SignedRedRGTC1)),
+    (141, KtxFormat::Compressed(C::RGRGTC2)),
+    (142, KtxFormat::Compressed(C::SignedRGRGTC2)),
+    (143, KtxFormat::Compressed(C::RGBBPTCUnsignedFloat)),
+    (144, KtxFormat::Compressed(C::RGBBPTCSignedFloat)),
+    (145, KtxFormat::Compressed(C::RGBABPTCUnorm)),
+    (146, KtxFormat::Compressed(C::SRGBAlphaBPTCUnorm)),
+    (147, KtxFormat::Compressed(C::RGB8ETC2)),
+    (148, KtxFormat::Compressed(C::SRGB8ETC2)),
+    (149, KtxFormat::Compressed(C::RGB8PunchthroughAlpha1ETC2)),
+    (150, KtxFormat::Compressed(C::SRGB8PunchthroughAlpha1ETC2)),
+    (151, KtxFormat::Compressed(C::RGBA8ETC2EAC)),
+    (152, KtxFormat::Compressed(C::SRGB8Alpha8ETC2EAC)),
+    (153, KtxFormat::Compressed(C::R11EAC)),
+    (154, KtxFormat::Compressed(C::SignedR11EAC)),
+    (155, KtxFormat::Compressed(C::RG11EAC)),
+    (156, KtxFormat::Compressed(C::SignedRG11EAC)),
+  ]
+};
+
+/// A texture loaded from a KTX 1.1 or KTX2 file.
+///
+/// KTX2 files must not use supercompression, and both versions must use a
+/// format listed in [`InternalFormatEnum`] or [`CompressedFormatEnum`].
+///
+/// Whatever the file's layout was, the data of each level is tightly packed
+/// (no row padding) and in the host's byte order. A level holds one image
+/// per array layer, or per face within each layer for cube maps, and each
+/// image is `depth` slices of rows from the bottom up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KtxTexture {
+  pub target: TextureTargetEnum,
+  pub format: KtxFormat,
+  /// The width of the base level.
+  pub width: u32,
+  /// The height of the base level, 1 for 1D textures.
+  pub height: u32,
+  /// The depth of the base level, 1 for other than 3D textures.
+  pub depth: u32,
+  /// The number of array layers, 1 for other than array textures.
+  pub layers: u32,
+  /// 6 for cube maps, otherwise 1.
+  pub faces: u32,
+  /// The data of each mip level, starting with the base level.
+  pub levels: Vec<Vec<u8>>,
+  /// If the file only has the base level, and asks for the other levels to
+  /// be generated.
+  pub generate_mipmaps: bool,
+  /// The key/value pairs of metadata, in file order. Text values usually
+  /// include their null terminator.
+  pub key_values: Vec<(String, Vec<u8>)>,
+}
+impl KtxTexture {
+  /// Reads and parses a `.ktx` or `.ktx2` file.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+    let path = path.as_ref();
+    let bytes = fs::read(path)
+      .map_err(|e| format!("Couldn't read `{}`: {}", path.display(), e))?;
+    Self::parse(&bytes)
+  }
+
+  /// Parses the bytes of a KTX 1.1 or KTX2 file, picking the version from
+  /// the file's identifier.
+  ///
+  /// ## Failure
+  /// * The identifier, endianness marker, or header values are bad.
+  /// * The format isn't one that this crate knows.
+  /// * A compressed format is used for a 1D or 3D texture, or with a level
+  ///   count of 0 (which asks for mipmaps to be generated, but GL can't
+  ///   generate them for compressed formats).
+  /// * Any level's data is the wrong size or goes past the end of the file.
+  pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+    if bytes.starts_with(&KTX1_IDENTIFIER) {
+      parse_ktx1(bytes)
+    } else if bytes.starts_with(&KTX2_IDENTIFIER) {
+      parse_ktx2(bytes)
+    } else {
+      Err(String::from("Not a KTX file."))
+    }
+  }
+
+  /// The `[width, height, depth]` of a mip level.
+  pub fn level_size(&self, level: usize) -> [u32; 3] {
+    let shrink = |x: u32| x.checked_shr(level as u32).unwrap_or(0).max(1);
+    [shrink(self.width), shrink(self.height), shrink(self.depth)]
+  }
+
+  /// The value of the first key/value pair with the key given.
+  pub fn value(&self, key: &str) -> Option<&[u8]> {
+    self.key_values.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_slice())
+  }
+
+  /// How many levels the texture's storage should have.
+  fn storage_levels(&self) -> u32 {
+    if self.generate_mipmaps {
+      self.max_levels()
+    } else {
+      self.levels.len() as u32
+    }
+  }
+
+  /// The number of levels in a full mipmap chain.
+  fn max_levels(&self) -> u32 {
+    32 - self.width.max(self.height).max(self.depth).leading_zeros()
+  }
+}
+
+/// Picks the target for a texture with the (unadjusted) header values given.
+fn ktx_target(
+  width: u32, height: u32, depth: u32, layers: u32, faces: u32,
+) -> Result<TextureTargetEnum, String> {
+  use TextureTargetEnum::*;
+  if width == 0 {
+    return Err(String::from("The width is 0."));
+  }
+  if height == 0 && depth != 0 {
+    return Err(String::from("The height is 0, but the depth isn't."));
+  }
+  if depth != 0 && layers != 0 {
+    return Err(String::from("3D textures can't have array layers."));
+  }
+  match faces {
+    1 => Ok(match (height, depth, layers) {
+      (0, _, 0) => Texture1D,
+      (0, _, _) => Texture1DArray,
+      (_, 0, 0) => Texture2D,
+      (_, 0, _) => Texture2DArray,
+      _ => Texture3D,
+    }),
+    6 if width == height && depth == 0 => {
+      Ok(if layers == 0 { CubeMap } else { CubeMapArray })
+    }
+    6 => Err(format!(
+      "Cube map faces must be square and 2D, not {}x{}x{}.",
+      width, height, depth
+    )),
+    _ => Err(format!("There are {} faces.", faces)),
+  }
+}
+
+/// Starts a [`KtxTexture`] from the header values, before any levels are
+/// added.
+#[allow(clippy::too_many_arguments)]
+fn ktx_texture(
+  format: KtxFormat, width: u32, height: u32, depth: u32, layers: u32,
+  faces: u32, levels: u32, key_values: Vec<(String, Vec<u8>)>,
+) -> Result<KtxTexture, String> {
+  let target = ktx_target(width, height, depth, layers, faces)?;
+  if let KtxFormat::Compressed(compressed) = format {
+    if height == 0 {
+      return Err(String::from("1D textures can't be compressed."));
+    }
+    // none of the block formats can be used for 3D textures.
+    if target == TextureTargetEnum::Texture3D {
+      return Err(String::from("3D textures can't be compressed."));
+    }
+    // compressed formats aren't color-renderable, which GL needs in order to
+    // generate mipmaps.
+    if levels == 0 {
+      return Err(format!(
+        "Mipmaps can't be generated for the compressed format {:?}.",
+        compressed
+      ));
+    }
+  }
+  let texture = KtxTexture {
+    target,
+    format,
+    width,
+    height: height.max(1),
+    depth: depth.max(1),
+    layers: layers.max(1),
+    faces,
+    levels: Vec::new(),
+    generate_mipmaps: levels == 0,
+    key_values,
+  };
+  if levels > texture.max_levels() {
+    return Err(format!(
+      "There are {} levels, but a {}x{}x{} texture can only have {}.",
+      levels,
+      texture.width,
+      texture.height,
+      texture.depth,
+      texture.max_levels()
+    ));
+  }
+  Ok(texture)
+}
+
+/// Parses key/value data, which is the same in both versions.
+fn parse_key_values(
+  data: &[u8], little_endian: bool,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+  let mut out = Vec::new();
+  let mut offset = 0;
+  while offset < data.len() {
+    let size = data
+      .get(offset..offset + 4)
+      .map(|b| read_u32(b, little_endian) as usize)
+      .ok_or_else(|| String::from("The key/value data is truncated."))?;
+    let entry = data
+      .get(offset + 4..)
+      .and_then(|rest| rest.get(..size))
+      .ok_or_else(|| String::from("The key/value data is truncated."))?;
+    let key_len = entry
+      .iter()
+      .position(|&b| b == 0)
+      .ok_or_else(|| String::from("A key isn't null terminated."))?;
+    out.push((
+      String::from_utf8_lossy(&entry[..key_len]).into_owned(),
+      entry[key_len + 1..].to_vec(),
+    ));
+    offset += 4 + size.next_multiple_of(4);
+  }
+  Ok(out)
+}
+
+fn read_u32(bytes: &[u8], little_endian: bool) -> u32 {
+  let bytes = bytes[..4].try_into().unwrap();
+  if little_endian {
+    u32::from_le_bytes(bytes)
+  } else {
+    u32::from_be_bytes(bytes)
+  }
+}
+
+/// Reverses the bytes of each `type_size` unit, if the data's byte order
+/// isn't the host's.
+fn fix_byte_order(data: &mut [u8], type_size: u32, little_endian: bool) {
+  if little_endian != cfg!(target_endian = "little") && type_size > 1 {
+    data.chunks_exact_mut(type_size as usize).for_each(|c| c.reverse());
+  }
+}
+
+fn parse_ktx1(bytes: &[u8]) -> Result<KtxTexture, String> {
+  if bytes.len() < KTX1_HEADER_SIZE {
+    return Err(format!("File too short: {} bytes.", bytes.len()));
+  }
+  let little_endian = match read_u32(&bytes[12..], true) {
+    0x0403_0201 => true,
+    0x0102_0304 => false,
+    other => {
+      return Err(format!("Bad endianness marker: 0x{:08X}.", other));
+    }
+  };
+  let u32_at = |i: usize| -> Result<u32, String> {
+    bytes
+      .get(i..i + 4)
+      .map(|b| read_u32(b, little_endian))
+      .ok_or_else(|| format!("Unexpected end of file at byte {}.", i))
+  };
+  let [gl_type, type_size, gl_format, gl_internal_format] =
+    [u32_at(16)?, u32_at(20)?, u32_at(24)?, u32_at(28)?];
+  let [width, height, depth] = [u32_at(36)?, u32_at(40)?, u32_at(44)?];
+  let [layers, faces, levels] = [u32_at(48)?, u32_at(52)?, u32_at(56)?];
+  let key_value_size = u32_at(60)? as usize;
+
+  let format = if gl_type == 0 {
+    KtxFormat::Compressed(
+      CompressedFormatEnum::from_enum(GLenum(gl_internal_format)).ok_or_else(
+        || format!("Unsupported compressed format 0x{:X}.", gl_internal_format),
+      )?,
+    )
+  } else {
+    let internal_format =
+      InternalFormatEnum::from_enum(GLenum(gl_internal_format)).ok_or_else(
+        || format!("Unsupported internal format 0x{:X}.", gl_internal_format),
+      )?;
+    let (format, data_type) = (GLenum(gl_format), GLenum(gl_type));
+    if pixel_size(format, data_type).is_none() {
+      return Err(format!(
+        "Unsupported format and type: 0x{:X} and 0x{:X}.",
+        gl_format, gl_type
+      ));
+    }
+    if ![1, 2, 4].contains(&type_size) {
+      return Err(format!("The type size is {}.", type_size));
+    }
+    KtxFormat::Uncompressed { internal_format, format, data_type, type_size }
+  };
+  let key_values = bytes
+    .get(KTX1_HEADER_SIZE..)
+    .and_then(|rest| rest.get(..key_value_size))
+    .ok_or_else(|| String::from("The key/value data is truncated."))?;
+  let key_values = parse_key_values(key_values, little_endian)?;
+  let mut texture = ktx_texture(
+    format, width, height, depth, layers, faces, levels, key_values,
+  )?;
+
+  let mut offset = KTX1_HEADER_SIZE + key_value_size;
+  for level in 0..levels.max(1) as usize {
+    let [w, h, d] = texture.level_size(level);
+    let image_size = u32_at(offset)? as usize;
+    offset += 4;
+    // rows of uncompressed data are padded to 4 bytes.
+    let (row_size, padded_row_size) = match format {
+      KtxFormat::Uncompressed { .. } => {
+        let row_size = format.image_size(w, 1, 1).unwrap();
+        (row_size, row_size.next_multiple_of(4))
+      }
+      KtxFormat::Compressed(_) => (0, 0),
+    };
+    let too_big = || format!("Level {} is too big.", level);
+    let packed_size = format.image_size(w, h, d).ok_or_else(too_big)?;
+    let padded_size = match format {
+      KtxFormat::Uncompressed { .. } => (packed_size / row_size)
+        .checked_mul(padded_row_size)
+        .ok_or_else(too_big)?,
+      KtxFormat::Compressed(_) => packed_size,
+    };
+    let images = texture.layers as usize * texture.faces as usize;
+    let total = padded_size.checked_mul(images).ok_or_else(too_big)?;
+    // a non-array cube map gives the size of one face.
+    let expected = if texture.target == TextureTargetEnum::CubeMap {
+      padded_size
+    } else {
+      total
+    };
+    if image_size != expected {
+      return Err(format!(
+        "Level {} has {} bytes of image data, but {} were expected.",
+        level, image_size, expected
+      ));
+    }
+    // each image is a whole number of padded rows (or of blocks), so the
+    // faces of a cube map never need padding between them.
+    let data = bytes
+      .get(offset..)
+      .and_then(|rest| rest.get(..total))
+      .ok_or_else(|| format!("Level {} is truncated.", level))?;
+    let mut data = if padded_row_size != row_size {
+      data
+        .chunks_exact(padded_row_size)
+        .flat_map(|row| &row[..row_size])
+        .copied()
+        .collect()
+    } else {
+      data.to_vec()
+    };
+    if let KtxFormat::Uncompressed { type_size, .. } = format {
+      fix_byte_order(&mut data, type_size, little_endian);
+    }
+    texture.levels.push(data);
+    offset += total.next_multiple_of(4);
+  }
+  Ok(texture)
+}
+
+fn parse_ktx2(bytes: &[u8]) -> Result<KtxTexture, String> {
+  if bytes.len() < KTX2_HEADER_SIZE {
+    return Err(format!("File too short: {} bytes.", bytes.len()));
+  }
+  let u32_at = |i: usize| read_u32(&bytes[i..], true);
+  let u64_at = |i: usize| -> Result<u64, String> {
+    bytes
+      .get(i..i + 8)
+      .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+      .ok_or_else(|| format!("Unexpected end of file at byte {}.", i))
+  };
+  let [vk_format, type_size] = [u32_at(12), u32_at(16)];
+  let [width, height, depth] = [u32_at(20), u32_at(24), u32_at(28)];
+  let [layers, faces, levels] = [u32_at(32), u32_at(36), u32_at(40)];
+  let supercompression = u32_at(44);
+  let [key_value_offset, key_value_size] = [u32_at(56), u32_at(60)];
+
+  if supercompression != 0 {
+    return Err(format!(
+      "Supercompression scheme {} isn't supported.",
+      supercompression
+    ));
+  }
+  let format = VK_FORMATS
+    .iter()
+    .find(|(vk, _)| *vk == vk_format)
+    .map(|(_, format)| *format)
+    .ok_or_else(|| format!("Unsupported VkFormat {}.", vk_format))?;
+  let expected_type_size = match format {
+    KtxFormat::Uncompressed { type_size, .. } => type_size,
+    KtxFormat::Compressed(_) => 1,
+  };
+  if type_size != expected_type_size {
+    return Err(format!(
+      "The type size is {}, but VkFormat {} needs {}.",
+      type_size, vk_format, expected_type_size
+    ));
+  }
+  let key_values = bytes
+    .get(key_value_offset as usize..)
+    .and_then(|rest| rest.get(..key_value_size as usize))
+    .ok_or_else(|| String::from("The key/value data is truncated."))?;
+  let key_values = parse_key_values(key_values, true)?;
+  let mut texture = ktx_texture(
+    format, width, height, depth, layers, faces, levels, key_values,
+  )?;
+
+  for level in 0..levels.max(1) as usize {
+    let entry = KTX2_HEADER_SIZE + level * 24;
+    let (offset, length) = (u64_at(entry)?, u64_at(entry + 8)?);
+    let [w, h, d] = texture.level_size(level);
+    let expected = format
+      .image_size(w, h, d)
+      .and_then(|size| {
+        size.checked_mul(texture.layers as usize * texture.faces as usize)
+      })
+      .ok_or_else(|| format!("Level {} is too big.", level))?;
+    if length != expected as u64 {
+      return Err(format!(
+        "Level {} has {} bytes of image data, but {} were expected.",
+        level, length, expected
+      ));
+    }
+    let offset: Option<usize> = offset.try_into().ok();
+    let mut data = offset
+      .and_then(|offset| bytes.get(offset..))
+      .and_then(|rest| rest.get(..expected))
+      .ok_or_else(|| format!("Level {} is truncated.", level))?
+      .to_vec();
+    fix_byte_order(&mut data, type_size, true);
+    texture.levels.push(data);
+  }
+  Ok(texture)
+}
+
+impl GlFnsRusty {
+  /// Creates a texture from a [`KtxTexture`], with storage for every level
+  /// and the data of every level uploaded.
+  ///
+  /// If the file asked for mipmaps to be generated, the texture gets a full
+  /// chain of levels, generated from the base level.
+  ///
+  /// The data is uploaded with `GL_UNPACK_ALIGNMENT` at 1, and the old
+  /// alignment is put back afterwards.
+  pub fn create_ktx_texture(&self, ktx: &KtxTexture) -> Option<TextureID> {
+    use TextureTargetEnum::*;
+    let texture = self.create_textures::<1>(ktx.target)[0]?;
+    let name = texture.0.get();
+    let storage_levels = ktx.storage_levels() as i32;
+    let internal_format = ktx.format.internal_format_enum();
+    let [width, height, depth] =
+      [ktx.width as i32, ktx.height as i32, ktx.depth as i32];
+    let layers = ktx.layers as i32;
+    unsafe {
+      match ktx.target {
+        Texture1D => {
+          self.TextureStorage1D(name, storage_levels, internal_format, width)
+        }
+        Texture1DArray => self.TextureStorage2D(
+          name,
+          storage_levels,
+          internal_format,
+          width,
+          layers,
+        ),
+        Texture2D | CubeMap => self.TextureStorage2D(
+          name,
+          storage_levels,
+          internal_format,
+          width,
+          height,
+        ),
+        _ => {
+          let depth = match ktx.target {
+            Texture2DArray => layers,
+            CubeMapArray => layers * 6,
+            _ => depth,
+          };
+          self.TextureStorage3D(
+            name,
+            storage_levels,
+            internal_format,
+            width,
+            height,
+            depth,
+          )
+        }
+      }
+    }
+    self.with_unpack_alignment_1(|| self.upload_ktx_levels(texture, ktx));
+    if ktx.generate_mipmaps {
+      self.generate_texture_mipmap(texture);
+    }
+    Some(texture)
+  }
+
+  fn upload_ktx_levels(&self, texture: TextureID, ktx: &KtxTexture) {
+    use TextureTargetEnum::*;
+    let name = texture.0.get();
+    let layers = ktx.layers as i32;
+    for (level, data) in ktx.levels.iter().enumerate() {
+      let [w, h, d] = ktx.level_size(level);
+      let [w, h, d] = [w as i32, h as i32, d as i32];
+      // the region, with array layers and cube faces along the last axis.
+      let [w, h, d] = match ktx.target {
+        Texture1DArray => [w, layers, 1],
+        CubeMap => [w, h, 6],
+        Texture2DArray => [w, h, layers],
+        CubeMapArray => [w, h, layers * 6],
+        _ => [w, h, d],
+      };
+      let level = level as i32;
+      let pixels = data.as_ptr().cast();
+      unsafe {
+        match (ktx.format, ktx.target) {
+          (KtxFormat::Uncompressed { format, data_type, .. }, Texture1D) => {
+            self.TextureSubImage1D(name, level, 0, w, format, data_type, pixels)
+          }
+          (
+            KtxFormat::Uncompressed { format, data_type, .. },
+            Texture1DArray | Texture2D,
+          ) => self.TextureSubImage2D(
+            name, level, 0, 0, w, h, format, data_type, pixels,
+          ),
+          (KtxFormat::Uncompressed { format, data_type, .. }, _) => self
+            .TextureSubImage3D(
+              name, level, 0, 0, 0, w, h, d, format, data_type, pixels,
+            ),
+          (KtxFormat::Compressed(compressed), Texture2D) => self
+            .CompressedTextureSubImage2D(
+              name,
+              level,
+              0,
+              0,
+              w,
+              h,
+              compressed.as_enum(),
+              data.len().try_into().unwrap(),
+              pixels,
+            ),
+          (KtxFormat::Compressed(compressed), _) => self
+            .CompressedTextureSubImage3D(
+              name,
+              level,
+              0,
+              0,
+              0,
+              w,
+              h,
+              d,
+              compressed.as_enum(),
+              data.len().try_into().unwrap(),
+              pixels,
+            ),
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+const TEST_FILES: &[(&str, &[u8])] = &[
+  (
+    "rgb8_3x2_mips.ktx",
+    include_bytes!("../../test_data/ktx/rgb8_3x2_mips.ktx"),
+  ),
+  ("r16_2x2_be.ktx", include_bytes!("../../test_data/ktx/r16_2x2_be.ktx")),
+  (
+    "cube_rgba8_1x1.ktx",
+    include_bytes!("../../test_data/ktx/cube_rgba8_1x1.ktx"),
+  ),
+  ("rgtc1_4x4.ktx", include_bytes!("../../test_data/ktx/rgtc1_4x4.ktx")),
+  (
+    "r32f_2x1_array.ktx2",
+    include_bytes!("../../test_data/ktx/r32f_2x1_array.ktx2"),
+  ),
+];
+
+#[cfg(test)]
+fn test_file(name: &str) -> KtxTexture {
+  let (_, bytes) = TEST_FILES.iter().find(|(n, _)| *n == name).unwrap();
+  KtxTexture::parse(bytes).unwrap()
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_KtxTexture_parse_ktx1() {
+  let rgb = test_file("rgb8_3x2_mips.ktx");
+  assert_eq!(rgb.target, TextureTargetEnum::Texture2D);
+  assert_eq!(
+    rgb.format,
+    uncompressed(InternalFormatEnum::RGB8, GL_RGB, GL_UNSIGNED_BYTE, 1)
+  );
+  assert_eq!([rgb.width, rgb.height, rgb.depth, rgb.layers], [3, 2, 1, 1]);
+  // the row padding is gone.
+  assert_eq!(rgb.levels[0], (1..=18).collect::<Vec<u8>>());
+  assert_eq!(rgb.levels[1], vec![50, 60, 70]);
+  assert_eq!(rgb.level_size(1), [1, 1, 1]);
+  assert!(!rgb.generate_mipmaps);
+  assert_eq!(rgb.value("KTXorientation"), Some(&b"S=r,T=u\0"[..]));
+
+  let be = test_file("r16_2x2_be.ktx");
+  let values: Vec<u16> = be.levels[0]
+    .chunks_exact(2)
+    .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+    .collect();
+  assert_eq!(values, vec![0x0102, 0x0304, 0x0506, 0x0708]);
+
+  let cube = test_file("cube_rgba8_1x1.ktx");
+  assert_eq!(cube.target, TextureTargetEnum::CubeMap);
+  assert_eq!(cube.faces, 6);
+  assert_eq!(cube.levels[0].len(), 24);
+  assert_eq!(cube.levels[0][20], 200);
+
+  let rgtc = test_file("rgtc1_4x4.ktx");
+  assert_eq!(
+    rgtc.format,
+    KtxFormat::Compressed(CompressedFormatEnum::RedRGTC1)
+  );
+  assert!(!rgtc.generate_mipmaps);
+  assert_eq!(rgtc.storage_levels(), 1);
+  assert_eq!(rgtc.levels, vec![vec![255, 0, 0, 0, 0, 0, 0, 0]]);
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_KtxTexture_parse_ktx2() {
+  let array = test_file("r32f_2x1_array.ktx2");
+  assert_eq!(array.target, TextureTargetEnum::Texture2DArray);
+  assert_eq!(
+    array.format,
+    uncompressed(InternalFormatEnum::R32F, GL_RED, GL_FLOAT, 4)
+  );
+  assert_eq!([array.width, array.height, array.layers], [2, 1, 3]);
+  let floats = |data: &[u8]| -> Vec<f32> {
+    data
+      .chunks_exact(4)
+      .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+      .collect()
+  };
+  assert_eq!(floats(&array.levels[0]), vec![0.0, 1.0, 10.0, 11.0, 20.0, 21.0]);
+  assert_eq!(floats(&array.levels[1]), vec![0.5, 10.5, 20.5]);
+  assert_eq!(array.value("KTXwriter"), Some(&b"sb7 test\0"[..]));
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_KtxTexture_parse_errors() {
+  for (name, bytes) in TEST_FILES.iter() {
+    // every truncation is an error rather than a panic.
+    for len in 0..bytes.len() {
+      assert!(KtxTexture::parse(&bytes[..len]).is_err(), "{} {}", name, len);
+    }
+  }
+  let with = |name: &str, offset: usize, value: u32| {
+    let (_, bytes) = TEST_FILES.iter().find(|(n, _)| *n == name).unwrap();
+    let mut bytes = bytes.to_vec();
+    bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    KtxTexture::parse(&bytes).unwrap_err()
+  };
+  let rgb = "rgb8_3x2_mips.ktx";
+  assert_eq!(with(rgb, 0, 0), "Not a KTX file.");
+  assert!(with(rgb, 12, 7).contains("endianness"));
+  // the internal format.
+  assert!(with(rgb, 28, 0x1234).contains("internal format 0x1234"));
+  // the level count.
+  assert!(with(rgb, 56, 4).contains("can only have 2"));
+  // the faces.
+  assert!(with(rgb, 52, 6).contains("must be square"));
+  // the first level's image size.
+  assert!(with(rgb, 64 + 28, 20).contains("but 24 were expected"));
+
+  let rgtc = "rgtc1_4x4.ktx";
+  assert!(with(rgtc, 56, 0).contains("Mipmaps can't be generated"));
+  // the depth.
+  assert!(with(rgtc, 44, 4).contains("3D textures can't be compressed"));
+
+  let array = "r32f_2x1_array.ktx2";
+  // the supercompression scheme.
+  assert!(with(array, 44, 1).contains("Supercompression"));
+  assert!(with(array, 12, 1000).contains("VkFormat 1000"));
+  // the base level's length.
+  assert!(with(array, 88, 25).contains("but 24 were expected"));
+  // the base level's offset.
+  assert!(with(array, 80, 180).contains("Level 0 is truncated"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_create_ktx_texture() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  // reads a level back as bytes of the format and type given.
+  let read = |texture: TextureID, level: i32, format, data_type, len| {
+    let mut out = vec![0_u8; len];
+    unsafe {
+      gl.PixelStorei(GL_PACK_ALIGNMENT, 1);
+      gl.GetTextureImage(
+        texture.0.get(),
+        level,
+        format,
+        data_type,
+        len as i32,
+        out.as_mut_ptr().cast(),
+      )
+    };
+    out
+  };
+  let mut textures = Vec::new();
+  unsafe { gl.PixelStorei(GL_UNPACK_ALIGNMENT, 8) };
+  for (name, _) in TEST_FILES.iter() {
+    let ktx = test_file(name);
+    let texture = gl.create_ktx_texture(&ktx).unwrap();
+    textures.push(texture);
+    match ktx.format {
+      KtxFormat::Uncompressed { format, data_type, .. } => {
+        for (level, data) in ktx.levels.iter().enumerate() {
+          let out = read(texture, level as i32, format, data_type, data.len());
+          assert_eq!(&out, data, "{} level {}", name, level);
+        }
+      }
+      KtxFormat::Compressed(_) => {
+        // the block decodes to all 255.
+        assert_eq!(read(texture, 0, GL_RED, GL_UNSIGNED_BYTE, 16), [255; 16]);
+      }
+    }
+    assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR, "{}", name);
+  }
+  // the unpack alignment is put back.
+  let mut alignment = 0;
+  unsafe { gl.GetIntegerv(GL_UNPACK_ALIGNMENT, &mut alignment) };
+  assert_eq!(alignment, 8);
+  unsafe { gl.PixelStorei(GL_UNPACK_ALIGNMENT, 4) };
+  for texture in textures {
+    gl.delete_textures([Some(texture)]);
+  }
+}
@@ -0,0 +1,614 @@
+//! A fake GL, for testing the wrappers without a context (or a GPU).
+
+use super::*;
+use core::{
+  cell::{Cell, RefCell},
+  marker::PhantomData,
+};
+use std::collections::{HashMap, VecDeque};
+
+/// One argument of a [`MockCall`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockArg {
+  Int(i64),
+  Float(f32),
+  Enum(GLenum),
+  /// The text of string arguments (eg: shader sources), rather than the
+  /// pointer.
+  Str(String),
+  /// The values of a small input array (eg: a clear color).
+  Floats(Vec<f32>),
+  /// Any other non-null pointer, including output pointers.
+  Ptr,
+  Null,
+}
+
+/// A call made to a [`MockGl`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockCall {
+  /// The GL name of the function, eg: `glCreateShader`.
+  pub name: &'static str,
+  pub args: Vec<MockArg>,
+}
+
+struct MockState {
+  /// Which [`MockGl`] owns this state.
+  generation: u64,
+  calls: Vec<MockCall>,
+  next_name: u32,
+  creates_fail: bool,
+  compile_status: bool,
+  link_status: bool,
+  shader_info_log: String,
+  program_info_log: String,
+  /// Null terminated, so that pointers into them can be given out.
+  extensions: Vec<Vec<u8>>,
+  strings: HashMap<u32, Vec<u8>>,
+  integers: HashMap<u32, i32>,
+  errors: VecDeque<GLenum>,
+}
+impl Default for MockState {
+  fn default() -> Self {
+    let mut strings = HashMap::new();
+    strings.insert(GL_VENDOR.0, b"superbible7\0".to_vec());
+    strings.insert(GL_RENDERER.0, b"MockGl\0".to_vec());
+    strings.insert(GL_VERSION.0, b"4.6 MockGl\0".to_vec());
+    Self {
+      generation: 0,
+      calls: Vec::new(),
+      next_name: 1,
+      creates_fail: false,
+      compile_status: true,
+      link_status: true,
+      shader_info_log: String::new(),
+      program_info_log: String::new(),
+      extensions: Vec::new(),
+      strings,
+      integers: HashMap::new(),
+      errors: VecDeque::new(),
+    }
+  }
+}
+
+thread_local! {
+  static MOCK_STATE: RefCell<Option<MockState>> = const { RefCell::new(None) };
+  static MOCK_GENERATION: Cell<u64> = const { Cell::new(0) };
+}
+
+fn with_state<R>(f: impl FnOnce(&mut MockState) -> R) -> R {
+  MOCK_STATE.with(|cell| {
+    f(cell.borrow_mut().as_mut().expect("no MockGl is active on this thread"))
+  })
+}
+
+/// A fake GL driver that logs every call, for unit tests.
+///
+/// [`MockGl::new`] also gives a [`GlFnsRusty`] where each function records
+/// its name and arguments into this log, and gives back fake results that
+/// you can configure. The defaults are that everything works: object names
+/// count up from 1, compiles and links succeed, the info logs are empty, and
+/// there are no extensions or errors.
+///
+/// The log is per-thread, so the GL functions must only be used on the
+/// thread that made the `MockGl`, and only while it's alive.
+///
+/// Only the shader, program, string, and basic state calls are faked.
+/// Calling any other GL function panics, which aborts the test process
+/// (since GL functions can't unwind).
+pub struct MockGl {
+  generation: u64,
+  // the state is thread local.
+  _not_send: PhantomData<*const ()>,
+}
+impl MockGl {
+  /// Makes a new mock and its functions.
+  ///
+  /// This replaces any older mock on this thread: the functions of both
+  /// mocks then use the new state, using the older `MockGl` panics, and
+  /// dropping it does nothing.
+  pub fn new() -> (Self, GlFnsRusty) {
+    let generation = MOCK_GENERATION.with(|g| {
+      g.set(g.get() + 1);
+      g.get()
+    });
+    MOCK_STATE.with(|cell| {
+      *cell.borrow_mut() =
+        Some(MockState { generation, ..MockState::default() })
+    });
+    let gl = unsafe { GlFns::load_from(&Self::proc_address) }.unwrap();
+    (Self { generation, _not_send: PhantomData }, GlFnsRusty(gl))
+  }
+
+  fn state<R>(&self, f: impl FnOnce(&mut MockState) -> R) -> R {
+    with_state(|s| {
+      assert_eq!(s.generation, self.generation, "this MockGl was replaced");
+      f(s)
+    })
+  }
+
+  /// Looks up a fake GL function by its null terminated name, like a
+  /// context's loader would.
+  ///
+  /// This lets the mock be wrapped by another loader (eg: a [`GlTracer`]).
+  pub fn proc_address(name: *const u8) -> *const c_void {
+    lookup_stub(unsafe { core::ffi::CStr::from_ptr(name.cast()) }.to_bytes())
+  }
+
+  /// All the calls so far, oldest first.
+  pub fn calls(&self) -> Vec<MockCall> {
+    self.state(|s| s.calls.clone())
+  }
+
+  /// The names of all the calls so far, oldest first.
+  pub fn call_names(&self) -> Vec<&'static str> {
+    self.state(|s| s.calls.iter().map(|c| c.name).collect())
+  }
+
+  /// Empties the call log.
+  pub fn clear_calls(&self) {
+    self.state(|s| s.calls.clear())
+  }
+
+  /// Makes object creation give 0 (the same as a driver failing).
+  pub fn set_creates_fail(&self, fail: bool) {
+    self.state(|s| s.creates_fail = fail)
+  }
+
+  /// Sets `GL_COMPILE_STATUS` for all shaders.
+  pub fn set_compile_status(&self, success: bool) {
+    self.state(|s| s.compile_status = success)
+  }
+
+  /// Sets `GL_LINK_STATUS` for all programs.
+  pub fn set_link_status(&self, success: bool) {
+    self.state(|s| s.link_status = success)
+  }
+
+  /// Sets the info log of all shaders.
+  pub fn set_shader_info_log(&self, log: &str) {
+    self.state(|s| s.shader_info_log = String::from(log))
+  }
+
+  /// Sets the info log of all programs.
+  pub fn set_program_info_log(&self, log: &str) {
+    self.state(|s| s.program_info_log = String::from(log))
+  }
+
+  /// Sets the extensions listed by `glGetStringi`, and their count.
+  pub fn set_extensions(&self, extensions: &[&str]) {
+    self.state(|s| {
+      s.extensions = extensions.iter().map(|e| null_terminated(e)).collect()
+    })
+  }
+
+  /// Sets what `glGetString` gives for `name`.
+  pub fn set_string(&self, name: GLenum, value: &str) {
+    self.state(|s| s.strings.insert(name.0, null_terminated(value)));
+  }
+
+  /// Sets what `glGetIntegerv` gives for `pname` (otherwise it's 0).
+  pub fn set_integer(&self, pname: GLenum, value: i32) {
+    self.state(|s| s.integers.insert(pname.0, value));
+  }
+
+  /// Queues an error for `glGetError` to give.
+  pub fn push_error(&self, error: GLenum) {
+    self.state(|s| s.errors.push_back(error))
+  }
+}
+impl Drop for MockGl {
+  /// Removes the state, unless a newer mock replaced this one.
+  fn drop(&mut self) {
+    MOCK_STATE.with(|cell| {
+      let mut state = cell.borrow_mut();
+      if state.as_ref().map(|s| s.generation) == Some(self.generation) {
+        *state = None;
+      }
+    });
+  }
+}
+
+fn null_terminated(s: &str) -> Vec<u8> {
+  let mut v = Vec::with_capacity(s.len() + 1);
+  v.extend_from_slice(s.as_bytes());
+  v.push(0);
+  v
+}
+
+fn record(name: &'static str, args: Vec<MockArg>) {
+  with_state(|s| s.calls.push(MockCall { name, args }))
+}
+
+fn ptr_arg<T>(p: *const T) -> MockArg {
+  if p.is_null() {
+    MockArg::Null
+  } else {
+    MockArg::Ptr
+  }
+}
+
+fn new_name(s: &mut MockState) -> u32 {
+  if s.creates_fail {
+    0
+  } else {
+    s.next_name += 1;
+    s.next_name - 1
+  }
+}
+
+/// Joins the strings given to `glShaderSource` (and similar).
+///
+/// A null `lengths`, or a negative length, means a null terminated string.
+unsafe fn gather_strings(
+  count: i32, strings: *const *const u8, lengths: *const i32,
+) -> String {
+  let mut v = Vec::new();
+  for i in 0..count.max(0) as usize {
+    let p = *strings.add(i);
+    let len = if lengths.is_null() { -1 } else { *lengths.add(i) };
+    if len < 0 {
+      v.extend(gather_null_terminated_bytes(p));
+    } else {
+      v.extend_from_slice(core::slice::from_raw_parts(p, len as usize));
+    }
+  }
+  min_alloc_lossy_into_string(v)
+}
+
+/// Writes an info log the way `glGet*InfoLog` does.
+unsafe fn write_info_log(
+  log: &str, buf_size: i32, length: *mut i32, info_log: *mut u8,
+) {
+  if buf_size <= 0 {
+    return;
+  }
+  let n = log.len().min(buf_size as usize - 1);
+  core::ptr::copy_nonoverlapping(log.as_ptr(), info_log, n);
+  *info_log.add(n) = 0;
+  if !length.is_null() {
+    *length = n as i32;
+  }
+}
+
+/// The `GL_INFO_LOG_LENGTH` of a log, which includes the null terminator.
+fn info_log_length(log: &str) -> i32 {
+  if log.is_empty() {
+    0
+  } else {
+    log.len() as i32 + 1
+  }
+}
+
+fn lookup_stub(name: &[u8]) -> *const c_void {
+  match name {
+    b"glGetError" => get_error as *const c_void,
+    b"glGetString" => get_string as *const c_void,
+    b"glGetStringi" => get_string_i as *const c_void,
+    b"glGetIntegerv" => get_integer_v as *const c_void,
+    b"glCreateShader" => create_shader as *const c_void,
+    b"glShaderSource" => shader_source as *const c_void,
+    b"glCompileShader" => compile_shader as *const c_void,
+    b"glGetShaderiv" => get_shader_iv as *const c_void,
+    b"glGetShaderInfoLog" => get_shader_info_log as *const c_void,
+    b"glDeleteShader" => delete_shader as *const c_void,
+    b"glCreateProgram" => create_program as *const c_void,
+    b"glCreateShaderProgramv" => create_shader_program_v as *const c_void,
+    b"glAttachShader" => attach_shader as *const c_void,
+    b"glLinkProgram" => link_program as *const c_void,
+    b"glProgramParameteri" => program_parameter_i as *const c_void,
+    b"glGetProgramiv" => get_program_iv as *const c_void,
+    b"glGetProgramInfoLog" => get_program_info_log as *const c_void,
+    b"glUseProgram" => use_program as *const c_void,
+    b"glDeleteProgram" => delete_program as *const c_void,
+    b"glClearBufferfv" => clear_buffer_fv as *const c_void,
+    b"glPointSize" => point_size as *const c_void,
+    b"glPolygonOffset" => polygon_offset as *const c_void,
+    b"glViewport" => viewport as *const c_void,
+    b"glPolygonMode" => polygon_mode as *const c_void,
+    b"glPixelStorei" => pixel_store_i as *const c_void,
+    // everything else is a stub that panics.
+    _ => not_mocked_stub(name),
+  }
+}
+
+macro_rules! not_mocked {
+  ($(fn $func:ident($($arg:ident: $t:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+    /// A stub for each GL function that the mock doesn't fake, with the real
+    /// signature (calling through a pointer of the wrong signature is UB), or
+    /// null for an unknown name.
+    fn not_mocked_stub(name: &[u8]) -> *const c_void {
+      $(
+        if name == concat!("gl", stringify!($func)).as_bytes() {
+          #[allow(clippy::too_many_arguments)]
+          extern "system" fn stub($(_: $t),*) $(-> $ret)? {
+            panic!(concat!("MockGl doesn't fake gl", stringify!($func)))
+          }
+          return stub as *const c_void;
+        }
+      )*
+      core::ptr::null()
+    }
+  };
+}
+
+gl_signatures!(not_mocked);
+
+extern "system" fn get_error() -> GLenum {
+  record("glGetError", vec![]);
+  with_state(|s| s.errors.pop_front().unwrap_or(GL_NO_ERROR))
+}
+
+extern "system" fn get_string(name: GLenum) -> *const u8 {
+  record("glGetString", vec![MockArg::Enum(name)]);
+  with_state(|s| match s.strings.get(&name.0) {
+    Some(v) => v.as_ptr(),
+    None => {
+      s.errors.push_back(GL_INVALID_ENUM);
+      core::ptr::null()
+    }
+  })
+}
+
+extern "system" fn get_string_i(name: GLenum, index: u32) -> *const u8 {
+  record("glGetStringi", vec![MockArg::Enum(name), MockArg::Int(index.into())]);
+  with_state(|s| match s.extensions.get(index as usize) {
+    Some(v) if name == GL_EXTENSIONS => v.as_ptr(),
+    _ => {
+      s.errors.push_back(GL_INVALID_VALUE);
+      core::ptr::null()
+    }
+  })
+}
+
+unsafe extern "system" fn get_integer_v(pname: GLenum, data: *mut i32) {
+  record("glGetIntegerv", vec![MockArg::Enum(pname), ptr_arg(data)]);
+  *data = with_state(|s| match pname {
+    GL_NUM_EXTENSIONS => s.extensions.len() as i32,
+    _ => s.integers.get(&pname.0).copied().unwrap_or(0),
+  });
+}
+
+extern "system" fn create_shader(ty: GLenum) -> u32 {
+  record("glCreateShader", vec![MockArg::Enum(ty)]);
+  with_state(new_name)
+}
+
+unsafe extern "system" fn shader_source(
+  shader: u32, count: i32, strings: *const *const u8, lengths: *const i32,
+) {
+  let src = gather_strings(count, strings, lengths);
+  record(
+    "glShaderSource",
+    vec![
+      MockArg::Int(shader.into()),
+      MockArg::Int(count.into()),
+      MockArg::Str(src),
+      ptr_arg(lengths),
+    ],
+  );
+}
+
+extern "system" fn compile_shader(shader: u32) {
+  record("glCompileShader", vec![MockArg::Int(shader.into())]);
+}
+
+unsafe extern "system" fn get_shader_iv(
+  shader: u32, pname: GLenum, params: *mut i32,
+) {
+  record(
+    "glGetShaderiv",
+    vec![MockArg::Int(shader.into()), MockArg::Enum(pname), ptr_arg(params)],
+  );
+  *params = with_state(|s| match pname {
+    GL_COMPILE_STATUS => s.compile_status as i32,
+    GL_INFO_LOG_LENGTH => info_log_length(&s.shader_info_log),
+    _ => 0,
+  });
+}
+
+unsafe extern "system" fn get_shader_info_log(
+  shader: u32, buf_size: i32, length: *mut i32, info_log: *mut u8,
+) {
+  record(
+    "glGetShaderInfoLog",
+    vec![
+      MockArg::Int(shader.into()),
+      MockArg::Int(buf_size.into()),
+      ptr_arg(length),
+      ptr_arg(info_log),
+    ],
+  );
+  let log = with_state(|s| s.shader_info_log.clone());
+  write_info_log(&log, buf_size, length, info_log);
+}
+
+extern "system" fn delete_shader(shader: u32) {
+  record("glDeleteShader", vec![MockArg::Int(shader.into())]);
+}
+
+extern "system" fn create_program() -> u32 {
+  record("glCreateProgram", vec![]);
+  with_state(new_name)
+}
+
+unsafe extern "system" fn create_shader_program_v(
+  ty: GLenum, count: i32, strings: *const *const u8,
+) -> u32 {
+  let src = gather_strings(count, strings, core::ptr::null());
+  record(
+    "glCreateShaderProgramv",
+    vec![MockArg::Enum(ty), MockArg::Int(count.into()), MockArg::Str(src)],
+  );
+  with_state(new_name)
+}
+
+extern "system" fn attach_shader(program: u32, shader: u32) {
+  record(
+    "glAttachShader",
+    vec![MockArg::Int(program.into()), MockArg::Int(shader.into())],
+  );
+}
+
+extern "system" fn link_program(program: u32) {
+  record("glLinkProgram", vec![MockArg::Int(program.into())]);
+}
+
+extern "system" fn program_parameter_i(
+  program: u32, pname: GLenum, value: i32,
+) {
+  record(
+    "glProgramParameteri",
+    vec![
+      MockArg::Int(program.into()),
+      MockArg::Enum(pname),
+      MockArg::Int(value.into()),
+    ],
+  );
+}
+
+unsafe extern "system" fn get_program_iv(
+  program: u32, pname: GLenum, params: *mut i32,
+) {
+  record(
+    "glGetProgramiv",
+    vec![MockArg::Int(program.into()), MockArg::Enum(pname), ptr_arg(params)],
+  );
+  *params = with_state(|s| match pname {
+    GL_LINK_STATUS => s.link_status as i32,
+    GL_INFO_LOG_LENGTH => info_log_length(&s.program_info_log),
+    _ => 0,
+  });
+}
+
+unsafe extern "system" fn get_program_info_log(
+  program: u32, buf_size: i32, length: *mut i32, info_log: *mut u8,
+) {
+  record(
+    "glGetProgramInfoLog",
+    vec![
+      MockArg::Int(program.into()),
+      MockArg::Int(buf_size.into()),
+      ptr_arg(length),
+      ptr_arg(info_log),
+    ],
+  );
+  let log = with_state(|s| s.program_info_log.clone());
+  write_info_log(&log, buf_size, length, info_log);
+}
+
+extern "system" fn use_program(program: u32) {
+  record("glUseProgram", vec![MockArg::Int(program.into())]);
+}
+
+extern "system" fn delete_program(program: u32) {
+  record("glDeleteProgram", vec![MockArg::Int(program.into())]);
+}
+
+unsafe extern "system" fn clear_buffer_fv(
+  buffer: GLenum, draw_buffer: i32, value: *const f32,
+) {
+  // GL_COLOR takes 4 values and GL_DEPTH takes 1.
+  let n = if buffer == GL_COLOR { 4 } else { 1 };
+  record(
+    "glClearBufferfv",
+    vec![
+      MockArg::Enum(buffer),
+      MockArg::Int(draw_buffer.into()),
+      MockArg::Floats(core::slice::from_raw_parts(value, n).to_vec()),
+    ],
+  );
+}
+
+extern "system" fn point_size(size: f32) {
+  record("glPointSize", vec![MockArg::Float(size)]);
+}
+
+extern "system" fn polygon_offset(factor: f32, units: f32) {
+  record(
+    "glPolygonOffset",
+    vec![MockArg::Float(factor), MockArg::Float(units)],
+  );
+}
+
+extern "system" fn viewport(x: i32, y: i32, width: i32, height: i32) {
+  record(
+    "glViewport",
+    [x, y, width, height].iter().map(|&i| MockArg::Int(i.into())).collect(),
+  );
+}
+
+extern "system" fn polygon_mode(face: GLenum, mode: GLenum) {
+  record("glPolygonMode", vec![MockArg::Enum(face), MockArg::Enum(mode)]);
+}
+
+extern "system" fn pixel_store_i(pname: GLenum, param: i32) {
+  record(
+    "glPixelStorei",
+    vec![MockArg::Enum(pname), MockArg::Int(param.into())],
+  );
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_MockGl() {
+  let (mock, gl) = MockGl::new();
+  assert_eq!(gl.get_renderer_string(), "MockGl");
+  mock.set_string(GL_RENDERER, "Test Renderer");
+  assert_eq!(gl.get_renderer_string(), "Test Renderer");
+
+  gl.clear_color_draw_buffer(0, [0.5, 0.0, 1.0, 1.0]);
+  gl.viewport(1, 2, 30, 40);
+  let calls = mock.calls();
+  assert_eq!(calls.len(), 4);
+  assert_eq!(calls[0].args, [MockArg::Enum(GL_RENDERER)]);
+  assert_eq!(
+    calls[2].args,
+    [
+      MockArg::Enum(GL_COLOR),
+      MockArg::Int(0),
+      MockArg::Floats(vec![0.5, 0.0, 1.0, 1.0])
+    ]
+  );
+  assert_eq!(
+    calls[3].args,
+    [MockArg::Int(1), MockArg::Int(2), MockArg::Int(30), MockArg::Int(40)]
+  );
+
+  mock.clear_calls();
+  mock.set_integer(GL_MAX_VERTEX_ATTRIBS, 16);
+  let mut max_attribs = 0;
+  unsafe { gl.GetIntegerv(GL_MAX_VERTEX_ATTRIBS, &mut max_attribs) };
+  assert_eq!(max_attribs, 16);
+  mock.push_error(GL_OUT_OF_MEMORY);
+  assert_eq!(unsafe { gl.GetError() }, GL_OUT_OF_MEMORY);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+  assert_eq!(mock.call_names(), ["glGetIntegerv", "glGetError", "glGetError"]);
+  drop(mock);
+
+  // unfaked functions get their own stub, and unknown names are null.
+  let stub = |name: &[u8]| MockGl::proc_address(name.as_ptr());
+  assert!(!stub(b"glBindTexture\0").is_null());
+  assert_ne!(stub(b"glBindTexture\0"), stub(b"glBindSampler\0"));
+  assert!(stub(b"glNotAFunction\0").is_null());
+
+  // a new mock starts fresh.
+  let (mock, gl) = MockGl::new();
+  assert_eq!(gl.get_renderer_string(), "MockGl");
+  assert_eq!(mock.calls().len(), 1);
+
+  // dropping a replaced mock leaves the newer one working.
+  let (newer, newer_gl) = MockGl::new();
+  drop(mock);
+  assert_eq!(gl.get_renderer_string(), "MockGl");
+  assert_eq!(newer_gl.get_renderer_string(), "MockGl");
+  assert_eq!(newer.calls().len(), 2);
+}
+
+#[allow(non_snake_case)]
+#[test]
+#[should_panic(expected = "replaced")]
+fn test_replaced_MockGl_panics() {
+  let (old, _) = MockGl::new();
+  let (_new, _) = MockGl::new();
+  old.calls();
+}
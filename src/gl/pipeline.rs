@@ -0,0 +1,246 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct ProgramPipelineID(pub(crate) NonZeroU32);
+
+/// A set of shader stages within a program pipeline.
+///
+/// Combine flags with `|`, or convert a single [`ShaderEnum`] with `into`. See
+/// [glUseProgramStages](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glUseProgramStages.xhtml)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ShaderStageFlags(pub(crate) u32);
+impl_bit_ops_for_flags!(ShaderStageFlags);
+impl ShaderStageFlags {
+  pub const VERTEX: Self = Self(GL_VERTEX_SHADER_BIT.0);
+  pub const TESS_CONTROL: Self = Self(GL_TESS_CONTROL_SHADER_BIT.0);
+  pub const TESS_EVAL: Self = Self(GL_TESS_EVALUATION_SHADER_BIT.0);
+  pub const GEOMETRY: Self = Self(GL_GEOMETRY_SHADER_BIT.0);
+  pub const FRAGMENT: Self = Self(GL_FRAGMENT_SHADER_BIT.0);
+  pub const COMPUTE: Self = Self(GL_COMPUTE_SHADER_BIT.0);
+  /// Every stage, including any that a later GL version might add.
+  pub const ALL: Self = Self(GL_ALL_SHADER_BITS.0);
+}
+impl From<ShaderEnum> for ShaderStageFlags {
+  fn from(t: ShaderEnum) -> Self {
+    match t {
+      ShaderEnum::Compute => Self::COMPUTE,
+      ShaderEnum::Vertex => Self::VERTEX,
+      ShaderEnum::TessControl => Self::TESS_CONTROL,
+      ShaderEnum::TessEval => Self::TESS_EVAL,
+      ShaderEnum::Geometry => Self::GEOMETRY,
+      ShaderEnum::Fragment => Self::FRAGMENT,
+    }
+  }
+}
+
+impl GlFnsRusty {
+  /// Creates new program pipeline objects.
+  pub fn create_program_pipelines<const X: usize>(
+    &self,
+  ) -> [Option<ProgramPipelineID>; X] {
+    let mut out = [None; X];
+    unsafe {
+      self
+        .CreateProgramPipelines(X.try_into().unwrap(), out.as_mut_ptr().cast())
+    };
+    out
+  }
+
+  /// Deletes the given list of program pipeline objects.
+  pub fn delete_program_pipelines<const X: usize>(
+    &self, pipelines: [Option<ProgramPipelineID>; X],
+  ) {
+    unsafe {
+      self.DeleteProgramPipelines(
+        X.try_into().unwrap(),
+        pipelines.as_ptr().cast(),
+      )
+    };
+  }
+
+  /// Sets which program the pipeline uses for the given stages.
+  ///
+  /// The program must be separable (eg: from
+  /// [`create_shader_program`](GlFnsRusty::create_shader_program)). Stages in
+  /// `stages` that the program doesn't contain are cleared. Passing `None`
+  /// clears all of `stages`.
+  pub fn use_program_stages(
+    &self, pipeline: ProgramPipelineID, stages: ShaderStageFlags,
+    opt_program: Option<ProgramID>,
+  ) {
+    unsafe {
+      self.UseProgramStages(
+        pipeline.0.get(),
+        stages.as_bitfield(),
+        core::mem::transmute::<Option<ProgramID>, u32>(opt_program),
+      )
+    }
+  }
+
+  /// Sets the program that non-DSA `glUniform*` calls affect while this
+  /// pipeline is bound.
+  pub fn active_shader_program(
+    &self, pipeline: ProgramPipelineID, opt_program: Option<ProgramID>,
+  ) {
+    unsafe {
+      self.ActiveShaderProgram(
+        pipeline.0.get(),
+        core::mem::transmute::<Option<ProgramID>, u32>(opt_program),
+      )
+    }
+  }
+
+  /// Gets the program that the pipeline uses for a given stage, if any.
+  pub fn get_program_pipeline_stage(
+    &self, pipeline: ProgramPipelineID, t: ShaderEnum,
+  ) -> Option<ProgramID> {
+    let mut out = 0;
+    unsafe {
+      self.GetProgramPipelineiv(pipeline.0.get(), t.as_enum(), &mut out)
+    };
+    NonZeroU32::new(out as u32).map(ProgramID)
+  }
+
+  /// Binds a program pipeline as the source of shader stages.
+  ///
+  /// A program set with [`use_program`](GlFnsRusty::use_program) takes
+  /// priority over the bound pipeline, so that must be cleared for the
+  /// pipeline to have any effect.
+  pub fn bind_program_pipeline(&self, opt_pipeline: Option<ProgramPipelineID>) {
+    unsafe {
+      self.BindProgramPipeline(core::mem::transmute::<
+        Option<ProgramPipelineID>,
+        u32,
+      >(opt_pipeline))
+    }
+  }
+
+  /// Gets the information log for this program pipeline.
+  pub fn get_program_pipeline_info_log(
+    &self, pipeline: ProgramPipelineID,
+  ) -> String {
+    // capacity needed for the log (including null terminator)
+    let mut info_log_length = 0;
+    unsafe {
+      self.GetProgramPipelineiv(
+        pipeline.0.get(),
+        GL_INFO_LOG_LENGTH,
+        &mut info_log_length,
+      )
+    };
+    if info_log_length == 0 {
+      String::new()
+    } else {
+      let mut v = Vec::with_capacity(info_log_length.try_into().unwrap());
+      // printable chars of the log (excludes null terminator)
+      let mut printable_byte_count = 0;
+      unsafe {
+        self.GetProgramPipelineInfoLog(
+          pipeline.0.get(),
+          v.capacity().try_into().unwrap(),
+          &mut printable_byte_count,
+          v.as_mut_ptr(),
+        );
+        v.set_len(printable_byte_count.try_into().unwrap());
+      }
+      min_alloc_lossy_into_string(v)
+    }
+  }
+
+  /// Checks if the pipeline can execute given the current GL state.
+  ///
+  /// On failure, you get the pipeline's info log.
+  pub fn validate_program_pipeline(
+    &self, pipeline: ProgramPipelineID,
+  ) -> Result<(), String> {
+    let mut status = 0;
+    unsafe {
+      self.ValidateProgramPipeline(pipeline.0.get());
+      self.GetProgramPipelineiv(
+        pipeline.0.get(),
+        GL_VALIDATE_STATUS,
+        &mut status,
+      );
+    }
+    if status != 0 {
+      Ok(())
+    } else {
+      Err(self.get_program_pipeline_info_log(pipeline))
+    }
+  }
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderStageFlags_from_ShaderEnum() {
+  let vf: ShaderStageFlags =
+    ShaderStageFlags::from(ShaderEnum::Vertex) | ShaderEnum::Fragment.into();
+  assert_eq!(vf, ShaderStageFlags::VERTEX | ShaderStageFlags::FRAGMENT);
+  assert!(ShaderStageFlags::ALL.contains(vf));
+  assert!(!vf.contains(ShaderEnum::Geometry.into()));
+  assert_eq!(
+    ShaderStageFlags::from(ShaderEnum::TessEval).as_bitfield(),
+    GL_TESS_EVALUATION_SHADER_BIT
+  );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_program_pipeline_mixes_stages() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let vert = gl
+    .create_shader_program(
+      ShaderEnum::Vertex,
+      "#version 450 core
+      out gl_PerVertex { vec4 gl_Position; };
+      layout(location = 0) out vec4 vs_color;
+      void main() {
+        gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+        vs_color = vec4(1.0);
+      }",
+    )
+    .unwrap();
+  let frag = gl
+    .create_shader_program(
+      ShaderEnum::Fragment,
+      "#version 450 core
+      layout(location = 0) in vec4 vs_color;
+      out vec4 color;
+      void main() { color = vs_color; }",
+    )
+    .unwrap();
+  let err = gl
+    .create_shader_program(ShaderEnum::Fragment, "#version 450 core\nnope")
+    .unwrap_err();
+  assert_eq!(err.stage, Some(ShaderEnum::Fragment));
+  assert_eq!(err.errors().next().unwrap().line, Some(2));
+
+  let pipeline = gl.create_program_pipelines::<1>()[0].unwrap();
+  gl.use_program_stages(pipeline, ShaderEnum::Vertex.into(), Some(vert));
+  gl.use_program_stages(pipeline, ShaderEnum::Fragment.into(), Some(frag));
+  let stage_ids = |t| gl.get_program_pipeline_stage(pipeline, t).map(|p| p.0);
+  assert_eq!(stage_ids(ShaderEnum::Vertex), Some(vert.0));
+  assert_eq!(stage_ids(ShaderEnum::Fragment), Some(frag.0));
+  assert_eq!(stage_ids(ShaderEnum::Geometry), None);
+
+  let vao = gl.create_vertex_arrays::<1>()[0].unwrap();
+  gl.bind_vertex_array(Some(vao));
+  gl.bind_program_pipeline(Some(pipeline));
+  gl.validate_program_pipeline(pipeline).unwrap();
+
+  gl.use_program_stages(pipeline, ShaderStageFlags::ALL, None);
+  assert_eq!(stage_ids(ShaderEnum::Vertex), None);
+
+  gl.bind_program_pipeline(None);
+  gl.bind_vertex_array(None);
+  gl.delete_vertex_arrays([Some(vao)]);
+  gl.delete_program_pipelines([Some(pipeline)]);
+  gl.delete_program(vert);
+  gl.delete_program(frag);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
@@ -0,0 +1,496 @@
+#[cfg(all(test, target_os = "linux"))]
+use super::*;
+use std::{
+  borrow::Borrow,
+  collections::{HashMap, HashSet},
+  hash::Hash,
+  path::PathBuf,
+};
+
+/// Somewhere that `#include` directives can read files from.
+pub trait IncludeSource {
+  /// Reads the text of a file.
+  ///
+  /// The `path` is already normalized: it's relative to the root of the
+  /// source, `/` separated, and has no `.` or `..` parts.
+  fn read_include(&self, path: &str) -> Result<String, String>;
+}
+
+/// A map of paths to file contents works as a virtual file system.
+impl<K, V> IncludeSource for HashMap<K, V>
+where
+  K: Borrow<str> + Hash + Eq,
+  V: AsRef<str>,
+{
+  fn read_include(&self, path: &str) -> Result<String, String> {
+    self
+      .get(path)
+      .map(|text| String::from(text.as_ref()))
+      .ok_or_else(|| format!("No such file: {}", path))
+  }
+}
+
+/// Reads included files from a directory on disk.
+#[derive(Debug, Clone)]
+pub struct IncludeDir(pub PathBuf);
+impl IncludeSource for IncludeDir {
+  fn read_include(&self, path: &str) -> Result<String, String> {
+    std::fs::read_to_string(self.0.join(path))
+      .map_err(|e| format!("Couldn't read {}: {}", path, e))
+  }
+}
+
+/// A line within one of the files that made up a preprocessed shader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceLocation {
+  /// The file's index within the [`SourceMap`]. This is also the "source
+  /// string number" that the compiler reports, because of the `#line`
+  /// directives. Note that Mesa has been seen to report source string 0 for
+  /// some errors within included files (the line number is still right).
+  pub file: usize,
+  /// The 1-based line number within the file.
+  pub line: u32,
+}
+
+/// Connects the lines of a preprocessed shader back to the original files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+  files: Vec<String>,
+  lines: Vec<Option<SourceLocation>>,
+}
+impl SourceMap {
+  /// The name of every file that was used, with the root file first.
+  pub fn files(&self) -> &[String] {
+    &self.files
+  }
+
+  /// The name of a file by index.
+  pub fn file_name(&self, file: usize) -> Option<&str> {
+    self.files.get(file).map(String::as_str)
+  }
+
+  /// Where a (1-based) line of the output came from.
+  ///
+  /// Lines that the preprocessor added (eg: `#define` and `#line`) have no
+  /// location.
+  pub fn output_line(&self, line: u32) -> Option<SourceLocation> {
+    let index = (line as usize).checked_sub(1)?;
+    self.lines.get(index).copied().flatten()
+  }
+
+  fn file_index(&mut self, name: &str) -> usize {
+    match self.files.iter().position(|f| f == name) {
+      Some(i) => i,
+      None => {
+        self.files.push(String::from(name));
+        self.files.len() - 1
+      }
+    }
+  }
+}
+
+/// The output of a [`ShaderPreprocessor`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreprocessedShader {
+  /// The complete source, ready for
+  /// [`set_shader_source`](crate::GlFnsRusty::set_shader_source).
+  pub source: String,
+  pub source_map: SourceMap,
+}
+
+/// Resolves `#include` directives and adds `#define`s to GLSL source.
+///
+/// * `#include "path"` (or `<path>`) is replaced by the file's contents.
+///   Paths are relative to the including file, unless they start with `/`.
+/// * `#pragma once` in a file stops it being included more than once.
+/// * The root file's `#version` line is moved to be the first line, followed
+///   by the `#define`s. Included files can't have a `#version`.
+/// * `#line` directives are placed around each included file, and the file
+///   numbers are listed in the [`SourceMap`]. These use the GLSL 3.30+
+///   meaning of `#line`.
+///
+/// Everything else, including `#if` blocks, is left for the GLSL compiler.
+/// That means an `#include` is resolved even inside an `#if` that turns out
+/// to be false.
+///
+/// ```
+/// # use superbible7::*;
+/// # use std::collections::HashMap;
+/// let mut files = HashMap::new();
+/// files.insert("common.glsl", "vec4 tint() { return vec4(TINT); }");
+/// let out = ShaderPreprocessor::new(&files)
+///   .define("TINT", "1.0")
+///   .process(
+///     "main.frag",
+///     "#version 450 core\n#include \"common.glsl\"\nvoid main() {}",
+///   )
+///   .unwrap();
+/// assert!(out.source.starts_with("#version 450 core\n#define TINT 1.0\n"));
+/// assert_eq!(out.source_map.file_name(1), Some("common.glsl"));
+/// ```
+pub struct ShaderPreprocessor<'a> {
+  includes: &'a dyn IncludeSource,
+  defines: Vec<(String, String)>,
+}
+impl<'a> ShaderPreprocessor<'a> {
+  /// Makes a preprocessor that reads included files from `includes`.
+  pub fn new(includes: &'a dyn IncludeSource) -> Self {
+    Self { includes, defines: Vec::new() }
+  }
+
+  /// Adds a `#define name value` after the `#version` line.
+  pub fn define(mut self, name: &str, value: &str) -> Self {
+    self.defines.push((String::from(name), String::from(value)));
+    self
+  }
+
+  /// Processes a root file that's read from the include source.
+  pub fn process_file(&self, path: &str) -> Result<PreprocessedShader, String> {
+    let path = normalize_include_path("", path)?;
+    let src = self.includes.read_include(&path)?;
+    self.process(&path, &src)
+  }
+
+  /// Processes the source of a root file.
+  ///
+  /// The `name` is used for the source map, error messages, and to resolve
+  /// relative includes.
+  pub fn process(
+    &self, name: &str, src: &str,
+  ) -> Result<PreprocessedShader, String> {
+    let mut state = PreprocessState::default();
+    let root = state.out.source_map.file_index(name);
+    let version_line = find_version_line(src);
+    if let Some((line_number, text)) = version_line {
+      state.push(text, Some(SourceLocation { file: root, line: line_number }));
+    }
+    for (name, value) in self.defines.iter() {
+      state.push(&format!("#define {} {}", name, value), None);
+    }
+    if !state.out.source.is_empty() {
+      state.push_line_directive(1, root);
+    }
+    state.stack.push(String::from(name));
+    self.process_lines(
+      &mut state,
+      root,
+      src,
+      version_line.map(|(line_number, _)| line_number),
+    )?;
+    Ok(state.out)
+  }
+
+  fn process_lines(
+    &self, state: &mut PreprocessState, file: usize, src: &str,
+    version_line: Option<u32>,
+  ) -> Result<(), String> {
+    let name = state.out.source_map.files[file].clone();
+    let mut in_block_comment = false;
+    for (line_number, text) in (1..).zip(src.lines()) {
+      let here = Some(SourceLocation { file, line: line_number });
+      let directive =
+        if in_block_comment { None } else { parse_directive(text) };
+      // directive lines can open a comment too (eg: `#define X 1 /* note`).
+      in_block_comment = block_comment_after(text, in_block_comment);
+      match directive {
+        _ if version_line == Some(line_number) => state.push("", here),
+        Some(("version", _)) => {
+          return Err(format!(
+            "{}:{}: only the root file can have a #version",
+            name, line_number
+          ));
+        }
+        Some(("pragma", "once")) => {
+          state.once.insert(file);
+          state.push("", here);
+        }
+        Some(("include", arg)) => {
+          let path = parse_include_arg(arg)
+            .and_then(|path| normalize_include_path(&name, path))
+            .map_err(|e| format!("{}:{}: {}", name, line_number, e))?;
+          let child = state.out.source_map.file_index(&path);
+          if state.once.contains(&child) {
+            state.push("", here);
+            continue;
+          }
+          if state.stack.contains(&path) {
+            let mut chain = state.stack.join(" -> ");
+            chain.push_str(" -> ");
+            chain.push_str(&path);
+            return Err(format!("Include cycle: {}", chain));
+          }
+          let child_src = self
+            .includes
+            .read_include(&path)
+            .map_err(|e| format!("{}:{}: {}", name, line_number, e))?;
+          state.push_line_directive(1, child);
+          state.stack.push(path);
+          self.process_lines(state, child, &child_src, None)?;
+          state.stack.pop();
+          state.push_line_directive(line_number + 1, file);
+        }
+        Some(_) | None => state.push(text, here),
+      }
+    }
+    Ok(())
+  }
+}
+
+#[derive(Default)]
+struct PreprocessState {
+  out: PreprocessedShader,
+  /// Files that have a `#pragma once`.
+  once: HashSet<usize>,
+  /// The chain of files currently being included.
+  stack: Vec<String>,
+}
+impl PreprocessState {
+  fn push(&mut self, text: &str, location: Option<SourceLocation>) {
+    self.out.source.push_str(text);
+    self.out.source.push('\n');
+    self.out.source_map.lines.push(location);
+  }
+  fn push_line_directive(&mut self, line: u32, file: usize) {
+    self.push(&format!("#line {} {}", line, file), None);
+  }
+}
+
+/// Splits a preprocessor line into the directive name and the rest.
+fn parse_directive(line: &str) -> Option<(&str, &str)> {
+  let rest = line.trim_start().strip_prefix('#')?.trim_start();
+  let name_end = rest
+    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+    .unwrap_or(rest.len());
+  Some((&rest[..name_end], rest[name_end..].trim()))
+}
+
+/// Finds the `#version` line (1-based number and text), if any.
+fn find_version_line(src: &str) -> Option<(u32, &str)> {
+  let mut in_block_comment = false;
+  for (line_number, text) in (1..).zip(src.lines()) {
+    if !in_block_comment {
+      if let Some(("version", _)) = parse_directive(text) {
+        return Some((line_number, text));
+      }
+    }
+    in_block_comment = block_comment_after(text, in_block_comment);
+  }
+  None
+}
+
+/// If a `/* */` comment is still open at the end of the line.
+fn block_comment_after(line: &str, mut in_block_comment: bool) -> bool {
+  let mut rest = line;
+  loop {
+    if in_block_comment {
+      match rest.find("*/") {
+        Some(i) => {
+          rest = &rest[i + 2..];
+          in_block_comment = false;
+        }
+        None => return true,
+      }
+    } else {
+      match (rest.find("//"), rest.find("/*")) {
+        (Some(line_c), Some(block_c)) if block_c < line_c => {
+          rest = &rest[block_c + 2..];
+          in_block_comment = true;
+        }
+        (None, Some(block_c)) => {
+          rest = &rest[block_c + 2..];
+          in_block_comment = true;
+        }
+        _ => return false,
+      }
+    }
+  }
+}
+
+/// Gets the path out of `"path"` or `<path>`.
+fn parse_include_arg(arg: &str) -> Result<&str, String> {
+  let close = match arg.chars().next() {
+    Some('"') => '"',
+    Some('<') => '>',
+    _ => return Err(format!("expected a \"path\" after #include: {}", arg)),
+  };
+  let end = arg[1..]
+    .find(close)
+    .ok_or_else(|| format!("unterminated #include path: {}", arg))?;
+  let trailing = arg[end + 2..].trim_start();
+  if !(trailing.is_empty() || trailing.starts_with("//")) {
+    return Err(format!("unexpected text after #include path: {}", trailing));
+  }
+  Ok(&arg[1..end + 1])
+}
+
+/// Resolves `path` relative to the directory of the `includer`.
+fn normalize_include_path(
+  includer: &str, path: &str,
+) -> Result<String, String> {
+  let mut parts: Vec<&str> = Vec::new();
+  if !path.starts_with('/') {
+    parts.extend(includer.split('/').filter(|p| !p.is_empty()));
+    // the includer's own name isn't part of its directory.
+    parts.pop();
+  }
+  for part in path.split('/') {
+    match part {
+      "" | "." => (),
+      ".." => {
+        if parts.pop().is_none() {
+          return Err(format!("include path goes outside the root: {}", path));
+        }
+      }
+      _ => parts.push(part),
+    }
+  }
+  if parts.is_empty() {
+    return Err(format!("empty include path: {:?}", path));
+  }
+  Ok(parts.join("/"))
+}
+
+#[cfg(test)]
+fn test_files(
+  files: &[(&'static str, &'static str)],
+) -> HashMap<String, String> {
+  files.iter().map(|(k, v)| (String::from(*k), String::from(*v))).collect()
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderPreprocessor_version_and_defines() {
+  let files = test_files(&[]);
+  let src = "// comment first\n#version 450 core\nvoid main() {}\n";
+  let out = ShaderPreprocessor::new(&files)
+    .define("LIGHTS", "4")
+    .define("USE_FOG", "")
+    .process("main.frag", src)
+    .unwrap();
+  assert_eq!(
+    out.source,
+    "#version 450 core\n#define LIGHTS 4\n#define USE_FOG \n#line 1 0\n\
+     // comment first\n\nvoid main() {}\n"
+  );
+  let map = &out.source_map;
+  assert_eq!(map.files(), &[String::from("main.frag")]);
+  assert_eq!(map.output_line(1), Some(SourceLocation { file: 0, line: 2 }));
+  assert_eq!(map.output_line(2), None);
+  assert_eq!(map.output_line(5), Some(SourceLocation { file: 0, line: 1 }));
+  assert_eq!(map.output_line(7), Some(SourceLocation { file: 0, line: 3 }));
+  assert_eq!(map.output_line(8), None);
+  assert_eq!(map.output_line(0), None);
+
+  // with nothing to add, the source is unchanged.
+  let out = ShaderPreprocessor::new(&files).process("a", "x\ny").unwrap();
+  assert_eq!(out.source, "x\ny\n");
+  // a #version inside a comment isn't moved.
+  let out =
+    ShaderPreprocessor::new(&files).process("a", "/*\n#version 1\n*/").unwrap();
+  assert_eq!(out.source, "/*\n#version 1\n*/\n");
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderPreprocessor_includes() {
+  let files = test_files(&[
+    ("shaders/main.vert", "#version 450\n#include \"lib/vs_out.glsl\"\nvoid main() {}"),
+    ("shaders/lib/vs_out.glsl", "#pragma once\n#include \"../math.glsl\"\nout VS_OUT { vec4 color; } vs_out;"),
+    ("shaders/math.glsl", "#include </shaders/lib/vs_out.glsl>\nfloat sq(float x) { return x * x; }"),
+  ]);
+  let out = ShaderPreprocessor::new(&files)
+    .process_file("./shaders/main.vert")
+    .unwrap();
+  assert_eq!(
+    out.source,
+    "#version 450\n#line 1 0\n\
+     \n\
+     #line 1 1\n\
+     \n\
+     #line 1 2\n\
+     \n\
+     float sq(float x) { return x * x; }\n\
+     #line 3 1\n\
+     out VS_OUT { vec4 color; } vs_out;\n\
+     #line 3 0\n\
+     void main() {}\n"
+  );
+  let map = &out.source_map;
+  assert_eq!(map.file_name(0), Some("shaders/main.vert"));
+  assert_eq!(map.file_name(1), Some("shaders/lib/vs_out.glsl"));
+  assert_eq!(map.file_name(2), Some("shaders/math.glsl"));
+  assert_eq!(map.file_name(3), None);
+  assert_eq!(map.output_line(8), Some(SourceLocation { file: 2, line: 2 }));
+  assert_eq!(map.output_line(10), Some(SourceLocation { file: 1, line: 3 }));
+  assert_eq!(map.output_line(12), Some(SourceLocation { file: 0, line: 3 }));
+  assert_eq!(map.output_line(11), None);
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderPreprocessor_errors() {
+  let files = test_files(&[
+    ("a.glsl", "#include \"b.glsl\""),
+    ("b.glsl", "\n#include \"a.glsl\""),
+    ("v.glsl", "#version 450"),
+  ]);
+  let pp = ShaderPreprocessor::new(&files);
+  assert_eq!(
+    pp.process_file("a.glsl"),
+    Err(String::from("Include cycle: a.glsl -> b.glsl -> a.glsl"))
+  );
+  assert_eq!(
+    pp.process("main", "\n\n#include \"nope.glsl\""),
+    Err(String::from("main:3: No such file: nope.glsl"))
+  );
+  assert!(pp.process("main", "#include \"../up.glsl\"").is_err());
+  assert!(pp.process("main", "#include nope.glsl").is_err());
+  assert!(pp.process("main", "#include \"v.glsl\" junk").is_err());
+  assert!(pp.process("main", "#version 450\n#include \"v.glsl\"").is_err());
+  // includes inside block comments are ignored.
+  let out = pp.process("main", "/* old:\n#include \"nope.glsl\" */").unwrap();
+  assert_eq!(out.source, "/* old:\n#include \"nope.glsl\" */\n");
+  // also when the comment starts on a directive line.
+  let src = "#define X 1 /* note\n#include \"nope.glsl\" */";
+  assert_eq!(pp.process("main", src).unwrap().source, format!("{}\n", src));
+}
+
+#[test]
+fn test_block_comment_after() {
+  assert!(!block_comment_after("int x; // /*", false));
+  assert!(block_comment_after("int x; /* // */ /*", false));
+  assert!(!block_comment_after("*/ int x;", true));
+  assert!(block_comment_after("still a comment", true));
+}
+
+#[cfg(target_os = "linux")]
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderPreprocessor_line_numbers_in_gl_log() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let files = test_files(&[("common.glsl", "float ok() { return 1.0; }\n")]);
+  let pp = ShaderPreprocessor::new(&files).define("BAD", "nope");
+  let out = pp
+    .process(
+      "main.frag",
+      "#version 450 core\n#include \"common.glsl\"\nout vec4 c;\n\
+       void main() { c = vec4(BAD); }",
+    )
+    .unwrap();
+  let err =
+    gl.create_compiled_shader(ShaderEnum::Fragment, &out.source).unwrap_err();
+  // Mesa reports errors as `string:line(column)`
+  assert!(err.log.contains("0:4("), "{}", err.log);
+
+  let files = test_files(&[("common.glsl", "\nfloat bad() { return nope; }")]);
+  let out = ShaderPreprocessor::new(&files)
+    .process("main.frag", "#version 450 core\n#include \"common.glsl\"")
+    .unwrap();
+  let err =
+    gl.create_compiled_shader(ShaderEnum::Fragment, &out.source).unwrap_err();
+  assert!(err.log.contains("1:2("), "{}", err.log);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
@@ -4,6 +4,16 @@ use super::*;
 #[repr(transparent)]
 pub struct ProgramID(pub(crate) NonZeroU32);
 
+/// The linked binary of a program, in a driver specific format.
+///
+/// A binary can only be loaded back into the same renderer and driver version
+/// that produced it, and even then the driver is allowed to reject it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramBinary {
+  pub format: GLenum,
+  pub data: Vec<u8>,
+}
+
 impl GlFnsRusty {
   /// Creates a new program object.
   pub fn create_program(&self) -> Option<ProgramID> {
@@ -66,15 +76,157 @@ impl GlFnsRusty {
     self.UseProgram(program.0.get())
   }
 
+  /// Compiles a single source string into a new *separable* program.
+  ///
+  /// The program can then be placed into a [`ProgramPipelineID`] with
+  /// [`use_program_stages`](GlFnsRusty::use_program_stages). On failure you
+  /// get the info log, which includes the compile log of the shader.
+  ///
+  /// The source is given to GL as a null terminated string, so a source with
+  /// a null byte in it is an error (rather than being cut short).
+  ///
+  /// See [glCreateShaderProgramv](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glCreateShaderProgram.xhtml)
+  pub fn create_shader_program(
+    &self, t: ShaderEnum, src: &str,
+  ) -> Result<ProgramID, ShaderError> {
+    // the call takes null terminated strings, with no length array.
+    if src.contains('\0') {
+      return Err(String::from("The source has a null byte in it.").into());
+    }
+    let mut v = Vec::with_capacity(src.len() + 1);
+    v.extend_from_slice(src.as_bytes());
+    v.push(0);
+    let program = NonZeroU32::new(unsafe {
+      self.CreateShaderProgramv(t.as_enum(), 1, [v.as_ptr()].as_ptr())
+    })
+    .map(ProgramID)
+    .ok_or_else(|| String::from("Couldn't create a program."))?;
+    if self.get_program_last_link_successful(program) {
+      Ok(program)
+    } else {
+      let e = Err(
+        ShaderError::from_log(&self.get_program_info_log(program))
+          .with_stage(t)
+          .with_source(src),
+      );
+      self.delete_program(program);
+      e
+    }
+  }
+
+  /// Hints that the program's binary will be retrieved after linking.
+  ///
+  /// Set this before linking the program.
+  pub fn program_binary_retrievable_hint(&self, program: ProgramID, b: bool) {
+    unsafe {
+      self.ProgramParameteri(
+        program.0.get(),
+        GL_PROGRAM_BINARY_RETRIEVABLE_HINT,
+        b as i32,
+      )
+    }
+  }
+
+  /// Gets the program binary formats that the driver supports.
+  pub fn get_program_binary_formats(&self) -> Vec<GLenum> {
+    let mut count = 0;
+    unsafe { self.GetIntegerv(GL_NUM_PROGRAM_BINARY_FORMATS, &mut count) };
+    let mut v = vec![0; count.max(0) as usize];
+    if !v.is_empty() {
+      unsafe { self.GetIntegerv(GL_PROGRAM_BINARY_FORMATS, v.as_mut_ptr()) };
+    }
+    v.into_iter().map(|f| GLenum(f as u32)).collect()
+  }
+
+  /// Gets the binary of a linked program.
+  ///
+  /// This is `None` if the driver doesn't give a binary (eg: it supports no
+  /// binary formats, or the program isn't linked).
+  ///
+  /// See [glGetProgramBinary](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetProgramBinary.xhtml)
+  pub fn get_program_binary(
+    &self, program: ProgramID,
+  ) -> Option<ProgramBinary> {
+    let mut length = 0;
+    unsafe {
+      self.GetProgramiv(program.0.get(), GL_PROGRAM_BINARY_LENGTH, &mut length)
+    };
+    if length <= 0 {
+      return None;
+    }
+    let mut data = vec![0_u8; length as usize];
+    let mut written = 0;
+    let mut format = GLenum(0);
+    unsafe {
+      self.GetProgramBinary(
+        program.0.get(),
+        length,
+        &mut written,
+        &mut format,
+        data.as_mut_ptr().cast(),
+      )
+    };
+    if written <= 0 {
+      return None;
+    }
+    data.truncate(written as usize);
+    Some(ProgramBinary { format, data })
+  }
+
+  /// Loads a previously saved binary into a program.
+  ///
+  /// This replaces any previous state of the program, and is `true` if the
+  /// driver accepted the binary (the program is then linked). A binary in a
+  /// format the driver doesn't list is rejected without calling GL.
+  ///
+  /// See [glProgramBinary](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glProgramBinary.xhtml)
+  pub fn program_binary(
+    &self, program: ProgramID, binary: &ProgramBinary,
+  ) -> bool {
+    if !self.get_program_binary_formats().contains(&binary.format) {
+      return false;
+    }
+    unsafe {
+      self.ProgramBinary(
+        program.0.get(),
+        binary.format,
+        binary.data.as_ptr().cast(),
+        binary.data.len().try_into().unwrap(),
+      )
+    };
+    self.get_program_last_link_successful(program)
+  }
+
   /// Creates a new program, attaches all named shaders, and links.
   ///
-  /// On failure, you get the link error log.
+  /// On failure, you get the parsed link error log.
   pub fn create_linked_program(
     &self, shaders: &[ShaderID],
-  ) -> Result<ProgramID, String> {
+  ) -> Result<ProgramID, ShaderError> {
+    self.create_linked_program_with_hint(shaders, false)
+  }
+
+  /// Like [`create_linked_program`](Self::create_linked_program), but sets
+  /// the [binary retrievable hint](Self::program_binary_retrievable_hint)
+  /// before linking.
+  ///
+  /// Some drivers only give a [binary](Self::get_program_binary) of programs
+  /// linked with the hint.
+  pub fn create_linked_program_retrievable(
+    &self, shaders: &[ShaderID],
+  ) -> Result<ProgramID, ShaderError> {
+    self.create_linked_program_with_hint(shaders, true)
+  }
+
+  fn create_linked_program_with_hint(
+    &self, shaders: &[ShaderID], retrievable: bool,
+  ) -> Result<ProgramID, ShaderError> {
     let program = self
       .create_program()
       .ok_or_else(|| String::from("Couldn't create a program."))?;
+    if retrievable {
+      self.program_binary_retrievable_hint(program, true);
+    }
     for shader in shaders.iter().copied() {
       self.attach_shader(program, shader);
     }
@@ -82,9 +234,73 @@ impl GlFnsRusty {
     if self.get_program_last_link_successful(program) {
       Ok(program)
     } else {
-      let e = Err(self.get_program_info_log(program));
+      let e = Err(ShaderError::from_log(&self.get_program_info_log(program)));
       self.delete_program(program);
       e
     }
   }
 }
+
+#[test]
+fn test_create_linked_program_mock() {
+  let (mock, gl) = MockGl::new();
+  let vertex = gl.create_compiled_shader(ShaderEnum::Vertex, "").unwrap();
+  let fragment = gl.create_compiled_shader(ShaderEnum::Fragment, "").unwrap();
+  mock.clear_calls();
+  let program = gl.create_linked_program(&[vertex, fragment]).unwrap();
+  assert_eq!(program.0.get(), 3);
+  let calls = mock.calls();
+  assert_eq!(
+    calls.iter().map(|c| c.name).collect::<Vec<_>>(),
+    [
+      "glCreateProgram",
+      "glAttachShader",
+      "glAttachShader",
+      "glLinkProgram",
+      "glGetProgramiv"
+    ]
+  );
+  assert_eq!(calls[1].args, [MockArg::Int(3), MockArg::Int(1)]);
+  assert_eq!(calls[2].args, [MockArg::Int(3), MockArg::Int(2)]);
+  assert_eq!(calls[4].args[1], MockArg::Enum(GL_LINK_STATUS));
+
+  // link errors have no stage, and the program is deleted.
+  mock.clear_calls();
+  mock.set_link_status(false);
+  mock.set_program_info_log("error: no main function");
+  let err = gl.create_linked_program(&[vertex]).unwrap_err();
+  assert_eq!(err.stage, None);
+  assert_eq!(err.log, "error: no main function");
+  assert_eq!(mock.call_names().last(), Some(&"glDeleteProgram"));
+
+  // the binary hint is set before linking.
+  mock.set_link_status(true);
+  mock.clear_calls();
+  gl.create_linked_program_retrievable(&[vertex]).unwrap();
+  let calls = mock.calls();
+  assert_eq!(calls[1].name, "glProgramParameteri");
+  assert_eq!(
+    calls[1].args[1..],
+    [MockArg::Enum(GL_PROGRAM_BINARY_RETRIEVABLE_HINT), MockArg::Int(1)]
+  );
+  assert_eq!(calls[3].name, "glLinkProgram");
+  mock.set_link_status(false);
+
+  // separable programs give the source in one null terminated string.
+  mock.clear_calls();
+  let err = gl.create_shader_program(ShaderEnum::Vertex, "nope").unwrap_err();
+  assert_eq!(err.stage, Some(ShaderEnum::Vertex));
+  assert_eq!(
+    mock.calls()[0].args,
+    [
+      MockArg::Enum(GL_VERTEX_SHADER),
+      MockArg::Int(1),
+      MockArg::Str("nope".into())
+    ]
+  );
+
+  // interior nulls would cut the source short, so GL isn't called.
+  mock.clear_calls();
+  assert!(gl.create_shader_program(ShaderEnum::Vertex, "a\0b").is_err());
+  assert!(mock.calls().is_empty());
+}
@@ -0,0 +1,354 @@
+use super::*;
+use std::{
+  fs, io,
+  path::{Path, PathBuf},
+};
+
+/// Identifies one program (by its shader sources) built by one driver.
+///
+/// The key is a 64-bit FNV-1a hash, so it's the same from run to run and
+/// between builds of the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProgramCacheKey(pub u64);
+impl ProgramCacheKey {
+  /// Computes the key for some shader sources on a particular driver.
+  ///
+  /// * `renderer` and `version` should be the `GL_RENDERER` and `GL_VERSION`
+  ///   strings, so that a driver update makes a different key.
+  /// * The order of `sources` matters.
+  pub fn new(
+    renderer: &str, version: &str, sources: &[(ShaderEnum, &str)],
+  ) -> Self {
+    let mut h = Fnv1a::new();
+    h.write_str(renderer);
+    h.write_str(version);
+    for (t, src) in sources.iter() {
+      h.write(&(*t as u32).to_le_bytes());
+      h.write_str(src);
+    }
+    Self(h.0)
+  }
+
+  /// Computes the key using the strings of the current GL context.
+  pub fn for_context(gl: &GlFnsRusty, sources: &[(ShaderEnum, &str)]) -> Self {
+    Self::new(&gl.get_renderer_string(), &gl.get_version_string(), sources)
+  }
+
+  /// The name of this key's file within a cache directory.
+  pub fn file_name(self) -> String {
+    format!("{:016x}.sb7prog", self.0)
+  }
+}
+
+/// The 64-bit FNV-1a hash of some bytes (eg: to name trace payloads).
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+  let mut h = Fnv1a::new();
+  h.write(bytes);
+  h.0
+}
+
+struct Fnv1a(u64);
+impl Fnv1a {
+  const fn new() -> Self {
+    Self(0xcbf2_9ce4_8422_2325)
+  }
+  fn write(&mut self, bytes: &[u8]) {
+    for b in bytes.iter().copied() {
+      self.0 ^= u64::from(b);
+      self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+    }
+  }
+  /// Writes the length first, so that `("ab", "c")` and `("a", "bc")` differ.
+  fn write_str(&mut self, s: &str) {
+    self.write(&(s.len() as u64).to_le_bytes());
+    self.write(s.as_bytes());
+  }
+}
+
+const CACHE_MAGIC: [u8; 8] = *b"SB7PROG\0";
+const CACHE_FORMAT_VERSION: u32 = 1;
+const CACHE_HEADER_SIZE: usize = 28;
+
+impl ProgramBinary {
+  /// Encodes the binary as a cache file.
+  ///
+  /// All values are little-endian:
+  /// * `[u8; 8]` magic: `b"SB7PROG\0"`
+  /// * `u32` cache format version (currently 1)
+  /// * `u64` the [`ProgramCacheKey`]
+  /// * `u32` the GL binary format
+  /// * `u32` the byte length of the binary, then the binary itself.
+  pub fn to_cache_bytes(&self, key: ProgramCacheKey) -> Vec<u8> {
+    let mut out = Vec::with_capacity(CACHE_HEADER_SIZE + self.data.len());
+    out.extend_from_slice(&CACHE_MAGIC);
+    out.extend_from_slice(&CACHE_FORMAT_VERSION.to_le_bytes());
+    out.extend_from_slice(&key.0.to_le_bytes());
+    out.extend_from_slice(&self.format.0.to_le_bytes());
+    out.extend_from_slice(&(self.data.len() as u32).to_le_bytes());
+    out.extend_from_slice(&self.data);
+    out
+  }
+
+  /// Decodes a cache file made by
+  /// [`to_cache_bytes`](ProgramBinary::to_cache_bytes).
+  ///
+  /// Fails if the header is wrong (including a different key), or if the data
+  /// length doesn't match.
+  pub fn from_cache_bytes(
+    key: ProgramCacheKey, bytes: &[u8],
+  ) -> Result<Self, String> {
+    if bytes.len() < CACHE_HEADER_SIZE {
+      return Err(format!("Cache file too short: {} bytes.", bytes.len()));
+    }
+    if bytes[..8] != CACHE_MAGIC {
+      return Err(String::from("Not a program cache file."));
+    }
+    let u32_at =
+      |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+    let version = u32_at(8);
+    if version != CACHE_FORMAT_VERSION {
+      return Err(format!("Unknown cache format version: {}", version));
+    }
+    let file_key = u64::from_le_bytes(bytes[12..20].try_into().unwrap());
+    if file_key != key.0 {
+      return Err(format!(
+        "Cache key mismatch: expected {:016x}, found {:016x}.",
+        key.0, file_key
+      ));
+    }
+    let format = GLenum(u32_at(20));
+    let len = u32_at(24) as usize;
+    let data = &bytes[CACHE_HEADER_SIZE..];
+    if data.len() != len {
+      return Err(format!(
+        "Cache data length mismatch: expected {}, found {}.",
+        len,
+        data.len()
+      ));
+    }
+    Ok(Self { format, data: data.to_vec() })
+  }
+}
+
+/// Saves linked program binaries to a directory, so that later runs can skip
+/// compiling and linking.
+///
+/// Each program gets one file, named by its [`ProgramCacheKey`].
+#[derive(Debug, Clone)]
+pub struct ProgramCache {
+  dir: PathBuf,
+}
+impl ProgramCache {
+  /// Uses the given directory, which is created when the first file is stored.
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    Self { dir: dir.into() }
+  }
+
+  /// The cache directory.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// The path of the file for a key.
+  pub fn path_of(&self, key: ProgramCacheKey) -> PathBuf {
+    self.dir.join(key.file_name())
+  }
+
+  /// Loads the binary stored for a key.
+  ///
+  /// A missing, unreadable, or malformed file is all just `None`.
+  pub fn load(&self, key: ProgramCacheKey) -> Option<ProgramBinary> {
+    let bytes = fs::read(self.path_of(key)).ok()?;
+    ProgramBinary::from_cache_bytes(key, &bytes).ok()
+  }
+
+  /// Stores the binary for a key, replacing any previous file.
+  ///
+  /// The file is written under a temporary name and then renamed, so a crash
+  /// part way through can't leave a truncated file with the real name.
+  pub fn store(
+    &self, key: ProgramCacheKey, binary: &ProgramBinary,
+  ) -> io::Result<()> {
+    fs::create_dir_all(&self.dir)?;
+    let path = self.path_of(key);
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, binary.to_cache_bytes(key))?;
+    fs::rename(&tmp, &path)
+  }
+
+  /// Removes the file for a key, if there is one.
+  pub fn remove(&self, key: ProgramCacheKey) -> io::Result<()> {
+    match fs::remove_file(self.path_of(key)) {
+      Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+      _ => Ok(()),
+    }
+  }
+
+  /// Gets a program from the cache, or builds it (and caches it) on a miss.
+  ///
+  /// * On a hit the binary is given to `glProgramBinary`. If the driver
+  ///   rejects it the file is removed, and this falls back to building.
+  /// * Building compiles each of the `sources` and uses
+  ///   [`create_linked_program_retrievable`](GlFnsRusty::create_linked_program_retrievable).
+  ///   On failure you get the compile or link log.
+  /// * Storing the new binary is best effort: a driver that gives no binary,
+  ///   or a failed write, doesn't stop you getting the program.
+  pub fn load_or_link(
+    &self, gl: &GlFnsRusty, sources: &[(ShaderEnum, &str)],
+  ) -> Result<ProgramID, ShaderError> {
+    let key = ProgramCacheKey::for_context(gl, sources);
+    if let Some(binary) = self.load(key) {
+      let program = gl
+        .create_program()
+        .ok_or_else(|| String::from("Couldn't create a program."))?;
+      if gl.program_binary(program, &binary) {
+        return Ok(program);
+      }
+      gl.delete_program(program);
+      let _ = self.remove(key);
+    }
+
+    let mut shaders = Vec::with_capacity(sources.len());
+    for (t, src) in sources.iter().copied() {
+      match gl.create_compiled_shader(t, src) {
+        Ok(shader) => shaders.push(shader),
+        Err(e) => {
+          shaders.into_iter().for_each(|s| gl.delete_shader(s));
+          return Err(e);
+        }
+      }
+    }
+    let res = gl.create_linked_program_retrievable(&shaders);
+    shaders.into_iter().for_each(|s| gl.delete_shader(s));
+    let program = res?;
+    if let Some(binary) = gl.get_program_binary(program) {
+      let _ = self.store(key, &binary);
+    }
+    Ok(program)
+  }
+}
+
+#[cfg(test)]
+fn temp_cache_dir(name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!(
+    "sb7-program-cache-{}-{}",
+    name,
+    std::process::id()
+  ));
+  let _ = fs::remove_dir_all(&dir);
+  dir
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ProgramCacheKey_new() {
+  let vs = (ShaderEnum::Vertex, "void main() {}");
+  let fs = (ShaderEnum::Fragment, "void main() {}");
+  let key = ProgramCacheKey::new("llvmpipe", "4.5", &[vs, fs]);
+  // stable across runs and builds
+  assert_eq!(key, ProgramCacheKey::new("llvmpipe", "4.5", &[vs, fs]));
+  assert_eq!(ProgramCacheKey::new("", "", &[]).0, 0x8820_1fb9_60ff_6465);
+  // everything is part of the key
+  assert_ne!(key, ProgramCacheKey::new("llvmpipe", "4.6", &[vs, fs]));
+  assert_ne!(key, ProgramCacheKey::new("radeonsi", "4.5", &[vs, fs]));
+  assert_ne!(key, ProgramCacheKey::new("llvmpipe", "4.5", &[fs, vs]));
+  assert_ne!(key, ProgramCacheKey::new("llvmpipe", "4.5", &[vs]));
+  // moving bytes between strings changes the key
+  assert_ne!(
+    ProgramCacheKey::new("ab", "c", &[]),
+    ProgramCacheKey::new("a", "bc", &[])
+  );
+  assert_eq!(key.file_name(), format!("{:016x}.sb7prog", key.0));
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ProgramBinary_cache_bytes() {
+  let key = ProgramCacheKey(0x0123_4567_89ab_cdef);
+  let binary = ProgramBinary { format: GLenum(0x8741), data: vec![1, 2, 3] };
+  let bytes = binary.to_cache_bytes(key);
+  assert_eq!(bytes.len(), CACHE_HEADER_SIZE + 3);
+  assert_eq!(&bytes[..8], b"SB7PROG\0");
+  assert_eq!(&bytes[12..20], &[0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01]);
+  assert_eq!(ProgramBinary::from_cache_bytes(key, &bytes), Ok(binary));
+
+  let other_key = ProgramCacheKey(1);
+  assert!(ProgramBinary::from_cache_bytes(other_key, &bytes).is_err());
+  assert!(ProgramBinary::from_cache_bytes(key, &bytes[..10]).is_err());
+  assert!(
+    ProgramBinary::from_cache_bytes(key, &bytes[..bytes.len() - 1]).is_err()
+  );
+  let mut bad_magic = bytes.clone();
+  bad_magic[0] = b'X';
+  assert!(ProgramBinary::from_cache_bytes(key, &bad_magic).is_err());
+  let mut bad_version = bytes;
+  bad_version[8] = 2;
+  assert!(ProgramBinary::from_cache_bytes(key, &bad_version).is_err());
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ProgramCache_store_load() {
+  let cache = ProgramCache::new(temp_cache_dir("store-load"));
+  let key = ProgramCacheKey(7);
+  assert_eq!(cache.load(key), None);
+  let binary = ProgramBinary { format: GLenum(1), data: vec![9; 100] };
+  cache.store(key, &binary).unwrap();
+  assert_eq!(cache.load(key), Some(binary));
+  assert_eq!(cache.load(ProgramCacheKey(8)), None);
+  // a file under the wrong name is rejected by the key check
+  fs::copy(cache.path_of(key), cache.path_of(ProgramCacheKey(8))).unwrap();
+  assert_eq!(cache.load(ProgramCacheKey(8)), None);
+  cache.remove(key).unwrap();
+  cache.remove(key).unwrap();
+  assert_eq!(cache.load(key), None);
+  fs::remove_dir_all(cache.dir()).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[allow(non_snake_case)]
+#[test]
+fn test_ProgramCache_load_or_link() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let cache = ProgramCache::new(temp_cache_dir("load-or-link"));
+  let sources = [
+    (
+      ShaderEnum::Vertex,
+      "#version 450 core
+      void main() { gl_Position = vec4(0.0, 0.0, 0.0, 1.0); }",
+    ),
+    (
+      ShaderEnum::Fragment,
+      "#version 450 core
+      uniform vec4 tint;
+      out vec4 color;
+      void main() { color = tint; }",
+    ),
+  ];
+  let key = ProgramCacheKey::for_context(&gl, &sources);
+  let first = cache.load_or_link(&gl, &sources).unwrap();
+  if !gl.get_program_binary_formats().is_empty() {
+    assert!(cache.load(key).is_some());
+  }
+  // the second time is a cache hit (if the driver gave a binary)
+  let second = cache.load_or_link(&gl, &sources).unwrap();
+  assert!(gl.get_program_uniforms(second).get("tint").is_some());
+
+  // a binary the driver rejects falls back to linking
+  let junk = ProgramBinary { format: GLenum(0xDEAD), data: vec![0; 16] };
+  cache.store(key, &junk).unwrap();
+  let third = cache.load_or_link(&gl, &sources).unwrap();
+  assert_ne!(cache.load(key), Some(junk));
+
+  let bad = [(ShaderEnum::Fragment, "#version 450 core\nnope")];
+  assert!(cache.load_or_link(&gl, &bad).is_err());
+
+  for program in [first, second, third].iter().copied() {
+    gl.delete_program(program);
+  }
+  fs::remove_dir_all(cache.dir()).unwrap();
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
@@ -0,0 +1,734 @@
+use super::*;
+
+/// A program input or output variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceVariable {
+  pub name: String,
+  pub ty: UniformTypeEnum,
+  /// The number of array elements (1 if it's not an array).
+  pub array_size: i32,
+  /// The location, or -1 for built-in variables (eg: `gl_VertexID`).
+  pub location: i32,
+}
+
+/// A uniform variable, either in the default block or in a uniform block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniformVariable {
+  pub name: String,
+  pub ty: UniformTypeEnum,
+  /// The number of array elements (1 if it's not an array).
+  pub array_size: i32,
+  /// The location, or -1 for uniforms within a block.
+  pub location: i32,
+  /// The index in [`ProgramReflection::uniform_blocks`] of the block that
+  /// holds this uniform.
+  pub block_index: Option<usize>,
+  /// The byte offset within the block's buffer (or atomic counter buffer).
+  pub offset: i32,
+  /// The bytes between array elements within a buffer.
+  pub array_stride: i32,
+  /// The bytes between matrix columns (or rows) within a buffer.
+  pub matrix_stride: i32,
+  pub is_row_major: bool,
+  /// The index in [`ProgramReflection::atomic_counter_buffers`] of the
+  /// buffer that holds this atomic counter.
+  pub atomic_counter_buffer_index: Option<usize>,
+}
+
+/// A variable within a shader storage block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferVariable {
+  pub name: String,
+  pub ty: UniformTypeEnum,
+  /// The number of array elements, or 0 for an unsized array.
+  pub array_size: i32,
+  /// The index in [`ProgramReflection::shader_storage_blocks`] of the block
+  /// that holds this variable.
+  pub block_index: usize,
+  /// The byte offset within the block's buffer.
+  pub offset: i32,
+  /// The bytes between array elements.
+  pub array_stride: i32,
+  /// The bytes between matrix columns (or rows).
+  pub matrix_stride: i32,
+  pub is_row_major: bool,
+  /// The number of elements of the outermost array that this variable is
+  /// within (or 0 for an unsized outermost array).
+  pub top_level_array_size: i32,
+  /// The bytes between elements of the outermost array.
+  pub top_level_array_stride: i32,
+}
+
+/// A uniform block or shader storage block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockInfo {
+  pub name: String,
+  /// The buffer binding index the block reads from.
+  pub binding: i32,
+  /// The minimum size, in bytes, of the buffer backing the block.
+  pub data_size: i32,
+  /// The indices (in [`ProgramReflection::uniforms`] or
+  /// [`ProgramReflection::buffer_variables`]) of the block's variables.
+  pub variables: Vec<usize>,
+}
+
+/// A buffer binding used by atomic counters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomicCounterBufferInfo {
+  pub binding: i32,
+  /// The minimum size, in bytes, of the buffer.
+  pub data_size: i32,
+  /// The indices in [`ProgramReflection::uniforms`] of the atomic counters.
+  pub variables: Vec<usize>,
+}
+
+/// A subroutine function in one shader stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubroutineInfo {
+  pub stage: ShaderEnum,
+  pub name: String,
+  /// The index used with `glUniformSubroutinesuiv`.
+  pub index: u32,
+}
+
+/// A subroutine uniform in one shader stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubroutineUniformInfo {
+  pub stage: ShaderEnum,
+  pub name: String,
+  /// The number of array elements (1 if it's not an array).
+  pub array_size: i32,
+  pub location: i32,
+  /// The indices of the subroutines that this uniform can be set to.
+  pub compatible_subroutines: Vec<u32>,
+}
+
+/// Everything that program introspection can say about a linked program.
+///
+/// Get this with
+/// [`get_program_reflection`](GlFnsRusty::get_program_reflection). Each list
+/// is in the program's resource index order, so the indices within one item
+/// (eg: [`BlockInfo::variables`]) refer to positions in the other lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgramReflection {
+  pub inputs: Vec<InterfaceVariable>,
+  pub outputs: Vec<InterfaceVariable>,
+  pub uniforms: Vec<UniformVariable>,
+  pub uniform_blocks: Vec<BlockInfo>,
+  pub buffer_variables: Vec<BufferVariable>,
+  pub shader_storage_blocks: Vec<BlockInfo>,
+  pub atomic_counter_buffers: Vec<AtomicCounterBufferInfo>,
+  pub subroutines: Vec<SubroutineInfo>,
+  pub subroutine_uniforms: Vec<SubroutineUniformInfo>,
+}
+impl ProgramReflection {
+  /// Finds an input by name.
+  pub fn input(&self, name: &str) -> Option<&InterfaceVariable> {
+    self.inputs.iter().find(|v| v.name == name)
+  }
+
+  /// Finds an output by name.
+  pub fn output(&self, name: &str) -> Option<&InterfaceVariable> {
+    self.outputs.iter().find(|v| v.name == name)
+  }
+
+  /// Finds a uniform by name.
+  ///
+  /// Arrays are listed by their first element (eg: `lights[0]`), and members
+  /// of blocks that have an instance name use the block's name as a prefix
+  /// (eg: `Block.member`).
+  pub fn uniform(&self, name: &str) -> Option<&UniformVariable> {
+    self.uniforms.iter().find(|v| v.name == name)
+  }
+
+  /// Finds a uniform block by name.
+  pub fn uniform_block(&self, name: &str) -> Option<&BlockInfo> {
+    self.uniform_blocks.iter().find(|b| b.name == name)
+  }
+
+  /// Finds a shader storage block by name.
+  pub fn shader_storage_block(&self, name: &str) -> Option<&BlockInfo> {
+    self.shader_storage_blocks.iter().find(|b| b.name == name)
+  }
+
+  /// Checks that `V` provides every input of the program, with the right
+  /// number of components.
+  ///
+  /// Matrix and array inputs take up several locations (one per column, and
+  /// per element), and each of those must be given.
+  ///
+  /// ## Failure
+  /// * If an input location isn't given by any of `V`'s attributes, or is
+  ///   given with too many components for its type.
+  pub fn check_vertex_format<V: VertexFormat>(&self) -> Result<(), String> {
+    for input in self.inputs.iter().filter(|i| i.location >= 0) {
+      let (component_bytes, components, columns) =
+        type_shape(input.ty).unwrap_or((4, 4, 1));
+      // Note: a `dvec3` or `dvec4` column takes two locations.
+      let per_column =
+        if component_bytes == 8 && components > 2 { 2 } else { 1 };
+      let count = input.array_size.max(1) as usize * columns * per_column;
+      for location in (input.location as u32..).take(count) {
+        let attr = V::ATTRIBUTES
+          .iter()
+          .find(|a| a.location == location)
+          .ok_or_else(|| {
+            format!(
+              "Input `{}` uses location {}, which isn't in `{}`.",
+              input.name,
+              location,
+              core::any::type_name::<V>()
+            )
+          })?;
+        if attr.components > components as i32 {
+          return Err(format!(
+            "Input `{}` is a {:?}, but the attribute at location {} has {} \
+            components.",
+            input.name, input.ty, location, attr.components
+          ));
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Converts a resource index property to an `Option`.
+fn opt_index(i: i32) -> Option<usize> {
+  if i < 0 {
+    None
+  } else {
+    Some(i as usize)
+  }
+}
+
+impl GlFnsRusty {
+  /// Gets the number of active resources in an interface of a program.
+  fn program_interface_count(
+    &self, program: ProgramID, interface: GLenum,
+  ) -> u32 {
+    let mut out = 0;
+    unsafe {
+      self.GetProgramInterfaceiv(
+        program.0.get(),
+        interface,
+        GL_ACTIVE_RESOURCES,
+        &mut out,
+      )
+    };
+    out as u32
+  }
+
+  /// Gets the length of the longest name in an interface of a program
+  /// (including the null terminator).
+  fn program_max_name_length(
+    &self, program: ProgramID, interface: GLenum,
+  ) -> i32 {
+    let mut out = 0;
+    unsafe {
+      self.GetProgramInterfaceiv(
+        program.0.get(),
+        interface,
+        GL_MAX_NAME_LENGTH,
+        &mut out,
+      )
+    };
+    out
+  }
+
+  /// Gets the name of a resource of a program.
+  ///
+  /// * `max_len`: The
+  ///   [`program_max_name_length`](Self::program_max_name_length) of the
+  ///   interface, which only needs to be looked up once per interface.
+  fn program_resource_name(
+    &self, program: ProgramID, interface: GLenum, index: u32, max_len: i32,
+  ) -> String {
+    let mut buf: Vec<u8> = vec![0; max_len.max(1) as usize];
+    let mut len = 0;
+    unsafe {
+      self.GetProgramResourceName(
+        program.0.get(),
+        interface,
+        index,
+        buf.len().try_into().unwrap(),
+        &mut len,
+        buf.as_mut_ptr(),
+      )
+    };
+    buf.truncate(len as usize);
+    min_alloc_lossy_into_string(buf)
+  }
+
+  /// Gets properties of a resource of a program, one value per property.
+  fn program_resource_props<const N: usize>(
+    &self, program: ProgramID, interface: GLenum, index: u32,
+    props: [GLenum; N],
+  ) -> [i32; N] {
+    let mut out = [0; N];
+    unsafe {
+      self.GetProgramResourceiv(
+        program.0.get(),
+        interface,
+        index,
+        N.try_into().unwrap(),
+        props.as_ptr(),
+        N.try_into().unwrap(),
+        core::ptr::null_mut(),
+        out.as_mut_ptr(),
+      )
+    };
+    out
+  }
+
+  /// Gets an array property of a resource of a program, given the property
+  /// that holds the array's length.
+  fn program_resource_array(
+    &self, program: ProgramID, interface: GLenum, index: u32,
+    count_prop: GLenum, prop: GLenum,
+  ) -> Vec<i32> {
+    let [count] =
+      self.program_resource_props(program, interface, index, [count_prop]);
+    let mut out = vec![0; count.max(0) as usize];
+    if !out.is_empty() {
+      unsafe {
+        self.GetProgramResourceiv(
+          program.0.get(),
+          interface,
+          index,
+          1,
+          &prop,
+          count,
+          core::ptr::null_mut(),
+          out.as_mut_ptr(),
+        )
+      };
+    }
+    out
+  }
+
+  fn reflect_interface_variables(
+    &self, program: ProgramID, interface: GLenum,
+  ) -> Vec<InterfaceVariable> {
+    let max_len = self.program_max_name_length(program, interface);
+    (0..self.program_interface_count(program, interface))
+      .map(|i| {
+        let [ty, array_size, location] = self.program_resource_props(
+          program,
+          interface,
+          i,
+          [GL_TYPE, GL_ARRAY_SIZE, GL_LOCATION],
+        );
+        InterfaceVariable {
+          name: self.program_resource_name(program, interface, i, max_len),
+          ty: UniformTypeEnum::from_enum(GLenum(ty as u32)),
+          array_size,
+          location,
+        }
+      })
+      .collect()
+  }
+
+  fn reflect_blocks(
+    &self, program: ProgramID, interface: GLenum,
+  ) -> Vec<BlockInfo> {
+    let max_len = self.program_max_name_length(program, interface);
+    (0..self.program_interface_count(program, interface))
+      .map(|i| {
+        let [binding, data_size] = self.program_resource_props(
+          program,
+          interface,
+          i,
+          [GL_BUFFER_BINDING, GL_BUFFER_DATA_SIZE],
+        );
+        let variables = self
+          .program_resource_array(
+            program,
+            interface,
+            i,
+            GL_NUM_ACTIVE_VARIABLES,
+            GL_ACTIVE_VARIABLES,
+          )
+          .into_iter()
+          .map(|v| v as usize)
+          .collect();
+        BlockInfo {
+          name: self.program_resource_name(program, interface, i, max_len),
+          binding,
+          data_size,
+          variables,
+        }
+      })
+      .collect()
+  }
+
+  /// Introspects everything about a linked program.
+  ///
+  /// See [glGetProgramResource](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetProgramResource.xhtml)
+  pub fn get_program_reflection(
+    &self, program: ProgramID,
+  ) -> ProgramReflection {
+    let mut out = ProgramReflection {
+      inputs: self.reflect_interface_variables(program, GL_PROGRAM_INPUT),
+      outputs: self.reflect_interface_variables(program, GL_PROGRAM_OUTPUT),
+      uniform_blocks: self.reflect_blocks(program, GL_UNIFORM_BLOCK),
+      shader_storage_blocks: self
+        .reflect_blocks(program, GL_SHADER_STORAGE_BLOCK),
+      ..ProgramReflection::default()
+    };
+
+    let max_len = self.program_max_name_length(program, GL_UNIFORM);
+    out.uniforms = (0..self.program_interface_count(program, GL_UNIFORM))
+      .map(|i| {
+        let [
+          ty,
+          array_size,
+          location,
+          block_index,
+          offset,
+          array_stride,
+          matrix_stride,
+          is_row_major,
+          atomic_counter_buffer_index,
+        ] =
+          self.program_resource_props(
+            program,
+            GL_UNIFORM,
+            i,
+            [
+              GL_TYPE,
+              GL_ARRAY_SIZE,
+              GL_LOCATION,
+              GL_BLOCK_INDEX,
+              GL_OFFSET,
+              GL_ARRAY_STRIDE,
+              GL_MATRIX_STRIDE,
+              GL_IS_ROW_MAJOR,
+              GL_ATOMIC_COUNTER_BUFFER_INDEX,
+            ],
+          );
+        UniformVariable {
+          name: self.program_resource_name(program, GL_UNIFORM, i, max_len),
+          ty: UniformTypeEnum::from_enum(GLenum(ty as u32)),
+          array_size,
+          location,
+          block_index: opt_index(block_index),
+          offset,
+          array_stride,
+          matrix_stride,
+          is_row_major: is_row_major != 0,
+          atomic_counter_buffer_index: opt_index(atomic_counter_buffer_index),
+        }
+      })
+      .collect();
+
+    let max_len = self.program_max_name_length(program, GL_BUFFER_VARIABLE);
+    out.buffer_variables =
+      (0..self.program_interface_count(program, GL_BUFFER_VARIABLE))
+        .map(|i| {
+          let [
+            ty,
+            array_size,
+            block_index,
+            offset,
+            array_stride,
+            matrix_stride,
+            is_row_major,
+            top_level_array_size,
+            top_level_array_stride,
+          ] =
+            self.program_resource_props(
+              program,
+              GL_BUFFER_VARIABLE,
+              i,
+              [
+                GL_TYPE,
+                GL_ARRAY_SIZE,
+                GL_BLOCK_INDEX,
+                GL_OFFSET,
+                GL_ARRAY_STRIDE,
+                GL_MATRIX_STRIDE,
+                GL_IS_ROW_MAJOR,
+                GL_TOP_LEVEL_ARRAY_SIZE,
+                GL_TOP_LEVEL_ARRAY_STRIDE,
+              ],
+            );
+          BufferVariable {
+            name: self.program_resource_name(
+              program,
+              GL_BUFFER_VARIABLE,
+              i,
+              max_len,
+            ),
+            ty: UniformTypeEnum::from_enum(GLenum(ty as u32)),
+            array_size,
+            block_index: block_index as usize,
+            offset,
+            array_stride,
+            matrix_stride,
+            is_row_major: is_row_major != 0,
+            top_level_array_size,
+            top_level_array_stride,
+          }
+        })
+        .collect();
+
+    // Note: atomic counter buffers have no names, so we can't use the
+    // `GL_ATOMIC_COUNTER_BUFFER` interface with `program_resource_name`.
+    out.atomic_counter_buffers = (0..self
+      .program_interface_count(program, GL_ATOMIC_COUNTER_BUFFER))
+      .map(|i| {
+        let [binding, data_size] = self.program_resource_props(
+          program,
+          GL_ATOMIC_COUNTER_BUFFER,
+          i,
+          [GL_BUFFER_BINDING, GL_BUFFER_DATA_SIZE],
+        );
+        let variables = self
+          .program_resource_array(
+            program,
+            GL_ATOMIC_COUNTER_BUFFER,
+            i,
+            GL_NUM_ACTIVE_VARIABLES,
+            GL_ACTIVE_VARIABLES,
+          )
+          .into_iter()
+          .map(|v| v as usize)
+          .collect();
+        AtomicCounterBufferInfo { binding, data_size, variables }
+      })
+      .collect();
+
+    let stages = [
+      (ShaderEnum::Vertex, GL_VERTEX_SUBROUTINE, GL_VERTEX_SUBROUTINE_UNIFORM),
+      (
+        ShaderEnum::TessControl,
+        GL_TESS_CONTROL_SUBROUTINE,
+        GL_TESS_CONTROL_SUBROUTINE_UNIFORM,
+      ),
+      (
+        ShaderEnum::TessEval,
+        GL_TESS_EVALUATION_SUBROUTINE,
+        GL_TESS_EVALUATION_SUBROUTINE_UNIFORM,
+      ),
+      (
+        ShaderEnum::Geometry,
+        GL_GEOMETRY_SUBROUTINE,
+        GL_GEOMETRY_SUBROUTINE_UNIFORM,
+      ),
+      (
+        ShaderEnum::Fragment,
+        GL_FRAGMENT_SUBROUTINE,
+        GL_FRAGMENT_SUBROUTINE_UNIFORM,
+      ),
+      (
+        ShaderEnum::Compute,
+        GL_COMPUTE_SUBROUTINE,
+        GL_COMPUTE_SUBROUTINE_UNIFORM,
+      ),
+    ];
+    for (stage, subroutine, subroutine_uniform) in stages.iter().copied() {
+      let max_len = self.program_max_name_length(program, subroutine);
+      for i in 0..self.program_interface_count(program, subroutine) {
+        out.subroutines.push(SubroutineInfo {
+          stage,
+          name: self.program_resource_name(program, subroutine, i, max_len),
+          index: i,
+        });
+      }
+      let max_len = self.program_max_name_length(program, subroutine_uniform);
+      for i in 0..self.program_interface_count(program, subroutine_uniform) {
+        let [array_size, location] = self.program_resource_props(
+          program,
+          subroutine_uniform,
+          i,
+          [GL_ARRAY_SIZE, GL_LOCATION],
+        );
+        let compatible_subroutines = self
+          .program_resource_array(
+            program,
+            subroutine_uniform,
+            i,
+            GL_NUM_COMPATIBLE_SUBROUTINES,
+            GL_COMPATIBLE_SUBROUTINES,
+          )
+          .into_iter()
+          .map(|s| s as u32)
+          .collect();
+        out.subroutine_uniforms.push(SubroutineUniformInfo {
+          stage,
+          name: self.program_resource_name(
+            program,
+            subroutine_uniform,
+            i,
+            max_len,
+          ),
+          array_size,
+          location,
+          compatible_subroutines,
+        });
+      }
+    }
+    out
+  }
+}
+
+#[test]
+fn test_check_vertex_format_locations() {
+  #[derive(Clone, Copy)]
+  #[repr(C)]
+  struct Instance {
+    weights: [f32; 2],
+    model_x: [f32; 4],
+    model_y: [f32; 4],
+    model_z: [f32; 4],
+  }
+  unsafe impl bytemuck::Zeroable for Instance {}
+  unsafe impl bytemuck::Pod for Instance {}
+  impl_vertex_format!(Instance {
+    weights: [f32; 2] => 1,
+    model_x: [f32; 4] => 3,
+    model_y: [f32; 4] => 4,
+    model_z: [f32; 4] => 5,
+  });
+  let input = |name: &str, ty, array_size, location| InterfaceVariable {
+    name: String::from(name),
+    ty,
+    array_size,
+    location,
+  };
+  let mut r = ProgramReflection {
+    inputs: vec![input("weights", UniformTypeEnum::Float, 1, 1)],
+    ..ProgramReflection::default()
+  };
+  assert!(r.check_vertex_format::<Instance>().unwrap_err().contains("comp"));
+  // a `float[2]` uses locations 1 and 2.
+  r.inputs[0].ty = UniformTypeEnum::Vec2;
+  r.inputs[0].array_size = 2;
+  let err = r.check_vertex_format::<Instance>().unwrap_err();
+  assert!(err.contains("location 2"), "{}", err);
+  // a `mat3x4` uses locations 3 to 5, one per column, and a `mat4` 3 to 6.
+  r.inputs = vec![input("model", UniformTypeEnum::Mat3x4, 1, 3)];
+  assert_eq!(r.check_vertex_format::<Instance>(), Ok(()));
+  r.inputs[0].ty = UniformTypeEnum::Mat4;
+  let err = r.check_vertex_format::<Instance>().unwrap_err();
+  assert!(err.contains("location 6"), "{}", err);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_get_program_reflection() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let vertex_shader = gl
+    .create_compiled_shader(
+      ShaderEnum::Vertex,
+      "#version 450 core
+      layout(location = 0) in vec3 position;
+      layout(location = 2) in vec4 color;
+      layout(binding = 3, std140) uniform Camera {
+        mat4 view;
+        vec3 eye;
+        float exposure[2];
+      };
+      out vec4 vs_color;
+      void main() {
+        vs_color = color * exposure[1] + vec4(eye, 0.0);
+        gl_Position = view * vec4(position, 1.0);
+      }",
+    )
+    .unwrap();
+  let fragment_shader = gl
+    .create_compiled_shader(
+      ShaderEnum::Fragment,
+      "#version 450 core
+      layout(binding = 1, std430) buffer Lights {
+        uint count;
+        vec4 lights[];
+      };
+      layout(binding = 0) uniform atomic_uint fragments;
+      subroutine vec4 shade_fn(vec4 c);
+      subroutine(shade_fn) vec4 plain(vec4 c) { return c; }
+      subroutine(shade_fn) vec4 lit(vec4 c) { return c * lights[0]; }
+      subroutine uniform shade_fn shade;
+      in vec4 vs_color;
+      layout(location = 1) out vec4 color;
+      void main() {
+        atomicCounterIncrement(fragments);
+        color = shade(vs_color) * float(count);
+      }",
+    )
+    .unwrap();
+  let program =
+    gl.create_linked_program(&[vertex_shader, fragment_shader]).unwrap();
+  let r = gl.get_program_reflection(program);
+
+  assert_eq!(r.input("color").unwrap().location, 2);
+  assert_eq!(r.input("position").unwrap().ty, UniformTypeEnum::Vec3);
+  assert_eq!(r.output("color").unwrap().location, 1);
+
+  let camera = r.uniform_block("Camera").unwrap();
+  assert_eq!(camera.binding, 3);
+  assert_eq!(camera.variables.len(), 3);
+  let eye = r.uniform("eye").unwrap();
+  assert_eq!(eye.offset, 64);
+  assert_eq!(eye.location, -1);
+  assert_eq!(
+    eye.block_index,
+    r.uniform_blocks.iter().position(|b| b.name == "Camera")
+  );
+  let view = r.uniform("view").unwrap();
+  assert_eq!((view.ty, view.matrix_stride), (UniformTypeEnum::Mat4, 16));
+  let exposure = r.uniform("exposure[0]").unwrap();
+  assert_eq!((exposure.offset, exposure.array_stride), (80, 16));
+
+  let lights = r.shader_storage_block("Lights").unwrap();
+  assert_eq!(lights.binding, 1);
+  let lights_var =
+    r.buffer_variables.iter().find(|v| v.name == "lights[0]").unwrap();
+  assert_eq!((lights_var.offset, lights_var.array_stride), (16, 16));
+  assert_eq!(lights_var.array_size, 0);
+
+  assert_eq!(r.atomic_counter_buffers.len(), 1);
+  assert_eq!(r.atomic_counter_buffers[0].binding, 0);
+  let counter = &r.uniforms[r.atomic_counter_buffers[0].variables[0]];
+  assert_eq!(counter.name, "fragments");
+
+  let mut subroutine_names: Vec<&str> =
+    r.subroutines.iter().map(|s| s.name.as_str()).collect();
+  subroutine_names.sort_unstable();
+  assert_eq!(subroutine_names, ["lit", "plain"]);
+  assert_eq!(r.subroutine_uniforms.len(), 1);
+  assert_eq!(r.subroutine_uniforms[0].stage, ShaderEnum::Fragment);
+  assert_eq!(r.subroutine_uniforms[0].compatible_subroutines.len(), 2);
+
+  #[derive(Clone, Copy)]
+  #[repr(C)]
+  struct GoodVertex {
+    position: [f32; 3],
+    color: [u8; 4],
+  }
+  unsafe impl bytemuck::Zeroable for GoodVertex {}
+  unsafe impl bytemuck::Pod for GoodVertex {}
+  impl_vertex_format!(GoodVertex {
+    position: [f32; 3] => 0,
+    color: [u8; 4] => 2 normalized,
+  });
+  #[derive(Clone, Copy)]
+  #[repr(C)]
+  struct BadVertex {
+    position: [f32; 3],
+  }
+  unsafe impl bytemuck::Zeroable for BadVertex {}
+  unsafe impl bytemuck::Pod for BadVertex {}
+  impl_vertex_format!(BadVertex { position: [f32; 3] => 0 });
+  assert_eq!(r.check_vertex_format::<GoodVertex>(), Ok(()));
+  assert!(r.check_vertex_format::<BadVertex>().is_err());
+
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+  gl.delete_program(program);
+  gl.delete_shader(vertex_shader);
+  gl.delete_shader(fragment_shader);
+}
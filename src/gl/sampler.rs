@@ -0,0 +1,266 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct SamplerID(pub(crate) NonZeroU32);
+
+/// Comparison functions, as used for depth comparison sampling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum CompareFuncEnum {
+  Never = GL_NEVER.0,
+  Less = GL_LESS.0,
+  Equal = GL_EQUAL.0,
+  LessEqual = GL_LEQUAL.0,
+  Greater = GL_GREATER.0,
+  NotEqual = GL_NOTEQUAL.0,
+  GreaterEqual = GL_GEQUAL.0,
+  Always = GL_ALWAYS.0,
+}
+impl CompareFuncEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+}
+
+/// Everything about how a sampler object samples.
+///
+/// The [`Default`] is the same as a newly created sampler object, so you can
+/// just change the fields you care about:
+///
+/// ```no_run
+/// # use superbible7::*;
+/// # fn f(gl: &GlFnsRusty) {
+/// let anisotropic = gl.has_anisotropic_filtering();
+/// let nearest = gl.create_sampler(
+///   &SamplerDesc {
+///     min_filter: MinFilterEnum::Nearest,
+///     mag_filter: MagFilterEnum::Nearest,
+///     ..SamplerDesc::default()
+///   },
+///   anisotropic,
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplerDesc {
+  pub min_filter: MinFilterEnum,
+  pub mag_filter: MagFilterEnum,
+  /// The wrap modes of the S, T, and R axes.
+  pub wrap: [WrapEnum; 3],
+  /// The maximum anisotropy, 1.0 turns anisotropic filtering off.
+  ///
+  /// Anisotropic filtering is only core in GL 4.6, so on older contexts
+  /// without the extension this is ignored (see
+  /// [`has_anisotropic_filtering`](GlFnsRusty::has_anisotropic_filtering)).
+  pub max_anisotropy: f32,
+  /// Added to the level of detail before a mipmap level is picked.
+  pub lod_bias: f32,
+  /// The `[min, max]` level of detail that can be picked.
+  pub lod_range: [f32; 2],
+  /// When `Some`, sampling a depth texture compares the `r` coordinate
+  /// against the texel with this function (instead of returning the texel).
+  pub compare: Option<CompareFuncEnum>,
+  /// The color used with [`WrapEnum::ClampToBorder`].
+  pub border_color: [f32; 4],
+}
+impl Default for SamplerDesc {
+  fn default() -> Self {
+    Self {
+      min_filter: MinFilterEnum::NearestMipmapLinear,
+      mag_filter: MagFilterEnum::Linear,
+      wrap: [WrapEnum::Repeat; 3],
+      max_anisotropy: 1.0,
+      lod_bias: 0.0,
+      lod_range: [-1000.0, 1000.0],
+      compare: None,
+      border_color: [0.0; 4],
+    }
+  }
+}
+
+impl GlFnsRusty {
+  /// Attempts to create a given number of sampler objects.
+  ///
+  /// The samplers start with the default settings (see
+  /// [`SamplerDesc::default`]).
+  pub fn create_samplers<const X: usize>(&self) -> [Option<SamplerID>; X] {
+    let mut out = [None; X];
+    unsafe {
+      self.CreateSamplers(X.try_into().unwrap(), out.as_mut_ptr().cast())
+    };
+    out
+  }
+
+  /// Deletes the given list of sampler objects.
+  pub fn delete_samplers<const X: usize>(
+    &self, samplers: [Option<SamplerID>; X],
+  ) {
+    unsafe {
+      self.DeleteSamplers(X.try_into().unwrap(), samplers.as_ptr().cast())
+    };
+  }
+
+  /// Creates a sampler object and configures it according to `desc`.
+  ///
+  /// * `anisotropic`: As with [`set_sampler_desc`](Self::set_sampler_desc).
+  pub fn create_sampler(
+    &self, desc: &SamplerDesc, anisotropic: bool,
+  ) -> Option<SamplerID> {
+    let sampler = self.create_samplers::<1>()[0]?;
+    self.set_sampler_desc(sampler, desc, anisotropic);
+    Some(sampler)
+  }
+
+  /// If `GL_TEXTURE_MAX_ANISOTROPY` can be used.
+  ///
+  /// It's core in GL 4.6, and before that it needs the
+  /// `GL_ARB_texture_filter_anisotropic` or `GL_EXT_texture_filter_anisotropic`
+  /// extension.
+  pub fn has_anisotropic_filtering(&self) -> bool {
+    let (mut major, mut minor) = (0, 0);
+    unsafe {
+      self.GetIntegerv(GL_MAJOR_VERSION, &mut major);
+      self.GetIntegerv(GL_MINOR_VERSION, &mut minor);
+    }
+    (major, minor) >= (4, 6)
+      || self.get_all_extension_strings().iter().any(|ext| {
+        ext == "GL_ARB_texture_filter_anisotropic"
+          || ext == "GL_EXT_texture_filter_anisotropic"
+      })
+  }
+
+  /// Sets every parameter of a sampler object according to `desc`.
+  ///
+  /// * `anisotropic`: If [anisotropic
+  ///   filtering](Self::has_anisotropic_filtering) is available, which only
+  ///   needs to be looked up once per context. The max anisotropy is only set
+  ///   when it is. Without it the sampler always has 1.0, and any other
+  ///   `max_anisotropy` is ignored.
+  pub fn set_sampler_desc(
+    &self, sampler: SamplerID, desc: &SamplerDesc, anisotropic: bool,
+  ) {
+    let id = sampler.0.get();
+    let min_filter = desc.min_filter.as_enum().0 as i32;
+    let mag_filter = desc.mag_filter.as_enum().0 as i32;
+    let [wrap_s, wrap_t, wrap_r] = desc.wrap.map(|w| w.as_enum().0 as i32);
+    let [min_lod, max_lod] = desc.lod_range;
+    let (compare_mode, compare_func) = match desc.compare {
+      Some(func) => (GL_COMPARE_REF_TO_TEXTURE, func),
+      None => (GL_NONE, CompareFuncEnum::LessEqual),
+    };
+    unsafe {
+      self.SamplerParameteri(id, GL_TEXTURE_MIN_FILTER, min_filter);
+      self.SamplerParameteri(id, GL_TEXTURE_MAG_FILTER, mag_filter);
+      self.SamplerParameteri(id, GL_TEXTURE_WRAP_S, wrap_s);
+      self.SamplerParameteri(id, GL_TEXTURE_WRAP_T, wrap_t);
+      self.SamplerParameteri(id, GL_TEXTURE_WRAP_R, wrap_r);
+      // when it's available even 1.0 is set, since an earlier desc might
+      // have set something else.
+      if anisotropic {
+        self.SamplerParameterf(
+          id,
+          GL_TEXTURE_MAX_ANISOTROPY,
+          desc.max_anisotropy,
+        );
+      }
+      self.SamplerParameterf(id, GL_TEXTURE_LOD_BIAS, desc.lod_bias);
+      self.SamplerParameterf(id, GL_TEXTURE_MIN_LOD, min_lod);
+      self.SamplerParameterf(id, GL_TEXTURE_MAX_LOD, max_lod);
+      self.SamplerParameteri(
+        id,
+        GL_TEXTURE_COMPARE_MODE,
+        compare_mode.0 as i32,
+      );
+      self.SamplerParameteri(
+        id,
+        GL_TEXTURE_COMPARE_FUNC,
+        compare_func.as_enum().0 as i32,
+      );
+      self.SamplerParameterfv(
+        id,
+        GL_TEXTURE_BORDER_COLOR,
+        desc.border_color.as_ptr(),
+      );
+    }
+  }
+
+  /// Binds a sampler to a texture unit (`Some`), or clears the unit's sampler
+  /// binding (`None`) so that the texture's own parameters are used.
+  ///
+  /// See [glBindSampler](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBindSampler.xhtml)
+  pub fn bind_sampler(&self, unit: u32, opt_sampler: Option<SamplerID>) {
+    unsafe {
+      self.BindSampler(
+        unit,
+        core::mem::transmute::<Option<SamplerID>, u32>(opt_sampler),
+      )
+    }
+  }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_create_sampler_applies_desc() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let desc = SamplerDesc {
+    min_filter: MinFilterEnum::LinearMipmapLinear,
+    mag_filter: MagFilterEnum::Nearest,
+    wrap: [WrapEnum::ClampToEdge, WrapEnum::MirroredRepeat, WrapEnum::Repeat],
+    lod_bias: 0.5,
+    lod_range: [1.0, 4.0],
+    compare: Some(CompareFuncEnum::Greater),
+    border_color: [0.25, 0.5, 0.75, 1.0],
+    ..SamplerDesc::default()
+  };
+  let anisotropic = gl.has_anisotropic_filtering();
+  let sampler = gl.create_sampler(&desc, anisotropic).unwrap();
+  let get_i = |pname: GLenum| {
+    let mut out = 0;
+    unsafe { gl.GetSamplerParameteriv(sampler.0.get(), pname, &mut out) };
+    out as u32
+  };
+  let get_f = |pname: GLenum| {
+    let mut out = 0.0;
+    unsafe { gl.GetSamplerParameterfv(sampler.0.get(), pname, &mut out) };
+    out
+  };
+  assert_eq!(get_i(GL_TEXTURE_MIN_FILTER), GL_LINEAR_MIPMAP_LINEAR.0);
+  assert_eq!(get_i(GL_TEXTURE_MAG_FILTER), GL_NEAREST.0);
+  assert_eq!(get_i(GL_TEXTURE_WRAP_S), GL_CLAMP_TO_EDGE.0);
+  assert_eq!(get_i(GL_TEXTURE_WRAP_T), GL_MIRRORED_REPEAT.0);
+  assert_eq!(get_i(GL_TEXTURE_COMPARE_MODE), GL_COMPARE_REF_TO_TEXTURE.0);
+  assert_eq!(get_i(GL_TEXTURE_COMPARE_FUNC), GL_GREATER.0);
+  assert_eq!(get_f(GL_TEXTURE_LOD_BIAS), 0.5);
+  assert_eq!(get_f(GL_TEXTURE_MAX_LOD), 4.0);
+  let mut border = [0.0_f32; 4];
+  unsafe {
+    gl.GetSamplerParameterfv(
+      sampler.0.get(),
+      GL_TEXTURE_BORDER_COLOR,
+      border.as_mut_ptr(),
+    )
+  };
+  assert_eq!(border, desc.border_color);
+
+  gl.bind_sampler(0, Some(sampler));
+  gl.bind_sampler(0, None);
+  gl.delete_samplers([Some(sampler)]);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
+
+#[test]
+fn test_has_anisotropic_filtering_mock() {
+  let (mock, gl) = MockGl::new();
+  mock.set_integer(GL_MAJOR_VERSION, 4);
+  mock.set_integer(GL_MINOR_VERSION, 5);
+  assert!(!gl.has_anisotropic_filtering());
+  mock.set_extensions(&["GL_EXT_texture_filter_anisotropic"]);
+  assert!(gl.has_anisotropic_filtering());
+  mock.set_extensions(&[]);
+  mock.set_integer(GL_MINOR_VERSION, 6);
+  assert!(gl.has_anisotropic_filtering());
+}
@@ -0,0 +1,626 @@
+use super::*;
+use std::{fs, path::Path};
+
+/// `SB6M`, the first 4 bytes of every `.sbm` file.
+const SBM_MAGIC: [u8; 4] = *b"SB6M";
+const SBM_HEADER_SIZE: usize = 16;
+const SBM_CHUNK_HEADER_SIZE: usize = 8;
+/// The size of one attribute declaration within an `ATRB` chunk.
+const SBM_ATTRIB_DECL_SIZE: usize = 84;
+const SBM_ATTRIB_FLAG_NORMALIZED: u32 = 1;
+const SBM_ATTRIB_FLAG_INTEGER: u32 = 2;
+
+/// The types that index data can be stored as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum IndexTypeEnum {
+  U8 = GL_UNSIGNED_BYTE.0,
+  U16 = GL_UNSIGNED_SHORT.0,
+  U32 = GL_UNSIGNED_INT.0,
+}
+impl IndexTypeEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+
+  /// The size of one index, in bytes.
+  pub const fn size(self) -> u32 {
+    match self {
+      Self::U8 => 1,
+      Self::U16 => 2,
+      Self::U32 => 4,
+    }
+  }
+}
+
+/// A vertex attribute declared in an `.sbm` file.
+///
+/// The attribute's location is its index within
+/// [`SbmObject::attributes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbmAttribute {
+  /// The name given in the file (eg: `position`).
+  pub name: String,
+  /// How many components (1 to 4) the attribute has.
+  pub components: i32,
+  /// The type of each component.
+  pub component_type: AttribTypeEnum,
+  /// How the data is converted for the shader.
+  pub mode: AttribModeEnum,
+  /// The bytes from one vertex to the next. The file can say 0 for tightly
+  /// packed data, but that's replaced with the attribute's size when parsed.
+  pub stride: u32,
+  /// The byte offset of the first vertex within the vertex data.
+  pub data_offset: u32,
+}
+
+/// A range of an [`SbmObject`] that's drawn in one call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SbmSubObject {
+  /// The first index (or the first vertex, with no index data).
+  pub first: u32,
+  /// How many indices (or vertices) there are.
+  pub count: u32,
+}
+
+/// The index data of an [`SbmObject`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbmIndices {
+  pub index_type: IndexTypeEnum,
+  pub count: u32,
+  /// The raw index data, `count * index_type.size()` bytes.
+  pub data: Vec<u8>,
+}
+
+/// A mesh loaded from the SuperBible's `.sbm` format.
+///
+/// The file is a header followed by chunks, each tagged with a four
+/// character code:
+/// * `VRTX`: The vertex data (required).
+/// * `ATRB`: The vertex attributes (required).
+/// * `INDX`: The index data. Without this, vertices are drawn in order.
+/// * `OLST`: The sub-objects. Without this, the whole mesh is one
+///   sub-object.
+/// * `CMNT`: A comment.
+///
+/// Other chunks (such as `DATA`) are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbmObject {
+  pub attributes: Vec<SbmAttribute>,
+  /// The raw vertex data, which the attributes read from.
+  pub vertex_data: Vec<u8>,
+  pub vertex_count: u32,
+  pub indices: Option<SbmIndices>,
+  /// Always at least one entry.
+  pub sub_objects: Vec<SbmSubObject>,
+  pub comments: Vec<String>,
+}
+impl SbmObject {
+  /// Reads and parses an `.sbm` file.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+    let path = path.as_ref();
+    let bytes = fs::read(path)
+      .map_err(|e| format!("Couldn't read `{}`: {}", path.display(), e))?;
+    Self::parse(&bytes)
+  }
+
+  /// Parses the bytes of an `.sbm` file.
+  ///
+  /// ## Failure
+  /// * The header or any chunk is truncated or has a bad size.
+  /// * The vertex data, attributes, or index data are missing or refer to
+  ///   bytes outside the file.
+  /// * An attribute or index type isn't one that GL understands.
+  /// * A sub-object goes past the end of the indices (or vertices).
+  pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+    let u32_at = |i: usize| -> Result<u32, String> {
+      bytes
+        .get(i..i + 4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| format!("Unexpected end of file at byte {}.", i))
+    };
+    // gets `len` bytes at `offset`, with the arithmetic done in u64 so that
+    // huge values from a bad file can't overflow.
+    let bytes_at =
+      |offset: u32, len: u64, what: &str| -> Result<&[u8], String> {
+        let end = u64::from(offset) + len;
+        if end > bytes.len() as u64 {
+          return Err(format!(
+            "The {} is at bytes {}..{}, but the file is only {} bytes.",
+            what,
+            offset,
+            end,
+            bytes.len()
+          ));
+        }
+        Ok(&bytes[offset as usize..end as usize])
+      };
+
+    if bytes.len() < SBM_HEADER_SIZE {
+      return Err(format!("File too short: {} bytes.", bytes.len()));
+    }
+    if bytes[..4] != SBM_MAGIC {
+      return Err(String::from("Not an SBM file."));
+    }
+    let header_size = u32_at(4)? as usize;
+    let num_chunks = u32_at(8)?;
+    if header_size < SBM_HEADER_SIZE {
+      return Err(format!("Header size is only {} bytes.", header_size));
+    }
+
+    let mut vertex_data = None;
+    let mut vertex_count = 0;
+    let mut attributes = None;
+    let mut indices = None;
+    let mut sub_objects = None;
+    let mut comments = Vec::new();
+    let mut chunk_start = header_size;
+    for chunk_index in 0..num_chunks {
+      let tag = bytes
+        .get(chunk_start..chunk_start + 4)
+        .ok_or_else(|| format!("Chunk {} is missing.", chunk_index))?;
+      let tag = String::from_utf8_lossy(tag).into_owned();
+      let chunk_size = u32_at(chunk_start + 4)? as usize;
+      if chunk_size < SBM_CHUNK_HEADER_SIZE {
+        return Err(format!(
+          "Chunk {} (`{}`) has a size of {} bytes.",
+          chunk_index, tag, chunk_size
+        ));
+      }
+      let chunk_end = chunk_start.saturating_add(chunk_size);
+      if chunk_end > bytes.len() {
+        return Err(format!(
+          "Chunk {} (`{}`) is at bytes {}..{}, but the file is only {} bytes.",
+          chunk_index,
+          tag,
+          chunk_start,
+          chunk_end,
+          bytes.len()
+        ));
+      }
+      let body = chunk_start + SBM_CHUNK_HEADER_SIZE;
+      // reads a u32 within the chunk's body.
+      let field = |i: usize| -> Result<u32, String> {
+        if body + i * 4 + 4 > chunk_end {
+          return Err(format!("Chunk `{}` is too short.", tag));
+        }
+        u32_at(body + i * 4)
+      };
+      match tag.as_str() {
+        "VRTX" => {
+          let data_size = field(0)?;
+          let data_offset = field(1)?;
+          vertex_count = field(2)?;
+          let data =
+            bytes_at(data_offset, u64::from(data_size), "vertex data")?;
+          vertex_data = Some(data.to_vec());
+        }
+        "ATRB" => {
+          let count = field(0)? as usize;
+          let decls_start = body + 4;
+          if (chunk_end - decls_start) / SBM_ATTRIB_DECL_SIZE < count {
+            return Err(format!(
+              "Chunk `ATRB` is too short for {} attributes.",
+              count
+            ));
+          }
+          let mut list = Vec::with_capacity(count);
+          for i in 0..count {
+            let decl = decls_start + i * SBM_ATTRIB_DECL_SIZE;
+            let name = &bytes[decl..decl + 64];
+            let name_len = name.iter().position(|&b| b == 0).unwrap_or(64);
+            let name = String::from_utf8_lossy(&name[..name_len]).into_owned();
+            let components = u32_at(decl + 64)?;
+            let type_value = u32_at(decl + 68)?;
+            let stride = u32_at(decl + 72)?;
+            let flags = u32_at(decl + 76)?;
+            let data_offset = u32_at(decl + 80)?;
+            if !(1..=4).contains(&components) {
+              return Err(format!(
+                "Attribute `{}` has {} components.",
+                name, components
+              ));
+            }
+            let component_type = attrib_type_from_enum(GLenum(type_value))
+              .ok_or_else(|| {
+                format!("Attribute `{}` has type 0x{:X}.", name, type_value)
+              })?;
+            let mode = if flags & SBM_ATTRIB_FLAG_INTEGER != 0 {
+              AttribModeEnum::Integer
+            } else if flags & SBM_ATTRIB_FLAG_NORMALIZED != 0 {
+              AttribModeEnum::Normalized
+            } else {
+              AttribModeEnum::Float
+            };
+            let size = components * component_type.size();
+            let stride = if stride == 0 { size } else { stride };
+            list.push(SbmAttribute {
+              name,
+              components: components as i32,
+              component_type,
+              mode,
+              stride,
+              data_offset,
+            });
+          }
+          attributes = Some(list);
+        }
+        "INDX" => {
+          let type_value = field(0)?;
+          let count = field(1)?;
+          let data_offset = field(2)?;
+          let index_type = match GLenum(type_value) {
+            GL_UNSIGNED_BYTE => IndexTypeEnum::U8,
+            GL_UNSIGNED_SHORT => IndexTypeEnum::U16,
+            GL_UNSIGNED_INT => IndexTypeEnum::U32,
+            _ => return Err(format!("Index type 0x{:X}.", type_value)),
+          };
+          let len = u64::from(count) * u64::from(index_type.size());
+          let data = bytes_at(data_offset, len, "index data")?.to_vec();
+          indices = Some(SbmIndices { index_type, count, data });
+        }
+        "OLST" => {
+          let count = field(0)? as usize;
+          if (chunk_end - body - 4) / 8 < count {
+            return Err(format!(
+              "Chunk `OLST` is too short for {} sub-objects.",
+              count
+            ));
+          }
+          let list = (0..count)
+            .map(|i| {
+              let first = u32_at(body + 4 + i * 8)?;
+              let count = u32_at(body + 8 + i * 8)?;
+              Ok(SbmSubObject { first, count })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+          sub_objects = Some(list);
+        }
+        "CMNT" => {
+          let text = &bytes[body..chunk_end];
+          let len = text.iter().position(|&b| b == 0).unwrap_or(text.len());
+          comments.push(String::from_utf8_lossy(&text[..len]).into_owned());
+        }
+        _ => (),
+      }
+      chunk_start = chunk_end;
+    }
+
+    let vertex_data =
+      vertex_data.ok_or_else(|| String::from("No `VRTX` chunk."))?;
+    let attributes =
+      attributes.ok_or_else(|| String::from("No `ATRB` chunk."))?;
+    for attr in attributes.iter() {
+      if vertex_count == 0 {
+        break;
+      }
+      let size = attr.components as u32 * attr.component_type.size();
+      let end = u64::from(attr.data_offset)
+        + u64::from(vertex_count - 1) * u64::from(attr.stride)
+        + u64::from(size);
+      if end > vertex_data.len() as u64 {
+        return Err(format!(
+          "Attribute `{}` reads up to byte {}, but there's only {} bytes of \
+           vertex data.",
+          attr.name,
+          end,
+          vertex_data.len()
+        ));
+      }
+    }
+    let limit = indices.as_ref().map_or(vertex_count, |i| i.count);
+    let sub_objects = match sub_objects {
+      Some(list) if !list.is_empty() => list,
+      _ => vec![SbmSubObject { first: 0, count: limit }],
+    };
+    for (i, sub) in sub_objects.iter().enumerate() {
+      if u64::from(sub.first) + u64::from(sub.count) > u64::from(limit) {
+        return Err(format!(
+          "Sub-object {} is {}..{}, but there's only {} {}.",
+          i,
+          sub.first,
+          u64::from(sub.first) + u64::from(sub.count),
+          limit,
+          if indices.is_some() { "indices" } else { "vertices" }
+        ));
+      }
+    }
+    Ok(Self {
+      attributes,
+      vertex_data,
+      vertex_count,
+      indices,
+      sub_objects,
+      comments,
+    })
+  }
+}
+
+fn attrib_type_from_enum(e: GLenum) -> Option<AttribTypeEnum> {
+  Some(match e {
+    GL_BYTE => AttribTypeEnum::I8,
+    GL_UNSIGNED_BYTE => AttribTypeEnum::U8,
+    GL_SHORT => AttribTypeEnum::I16,
+    GL_UNSIGNED_SHORT => AttribTypeEnum::U16,
+    GL_INT => AttribTypeEnum::I32,
+    GL_UNSIGNED_INT => AttribTypeEnum::U32,
+    GL_FLOAT => AttribTypeEnum::F32,
+    _ => return None,
+  })
+}
+
+/// An [`SbmObject`] uploaded to GL, ready to draw.
+///
+/// Make one with [`create_sbm_mesh`](GlFnsRusty::create_sbm_mesh).
+#[derive(Debug, Clone)]
+pub struct SbmMesh {
+  pub vao: VertexArrayID,
+  pub vertex_buffer: BufferID,
+  pub index_buffer: Option<BufferID>,
+  pub index_type: Option<IndexTypeEnum>,
+  pub sub_objects: Vec<SbmSubObject>,
+}
+impl SbmMesh {
+  /// Draws every sub-object as triangles.
+  ///
+  /// This binds the mesh's vertex array.
+  pub fn draw(&self, gl: &GlFnsRusty) {
+    gl.bind_vertex_array(Some(self.vao));
+    for index in 0..self.sub_objects.len() {
+      self.draw_sub_object(gl, index, 1);
+    }
+  }
+
+  /// Draws instances of one sub-object as triangles.
+  ///
+  /// The mesh's vertex array must be bound.
+  ///
+  /// ## Panics
+  /// * If the index is out of bounds.
+  pub fn draw_sub_object(
+    &self, gl: &GlFnsRusty, index: usize, instance_count: u32,
+  ) {
+    let sub = self.sub_objects[index];
+    match self.index_type {
+      Some(index_type) => unsafe {
+        gl.DrawElementsInstanced(
+          GL_TRIANGLES,
+          sub.count.try_into().unwrap(),
+          index_type.as_enum(),
+          (sub.first as usize * index_type.size() as usize) as *const c_void,
+          instance_count.try_into().unwrap(),
+        )
+      },
+      None => unsafe {
+        gl.DrawArraysInstanced(
+          GL_TRIANGLES,
+          sub.first.try_into().unwrap(),
+          sub.count.try_into().unwrap(),
+          instance_count.try_into().unwrap(),
+        )
+      },
+    }
+  }
+
+  /// Deletes the vertex array and buffers.
+  pub fn delete(self, gl: &GlFnsRusty) {
+    gl.delete_vertex_arrays([Some(self.vao)]);
+    gl.delete_buffers([Some(self.vertex_buffer), self.index_buffer]);
+  }
+}
+
+impl GlFnsRusty {
+  /// Uploads an [`SbmObject`] into a new vertex array and buffers.
+  ///
+  /// Each attribute uses its index as both its location and its vertex
+  /// buffer binding.
+  pub fn create_sbm_mesh(&self, object: &SbmObject) -> Option<SbmMesh> {
+    let vao = self.create_vertex_arrays::<1>()[0]?;
+    let vertex_buffer = match self.create_buffers::<1>()[0] {
+      Some(buffer) => buffer,
+      None => {
+        self.delete_vertex_arrays([Some(vao)]);
+        return None;
+      }
+    };
+    self.named_buffer_storage(
+      vertex_buffer,
+      &object.vertex_data,
+      BufferStorageFlags::empty(),
+    );
+    let vaobj = vao.0.get();
+    for (location, attr) in object.attributes.iter().enumerate() {
+      let location = location as u32;
+      unsafe {
+        match attr.mode {
+          AttribModeEnum::Integer => self.VertexArrayAttribIFormat(
+            vaobj,
+            location,
+            attr.components,
+            attr.component_type.as_enum(),
+            0,
+          ),
+          _ => self.VertexArrayAttribFormat(
+            vaobj,
+            location,
+            attr.components,
+            attr.component_type.as_enum(),
+            (attr.mode == AttribModeEnum::Normalized) as _,
+            0,
+          ),
+        }
+        self.VertexArrayAttribBinding(vaobj, location, location);
+        self.VertexArrayVertexBuffer(
+          vaobj,
+          location,
+          vertex_buffer.0.get(),
+          attr.data_offset.try_into().unwrap(),
+          attr.stride.try_into().unwrap(),
+        );
+        self.EnableVertexArrayAttrib(vaobj, location);
+      }
+    }
+    let (index_buffer, index_type) = match &object.indices {
+      Some(indices) => {
+        let index_buffer = self.create_buffers::<1>()[0];
+        if let Some(buffer) = index_buffer {
+          self.named_buffer_storage(
+            buffer,
+            &indices.data,
+            BufferStorageFlags::empty(),
+          );
+          unsafe { self.VertexArrayElementBuffer(vaobj, buffer.0.get()) };
+        } else {
+          self.delete_vertex_arrays([Some(vao)]);
+          self.delete_buffers([Some(vertex_buffer)]);
+          return None;
+        }
+        (index_buffer, Some(indices.index_type))
+      }
+      None => (None, None),
+    };
+    Some(SbmMesh {
+      vao,
+      vertex_buffer,
+      index_buffer,
+      index_type,
+      sub_objects: object.sub_objects.clone(),
+    })
+  }
+}
+
+/// Builds an `.sbm` file with a triangle, two sub-objects, and a comment.
+#[cfg(test)]
+fn test_sbm_bytes() -> Vec<u8> {
+  let mut out = Vec::new();
+  let push = |out: &mut Vec<u8>, words: &[u32]| {
+    words.iter().for_each(|w| out.extend_from_slice(&w.to_le_bytes()))
+  };
+  out.extend_from_slice(b"SB6M");
+  push(&mut out, &[16, 5, 0]);
+  out.extend_from_slice(b"CMNT");
+  push(&mut out, &[16]);
+  out.extend_from_slice(b"hi!\0\0\0\0\0");
+  // positions (3 x f32), then colors (4 x u8), not interleaved.
+  out.extend_from_slice(b"ATRB");
+  push(&mut out, &[8 + 4 + 84 * 2, 2]);
+  for (name, components, gl_type, flags, offset) in
+    [("position", 3, GL_FLOAT, 0, 0), ("color", 4, GL_UNSIGNED_BYTE, 1, 36)]
+  {
+    let mut name_bytes = [0_u8; 64];
+    name_bytes[..name.len()].copy_from_slice(name.as_bytes());
+    out.extend_from_slice(&name_bytes);
+    push(&mut out, &[components, gl_type.0, 0, flags, offset]);
+  }
+  let data_start = out.len() as u32 + 20 + 20 + 28 + 8;
+  out.extend_from_slice(b"VRTX");
+  push(&mut out, &[20, 48, data_start, 3]);
+  out.extend_from_slice(b"INDX");
+  push(&mut out, &[20, GL_UNSIGNED_SHORT.0, 4, data_start + 48]);
+  out.extend_from_slice(b"OLST");
+  push(&mut out, &[8 + 4 + 16, 2, 0, 3, 3, 1]);
+  out.extend_from_slice(b"DATA");
+  push(&mut out, &[8]);
+  assert_eq!(out.len() as u32, data_start);
+  #[rustfmt::skip]
+  let positions: [f32; 9] = [
+    -0.5, -0.5, 0.0,
+    0.5, -0.5, 0.0,
+    0.0, 0.5, 0.0,
+  ];
+  positions.iter().for_each(|f| out.extend_from_slice(&f.to_le_bytes()));
+  out.extend_from_slice(&[255; 12]);
+  [0_u16, 1, 2, 0].iter().for_each(|i| out.extend_from_slice(&i.to_le_bytes()));
+  out
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_SbmObject_parse() {
+  let object = SbmObject::parse(&test_sbm_bytes()).unwrap();
+  assert_eq!(object.comments, vec![String::from("hi!")]);
+  assert_eq!(object.attributes.len(), 2);
+  assert_eq!(object.attributes[0].name, "position");
+  assert_eq!(object.attributes[0].stride, 12);
+  assert_eq!(object.attributes[1].component_type, AttribTypeEnum::U8);
+  assert_eq!(object.attributes[1].mode, AttribModeEnum::Normalized);
+  assert_eq!(object.attributes[1].stride, 4);
+  assert_eq!(object.attributes[1].data_offset, 36);
+  assert_eq!(object.vertex_count, 3);
+  assert_eq!(object.vertex_data.len(), 48);
+  let indices = object.indices.as_ref().unwrap();
+  assert_eq!(indices.index_type, IndexTypeEnum::U16);
+  assert_eq!(indices.data, vec![0, 0, 1, 0, 2, 0, 0, 0]);
+  assert_eq!(
+    object.sub_objects,
+    vec![
+      SbmSubObject { first: 0, count: 3 },
+      SbmSubObject { first: 3, count: 1 }
+    ]
+  );
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_SbmObject_parse_errors() {
+  let good = test_sbm_bytes();
+  // every truncation is an error rather than a panic.
+  for len in 0..good.len() {
+    assert!(SbmObject::parse(&good[..len]).is_err(), "{}", len);
+  }
+
+  let with = |offset: usize, value: u32| {
+    let mut bytes = good.clone();
+    bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    SbmObject::parse(&bytes).unwrap_err()
+  };
+  assert_eq!(with(0, 0), "Not an SBM file.");
+  // the comment chunk's size.
+  assert!(with(20, 4).contains("has a size of 4 bytes"));
+  assert!(with(20, u32::MAX).contains("Chunk 0 (`CMNT`)"));
+  // the color attribute's data offset.
+  let color_decl = 32 + 12 + 84;
+  assert!(with(color_decl + 80, 40).contains("reads up to byte 52"));
+  assert!(with(color_decl + 68, 0x1234).contains("type 0x1234"));
+  // the vertex data offset.
+  let vrtx = 32 + 12 + 84 * 2;
+  assert!(with(vrtx + 12, u32::MAX).contains("vertex data is at bytes"));
+  // the second sub-object's count.
+  let olst = vrtx + 20 + 20;
+  assert!(with(olst + 24, 2).contains("Sub-object 1 is 3..5"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_create_sbm_mesh() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let object = SbmObject::parse(&test_sbm_bytes()).unwrap();
+  let mesh = gl.create_sbm_mesh(&object).unwrap();
+  let get = |index: u32, pname: GLenum| {
+    let mut out = 0;
+    unsafe {
+      gl.GetVertexArrayIndexediv(mesh.vao.0.get(), index, pname, &mut out)
+    };
+    out
+  };
+  assert_eq!(get(1, GL_VERTEX_ATTRIB_ARRAY_NORMALIZED), 1);
+  assert_eq!(get(1, GL_VERTEX_BINDING_OFFSET), 36);
+  assert_eq!(get(0, GL_VERTEX_BINDING_STRIDE), 12);
+  let mut element_buffer = 0;
+  unsafe {
+    gl.GetVertexArrayiv(
+      mesh.vao.0.get(),
+      GL_ELEMENT_ARRAY_BUFFER_BINDING,
+      &mut element_buffer,
+    )
+  };
+  assert_eq!(element_buffer as u32, mesh.index_buffer.unwrap().0.get());
+
+  mesh.draw(&gl);
+  gl.bind_vertex_array(None);
+  mesh.delete(&gl);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u32)]
 pub enum ShaderEnum {
   Compute = GL_COMPUTE_SHADER.0,
@@ -20,6 +20,53 @@ impl ShaderEnum {
 #[repr(transparent)]
 pub struct ShaderID(pub(crate) NonZeroU32);
 
+/// The first word of every SPIR-V module.
+pub const SPIRV_MAGIC: u32 = 0x0723_0203;
+
+/// Checks that some words look like a SPIR-V module.
+///
+/// This only checks the header (the magic number, and that the 5 word header
+/// is all there). The driver does the rest of the validation.
+pub fn validate_spirv(words: &[u32]) -> Result<(), String> {
+  match words.first() {
+    None => Err(String::from("SPIR-V module is empty.")),
+    Some(&w) if w == SPIRV_MAGIC.swap_bytes() => {
+      Err(String::from("SPIR-V module has the wrong byte order."))
+    }
+    Some(&w) if w != SPIRV_MAGIC => {
+      Err(format!("Not a SPIR-V module: the first word is {:#010x}.", w))
+    }
+    _ if words.len() < 5 => Err(format!(
+      "SPIR-V module is only {} words, the header alone is 5.",
+      words.len()
+    )),
+    _ => Ok(()),
+  }
+}
+
+/// Converts the bytes of a `.spv` file into SPIR-V words.
+///
+/// The byte count must be a multiple of 4. Files of either byte order are
+/// accepted (based on the magic number), and the output is always native
+/// order.
+pub fn spirv_words_from_bytes(bytes: &[u8]) -> Result<Vec<u32>, String> {
+  if !bytes.len().is_multiple_of(4) {
+    return Err(format!(
+      "SPIR-V is {} bytes, which isn't a whole number of words.",
+      bytes.len()
+    ));
+  }
+  let mut words: Vec<u32> = bytes
+    .chunks_exact(4)
+    .map(|c| u32::from_ne_bytes(c.try_into().unwrap()))
+    .collect();
+  if words.first() == Some(&SPIRV_MAGIC.swap_bytes()) {
+    words.iter_mut().for_each(|w| *w = w.swap_bytes());
+  }
+  validate_spirv(&words)?;
+  Ok(words)
+}
+
 impl GlFnsRusty {
   /// Creates a new shader of the given type.
   pub fn create_shader(&self, t: ShaderEnum) -> Option<ShaderID> {
@@ -45,6 +92,73 @@ impl GlFnsRusty {
     };
   }
 
+  /// Assigns a SPIR-V module to the shader (instead of a source string).
+  ///
+  /// The module is checked with [`validate_spirv`] before it's given to GL.
+  /// Afterwards, use [`specialize_shader`](GlFnsRusty::specialize_shader) to
+  /// pick the entry point instead of compiling. Needs GL 4.6 or
+  /// `GL_ARB_gl_spirv`.
+  ///
+  /// See [glShaderBinary](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glShaderBinary.xhtml)
+  pub fn set_shader_binary_spirv(
+    &self, shader: ShaderID, words: &[u32],
+  ) -> Result<(), String> {
+    validate_spirv(words)?;
+    self.shader_binary_spirv_unchecked(shader, words);
+    Ok(())
+  }
+
+  fn shader_binary_spirv_unchecked(&self, shader: ShaderID, words: &[u32]) {
+    unsafe {
+      self.ShaderBinary(
+        1,
+        &shader.0.get(),
+        GL_SHADER_BINARY_FORMAT_SPIR_V,
+        words.as_ptr().cast(),
+        core::mem::size_of_val(words).try_into().unwrap(),
+      )
+    };
+  }
+
+  /// Picks the entry point of a SPIR-V shader and sets its specialization
+  /// constants, which completes the shader like compiling would.
+  ///
+  /// Each of `constant_ids` is given the value at the same index of `values`
+  /// (as raw bits, so use `to_bits` for a float). Check the result with
+  /// [`get_shader_last_compile_successful`](GlFnsRusty::get_shader_last_compile_successful).
+  ///
+  /// ## Failure
+  /// * If `entry` has a null byte in it you get an error, and GL isn't
+  ///   called (since GL would cut the name short at the null).
+  ///
+  /// ## Panics
+  /// * If `constant_ids` and `values` have different lengths.
+  ///
+  /// See [glSpecializeShader](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glSpecializeShader.xhtml)
+  pub fn specialize_shader(
+    &self, shader: ShaderID, entry: &str, constant_ids: &[u32], values: &[u32],
+  ) -> Result<(), String> {
+    assert_eq!(constant_ids.len(), values.len());
+    let entry_z = null_terminated_entry_point(entry)?;
+    self.specialize_shader_z(shader, &entry_z, constant_ids, values);
+    Ok(())
+  }
+
+  fn specialize_shader_z(
+    &self, shader: ShaderID, entry_z: &[u8], constant_ids: &[u32],
+    values: &[u32],
+  ) {
+    unsafe {
+      self.SpecializeShader(
+        shader.0.get(),
+        entry_z.as_ptr(),
+        constant_ids.len().try_into().unwrap(),
+        constant_ids.as_ptr(),
+        values.as_ptr(),
+      )
+    };
+  }
+
   /// Compiles the shader's source string.
   pub fn compile_shader(&self, shader: ShaderID) {
     self.CompileShader(shader.0.get())
@@ -85,10 +199,10 @@ impl GlFnsRusty {
 
   /// Creates a new shader and compiles some source for it.
   ///
-  /// On failure, you get the compilation failure log.
+  /// On failure, you get the parsed compilation failure log.
   pub fn create_compiled_shader(
     &self, t: ShaderEnum, src: &str,
-  ) -> Result<ShaderID, String> {
+  ) -> Result<ShaderID, ShaderError> {
     let shader = self
       .create_shader(t)
       .ok_or_else(|| String::from("Couldn't create a shader."))?;
@@ -97,9 +211,190 @@ impl GlFnsRusty {
     if self.get_shader_last_compile_successful(shader) {
       Ok(shader)
     } else {
-      let e = Err(self.get_shader_info_log(shader));
+      let e = Err(
+        ShaderError::from_log(&self.get_shader_info_log(shader))
+          .with_stage(t)
+          .with_source(src),
+      );
+      self.delete_shader(shader);
+      e
+    }
+  }
+
+  /// Creates a new shader from a SPIR-V module and specializes it.
+  ///
+  /// * `specializations` are `(constant_id, value)` pairs.
+  ///
+  /// The module and the entry point name are checked before any GL calls
+  /// are made. On failure, you get the problem with them or the
+  /// specialization failure log.
+  pub fn create_compiled_spirv_shader(
+    &self, t: ShaderEnum, words: &[u32], entry: &str,
+    specializations: &[(u32, u32)],
+  ) -> Result<ShaderID, ShaderError> {
+    validate_spirv(words).map_err(|e| ShaderError::from(e).with_stage(t))?;
+    let entry_z = null_terminated_entry_point(entry)
+      .map_err(|e| ShaderError::from(e).with_stage(t))?;
+    let shader = self
+      .create_shader(t)
+      .ok_or_else(|| String::from("Couldn't create a shader."))?;
+    let (ids, values): (Vec<u32>, Vec<u32>) =
+      specializations.iter().copied().unzip();
+    self.shader_binary_spirv_unchecked(shader, words);
+    self.specialize_shader_z(shader, &entry_z, &ids, &values);
+    if self.get_shader_last_compile_successful(shader) {
+      Ok(shader)
+    } else {
+      let e = Err(
+        ShaderError::from_log(&self.get_shader_info_log(shader)).with_stage(t),
+      );
       self.delete_shader(shader);
       e
     }
   }
 }
+
+/// GL takes entry point names null terminated, so they can't have a null.
+fn null_terminated_entry_point(entry: &str) -> Result<Vec<u8>, String> {
+  if entry.contains('\0') {
+    return Err(format!("The entry point name {:?} has a null byte.", entry));
+  }
+  let mut v = Vec::with_capacity(entry.len() + 1);
+  v.extend_from_slice(entry.as_bytes());
+  v.push(0);
+  Ok(v)
+}
+
+/// A minimal compute shader module, with a spec constant (ID 3, default 7).
+#[cfg(test)]
+#[rustfmt::skip]
+const TEST_SPIRV: [u32; 47] = [
+  // header: magic, version 1.0, generator, id bound, schema
+  SPIRV_MAGIC, 0x0001_0000, 0, 7, 0,
+  // OpCapability Shader
+  2 << 16 | 17, 1,
+  // OpMemoryModel Logical GLSL450
+  3 << 16 | 14, 0, 1,
+  // OpEntryPoint GLCompute %1 "main"
+  5 << 16 | 15, 5, 1, u32::from_le_bytes(*b"main"), 0,
+  // OpExecutionMode %1 LocalSize 1 1 1
+  6 << 16 | 16, 1, 17, 1, 1, 1,
+  // OpDecorate %6 SpecId 3
+  4 << 16 | 71, 6, 1, 3,
+  // %2 = OpTypeVoid
+  2 << 16 | 19, 2,
+  // %3 = OpTypeFunction %2
+  3 << 16 | 33, 3, 2,
+  // %5 = OpTypeInt 32 0
+  4 << 16 | 21, 5, 32, 0,
+  // %6 = OpSpecConstant %5 7
+  4 << 16 | 50, 5, 6, 7,
+  // %1 = OpFunction %2 None %3
+  5 << 16 | 54, 2, 1, 0, 3,
+  // %4 = OpLabel, OpReturn, OpFunctionEnd
+  2 << 16 | 248, 4, 1 << 16 | 253, 1 << 16 | 56,
+];
+
+#[test]
+fn test_spirv_words_from_bytes() {
+  assert!(validate_spirv(&TEST_SPIRV).is_ok());
+  assert!(validate_spirv(&[]).is_err());
+  assert!(validate_spirv(&TEST_SPIRV[..4]).is_err());
+  assert!(validate_spirv(&[0x1234_5678; 5]).is_err());
+
+  let le: Vec<u8> = TEST_SPIRV.iter().flat_map(|w| w.to_le_bytes()).collect();
+  let be: Vec<u8> = TEST_SPIRV.iter().flat_map(|w| w.to_be_bytes()).collect();
+  assert_eq!(spirv_words_from_bytes(&le).unwrap(), TEST_SPIRV);
+  assert_eq!(spirv_words_from_bytes(&be).unwrap(), TEST_SPIRV);
+  assert!(spirv_words_from_bytes(&le[..le.len() - 1]).is_err());
+  assert!(spirv_words_from_bytes(b"#version 450").is_err());
+  // a byte swapped module can't be given to GL as-is.
+  let swapped: Vec<u32> = TEST_SPIRV.iter().map(|w| w.swap_bytes()).collect();
+  assert!(validate_spirv(&swapped).is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_create_compiled_spirv_shader() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  if !gl.get_all_extension_strings().iter().any(|e| e == "GL_ARB_gl_spirv") {
+    println!("skipping, no GL_ARB_gl_spirv");
+    return;
+  }
+  let shader = gl
+    .create_compiled_spirv_shader(
+      ShaderEnum::Compute,
+      &TEST_SPIRV,
+      "main",
+      &[(3, 12)],
+    )
+    .unwrap();
+  let mut is_spirv = 0;
+  unsafe { gl.GetShaderiv(shader.0.get(), GL_SPIR_V_BINARY, &mut is_spirv) };
+  assert_eq!(is_spirv, 1);
+  let program = gl.create_linked_program(&[shader]).unwrap();
+  gl.delete_program(program);
+  gl.delete_shader(shader);
+
+  // bad modules never reach GL.
+  let err = gl
+    .create_compiled_spirv_shader(ShaderEnum::Compute, &[0; 8], "main", &[])
+    .unwrap_err();
+  assert!(err.log.contains("Not a SPIR-V module"));
+  assert_eq!(err.stage, Some(ShaderEnum::Compute));
+  // nor do entry points with a null in them.
+  assert!(gl
+    .create_compiled_spirv_shader(
+      ShaderEnum::Compute,
+      &TEST_SPIRV,
+      "ma\0in",
+      &[]
+    )
+    .is_err());
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
+
+#[test]
+fn test_create_compiled_shader_mock() {
+  let (mock, gl) = MockGl::new();
+  let src = "#version 450 core\nvoid main() {}";
+  let shader = gl.create_compiled_shader(ShaderEnum::Vertex, src).unwrap();
+  assert_eq!(shader.0.get(), 1);
+  let calls = mock.calls();
+  assert_eq!(
+    calls.iter().map(|c| c.name).collect::<Vec<_>>(),
+    ["glCreateShader", "glShaderSource", "glCompileShader", "glGetShaderiv"]
+  );
+  assert_eq!(calls[0].args, [MockArg::Enum(GL_VERTEX_SHADER)]);
+  assert_eq!(calls[1].args[2], MockArg::Str(String::from(src)));
+  assert_eq!(calls[3].args[1], MockArg::Enum(GL_COMPILE_STATUS));
+
+  // a failed compile gives the parsed log and deletes the shader.
+  mock.clear_calls();
+  mock.set_compile_status(false);
+  mock.set_shader_info_log("0:2(14): error: `nope' undeclared\n");
+  let err = gl.create_compiled_shader(ShaderEnum::Fragment, src).unwrap_err();
+  assert_eq!(err.stage, Some(ShaderEnum::Fragment));
+  assert_eq!(err.log, "0:2(14): error: `nope' undeclared\n");
+  assert_eq!(err.diagnostics[0].line, Some(2));
+  assert_eq!(
+    mock.call_names(),
+    [
+      "glCreateShader",
+      "glShaderSource",
+      "glCompileShader",
+      "glGetShaderiv",
+      "glGetShaderiv",
+      "glGetShaderInfoLog",
+      "glDeleteShader"
+    ]
+  );
+  assert_eq!(mock.calls()[6].args, [MockArg::Int(2)]);
+
+  mock.set_creates_fail(true);
+  let err = gl.create_compiled_shader(ShaderEnum::Vertex, src).unwrap_err();
+  assert!(err.log.contains("Couldn't create a shader."));
+}
@@ -0,0 +1,514 @@
+use super::*;
+use core::fmt::{self, Write};
+
+/// How serious a [`ShaderDiagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+  Error,
+  Warning,
+  Note,
+}
+impl DiagnosticSeverity {
+  fn from_word(word: &str) -> Option<Self> {
+    match word.to_ascii_lowercase().as_str() {
+      "error" | "fatal error" => Some(Self::Error),
+      "warning" => Some(Self::Warning),
+      "note" | "info" => Some(Self::Note),
+      _ => None,
+    }
+  }
+}
+impl fmt::Display for DiagnosticSeverity {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Self::Error => "error",
+      Self::Warning => "warning",
+      Self::Note => "note",
+    })
+  }
+}
+
+/// One message from a shader compile or program link log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderDiagnostic {
+  /// The source string number (with a [`ShaderPreprocessor`] this is the file
+  /// index of the [`SourceMap`]).
+  pub file: Option<usize>,
+  /// The 1-based line.
+  pub line: Option<u32>,
+  /// The 1-based column, if the driver gives one.
+  pub column: Option<u32>,
+  pub severity: DiagnosticSeverity,
+  /// The message text. Vendor error codes (eg: `C0000:`) are kept.
+  pub message: String,
+}
+impl ShaderDiagnostic {
+  /// Parses one line of a driver log.
+  ///
+  /// These formats are understood:
+  /// * NVIDIA: `0(12) : error C0000: message`
+  /// * AMD, Intel (Windows), and Apple: `ERROR: 0:12: message`
+  /// * Mesa: `0:12(5): error: message`
+  /// * Messages with no location (eg: link errors): `error: message`
+  pub fn parse_line(line: &str) -> Option<Self> {
+    let line = line.trim();
+    parse_amd_line(line)
+      .or_else(|| parse_mesa_line(line))
+      .or_else(|| parse_nvidia_line(line))
+      .or_else(|| parse_bare_line(line))
+  }
+}
+
+/// `ERROR: 0:12: message`
+fn parse_amd_line(line: &str) -> Option<ShaderDiagnostic> {
+  let (word, rest) = line.split_once(':')?;
+  if !word.bytes().all(|b| b.is_ascii_uppercase()) {
+    return None;
+  }
+  let severity = DiagnosticSeverity::from_word(word)?;
+  let rest = rest.trim_start();
+  let (file, line, message) = match split_number(rest)
+    .and_then(|(file, rest)| Some((file, rest.strip_prefix(':')?)))
+    .and_then(|(file, rest)| Some((file, split_number(rest)?)))
+    .and_then(|(file, (line, rest))| {
+      Some((file, line, rest.strip_prefix(':')?))
+    }) {
+    Some((file, line, message)) => (Some(file), Some(line), message),
+    None => (None, None, rest),
+  };
+  Some(ShaderDiagnostic {
+    file: file.map(|f| f as usize),
+    line,
+    column: None,
+    severity,
+    message: String::from(message.trim()),
+  })
+}
+
+/// `0:12(5): error: message`
+fn parse_mesa_line(line: &str) -> Option<ShaderDiagnostic> {
+  let (file, rest) = split_number(line)?;
+  let (line, rest) = split_number(rest.strip_prefix(':')?)?;
+  let (column, rest) = split_number(rest.strip_prefix('(')?)?;
+  let rest = rest.strip_prefix("):")?;
+  let (word, message) = rest.split_once(':')?;
+  Some(ShaderDiagnostic {
+    file: Some(file as usize),
+    line: Some(line),
+    // Mesa counts columns from 0.
+    column: Some(column + 1),
+    severity: DiagnosticSeverity::from_word(word.trim())?,
+    message: String::from(message.trim()),
+  })
+}
+
+/// `0(12) : error C0000: message`
+fn parse_nvidia_line(line: &str) -> Option<ShaderDiagnostic> {
+  let (file, rest) = match split_number(line) {
+    Some((file, rest)) => (Some(file as usize), rest),
+    None => (None, line),
+  };
+  let (line, rest) = split_number(rest.strip_prefix('(')?)?;
+  let rest = rest.strip_prefix(')')?.trim_start().strip_prefix(':')?;
+  let rest = rest.trim_start();
+  let word_end = rest.find(|c: char| !c.is_ascii_alphabetic())?;
+  let severity = DiagnosticSeverity::from_word(&rest[..word_end])?;
+  Some(ShaderDiagnostic {
+    file,
+    line: Some(line),
+    column: None,
+    severity,
+    message: String::from(rest[word_end..].trim()),
+  })
+}
+
+/// `error: message`
+fn parse_bare_line(line: &str) -> Option<ShaderDiagnostic> {
+  let (word, message) = line.split_once(':')?;
+  Some(ShaderDiagnostic {
+    file: None,
+    line: None,
+    column: None,
+    severity: DiagnosticSeverity::from_word(word.trim())?,
+    message: String::from(message.trim()),
+  })
+}
+
+/// Splits off a leading decimal number.
+fn split_number(s: &str) -> Option<(u32, &str)> {
+  let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+  Some((s[..end].parse().ok()?, &s[end..]))
+}
+
+/// A failed shader compile or program link, with the log parsed into
+/// [`ShaderDiagnostic`]s.
+///
+/// The `Display` impl shows each diagnostic in `file:line:column: severity:
+/// message` form (which most editors can jump to), followed by the offending
+/// source line and a caret, when the source is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderError {
+  /// The shader stage, or `None` for a link error.
+  pub stage: Option<ShaderEnum>,
+  /// The driver's log, exactly as given.
+  pub log: String,
+  pub diagnostics: Vec<ShaderDiagnostic>,
+  source: Option<String>,
+  source_map: Option<Box<SourceMap>>,
+}
+impl ShaderError {
+  /// Parses a driver log.
+  ///
+  /// Lines that aren't a known format are added to the message of the
+  /// diagnostic before them. Any such lines before the first diagnostic are
+  /// headers (eg: `Fragment info`) and are skipped, unless the log has no
+  /// diagnostics at all, in which case the whole log is one error. Vendor
+  /// summary lines (eg: `ERROR: 2 compilation errors.  No code generated.`)
+  /// are also skipped.
+  pub fn from_log(log: &str) -> Self {
+    let mut diagnostics: Vec<ShaderDiagnostic> = Vec::new();
+    let mut header: Vec<&str> = Vec::new();
+    for line in log.lines().map(str::trim).filter(|l| !l.is_empty()) {
+      if line.ends_with("No code generated.") {
+        continue;
+      }
+      match (ShaderDiagnostic::parse_line(line), diagnostics.last_mut()) {
+        (Some(d), _) => diagnostics.push(d),
+        (None, Some(prev)) => {
+          prev.message.push('\n');
+          prev.message.push_str(line);
+        }
+        (None, None) => header.push(line),
+      }
+    }
+    if diagnostics.is_empty() && !header.is_empty() {
+      diagnostics.push(ShaderDiagnostic {
+        file: None,
+        line: None,
+        column: None,
+        severity: DiagnosticSeverity::Error,
+        message: header.join("\n"),
+      });
+    }
+    Self {
+      stage: None,
+      log: String::from(log),
+      diagnostics,
+      source: None,
+      source_map: None,
+    }
+  }
+
+  /// Sets the shader stage.
+  pub fn with_stage(mut self, stage: ShaderEnum) -> Self {
+    self.stage = Some(stage);
+    self
+  }
+
+  /// Sets the source that was compiled, so the display can show source lines.
+  pub fn with_source(mut self, src: &str) -> Self {
+    self.source = Some(String::from(src));
+    self
+  }
+
+  /// Sets the source map of preprocessed source, so the display can show file
+  /// names.
+  ///
+  /// ```no_run
+  /// # use superbible7::*;
+  /// # let gl: GlFnsRusty = todo!();
+  /// # let files = std::collections::HashMap::<String, String>::new();
+  /// let pp = ShaderPreprocessor::new(&files).process_file("main.frag").unwrap();
+  /// let shader = gl
+  ///   .create_compiled_shader(ShaderEnum::Fragment, &pp.source)
+  ///   .map_err(|e| e.with_source_map(&pp.source_map));
+  /// ```
+  pub fn with_source_map(mut self, source_map: &SourceMap) -> Self {
+    self.source_map = Some(Box::new(source_map.clone()));
+    self
+  }
+
+  /// The diagnostics that are errors.
+  pub fn errors(&self) -> impl Iterator<Item = &ShaderDiagnostic> {
+    self.diagnostics.iter().filter(|d| d.severity == DiagnosticSeverity::Error)
+  }
+
+  /// The name to show for a source string number.
+  fn file_name(&self, file: usize) -> String {
+    match self.source_map.as_ref().and_then(|m| m.file_name(file)) {
+      Some(name) => String::from(name),
+      None => file.to_string(),
+    }
+  }
+
+  /// The text of a line in a source string, if it's known.
+  fn source_line(&self, file: usize, line: u32) -> Option<&str> {
+    let source = self.source.as_ref()?;
+    let output_line = match &self.source_map {
+      Some(map) => {
+        let want = Some(SourceLocation { file, line });
+        (1..=source.lines().count() as u32)
+          .find(|&i| map.output_line(i) == want)?
+      }
+      None if file == 0 => line,
+      None => return None,
+    };
+    source.lines().nth(output_line.checked_sub(1)? as usize)
+  }
+}
+impl fmt::Display for ShaderError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for d in self.diagnostics.iter() {
+      let mut location = String::new();
+      if let Some(file) = d.file {
+        location.push_str(&self.file_name(file));
+        location.push(':');
+      }
+      for n in [d.line, d.column].iter().flatten() {
+        write!(location, "{}:", n)?;
+      }
+      if !location.is_empty() {
+        write!(f, "{} ", location)?;
+      }
+      writeln!(f, "{}: {}", d.severity, d.message)?;
+      if let (Some(file), Some(line)) = (d.file, d.line) {
+        if let Some(text) = self.source_line(file, line) {
+          writeln!(f, "  {}", text)?;
+          // the caret goes under the column, or the start of the text.
+          let indent = match d.column {
+            Some(c) => (c as usize).saturating_sub(1),
+            None => text.len() - text.trim_start().len(),
+          };
+          let pad: String = text
+            .chars()
+            .take(indent)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+          writeln!(f, "  {}^", pad)?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+impl std::error::Error for ShaderError {}
+impl From<String> for ShaderError {
+  fn from(log: String) -> Self {
+    Self::from_log(&log)
+  }
+}
+impl From<ShaderError> for String {
+  fn from(e: ShaderError) -> Self {
+    e.to_string()
+  }
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderDiagnostic_parse_line() {
+  let d = |file, line, column, severity, message: &str| ShaderDiagnostic {
+    file,
+    line,
+    column,
+    severity,
+    message: String::from(message),
+  };
+  use DiagnosticSeverity::*;
+  // NVIDIA
+  assert_eq!(
+    ShaderDiagnostic::parse_line(
+      "0(12) : error C0000: syntax error, unexpected '}' at token \"}\""
+    ),
+    Some(d(
+      Some(0),
+      Some(12),
+      None,
+      Error,
+      "C0000: syntax error, unexpected '}' at token \"}\""
+    ))
+  );
+  assert_eq!(
+    ShaderDiagnostic::parse_line(
+      "2(7) : warning C7050: \"x\" might be used before being initialized"
+    ),
+    Some(d(
+      Some(2),
+      Some(7),
+      None,
+      Warning,
+      "C7050: \"x\" might be used before being initialized"
+    ))
+  );
+  assert_eq!(
+    ShaderDiagnostic::parse_line(
+      "(0) : error C5145: must write to gl_Position"
+    ),
+    Some(d(None, Some(0), None, Error, "C5145: must write to gl_Position"))
+  );
+  // AMD and Intel (Windows)
+  assert_eq!(
+    ShaderDiagnostic::parse_line(
+      "ERROR: 0:12: 'nope' : undeclared identifier "
+    ),
+    Some(d(Some(0), Some(12), None, Error, "'nope' : undeclared identifier"))
+  );
+  assert_eq!(
+    ShaderDiagnostic::parse_line("WARNING: 1:3: 'x' : variable is not used"),
+    Some(d(Some(1), Some(3), None, Warning, "'x' : variable is not used"))
+  );
+  assert_eq!(
+    ShaderDiagnostic::parse_line("ERROR: Linking failed"),
+    Some(d(None, None, None, Error, "Linking failed"))
+  );
+  // Mesa (Intel and AMD on Linux, llvmpipe, ...)
+  assert_eq!(
+    ShaderDiagnostic::parse_line("0:5(11): error: `nope' undeclared"),
+    Some(d(Some(0), Some(5), Some(12), Error, "`nope' undeclared"))
+  );
+  assert_eq!(
+    ShaderDiagnostic::parse_line("1:20(3): warning: `x' used uninitialized"),
+    Some(d(Some(1), Some(20), Some(4), Warning, "`x' used uninitialized"))
+  );
+  assert_eq!(
+    ShaderDiagnostic::parse_line(
+      "error: fragment shader input `missing' has no matching output in the \
+       previous stage"
+    ),
+    Some(d(
+      None,
+      None,
+      None,
+      Error,
+      "fragment shader input `missing' has no matching output in the \
+       previous stage"
+    ))
+  );
+  assert_eq!(ShaderDiagnostic::parse_line("Vertex info"), None);
+  assert_eq!(ShaderDiagnostic::parse_line("-----------"), None);
+  assert_eq!(ShaderDiagnostic::parse_line("12:34 some text"), None);
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderError_from_log() {
+  // captured from an AMD driver
+  let amd = "ERROR: 0:3: 'nope' : undeclared identifier \n\
+             ERROR: 0:3: 'assign' :  cannot convert from 'float' to '4-component \
+             vector of float'\n\
+             ERROR: 2 compilation errors.  No code generated.\n\n";
+  let e = ShaderError::from_log(amd);
+  assert_eq!(e.log, amd);
+  assert_eq!(e.diagnostics.len(), 2);
+  assert_eq!(e.errors().count(), 2);
+  assert_eq!(e.diagnostics[1].line, Some(3));
+
+  // captured from an NVIDIA driver, with a header and continued lines
+  let nvidia = "Fragment info\n\
+                -------------\n\
+                0(4) : warning C7533: global variable gl_FragColor is \
+                deprecated after version 120\n\
+                0(5) : error C1008: undefined variable \"nope\"\n\
+                \tsee the declaration of \"nope\"\n";
+  let e = ShaderError::from_log(nvidia);
+  assert_eq!(e.diagnostics.len(), 2);
+  assert_eq!(e.diagnostics[0].severity, DiagnosticSeverity::Warning);
+  assert_eq!(
+    e.diagnostics[1].message,
+    "C1008: undefined variable \"nope\"\nsee the declaration of \"nope\""
+  );
+  assert_eq!(e.errors().count(), 1);
+
+  let e = ShaderError::from(String::from("Couldn't create a shader."));
+  assert_eq!(e.diagnostics[0].message, "Couldn't create a shader.");
+  assert_eq!(e.diagnostics[0].line, None);
+  assert!(ShaderError::from_log("").diagnostics.is_empty());
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderError_display() {
+  let src =
+    "#version 450 core\nout vec4 c;\nvoid main() {\n  c = vec4(nope);\n}";
+  let log = "0:4(11): error: `nope' undeclared\n\
+             0:4(6): error: cannot construct `vec4' from a non-numeric data \
+             type\nerror: no location here\n";
+  let e = ShaderError::from_log(log).with_source(src);
+  assert_eq!(
+    e.to_string(),
+    "0:4:12: error: `nope' undeclared\n\
+     \x20   c = vec4(nope);\n\
+     \x20            ^\n\
+     0:4:7: error: cannot construct `vec4' from a non-numeric data type\n\
+     \x20   c = vec4(nope);\n\
+     \x20       ^\n\
+     error: no location here\n"
+  );
+
+  // with a source map, the file names are used and lines come from the map.
+  let mut files = std::collections::HashMap::new();
+  files.insert("lib.glsl", "float f() {\n\treturn nope;\n}");
+  let pp = ShaderPreprocessor::new(&files)
+    .define("X", "1")
+    .process("main.frag", "#version 450\n#include \"lib.glsl\"")
+    .unwrap();
+  let e = ShaderError::from_log("ERROR: 1:2: 'nope' : undeclared identifier")
+    .with_source(&pp.source)
+    .with_source_map(&pp.source_map);
+  assert_eq!(
+    e.to_string(),
+    "lib.glsl:2: error: 'nope' : undeclared identifier\n\
+     \x20 \treturn nope;\n\
+     \x20 \t^\n"
+  );
+}
+
+#[cfg(target_os = "linux")]
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderError_from_gl() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let mut files = std::collections::HashMap::new();
+  files.insert("common.glsl", "vec4 tint() {\n  return vec4(nope);\n}");
+  let pp = ShaderPreprocessor::new(&files)
+    .process(
+      "main.frag",
+      "#version 450 core\n#include \"common.glsl\"\nout vec4 c;\n\
+       void main() { c = tint(); }",
+    )
+    .unwrap();
+  let e = gl
+    .create_compiled_shader(ShaderEnum::Fragment, &pp.source)
+    .map_err(|e| e.with_source_map(&pp.source_map))
+    .unwrap_err();
+  assert_eq!(e.stage, Some(ShaderEnum::Fragment));
+  // Mesa gives some errors source string 0 even after a `#line`, so only
+  // look for one that it gets right.
+  assert!(e.errors().any(|d| (d.file, d.line) == (Some(1), Some(2))));
+  let shown = e.to_string();
+  assert!(shown.contains("common.glsl:2:3: error:"), "{}", shown);
+  assert!(shown.contains("\n    return vec4(nope);\n    ^\n"), "{}", shown);
+
+  let v = gl
+    .create_compiled_shader(
+      ShaderEnum::Vertex,
+      "#version 450 core\nout vec4 q;\n\
+       void main() { gl_Position = vec4(0.0); q = vec4(1.0); }",
+    )
+    .unwrap();
+  let f = gl
+    .create_compiled_shader(
+      ShaderEnum::Fragment,
+      "#version 450 core\nin vec3 q;\nout vec4 c;\n\
+       void main() { c = vec4(q, 1.0); }",
+    )
+    .unwrap();
+  let e = gl.create_linked_program(&[v, f]).unwrap_err();
+  assert_eq!(e.stage, None);
+  assert!(e.errors().count() > 0, "{}", e.log);
+  gl.delete_shader(v);
+  gl.delete_shader(f);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
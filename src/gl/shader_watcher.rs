@@ -0,0 +1,349 @@
+use super::*;
+use core::cell::RefCell;
+use std::{fs, path::PathBuf, time::SystemTime};
+
+/// Identifies a program within a [`ShaderWatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WatchedProgramID(usize);
+
+/// Something that happened during [`ShaderWatcher::poll`].
+#[derive(Debug, Clone)]
+pub enum ShaderReloadEvent {
+  /// The program was rebuilt, and the old program was deleted.
+  ///
+  /// Uniform values don't carry over to the new program, so set them again.
+  Reloaded { id: WatchedProgramID, program: ProgramID },
+  /// Rebuilding failed, so the old program is still in use.
+  Failed { id: WatchedProgramID, error: ShaderError },
+}
+
+/// Files and their modified times.
+type FileTimes = Vec<(PathBuf, Option<SystemTime>)>;
+
+struct WatchedProgram {
+  stages: Vec<(ShaderEnum, String)>,
+  program: ProgramID,
+  /// Every file used (including `#include`d files) and its modified time
+  /// when it was last built.
+  files: FileTimes,
+}
+
+/// Rebuilds programs when their shader files change on disk.
+///
+/// Each frame, call [`poll`](ShaderWatcher::poll). This checks the modified
+/// time of every file used by each program (there's no OS file notification
+/// involved), and rebuilds any program with a changed file. The new program
+/// is only swapped in if it compiles and links, otherwise you get the
+/// diagnostics and the old program stays.
+///
+/// Files are read with a [`ShaderPreprocessor`], so they can `#include`
+/// other files from the same directory tree, and changes to those are also
+/// noticed.
+///
+/// ```no_run
+/// # use superbible7::*;
+/// # let gl: GlFnsRusty = todo!();
+/// let mut watcher = ShaderWatcher::new("shaders");
+/// let id = watcher
+///   .watch(&gl, &[
+///     (ShaderEnum::Vertex, "tri.vert"),
+///     (ShaderEnum::Fragment, "tri.frag"),
+///   ])
+///   .unwrap();
+/// // then each frame:
+/// for event in watcher.poll(&gl) {
+///   if let ShaderReloadEvent::Failed { error, .. } = event {
+///     eprintln!("{}", error);
+///   }
+/// }
+/// gl.use_program(watcher.program(id));
+/// ```
+pub struct ShaderWatcher {
+  includes: IncludeDir,
+  defines: Vec<(String, String)>,
+  programs: Vec<Option<WatchedProgram>>,
+}
+impl ShaderWatcher {
+  /// Makes a watcher for files within the `root` directory.
+  pub fn new(root: impl Into<PathBuf>) -> Self {
+    Self {
+      includes: IncludeDir(root.into()),
+      defines: Vec::new(),
+      programs: Vec::new(),
+    }
+  }
+
+  /// Adds a `#define` to every shader (see [`ShaderPreprocessor::define`]).
+  pub fn define(mut self, name: &str, value: &str) -> Self {
+    self.defines.push((String::from(name), String::from(value)));
+    self
+  }
+
+  /// Builds a program from shader files, and watches the files for changes.
+  ///
+  /// The paths are relative to the watcher's root directory.
+  pub fn watch(
+    &mut self, gl: &GlFnsRusty, stages: &[(ShaderEnum, &str)],
+  ) -> Result<WatchedProgramID, ShaderError> {
+    let stages: Vec<(ShaderEnum, String)> =
+      stages.iter().map(|(t, path)| (*t, String::from(*path))).collect();
+    let (result, files) = self.build(gl, &stages);
+    let program = result?;
+    self.programs.push(Some(WatchedProgram { stages, program, files }));
+    Ok(WatchedProgramID(self.programs.len() - 1))
+  }
+
+  /// The current program for an ID.
+  ///
+  /// ## Panics
+  /// * If the ID was already given to [`unwatch`](ShaderWatcher::unwatch).
+  pub fn program(&self, id: WatchedProgramID) -> ProgramID {
+    self.programs[id.0].as_ref().expect("program was unwatched").program
+  }
+
+  /// Stops watching a program, and deletes it.
+  pub fn unwatch(&mut self, gl: &GlFnsRusty, id: WatchedProgramID) {
+    if let Some(watched) = self.programs[id.0].take() {
+      gl.delete_program(watched.program);
+    }
+  }
+
+  /// Stops watching every program, and deletes them all.
+  pub fn unwatch_all(&mut self, gl: &GlFnsRusty) {
+    for watched in self.programs.drain(..).flatten() {
+      gl.delete_program(watched.program);
+    }
+  }
+
+  /// Rebuilds any program whose files have changed since it was last built.
+  pub fn poll(&mut self, gl: &GlFnsRusty) -> Vec<ShaderReloadEvent> {
+    let mut events = Vec::new();
+    for index in 0..self.programs.len() {
+      let (stages, changed) = match &self.programs[index] {
+        Some(watched) => (
+          watched.stages.clone(),
+          watched.files.iter().any(|(path, time)| modified_time(path) != *time),
+        ),
+        None => continue,
+      };
+      if !changed {
+        continue;
+      }
+      let id = WatchedProgramID(index);
+      let (result, files) = self.build(gl, &stages);
+      let watched = self.programs[index].as_mut().unwrap();
+      match result {
+        Ok(program) => {
+          gl.delete_program(watched.program);
+          watched.program = program;
+          watched.files = files;
+          events.push(ShaderReloadEvent::Reloaded { id, program });
+        }
+        Err(error) => {
+          // don't retry until a file changes again. The failed build might
+          // have used new files (eg: a new `#include` of a broken file), so
+          // those are watched too.
+          let mut paths: Vec<PathBuf> =
+            watched.files.drain(..).map(|(path, _)| path).collect();
+          for (path, _) in files {
+            if !paths.contains(&path) {
+              paths.push(path);
+            }
+          }
+          watched.files = paths
+            .into_iter()
+            .map(|path| {
+              let time = modified_time(&path);
+              (path, time)
+            })
+            .collect();
+          events.push(ShaderReloadEvent::Failed { id, error });
+        }
+      }
+    }
+    events
+  }
+
+  /// Builds a program, and gives the files that it used.
+  ///
+  /// The files are given even if the build fails, and include any files
+  /// that couldn't be read.
+  fn build(
+    &self, gl: &GlFnsRusty, stages: &[(ShaderEnum, String)],
+  ) -> (Result<ProgramID, ShaderError>, FileTimes) {
+    let includes = RecordingIncludes {
+      dir: &self.includes,
+      paths: RefCell::new(Vec::new()),
+    };
+    let mut shaders = Vec::with_capacity(stages.len());
+    let mut result = Ok(());
+    for (t, path) in stages.iter() {
+      let pp = self
+        .defines
+        .iter()
+        .fold(ShaderPreprocessor::new(&includes), |pp, (name, value)| {
+          pp.define(name, value)
+        });
+      let shader = pp
+        .process_file(path)
+        .map_err(|e| ShaderError::from(e).with_stage(*t))
+        .and_then(|out| {
+          gl.create_compiled_shader(*t, &out.source)
+            .map_err(|e| e.with_source_map(&out.source_map))
+        });
+      match shader {
+        Ok(shader) => shaders.push(shader),
+        Err(e) => {
+          result = Err(e);
+          break;
+        }
+      }
+    }
+    let result = result.and_then(|()| gl.create_linked_program(&shaders));
+    shaders.into_iter().for_each(|s| gl.delete_shader(s));
+    let files = includes
+      .paths
+      .into_inner()
+      .into_iter()
+      .map(|name| {
+        let path = self.includes.0.join(name);
+        let time = modified_time(&path);
+        (path, time)
+      })
+      .collect();
+    (result, files)
+  }
+}
+
+/// Reads from an [`IncludeDir`], and notes every path that's asked for (even
+/// if it can't be read).
+struct RecordingIncludes<'a> {
+  dir: &'a IncludeDir,
+  paths: RefCell<Vec<String>>,
+}
+impl IncludeSource for RecordingIncludes<'_> {
+  fn read_include(&self, path: &str) -> Result<String, String> {
+    let mut paths = self.paths.borrow_mut();
+    if !paths.iter().any(|p| p == path) {
+      paths.push(String::from(path));
+    }
+    self.dir.read_include(path)
+  }
+}
+
+/// The modified time of a file, or `None` if it can't be read.
+fn modified_time(path: &std::path::Path) -> Option<SystemTime> {
+  fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(target_os = "linux")]
+#[allow(non_snake_case)]
+#[test]
+fn test_ShaderWatcher_reloads() {
+  use std::time::Duration;
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let dir = std::env::temp_dir()
+    .join(format!("sb7-shader-watcher-{}", std::process::id()));
+  let _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(&dir).unwrap();
+  // sets the file's modified time explicitly, since file systems can have a
+  // coarse time resolution.
+  let mut tick = SystemTime::now();
+  let mut write = |name: &str, text: &str| {
+    let path = dir.join(name);
+    fs::write(&path, text).unwrap();
+    tick += Duration::from_secs(1);
+    fs::File::options()
+      .write(true)
+      .open(&path)
+      .unwrap()
+      .set_modified(tick)
+      .unwrap();
+  };
+  write(
+    "tri.vert",
+    "#version 450 core\nvoid main() { gl_Position = vec4(0.0); }",
+  );
+  write(
+    "tri.frag",
+    "#version 450 core\n#include \"color.glsl\"\nout vec4 c;\n\
+     void main() { c = COLOR; }",
+  );
+  write("color.glsl", "#define COLOR vec4(1.0)");
+
+  let mut watcher = ShaderWatcher::new(&dir).define("UNUSED", "1");
+  let id = watcher
+    .watch(
+      &gl,
+      &[(ShaderEnum::Vertex, "tri.vert"), (ShaderEnum::Fragment, "tri.frag")],
+    )
+    .unwrap();
+  let first = watcher.program(id);
+  assert!(watcher.poll(&gl).is_empty());
+
+  // a change to an included file is noticed.
+  write("color.glsl", "#define COLOR vec4(0.5)");
+  let events = watcher.poll(&gl);
+  let second = match events.as_slice() {
+    [ShaderReloadEvent::Reloaded { id: got, program }] => {
+      assert_eq!(*got, id);
+      *program
+    }
+    other => panic!("{:?}", other),
+  };
+  assert_ne!(first.0, second.0);
+  assert_eq!(watcher.program(id).0, second.0);
+  assert!(watcher.poll(&gl).is_empty());
+
+  // a broken file keeps the old program, and isn't retried until it changes.
+  write("color.glsl", "#define COLOR nope");
+  match watcher.poll(&gl).as_slice() {
+    [ShaderReloadEvent::Failed { error, .. }] => {
+      assert_eq!(error.stage, Some(ShaderEnum::Fragment));
+      assert!(error.to_string().contains("tri.frag:4:"), "{}", error);
+    }
+    other => panic!("{:?}", other),
+  }
+  assert_eq!(watcher.program(id).0, second.0);
+  assert!(watcher.poll(&gl).is_empty());
+
+  // a missing file is also an error.
+  fs::remove_file(dir.join("color.glsl")).unwrap();
+  match watcher.poll(&gl).as_slice() {
+    [ShaderReloadEvent::Failed { error, .. }] => {
+      assert!(error.to_string().contains("color.glsl"), "{}", error);
+    }
+    other => panic!("{:?}", other),
+  }
+  write("color.glsl", "#define COLOR vec4(0.25)");
+  assert!(matches!(
+    watcher.poll(&gl).as_slice(),
+    [ShaderReloadEvent::Reloaded { .. }]
+  ));
+
+  // a newly included file is watched even when the build with it fails.
+  write("extra.glsl", "#define EXTRA nope");
+  write(
+    "tri.frag",
+    "#version 450 core\n#include \"color.glsl\"\n#include \"extra.glsl\"\n\
+     out vec4 c;\nvoid main() { c = COLOR + EXTRA; }",
+  );
+  assert!(matches!(
+    watcher.poll(&gl).as_slice(),
+    [ShaderReloadEvent::Failed { .. }]
+  ));
+  assert!(watcher.poll(&gl).is_empty());
+  write("extra.glsl", "#define EXTRA vec4(0.0)");
+  assert!(matches!(
+    watcher.poll(&gl).as_slice(),
+    [ShaderReloadEvent::Reloaded { .. }]
+  ));
+
+  watcher.unwatch(&gl, id);
+  watcher.unwatch_all(&gl);
+  fs::remove_dir_all(&dir).unwrap();
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
@@ -0,0 +1,786 @@
+use super::*;
+use bytemuck::Pod;
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct TextureID(pub(crate) NonZeroU32);
+
+/// The kinds of texture object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum TextureTargetEnum {
+  Texture1D = GL_TEXTURE_1D.0,
+  Texture2D = GL_TEXTURE_2D.0,
+  Texture3D = GL_TEXTURE_3D.0,
+  Texture1DArray = GL_TEXTURE_1D_ARRAY.0,
+  Texture2DArray = GL_TEXTURE_2D_ARRAY.0,
+  Rectangle = GL_TEXTURE_RECTANGLE.0,
+  CubeMap = GL_TEXTURE_CUBE_MAP.0,
+  CubeMapArray = GL_TEXTURE_CUBE_MAP_ARRAY.0,
+  Texture2DMultisample = GL_TEXTURE_2D_MULTISAMPLE.0,
+  Texture2DMultisampleArray = GL_TEXTURE_2D_MULTISAMPLE_ARRAY.0,
+}
+impl TextureTargetEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+}
+
+/// Sized internal formats for texture storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InternalFormatEnum {
+  R8 = GL_R8.0,
+  RG8 = GL_RG8.0,
+  RGB8 = GL_RGB8.0,
+  RGBA8 = GL_RGBA8.0,
+  SRGB8 = GL_SRGB8.0,
+  SRGB8Alpha8 = GL_SRGB8_ALPHA8.0,
+  R16 = GL_R16.0,
+  RG16 = GL_RG16.0,
+  RGBA16 = GL_RGBA16.0,
+  R16F = GL_R16F.0,
+  RG16F = GL_RG16F.0,
+  RGB16F = GL_RGB16F.0,
+  RGBA16F = GL_RGBA16F.0,
+  R32F = GL_R32F.0,
+  RG32F = GL_RG32F.0,
+  RGB32F = GL_RGB32F.0,
+  RGBA32F = GL_RGBA32F.0,
+  R11FG11FB10F = GL_R11F_G11F_B10F.0,
+  R8UI = GL_R8UI.0,
+  RGBA8UI = GL_RGBA8UI.0,
+  R16UI = GL_R16UI.0,
+  R32UI = GL_R32UI.0,
+  RG32UI = GL_RG32UI.0,
+  RGBA32UI = GL_RGBA32UI.0,
+  R32I = GL_R32I.0,
+  RGBA32I = GL_RGBA32I.0,
+  DepthComponent16 = GL_DEPTH_COMPONENT16.0,
+  DepthComponent24 = GL_DEPTH_COMPONENT24.0,
+  DepthComponent32F = GL_DEPTH_COMPONENT32F.0,
+  Depth24Stencil8 = GL_DEPTH24_STENCIL8.0,
+  Depth32FStencil8 = GL_DEPTH32F_STENCIL8.0,
+}
+impl InternalFormatEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+
+  /// The variant for a GL enum value, if there is one.
+  pub fn from_enum(e: GLenum) -> Option<Self> {
+    use InternalFormatEnum::*;
+    [
+      R8,
+      RG8,
+      RGB8,
+      RGBA8,
+      SRGB8,
+      SRGB8Alpha8,
+      R16,
+      RG16,
+      RGBA16,
+      R16F,
+      RG16F,
+      RGB16F,
+      RGBA16F,
+      R32F,
+      RG32F,
+      RGB32F,
+      RGBA32F,
+      R11FG11FB10F,
+      R8UI,
+      RGBA8UI,
+      R16UI,
+      R32UI,
+      RG32UI,
+      RGBA32UI,
+      R32I,
+      RGBA32I,
+      DepthComponent16,
+      DepthComponent24,
+      DepthComponent32F,
+      Depth24Stencil8,
+      Depth32FStencil8,
+    ]
+    .iter()
+    .copied()
+    .find(|f| f.as_enum() == e)
+  }
+
+  /// If the format holds unnormalized integers (the `UI` and `I` formats).
+  pub fn is_integer(self) -> bool {
+    use InternalFormatEnum::*;
+    matches!(
+      self,
+      R8UI | RGBA8UI | R16UI | R32UI | RG32UI | RGBA32UI | R32I | RGBA32I
+    )
+  }
+
+  /// If the format is a depth format (with or without stencil).
+  pub fn is_depth(self) -> bool {
+    use InternalFormatEnum::*;
+    matches!(
+      self,
+      DepthComponent16
+        | DepthComponent24
+        | DepthComponent32F
+        | Depth24Stencil8
+        | Depth32FStencil8
+    )
+  }
+
+  /// If the format has stencil bits.
+  pub fn is_depth_stencil(self) -> bool {
+    use InternalFormatEnum::*;
+    matches!(self, Depth24Stencil8 | Depth32FStencil8)
+  }
+}
+
+/// Block compressed internal formats for texture storage.
+///
+/// These all use blocks of 4x4 texels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum CompressedFormatEnum {
+  RedRGTC1 = GL_COMPRESSED_RED_RGTC1.0,
+  SignedRedRGTC1 = GL_COMPRESSED_SIGNED_RED_RGTC1.0,
+  RGRGTC2 = GL_COMPRESSED_RG_RGTC2.0,
+  SignedRGRGTC2 = GL_COMPRESSED_SIGNED_RG_RGTC2.0,
+  RGBABPTCUnorm = GL_COMPRESSED_RGBA_BPTC_UNORM.0,
+  SRGBAlphaBPTCUnorm = GL_COMPRESSED_SRGB_ALPHA_BPTC_UNORM.0,
+  RGBBPTCSignedFloat = GL_COMPRESSED_RGB_BPTC_SIGNED_FLOAT.0,
+  RGBBPTCUnsignedFloat = GL_COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT.0,
+  RGB8ETC2 = GL_COMPRESSED_RGB8_ETC2.0,
+  SRGB8ETC2 = GL_COMPRESSED_SRGB8_ETC2.0,
+  RGB8PunchthroughAlpha1ETC2 = GL_COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2.0,
+  SRGB8PunchthroughAlpha1ETC2 = GL_COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2.0,
+  RGBA8ETC2EAC = GL_COMPRESSED_RGBA8_ETC2_EAC.0,
+  SRGB8Alpha8ETC2EAC = GL_COMPRESSED_SRGB8_ALPHA8_ETC2_EAC.0,
+  R11EAC = GL_COMPRESSED_R11_EAC.0,
+  SignedR11EAC = GL_COMPRESSED_SIGNED_R11_EAC.0,
+  RG11EAC = GL_COMPRESSED_RG11_EAC.0,
+  SignedRG11EAC = GL_COMPRESSED_SIGNED_RG11_EAC.0,
+}
+impl CompressedFormatEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+
+  /// The variant for a GL enum value, if there is one.
+  pub fn from_enum(e: GLenum) -> Option<Self> {
+    use CompressedFormatEnum::*;
+    [
+      RedRGTC1,
+      SignedRedRGTC1,
+      RGRGTC2,
+      SignedRGRGTC2,
+      RGBABPTCUnorm,
+      SRGBAlphaBPTCUnorm,
+      RGBBPTCSignedFloat,
+      RGBBPTCUnsignedFloat,
+      RGB8ETC2,
+      SRGB8ETC2,
+      RGB8PunchthroughAlpha1ETC2,
+      SRGB8PunchthroughAlpha1ETC2,
+      RGBA8ETC2EAC,
+      SRGB8Alpha8ETC2EAC,
+      R11EAC,
+      SignedR11EAC,
+      RG11EAC,
+      SignedRG11EAC,
+    ]
+    .iter()
+    .copied()
+    .find(|f| f.as_enum() == e)
+  }
+
+  /// The size of one 4x4 block, in bytes.
+  pub const fn block_size(self) -> u32 {
+    use CompressedFormatEnum::*;
+    match self {
+      RedRGTC1
+      | SignedRedRGTC1
+      | RGB8ETC2
+      | SRGB8ETC2
+      | RGB8PunchthroughAlpha1ETC2
+      | SRGB8PunchthroughAlpha1ETC2
+      | R11EAC
+      | SignedR11EAC => 8,
+      _ => 16,
+    }
+  }
+}
+
+/// A type that can be used as a pixel of client-side image data.
+///
+/// This is implemented for the scalar types GL understands, and for arrays
+/// of 1 to 4 of them (eg: `[u8; 4]` is one RGBA pixel).
+pub trait Pixel: Pod {
+  /// How many components (1 to 4) are in each pixel.
+  const COMPONENTS: u32;
+  /// The GL type of each component (eg: `GL_UNSIGNED_BYTE`).
+  const COMPONENT_TYPE: GLenum;
+}
+macro_rules! impl_pixel {
+  ($t:ty, $gl_type:expr) => {
+    impl Pixel for $t {
+      const COMPONENTS: u32 = 1;
+      const COMPONENT_TYPE: GLenum = $gl_type;
+    }
+    impl Pixel for [$t; 1] {
+      const COMPONENTS: u32 = 1;
+      const COMPONENT_TYPE: GLenum = $gl_type;
+    }
+    impl Pixel for [$t; 2] {
+      const COMPONENTS: u32 = 2;
+      const COMPONENT_TYPE: GLenum = $gl_type;
+    }
+    impl Pixel for [$t; 3] {
+      const COMPONENTS: u32 = 3;
+      const COMPONENT_TYPE: GLenum = $gl_type;
+    }
+    impl Pixel for [$t; 4] {
+      const COMPONENTS: u32 = 4;
+      const COMPONENT_TYPE: GLenum = $gl_type;
+    }
+  };
+}
+impl_pixel!(u8, GL_UNSIGNED_BYTE);
+impl_pixel!(i8, GL_BYTE);
+impl_pixel!(u16, GL_UNSIGNED_SHORT);
+impl_pixel!(i16, GL_SHORT);
+impl_pixel!(u32, GL_UNSIGNED_INT);
+impl_pixel!(i32, GL_INT);
+impl_pixel!(f32, GL_FLOAT);
+
+/// Picks the client pixel format to upload `P` data into a texture of the
+/// given internal format, checking that the combination is allowed.
+///
+/// * `pixel_count` is the number of pixels in the data.
+/// * `width`, `height`, and `depth` are the size of the region to upload to.
+pub(crate) fn pixel_upload_format<P: Pixel>(
+  internal_format: InternalFormatEnum, pixel_count: usize, width: i32,
+  height: i32, depth: i32,
+) -> Result<GLenum, String> {
+  if width < 0 || height < 0 || depth < 0 {
+    return Err(format!(
+      "Negative region size: {}x{}x{}",
+      width, height, depth
+    ));
+  }
+  let expected = (width as usize)
+    .checked_mul(height as usize)
+    .and_then(|wh| wh.checked_mul(depth as usize))
+    .ok_or_else(|| String::from("The region size overflows."))?;
+  if pixel_count != expected {
+    return Err(format!(
+      "A {}x{}x{} region needs {} pixels, but {} were given.",
+      width, height, depth, expected, pixel_count
+    ));
+  }
+  if internal_format.is_depth_stencil() {
+    return Err(format!("Can't upload pixels to {:?}.", internal_format));
+  }
+  if internal_format.is_depth() {
+    return if P::COMPONENTS == 1 {
+      Ok(GL_DEPTH_COMPONENT)
+    } else {
+      Err(format!(
+        "Depth data must have 1 component, but {} were given.",
+        P::COMPONENTS
+      ))
+    };
+  }
+  if internal_format.is_integer() {
+    if P::COMPONENT_TYPE == GL_FLOAT {
+      return Err(format!(
+        "Can't upload floating data to the integer format {:?}.",
+        internal_format
+      ));
+    }
+    Ok(match P::COMPONENTS {
+      1 => GL_RED_INTEGER,
+      2 => GL_RG_INTEGER,
+      3 => GL_RGB_INTEGER,
+      _ => GL_RGBA_INTEGER,
+    })
+  } else {
+    Ok(match P::COMPONENTS {
+      1 => GL_RED,
+      2 => GL_RG,
+      3 => GL_RGB,
+      _ => GL_RGBA,
+    })
+  }
+}
+
+/// Texture minification filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum MinFilterEnum {
+  Nearest = GL_NEAREST.0,
+  Linear = GL_LINEAR.0,
+  NearestMipmapNearest = GL_NEAREST_MIPMAP_NEAREST.0,
+  LinearMipmapNearest = GL_LINEAR_MIPMAP_NEAREST.0,
+  NearestMipmapLinear = GL_NEAREST_MIPMAP_LINEAR.0,
+  LinearMipmapLinear = GL_LINEAR_MIPMAP_LINEAR.0,
+}
+impl MinFilterEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+}
+
+/// Texture magnification filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum MagFilterEnum {
+  Nearest = GL_NEAREST.0,
+  Linear = GL_LINEAR.0,
+}
+impl MagFilterEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+}
+
+/// What happens to texture coordinates outside of `0.0 ..= 1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum WrapEnum {
+  Repeat = GL_REPEAT.0,
+  MirroredRepeat = GL_MIRRORED_REPEAT.0,
+  ClampToEdge = GL_CLAMP_TO_EDGE.0,
+  ClampToBorder = GL_CLAMP_TO_BORDER.0,
+  MirrorClampToEdge = GL_MIRROR_CLAMP_TO_EDGE.0,
+}
+impl WrapEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+}
+
+/// The texture coordinate axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum WrapAxisEnum {
+  S = GL_TEXTURE_WRAP_S.0,
+  T = GL_TEXTURE_WRAP_T.0,
+  R = GL_TEXTURE_WRAP_R.0,
+}
+impl WrapAxisEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+}
+
+impl GlFnsRusty {
+  /// Attempts to create a given number of texture objects of the target
+  /// type.
+  pub fn create_textures<const X: usize>(
+    &self, target: TextureTargetEnum,
+  ) -> [Option<TextureID>; X] {
+    let mut out = [None; X];
+    unsafe {
+      self.CreateTextures(
+        target.as_enum(),
+        X.try_into().unwrap(),
+        out.as_mut_ptr().cast(),
+      )
+    };
+    out
+  }
+
+  /// Deletes the given list of texture objects.
+  pub fn delete_textures<const X: usize>(
+    &self, textures: [Option<TextureID>; X],
+  ) {
+    unsafe {
+      self.DeleteTextures(X.try_into().unwrap(), textures.as_ptr().cast())
+    };
+  }
+
+  /// Gives immutable storage to a 2D texture, 1D array texture, rectangle
+  /// texture, or cube map.
+  ///
+  /// * `levels`: The number of mipmap levels (at least 1).
+  ///
+  /// See [glTextureStorage2D](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glTexStorage2D.xhtml)
+  pub fn texture_storage_2d(
+    &self, texture: TextureID, levels: i32,
+    internal_format: InternalFormatEnum, width: i32, height: i32,
+  ) {
+    unsafe {
+      self.TextureStorage2D(
+        texture.0.get(),
+        levels,
+        internal_format.as_enum(),
+        width,
+        height,
+      )
+    };
+  }
+
+  /// Gives immutable storage to a 3D texture, 2D array texture, or cube map
+  /// array (where `depth` is 6 times the number of cubes).
+  ///
+  /// * `levels`: The number of mipmap levels (at least 1).
+  ///
+  /// See [glTextureStorage3D](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glTexStorage3D.xhtml)
+  pub fn texture_storage_3d(
+    &self, texture: TextureID, levels: i32,
+    internal_format: InternalFormatEnum, width: i32, height: i32, depth: i32,
+  ) {
+    unsafe {
+      self.TextureStorage3D(
+        texture.0.get(),
+        levels,
+        internal_format.as_enum(),
+        width,
+        height,
+        depth,
+      )
+    };
+  }
+
+  /// Gives immutable storage to a cube map, with faces of `size` by `size`.
+  ///
+  /// * `levels`: The number of mipmap levels (at least 1).
+  pub fn texture_storage_cube(
+    &self, texture: TextureID, levels: i32,
+    internal_format: InternalFormatEnum, size: i32,
+  ) {
+    self.texture_storage_2d(texture, levels, internal_format, size, size)
+  }
+
+  /// The internal format of a texture's storage at a mipmap level (the
+  /// first face, for cube maps).
+  ///
+  /// See [glGetTextureLevelParameter](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetTexLevelParameter.xhtml)
+  ///
+  /// ## Failure
+  /// * If the format isn't one of the [`InternalFormatEnum`] formats (eg: a
+  ///   compressed format), you get `None`.
+  pub fn get_texture_internal_format(
+    &self, texture: TextureID, level: i32,
+  ) -> Option<InternalFormatEnum> {
+    let mut format = 0;
+    unsafe {
+      self.GetTextureLevelParameteriv(
+        texture.0.get(),
+        level,
+        GL_TEXTURE_INTERNAL_FORMAT,
+        &mut format,
+      )
+    };
+    InternalFormatEnum::from_enum(GLenum(format as u32))
+  }
+
+  /// Runs `f` with `GL_UNPACK_ALIGNMENT` at 1 (so that rows are tightly
+  /// packed), then puts the old alignment back.
+  pub(crate) fn with_unpack_alignment_1<R>(&self, f: impl FnOnce() -> R) -> R {
+    let mut old = 0;
+    unsafe {
+      self.GetIntegerv(GL_UNPACK_ALIGNMENT, &mut old);
+      self.PixelStorei(GL_UNPACK_ALIGNMENT, 1);
+    }
+    let out = f();
+    unsafe { self.PixelStorei(GL_UNPACK_ALIGNMENT, old) };
+    out
+  }
+
+  /// Replaces a 2D region of a texture's image data.
+  ///
+  /// The client pixel format that GL is told about is picked from the
+  /// format of the texture's storage, which is
+  /// [queried](Self::get_texture_internal_format) from GL.
+  ///
+  /// * `offset`: The `[x, y]` of the region's bottom left corner.
+  /// * `size`: The `[width, height]` of the region.
+  /// * `pixels`: Tightly packed rows of pixels, starting at the bottom left.
+  ///
+  /// See [glTextureSubImage2D](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glTexSubImage2D.xhtml)
+  ///
+  /// ## Failure
+  /// * If `pixels` isn't exactly `width * height` long, or the pixel type
+  ///   can't be uploaded to the texture's format, you get an error and the
+  ///   image isn't changed.
+  pub fn texture_sub_image_2d<P: Pixel>(
+    &self, texture: TextureID, level: i32, offset: [i32; 2], size: [i32; 2],
+    pixels: &[P],
+  ) -> Result<(), String> {
+    let [x, y] = offset;
+    let [width, height] = size;
+    let format = pixel_upload_format::<P>(
+      self.texture_upload_internal_format(texture, level)?,
+      pixels.len(),
+      width,
+      height,
+      1,
+    )?;
+    self.with_unpack_alignment_1(|| unsafe {
+      self.TextureSubImage2D(
+        texture.0.get(),
+        level,
+        x,
+        y,
+        width,
+        height,
+        format,
+        P::COMPONENT_TYPE,
+        pixels.as_ptr().cast(),
+      )
+    });
+    Ok(())
+  }
+
+  /// Replaces a 3D region of a texture's image data.
+  ///
+  /// This is also how you upload to array layers and to cube map faces, with
+  /// the `z` offset as the layer (or `layer * 6 + face` for cube map arrays).
+  ///
+  /// * `offset`: The `[x, y, z]` of the region's corner.
+  /// * `size`: The `[width, height, depth]` of the region.
+  ///
+  /// See [`texture_sub_image_2d`](Self::texture_sub_image_2d) and
+  /// [glTextureSubImage3D](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glTexSubImage3D.xhtml)
+  pub fn texture_sub_image_3d<P: Pixel>(
+    &self, texture: TextureID, level: i32, offset: [i32; 3], size: [i32; 3],
+    pixels: &[P],
+  ) -> Result<(), String> {
+    let [x, y, z] = offset;
+    let [width, height, depth] = size;
+    let format = pixel_upload_format::<P>(
+      self.texture_upload_internal_format(texture, level)?,
+      pixels.len(),
+      width,
+      height,
+      depth,
+    )?;
+    self.with_unpack_alignment_1(|| unsafe {
+      self.TextureSubImage3D(
+        texture.0.get(),
+        level,
+        x,
+        y,
+        z,
+        width,
+        height,
+        depth,
+        format,
+        P::COMPONENT_TYPE,
+        pixels.as_ptr().cast(),
+      )
+    });
+    Ok(())
+  }
+
+  fn texture_upload_internal_format(
+    &self, texture: TextureID, level: i32,
+  ) -> Result<InternalFormatEnum, String> {
+    self.get_texture_internal_format(texture, level).ok_or_else(|| {
+      format!(
+        "Texture {} level {} doesn't have a format that pixels can be uploaded to.",
+        texture.0, level
+      )
+    })
+  }
+
+  /// Replaces all of one face of a cube map at the given mipmap level.
+  ///
+  /// * `face`: 0 to 5, in the order +X, -X, +Y, -Y, +Z, -Z.
+  /// * `size`: The width (and height) of the face at this level.
+  ///
+  /// See [`texture_sub_image_2d`](Self::texture_sub_image_2d)
+  pub fn texture_cube_face_image<P: Pixel>(
+    &self, texture: TextureID, level: i32, face: i32, size: i32, pixels: &[P],
+  ) -> Result<(), String> {
+    if !(0..6).contains(&face) {
+      return Err(format!("Cube face index {} isn't 0 to 5.", face));
+    }
+    self.texture_sub_image_3d(
+      texture,
+      level,
+      [0, 0, face],
+      [size, size, 1],
+      pixels,
+    )
+  }
+
+  /// Generates all mipmap levels of a texture from its base level.
+  ///
+  /// See [glGenerateTextureMipmap](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGenerateMipmap.xhtml)
+  pub fn generate_texture_mipmap(&self, texture: TextureID) {
+    unsafe { self.GenerateTextureMipmap(texture.0.get()) }
+  }
+
+  /// Binds a texture to a texture unit (`Some`), or clears the unit's binding
+  /// (`None`).
+  ///
+  /// See [glBindTextureUnit](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBindTextureUnit.xhtml)
+  pub fn bind_texture_unit(&self, unit: u32, opt_texture: Option<TextureID>) {
+    unsafe {
+      self.BindTextureUnit(
+        unit,
+        core::mem::transmute::<Option<TextureID>, u32>(opt_texture),
+      )
+    }
+  }
+
+  /// Sets the minification filter of a texture.
+  pub fn texture_min_filter(&self, texture: TextureID, filter: MinFilterEnum) {
+    unsafe {
+      self.TextureParameteri(
+        texture.0.get(),
+        GL_TEXTURE_MIN_FILTER,
+        filter.as_enum().0 as i32,
+      )
+    }
+  }
+
+  /// Sets the magnification filter of a texture.
+  pub fn texture_mag_filter(&self, texture: TextureID, filter: MagFilterEnum) {
+    unsafe {
+      self.TextureParameteri(
+        texture.0.get(),
+        GL_TEXTURE_MAG_FILTER,
+        filter.as_enum().0 as i32,
+      )
+    }
+  }
+
+  /// Sets the wrap mode of a texture along one axis.
+  pub fn texture_wrap(
+    &self, texture: TextureID, axis: WrapAxisEnum, wrap: WrapEnum,
+  ) {
+    unsafe {
+      self.TextureParameteri(
+        texture.0.get(),
+        axis.as_enum(),
+        wrap.as_enum().0 as i32,
+      )
+    }
+  }
+
+  /// Sets the lowest and highest mipmap levels that a texture may use.
+  pub fn texture_level_range(
+    &self, texture: TextureID, base_level: i32, max_level: i32,
+  ) {
+    unsafe {
+      self.TextureParameteri(
+        texture.0.get(),
+        GL_TEXTURE_BASE_LEVEL,
+        base_level,
+      );
+      self.TextureParameteri(texture.0.get(), GL_TEXTURE_MAX_LEVEL, max_level);
+    }
+  }
+
+  /// Sets the border color used with [`WrapEnum::ClampToBorder`].
+  pub fn texture_border_color(&self, texture: TextureID, color: [f32; 4]) {
+    unsafe {
+      self.TextureParameterfv(
+        texture.0.get(),
+        GL_TEXTURE_BORDER_COLOR,
+        color.as_ptr(),
+      )
+    }
+  }
+}
+
+#[test]
+fn test_pixel_upload_format_validation() {
+  use InternalFormatEnum::*;
+  assert_eq!(pixel_upload_format::<[u8; 4]>(RGBA8, 6, 3, 2, 1), Ok(GL_RGBA));
+  assert_eq!(pixel_upload_format::<u8>(R8, 4, 2, 2, 1), Ok(GL_RED));
+  assert_eq!(pixel_upload_format::<[f32; 3]>(RGB32F, 8, 2, 2, 2), Ok(GL_RGB));
+  assert_eq!(
+    pixel_upload_format::<[u32; 2]>(RG32UI, 1, 1, 1, 1),
+    Ok(GL_RG_INTEGER)
+  );
+  assert_eq!(
+    pixel_upload_format::<f32>(DepthComponent32F, 1, 1, 1, 1),
+    Ok(GL_DEPTH_COMPONENT)
+  );
+  // wrong length
+  assert!(pixel_upload_format::<[u8; 4]>(RGBA8, 5, 3, 2, 1).is_err());
+  // negative size
+  assert!(pixel_upload_format::<[u8; 4]>(RGBA8, 0, -1, 0, 1).is_err());
+  // floats into an integer format
+  assert!(pixel_upload_format::<f32>(R32UI, 1, 1, 1, 1).is_err());
+  // multi-component depth
+  assert!(
+    pixel_upload_format::<[f32; 2]>(DepthComponent24, 1, 1, 1, 1).is_err()
+  );
+  // packed depth-stencil
+  assert!(pixel_upload_format::<u32>(Depth24Stencil8, 1, 1, 1, 1).is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_texture_upload_and_read_back() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let tex = gl.create_textures::<1>(TextureTargetEnum::Texture2D)[0].unwrap();
+  gl.texture_storage_2d(tex, 2, InternalFormatEnum::RGBA8, 2, 2);
+  let pixels: [[u8; 4]; 4] =
+    [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255], [9, 9, 9, 9]];
+  assert_eq!(
+    gl.get_texture_internal_format(tex, 0),
+    Some(InternalFormatEnum::RGBA8)
+  );
+  unsafe { gl.PixelStorei(GL_UNPACK_ALIGNMENT, 8) };
+  gl.texture_sub_image_2d(tex, 0, [0, 0], [2, 2], &pixels).unwrap();
+  assert!(gl
+    .texture_sub_image_2d(tex, 0, [0, 0], [2, 2], &pixels[..3])
+    .is_err());
+  // the old unpack alignment is put back.
+  let mut alignment = 0;
+  unsafe { gl.GetIntegerv(GL_UNPACK_ALIGNMENT, &mut alignment) };
+  assert_eq!(alignment, 8);
+  unsafe { gl.PixelStorei(GL_UNPACK_ALIGNMENT, 4) };
+  gl.generate_texture_mipmap(tex);
+  gl.texture_min_filter(tex, MinFilterEnum::LinearMipmapLinear);
+  gl.texture_mag_filter(tex, MagFilterEnum::Nearest);
+  gl.texture_wrap(tex, WrapAxisEnum::S, WrapEnum::ClampToEdge);
+  gl.texture_level_range(tex, 0, 1);
+  gl.texture_border_color(tex, [0.0; 4]);
+  gl.bind_texture_unit(0, Some(tex));
+  gl.bind_texture_unit(0, None);
+
+  let mut out = [[0_u8; 4]; 4];
+  unsafe {
+    gl.GetTextureImage(
+      tex.0.get(),
+      0,
+      GL_RGBA,
+      GL_UNSIGNED_BYTE,
+      core::mem::size_of_val(&out) as i32,
+      out.as_mut_ptr().cast(),
+    )
+  };
+  assert_eq!(out, pixels);
+
+  let cube = gl.create_textures::<1>(TextureTargetEnum::CubeMap)[0].unwrap();
+  gl.texture_storage_cube(cube, 1, InternalFormatEnum::R32F, 4);
+  for face in 0..6 {
+    gl.texture_cube_face_image(cube, 0, face, 4, &[1.0_f32; 16]).unwrap();
+  }
+  assert!(gl.texture_cube_face_image(cube, 0, 6, 4, &[1.0_f32; 16]).is_err());
+
+  // the storage's format is checked, so floats can't go to an integer format.
+  let int_tex =
+    gl.create_textures::<1>(TextureTargetEnum::Texture2D)[0].unwrap();
+  gl.texture_storage_2d(int_tex, 1, InternalFormatEnum::R32UI, 1, 1);
+  assert!(gl
+    .texture_sub_image_2d(int_tex, 0, [0, 0], [1, 1], &[1.0_f32])
+    .is_err());
+  gl.texture_sub_image_2d(int_tex, 0, [0, 0], [1, 1], &[1_u32]).unwrap();
+  gl.delete_textures([Some(int_tex)]);
+
+  gl.delete_textures([Some(tex), Some(cube)]);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
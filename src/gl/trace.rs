@@ -0,0 +1,2352 @@
+//! Recording GL calls to a trace file, and replaying them later.
+
+use super::*;
+use core::{
+  cell::{Cell, RefCell},
+  fmt::Write as _,
+  marker::PhantomData,
+};
+use std::{
+  collections::{HashMap, HashSet},
+  io::Write,
+  path::Path,
+};
+
+/// One argument (or return value) of a [`TraceCall`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceArg {
+  /// Any number: names, enums, sizes, offsets, and floats.
+  Num(f64),
+  /// The text of a string argument (eg: a shader source).
+  Str(String),
+  /// The values of an array argument (eg: uniform values or created names).
+  Nums(Vec<f64>),
+  /// A data payload, by the hash of its bytes. The bytes are in
+  /// [`Trace::blobs`].
+  Blob(u64),
+  /// A null pointer.
+  Null,
+}
+impl From<u32> for TraceArg {
+  fn from(x: u32) -> Self {
+    Self::Num(x.into())
+  }
+}
+impl From<i32> for TraceArg {
+  fn from(x: i32) -> Self {
+    Self::Num(x.into())
+  }
+}
+impl From<u8> for TraceArg {
+  fn from(x: u8) -> Self {
+    Self::Num(x.into())
+  }
+}
+impl From<f32> for TraceArg {
+  fn from(x: f32) -> Self {
+    Self::Num(x.into())
+  }
+}
+impl From<isize> for TraceArg {
+  fn from(x: isize) -> Self {
+    Self::Num(x as f64)
+  }
+}
+impl From<GLenum> for TraceArg {
+  fn from(x: GLenum) -> Self {
+    Self::Num(x.0.into())
+  }
+}
+impl From<GLbitfield> for TraceArg {
+  fn from(x: GLbitfield) -> Self {
+    Self::Num(x.0.into())
+  }
+}
+
+/// A GL call in a [`Trace`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceCall {
+  /// The GL name of the function, eg: `glCreateShader`.
+  pub func: String,
+  pub args: Vec<TraceArg>,
+  /// What the function returned (or the names it created), if anything.
+  pub ret: Option<TraceArg>,
+}
+impl TraceCall {
+  fn arg(&self, i: usize) -> Result<&TraceArg, String> {
+    self
+      .args
+      .get(i)
+      .ok_or_else(|| format!("{} is missing argument {}.", self.func, i))
+  }
+
+  fn num(&self, i: usize) -> Result<f64, String> {
+    match self.arg(i)? {
+      TraceArg::Num(x) => Ok(*x),
+      // the non-finite floats are written as strings.
+      TraceArg::Str(s) => s.parse().map_err(|_| self.bad_arg(i)),
+      _ => Err(self.bad_arg(i)),
+    }
+  }
+
+  fn int(&self, i: usize) -> Result<i64, String> {
+    let x = self.num(i)?;
+    if x.fract() == 0.0 && x.abs() < 2.0_f64.powi(53) {
+      Ok(x as i64)
+    } else {
+      Err(self.bad_arg(i))
+    }
+  }
+
+  fn u32(&self, i: usize) -> Result<u32, String> {
+    self.int(i)?.try_into().map_err(|_| self.bad_arg(i))
+  }
+
+  fn i32(&self, i: usize) -> Result<i32, String> {
+    self.int(i)?.try_into().map_err(|_| self.bad_arg(i))
+  }
+
+  fn isize(&self, i: usize) -> Result<isize, String> {
+    self.int(i)?.try_into().map_err(|_| self.bad_arg(i))
+  }
+
+  fn u8(&self, i: usize) -> Result<u8, String> {
+    self.int(i)?.try_into().map_err(|_| self.bad_arg(i))
+  }
+
+  fn f32(&self, i: usize) -> Result<f32, String> {
+    self.num(i).map(|x| x as f32)
+  }
+
+  fn enum_(&self, i: usize) -> Result<GLenum, String> {
+    self.u32(i).map(GLenum)
+  }
+
+  fn str(&self, i: usize) -> Result<&str, String> {
+    match self.arg(i)? {
+      TraceArg::Str(s) => Ok(s),
+      _ => Err(self.bad_arg(i)),
+    }
+  }
+
+  fn nums(&self, i: usize) -> Result<&[f64], String> {
+    match self.arg(i)? {
+      TraceArg::Nums(v) => Ok(v),
+      _ => Err(self.bad_arg(i)),
+    }
+  }
+
+  fn ret_names(&self) -> Result<&[f64], String> {
+    match &self.ret {
+      Some(TraceArg::Nums(v)) => Ok(v),
+      _ => Err(format!("{} has no created names.", self.func)),
+    }
+  }
+
+  fn ret_int(&self) -> Result<i64, String> {
+    match self.ret {
+      Some(TraceArg::Num(x)) if x.fract() == 0.0 => Ok(x as i64),
+      _ => Err(format!("{} has no return value.", self.func)),
+    }
+  }
+
+  fn bad_arg(&self, i: usize) -> String {
+    format!("{} has a bad argument {}: {:?}", self.func, i, self.args.get(i))
+  }
+}
+
+/// Something that happened while tracing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceEvent {
+  Call(TraceCall),
+  /// The end of a frame (the buffers were swapped).
+  EndFrame,
+}
+
+/// A recorded trace, see [`GlTracer`].
+///
+/// ## File Format
+/// The file is [JSON Lines](https://jsonlines.org/), with one of these per
+/// line:
+/// * A call: `{"call":"glCreateShader","args":[35633],"ret":1}`
+/// * The end of frame 0: `{"end_frame":0}`
+/// * A data payload: `{"blob":"<hash>","data":"<hex bytes>"}`
+/// * A function that isn't traced, at its first call:
+///   `{"untraced":"glBindTexture"}`
+///
+/// Args and return values are numbers, strings, arrays of numbers, `null`,
+/// or a payload as `{"blob":"<hash>"}`. Each payload is written once, before
+/// the first call that uses it, and the hash is the 64-bit FNV-1a of the
+/// bytes (in hex).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trace {
+  pub events: Vec<TraceEvent>,
+  /// Data payloads, by hash.
+  pub blobs: HashMap<u64, Vec<u8>>,
+  /// The functions that were called while tracing but aren't traced, in the
+  /// order they were first called.
+  ///
+  /// If there are any then the trace is incomplete, and a replay probably
+  /// won't match what was traced.
+  pub untraced: Vec<String>,
+}
+impl Trace {
+  /// Loads a trace file.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+      .map_err(|e| format!("Couldn't read `{}`: {}", path.display(), e))?;
+    Self::parse(&text)
+  }
+
+  /// Parses the text of a trace file.
+  pub fn parse(text: &str) -> Result<Self, String> {
+    let mut trace = Self::default();
+    for (i, line) in text.lines().enumerate() {
+      if line.trim().is_empty() {
+        continue;
+      }
+      trace.parse_line(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+    }
+    Ok(trace)
+  }
+
+  fn parse_line(&mut self, line: &str) -> Result<(), String> {
+    let fields = match JsonParser::parse(line)? {
+      Json::Obj(fields) => fields,
+      _ => return Err(String::from("not a JSON object")),
+    };
+    let field =
+      |name: &str| fields.iter().find(|(k, _)| k == name).map(|f| &f.1);
+    if let Some(func) = field("call") {
+      let func = match func {
+        Json::Str(s) => s.clone(),
+        _ => return Err(String::from("`call` isn't a string")),
+      };
+      let args = match field("args") {
+        Some(Json::Arr(args)) => {
+          args.iter().map(Json::to_trace_arg).collect::<Result<_, _>>()?
+        }
+        None => Vec::new(),
+        _ => return Err(String::from("`args` isn't an array")),
+      };
+      let ret = field("ret").map(Json::to_trace_arg).transpose()?;
+      self.events.push(TraceEvent::Call(TraceCall { func, args, ret }));
+    } else if field("end_frame").is_some() {
+      self.events.push(TraceEvent::EndFrame);
+    } else if let Some(func) = field("untraced") {
+      match func {
+        Json::Str(s) if !self.untraced.contains(s) => {
+          self.untraced.push(s.clone())
+        }
+        Json::Str(_) => (),
+        _ => return Err(String::from("`untraced` isn't a string")),
+      }
+    } else if let (Some(Json::Str(hash)), Some(Json::Str(data))) =
+      (field("blob"), field("data"))
+    {
+      let hash = parse_hash(hash)?;
+      let data = hex_decode(data)?;
+      if fnv1a_64(&data) != hash {
+        return Err(format!("blob {:016x} doesn't match its hash", hash));
+      }
+      self.blobs.insert(hash, data);
+    } else {
+      return Err(String::from("unknown line"));
+    }
+    Ok(())
+  }
+
+  /// The number of frames in the trace.
+  pub fn frame_count(&self) -> usize {
+    self.events.iter().filter(|e| **e == TraceEvent::EndFrame).count()
+  }
+
+  /// Checks that no [untraced](Trace::untraced) functions were called.
+  ///
+  /// ## Failure
+  /// * The names of the untraced functions.
+  pub fn check_complete(&self) -> Result<(), String> {
+    if self.untraced.is_empty() {
+      Ok(())
+    } else {
+      Err(format!(
+        "The trace is incomplete, these calls weren't traced: {}",
+        self.untraced.join(", ")
+      ))
+    }
+  }
+}
+
+fn parse_hash(s: &str) -> Result<u64, String> {
+  u64::from_str_radix(s, 16).map_err(|_| format!("bad blob hash `{}`", s))
+}
+
+fn hex_decode(s: &str) -> Result<Vec<u8>, String> {
+  if !s.len().is_multiple_of(2) {
+    return Err(String::from("blob data has an odd number of hex digits"));
+  }
+  (0..s.len())
+    .step_by(2)
+    .map(|i| {
+      s.get(i..i + 2)
+        .and_then(|d| u8::from_str_radix(d, 16).ok())
+        .ok_or_else(|| String::from("blob data isn't hex"))
+    })
+    .collect()
+}
+
+fn write_json_str(out: &mut String, s: &str) {
+  out.push('"');
+  for c in s.chars() {
+    match c {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      '\r' => out.push_str("\\r"),
+      '\t' => out.push_str("\\t"),
+      c if u32::from(c) < 0x20 => {
+        write!(out, "\\u{:04x}", u32::from(c)).unwrap()
+      }
+      c => out.push(c),
+    }
+  }
+  out.push('"');
+}
+
+fn write_json_num(out: &mut String, x: f64) {
+  if x.is_finite() {
+    write!(out, "{}", x).unwrap()
+  } else {
+    // JSON has no way to write these, so they're strings.
+    write_json_str(out, &x.to_string())
+  }
+}
+
+fn write_trace_arg(out: &mut String, arg: &TraceArg) {
+  match arg {
+    TraceArg::Num(x) => write_json_num(out, *x),
+    TraceArg::Str(s) => write_json_str(out, s),
+    TraceArg::Nums(v) => {
+      out.push('[');
+      for (i, x) in v.iter().enumerate() {
+        if i > 0 {
+          out.push(',');
+        }
+        write_json_num(out, *x);
+      }
+      out.push(']');
+    }
+    TraceArg::Blob(hash) => {
+      write!(out, "{{\"blob\":\"{:016x}\"}}", hash).unwrap()
+    }
+    TraceArg::Null => out.push_str("null"),
+  }
+}
+
+/// Just enough JSON for trace files.
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+  Null,
+  Bool(bool),
+  Num(f64),
+  Str(String),
+  Arr(Vec<Json>),
+  Obj(Vec<(String, Json)>),
+}
+impl Json {
+  fn to_trace_arg(&self) -> Result<TraceArg, String> {
+    Ok(match self {
+      Json::Null => TraceArg::Null,
+      Json::Num(x) => TraceArg::Num(*x),
+      Json::Str(s) => TraceArg::Str(s.clone()),
+      Json::Arr(v) => TraceArg::Nums(
+        v.iter()
+          .map(|x| match x {
+            Json::Num(x) => Ok(*x),
+            Json::Str(s) => {
+              s.parse().map_err(|_| format!("bad number `{}`", s))
+            }
+            _ => Err(String::from("arrays can only hold numbers")),
+          })
+          .collect::<Result<_, _>>()?,
+      ),
+      Json::Obj(fields) => match fields.as_slice() {
+        [(k, Json::Str(hash))] if k == "blob" => {
+          TraceArg::Blob(parse_hash(hash)?)
+        }
+        _ => return Err(String::from("unknown object argument")),
+      },
+      Json::Bool(_) => return Err(String::from("unexpected boolean")),
+    })
+  }
+}
+
+struct JsonParser<'a> {
+  bytes: &'a [u8],
+  pos: usize,
+}
+impl<'a> JsonParser<'a> {
+  fn parse(text: &'a str) -> Result<Json, String> {
+    let mut p = Self { bytes: text.as_bytes(), pos: 0 };
+    let value = p.value()?;
+    p.skip_whitespace();
+    if p.pos != p.bytes.len() {
+      return Err(format!("extra text at column {}", p.pos + 1));
+    }
+    Ok(value)
+  }
+
+  fn skip_whitespace(&mut self) {
+    while matches!(self.bytes.get(self.pos), Some(b' ' | b'\t' | b'\r' | b'\n'))
+    {
+      self.pos += 1;
+    }
+  }
+
+  fn error(&self) -> String {
+    format!("bad JSON at column {}", self.pos + 1)
+  }
+
+  fn expect(&mut self, b: u8) -> Result<(), String> {
+    self.skip_whitespace();
+    if self.bytes.get(self.pos) == Some(&b) {
+      self.pos += 1;
+      Ok(())
+    } else {
+      Err(self.error())
+    }
+  }
+
+  fn keyword(&mut self, word: &str, value: Json) -> Result<Json, String> {
+    if self.bytes[self.pos..].starts_with(word.as_bytes()) {
+      self.pos += word.len();
+      Ok(value)
+    } else {
+      Err(self.error())
+    }
+  }
+
+  fn value(&mut self) -> Result<Json, String> {
+    self.skip_whitespace();
+    match self.bytes.get(self.pos) {
+      Some(b'{') => self.object(),
+      Some(b'[') => self.array(),
+      Some(b'"') => self.string().map(Json::Str),
+      Some(b'n') => self.keyword("null", Json::Null),
+      Some(b't') => self.keyword("true", Json::Bool(true)),
+      Some(b'f') => self.keyword("false", Json::Bool(false)),
+      Some(b'-' | b'0'..=b'9') => self.number(),
+      _ => Err(self.error()),
+    }
+  }
+
+  fn object(&mut self) -> Result<Json, String> {
+    self.expect(b'{')?;
+    let mut fields = Vec::new();
+    self.skip_whitespace();
+    if self.bytes.get(self.pos) == Some(&b'}') {
+      self.pos += 1;
+      return Ok(Json::Obj(fields));
+    }
+    loop {
+      self.skip_whitespace();
+      let key = self.string()?;
+      self.expect(b':')?;
+      fields.push((key, self.value()?));
+      self.skip_whitespace();
+      match self.bytes.get(self.pos) {
+        Some(b',') => self.pos += 1,
+        Some(b'}') => {
+          self.pos += 1;
+          return Ok(Json::Obj(fields));
+        }
+        _ => return Err(self.error()),
+      }
+    }
+  }
+
+  fn array(&mut self) -> Result<Json, String> {
+    self.expect(b'[')?;
+    let mut values = Vec::new();
+    self.skip_whitespace();
+    if self.bytes.get(self.pos) == Some(&b']') {
+      self.pos += 1;
+      return Ok(Json::Arr(values));
+    }
+    loop {
+      values.push(self.value()?);
+      self.skip_whitespace();
+      match self.bytes.get(self.pos) {
+        Some(b',') => self.pos += 1,
+        Some(b']') => {
+          self.pos += 1;
+          return Ok(Json::Arr(values));
+        }
+        _ => return Err(self.error()),
+      }
+    }
+  }
+
+  fn number(&mut self) -> Result<Json, String> {
+    let start = self.pos;
+    while matches!(
+      self.bytes.get(self.pos),
+      Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+    ) {
+      self.pos += 1;
+    }
+    core::str::from_utf8(&self.bytes[start..self.pos])
+      .ok()
+      .and_then(|s| s.parse().ok())
+      .map(Json::Num)
+      .ok_or_else(|| format!("bad number at column {}", start + 1))
+  }
+
+  fn hex4(&mut self) -> Result<u16, String> {
+    let digits =
+      self.bytes.get(self.pos..self.pos + 4).ok_or_else(|| self.error())?;
+    let digits = core::str::from_utf8(digits).map_err(|_| self.error())?;
+    let x = u16::from_str_radix(digits, 16).map_err(|_| self.error())?;
+    self.pos += 4;
+    Ok(x)
+  }
+
+  fn string(&mut self) -> Result<String, String> {
+    self.expect(b'"')?;
+    let mut out = Vec::new();
+    loop {
+      let b = *self.bytes.get(self.pos).ok_or_else(|| self.error())?;
+      self.pos += 1;
+      match b {
+        b'"' => break,
+        b'\\' => {
+          let escape = *self.bytes.get(self.pos).ok_or_else(|| self.error())?;
+          self.pos += 1;
+          let c = match escape {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+              let mut units = vec![self.hex4()?];
+              // a surrogate pair is two escapes in a row.
+              if (0xD800..0xDC00).contains(&units[0])
+                && self.bytes[self.pos..].starts_with(b"\\u")
+              {
+                self.pos += 2;
+                units.push(self.hex4()?);
+              }
+              char::decode_utf16(units)
+                .next()
+                .and_then(Result::ok)
+                .ok_or_else(|| self.error())?
+            }
+            _ => return Err(self.error()),
+          };
+          out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        b => out.push(b),
+      }
+    }
+    String::from_utf8(out).map_err(|_| self.error())
+  }
+}
+
+struct TracerState {
+  /// Which [`GlTracer`] owns this state.
+  generation: u64,
+  gl: GlFns,
+  out: Box<dyn Write>,
+  blobs_written: HashSet<u64>,
+  untraced_written: HashSet<&'static str>,
+  frame: u64,
+  /// The first write error, which is reported by `end_frame` or `finish`.
+  error: Option<std::io::Error>,
+}
+impl TracerState {
+  fn write_line(&mut self, line: &str) {
+    if self.error.is_none() {
+      if let Err(e) = writeln!(self.out, "{}", line) {
+        self.error = Some(e);
+      }
+    }
+  }
+
+  fn take_error(&mut self) -> Result<(), String> {
+    match self.error.take() {
+      Some(e) => Err(format!("Couldn't write the GL trace: {}", e)),
+      None => Ok(()),
+    }
+  }
+}
+
+thread_local! {
+  static TRACER_STATE: RefCell<Option<TracerState>> = const { RefCell::new(None) };
+  static TRACER_GENERATION: Cell<u64> = const { Cell::new(0) };
+}
+
+fn with_tracer<R>(f: impl FnOnce(&mut TracerState) -> R) -> R {
+  TRACER_STATE.with(|cell| {
+    f(cell.borrow_mut().as_mut().expect("no GlTracer is active on this thread"))
+  })
+}
+
+/// Records GL calls into a trace, for replaying later with a
+/// [`TraceReplayer`].
+///
+/// [`GlTracer::load`] gives a [`GlFnsRusty`] that works as normal, except
+/// that each traced function also writes the call to the trace (see
+/// [`Trace`] for the format). Mark the end of each frame with
+/// [`end_frame`](GlTracer::end_frame) after the buffers are swapped (the
+/// [runner](run_with_context) does this when [`AppConfig::trace_path`] is
+/// set).
+///
+/// The traced functions are the ones that the samples use to change state:
+/// shaders and programs, buffers (with their data), vertex arrays and
+/// attributes, uniforms set with `glProgramUniform*v`, clearing, drawing
+/// (with indices from an element buffer), a bit of fixed function state, and
+/// the debug message callback. Uniform locations, from
+/// `glGetUniformLocation` or from
+/// [reflection](GlFnsRusty::get_program_reflection), are also recorded so
+/// that a replay can map them. Other queries (`glGet*`, `glIs*`, and
+/// `glCheck*Status`) go straight to the driver.
+///
+/// Every other function (textures, samplers, framebuffers, pipelines,
+/// program binaries, SPIR-V shaders, maps that can write to a buffer, draws
+/// with indices in client memory, etc) isn't traced, but the first call of
+/// each is written to the trace as [untraced](Trace::untraced), so that the
+/// trace is known to be incomplete.
+///
+/// The tracer is per-thread, like the context. The GL functions must only be
+/// used on the thread that made the tracer, and only while it's alive.
+pub struct GlTracer {
+  generation: u64,
+  // the state is thread local.
+  _not_send: PhantomData<*const ()>,
+}
+impl GlTracer {
+  /// Loads the GL functions of a context, with tracing into `out`.
+  ///
+  /// Any other tracer on this thread is replaced: the functions that it
+  /// loaded then trace into the new trace, its
+  /// [`end_frame`](GlTracer::end_frame) and [`finish`](GlTracer::finish)
+  /// give an error, and dropping it does nothing.
+  ///
+  /// ## Failure
+  /// * The name of the first GL function that couldn't be loaded.
+  pub fn load(
+    ctx: &impl GlContext, out: impl Write + 'static,
+  ) -> Result<(Self, GlFnsRusty), &'static str> {
+    unsafe {
+      Self::load_from(
+        &|name_ptr| {
+          let name = std::ffi::CStr::from_ptr(name_ptr.cast());
+          ctx
+            .get_proc_address(name.to_bytes_with_nul())
+            .map(|nn| nn.as_ptr() as *const c_void)
+            .unwrap_or(core::ptr::null())
+        },
+        out,
+      )
+    }
+  }
+
+  /// As [`load`](GlTracer::load), but with a loader function like
+  /// [`GlFns::load_from`] uses.
+  ///
+  /// ## Safety
+  /// * The loader must give correct function pointers (or null).
+  pub unsafe fn load_from(
+    loader: &dyn Fn(*const u8) -> *const c_void, out: impl Write + 'static,
+  ) -> Result<(Self, GlFnsRusty), &'static str> {
+    let real = GlFns::load_from(loader)?;
+    let generation = TRACER_GENERATION.with(|g| {
+      g.set(g.get() + 1);
+      g.get()
+    });
+    TRACER_STATE.with(|cell| {
+      *cell.borrow_mut() = Some(TracerState {
+        generation,
+        gl: real,
+        out: Box::new(out),
+        blobs_written: HashSet::new(),
+        untraced_written: HashSet::new(),
+        frame: 0,
+        error: None,
+      })
+    });
+    let traced = GlFns::load_from(&|name_ptr| {
+      let name = std::ffi::CStr::from_ptr(name_ptr.cast());
+      let f = loader(name_ptr);
+      if f.is_null() {
+        return f;
+      }
+      traced_shim(name.to_bytes()).unwrap_or(f)
+    })?;
+    Ok((Self { generation, _not_send: PhantomData }, GlFnsRusty(traced)))
+  }
+
+  fn state(
+    &self, f: impl FnOnce(&mut TracerState) -> Result<(), String>,
+  ) -> Result<(), String> {
+    TRACER_STATE.with(|cell| match cell.borrow_mut().as_mut() {
+      Some(s) if s.generation == self.generation => f(s),
+      _ => Err(String::from("This GlTracer was replaced by another.")),
+    })
+  }
+
+  /// Marks the end of a frame, and flushes the trace.
+  ///
+  /// ## Failure
+  /// * If writing the trace failed (at any point since the last check).
+  /// * If this tracer was replaced by another.
+  pub fn end_frame(&self) -> Result<(), String> {
+    self.state(|s| {
+      let line = format!("{{\"end_frame\":{}}}", s.frame);
+      s.frame += 1;
+      s.write_line(&line);
+      if let Err(e) = s.out.flush() {
+        s.error.get_or_insert(e);
+      }
+      s.take_error()
+    })
+  }
+
+  /// Flushes the trace, and checks that all of it was written.
+  ///
+  /// ## Failure
+  /// * If writing the trace failed.
+  /// * If this tracer was replaced by another.
+  pub fn finish(self) -> Result<(), String> {
+    self.state(|s| {
+      if let Err(e) = s.out.flush() {
+        s.error.get_or_insert(e);
+      }
+      s.take_error()
+    })
+  }
+}
+impl Drop for GlTracer {
+  fn drop(&mut self) {
+    TRACER_STATE.with(|cell| {
+      let mut state = cell.borrow_mut();
+      if state.as_ref().map(|s| s.generation) == Some(self.generation) {
+        *state = None;
+      }
+    });
+  }
+}
+
+/// Calls the real GL functions.
+///
+/// Don't record anything within `f`, the tracer is borrowed.
+fn with_real<R>(f: impl FnOnce(&GlFns) -> R) -> R {
+  TRACER_STATE.with(|cell| {
+    let state = cell.borrow();
+    f(&state.as_ref().expect("no GlTracer is active on this thread").gl)
+  })
+}
+
+fn record(func: &str, args: Vec<TraceArg>, ret: Option<TraceArg>) {
+  let mut line = String::from("{\"call\":");
+  write_json_str(&mut line, func);
+  line.push_str(",\"args\":[");
+  for (i, arg) in args.iter().enumerate() {
+    if i > 0 {
+      line.push(',');
+    }
+    write_trace_arg(&mut line, arg);
+  }
+  line.push(']');
+  if let Some(ret) = ret {
+    line.push_str(",\"ret\":");
+    write_trace_arg(&mut line, &ret);
+  }
+  line.push('}');
+  with_tracer(|s| s.write_line(&line));
+}
+
+/// Records that a function which isn't traced was called (if it's new).
+fn record_untraced(func: &'static str) {
+  with_tracer(|s| {
+    if s.untraced_written.insert(func) {
+      let mut line = String::from("{\"untraced\":");
+      write_json_str(&mut line, func);
+      line.push('}');
+      s.write_line(&line);
+    }
+  });
+}
+
+/// Records a data payload (if it's new), and gives the argument for it.
+unsafe fn blob_arg(data: *const c_void, size: isize) -> TraceArg {
+  if data.is_null() || size <= 0 {
+    return TraceArg::Null;
+  }
+  let bytes = core::slice::from_raw_parts(data.cast::<u8>(), size as usize);
+  let hash = fnv1a_64(bytes);
+  with_tracer(|s| {
+    if s.blobs_written.insert(hash) {
+      let mut line = format!("{{\"blob\":\"{:016x}\",\"data\":\"", hash);
+      for b in bytes {
+        write!(line, "{:02x}", b).unwrap();
+      }
+      line.push_str("\"}");
+      s.write_line(&line);
+    }
+  });
+  TraceArg::Blob(hash)
+}
+
+unsafe fn slice_arg<T: Copy + Into<f64>>(p: *const T, n: usize) -> TraceArg {
+  TraceArg::Nums(
+    core::slice::from_raw_parts(p, n).iter().map(|&x| x.into()).collect(),
+  )
+}
+
+/// Joins the strings given to `glShaderSource` (and similar).
+unsafe fn strings_arg(
+  count: i32, strings: *const *const u8, lengths: *const i32,
+) -> TraceArg {
+  let mut v = Vec::new();
+  for i in 0..count.max(0) as usize {
+    let p = *strings.add(i);
+    let len = if lengths.is_null() { -1 } else { *lengths.add(i) };
+    if len < 0 {
+      v.extend(gather_null_terminated_bytes(p));
+    } else {
+      v.extend_from_slice(core::slice::from_raw_parts(p, len as usize));
+    }
+  }
+  TraceArg::Str(min_alloc_lossy_into_string(v))
+}
+
+macro_rules! traced_uniforms {
+  ($(fn $func:ident($t:ty, $n:literal);)*) => {
+    fn uniform_shim(name: &[u8]) -> Option<*const c_void> {
+      $(
+        if name == concat!("gl", stringify!($func)).as_bytes() {
+          unsafe extern "system" fn shim(
+            program: u32, location: i32, count: i32, value: *const $t,
+          ) {
+            with_real(|gl| gl.$func(program, location, count, value));
+            record(
+              concat!("gl", stringify!($func)),
+              vec![
+                program.into(),
+                location.into(),
+                count.into(),
+                slice_arg(value, count.max(0) as usize * $n),
+              ],
+              None,
+            );
+          }
+          return Some(shim as *const c_void);
+        }
+      )*
+      None
+    }
+
+    /// Replays a `glProgramUniform*v` call, if that's what the call is.
+    unsafe fn replay_uniform(
+      gl: &GlFns, func: &str, program: u32, location: i32, count: i32,
+      values: &[f64],
+    ) -> Option<Result<(), String>> {
+      $(
+        if func == concat!("gl", stringify!($func)) {
+          if values.len() != count.max(0) as usize * $n {
+            return Some(Err(format!("{} has the wrong number of values.", func)));
+          }
+          let v: Vec<$t> = values.iter().map(|&x| x as $t).collect();
+          gl.$func(program, location, count, v.as_ptr());
+          return Some(Ok(()));
+        }
+      )*
+      None
+    }
+  };
+}
+
+traced_uniforms! {
+  fn ProgramUniform1fv(f32, 1);
+  fn ProgramUniform2fv(f32, 2);
+  fn ProgramUniform3fv(f32, 3);
+  fn ProgramUniform4fv(f32, 4);
+  fn ProgramUniform1iv(i32, 1);
+  fn ProgramUniform2iv(i32, 2);
+  fn ProgramUniform3iv(i32, 3);
+  fn ProgramUniform4iv(i32, 4);
+  fn ProgramUniform1uiv(u32, 1);
+  fn ProgramUniform2uiv(u32, 2);
+  fn ProgramUniform3uiv(u32, 3);
+  fn ProgramUniform4uiv(u32, 4);
+}
+
+macro_rules! traced_uniform_matrices {
+  ($(fn $func:ident($n:literal);)*) => {
+    fn uniform_matrix_shim(name: &[u8]) -> Option<*const c_void> {
+      $(
+        if name == concat!("gl", stringify!($func)).as_bytes() {
+          unsafe extern "system" fn shim(
+            program: u32, location: i32, count: i32, transpose: u8,
+            value: *const f32,
+          ) {
+            with_real(|gl| gl.$func(program, location, count, transpose, value));
+            record(
+              concat!("gl", stringify!($func)),
+              vec![
+                program.into(),
+                location.into(),
+                count.into(),
+                transpose.into(),
+                slice_arg(value, count.max(0) as usize * $n),
+              ],
+              None,
+            );
+          }
+          return Some(shim as *const c_void);
+        }
+      )*
+      None
+    }
+
+    /// Replays a `glProgramUniformMatrix*fv` call, if that's what the call
+    /// is.
+    unsafe fn replay_uniform_matrix(
+      gl: &GlFns, func: &str, program: u32, location: i32, count: i32,
+      transpose: u8, values: &[f64],
+    ) -> Option<Result<(), String>> {
+      $(
+        if func == concat!("gl", stringify!($func)) {
+          if values.len() != count.max(0) as usize * $n {
+            return Some(Err(format!("{} has the wrong number of values.", func)));
+          }
+          let v: Vec<f32> = values.iter().map(|&x| x as f32).collect();
+          gl.$func(program, location, count, transpose, v.as_ptr());
+          return Some(Ok(()));
+        }
+      )*
+      None
+    }
+  };
+}
+
+traced_uniform_matrices! {
+  fn ProgramUniformMatrix2fv(4);
+  fn ProgramUniformMatrix3fv(9);
+  fn ProgramUniformMatrix4fv(16);
+  fn ProgramUniformMatrix2x3fv(6);
+  fn ProgramUniformMatrix2x4fv(8);
+  fn ProgramUniformMatrix3x2fv(6);
+  fn ProgramUniformMatrix3x4fv(12);
+  fn ProgramUniformMatrix4x2fv(8);
+  fn ProgramUniformMatrix4x3fv(12);
+}
+
+macro_rules! untraced {
+  ($(fn $func:ident($($arg:ident: $t:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+    /// A shim for a function that isn't traced, which marks the trace as
+    /// incomplete and then calls the real function.
+    fn untraced_shim(name: &[u8]) -> Option<*const c_void> {
+      $(
+        if name == concat!("gl", stringify!($func)).as_bytes() {
+          #[allow(clippy::too_many_arguments)]
+          unsafe extern "system" fn shim($($arg: $t),*) $(-> $ret)? {
+            record_untraced(concat!("gl", stringify!($func)));
+            with_real(|gl| gl.$func($($arg),*))
+          }
+          return Some(shim as *const c_void);
+        }
+      )*
+      None
+    }
+  };
+}
+
+gl_signatures!(untraced);
+
+/// If a function only reads state, so that it doesn't need to be traced.
+fn is_query(name: &[u8]) -> bool {
+  name.starts_with(b"glGet")
+    || name.starts_with(b"glIs")
+    || (name.starts_with(b"glCheck") && name.ends_with(b"Status"))
+}
+
+/// The tracing version of a GL function, which records it (or marks it as
+/// untraced). Queries aren't shimmed.
+fn traced_shim(name: &[u8]) -> Option<*const c_void> {
+  let shim = match name {
+    b"glCreateShader" => create_shader as *const c_void,
+    b"glShaderSource" => shader_source as *const c_void,
+    b"glCompileShader" => compile_shader as *const c_void,
+    b"glDeleteShader" => delete_shader as *const c_void,
+    b"glCreateProgram" => create_program as *const c_void,
+    b"glCreateShaderProgramv" => create_shader_program_v as *const c_void,
+    b"glAttachShader" => attach_shader as *const c_void,
+    b"glDetachShader" => detach_shader as *const c_void,
+    b"glLinkProgram" => link_program as *const c_void,
+    b"glUseProgram" => use_program as *const c_void,
+    b"glDeleteProgram" => delete_program as *const c_void,
+    b"glProgramParameteri" => program_parameter_i as *const c_void,
+    b"glGetUniformLocation" => get_uniform_location as *const c_void,
+    b"glGetProgramResourceLocation" => {
+      get_program_resource_location as *const c_void
+    }
+    b"glGetProgramResourceiv" => get_program_resource_iv as *const c_void,
+    b"glCreateBuffers" => create_buffers as *const c_void,
+    b"glDeleteBuffers" => delete_buffers as *const c_void,
+    b"glNamedBufferStorage" => named_buffer_storage as *const c_void,
+    b"glNamedBufferData" => named_buffer_data as *const c_void,
+    b"glNamedBufferSubData" => named_buffer_sub_data as *const c_void,
+    b"glBindBufferBase" => bind_buffer_base as *const c_void,
+    b"glBindBufferRange" => bind_buffer_range as *const c_void,
+    b"glCreateVertexArrays" => create_vertex_arrays as *const c_void,
+    b"glDeleteVertexArrays" => delete_vertex_arrays as *const c_void,
+    b"glBindVertexArray" => bind_vertex_array as *const c_void,
+    b"glVertexArrayVertexBuffer" => vertex_array_vertex_buffer as *const c_void,
+    b"glVertexArrayElementBuffer" => {
+      vertex_array_element_buffer as *const c_void
+    }
+    b"glVertexArrayAttribFormat" => vertex_array_attrib_format as *const c_void,
+    b"glVertexArrayAttribIFormat" => {
+      vertex_array_attrib_i_format as *const c_void
+    }
+    b"glVertexArrayAttribBinding" => {
+      vertex_array_attrib_binding as *const c_void
+    }
+    b"glEnableVertexArrayAttrib" => enable_vertex_array_attrib as *const c_void,
+    b"glVertexAttrib4fv" => vertex_attrib_4fv as *const c_void,
+    b"glEnable" => enable as *const c_void,
+    b"glDisable" => disable as *const c_void,
+    b"glDebugMessageCallback" => debug_message_callback as *const c_void,
+    b"glViewport" => viewport as *const c_void,
+    b"glPointSize" => point_size as *const c_void,
+    b"glPolygonMode" => polygon_mode as *const c_void,
+    b"glPatchParameteri" => patch_parameter_i as *const c_void,
+    b"glClearColor" => clear_color as *const c_void,
+    b"glClear" => clear as *const c_void,
+    b"glClearBufferfv" => clear_buffer_fv as *const c_void,
+    b"glDrawArrays" => draw_arrays as *const c_void,
+    b"glDrawArraysInstanced" => draw_arrays_instanced as *const c_void,
+    b"glDrawElements" => draw_elements as *const c_void,
+    b"glDrawElementsInstanced" => draw_elements_instanced as *const c_void,
+    b"glMapBuffer" => map_buffer as *const c_void,
+    b"glMapBufferRange" => map_buffer_range as *const c_void,
+    b"glMapNamedBuffer" => map_named_buffer as *const c_void,
+    b"glMapNamedBufferRange" => map_named_buffer_range as *const c_void,
+    _ if is_query(name) => return None,
+    _ => {
+      return uniform_shim(name)
+        .or_else(|| uniform_matrix_shim(name))
+        .or_else(|| untraced_shim(name))
+    }
+  };
+  Some(shim)
+}
+
+extern "system" fn create_shader(ty: GLenum) -> u32 {
+  let shader = with_real(|gl| gl.CreateShader(ty));
+  record("glCreateShader", vec![ty.into()], Some(shader.into()));
+  shader
+}
+
+unsafe extern "system" fn shader_source(
+  shader: u32, count: i32, strings: *const *const u8, lengths: *const i32,
+) {
+  with_real(|gl| gl.ShaderSource(shader, count, strings, lengths));
+  let src = strings_arg(count, strings, lengths);
+  record("glShaderSource", vec![shader.into(), count.into(), src], None);
+}
+
+extern "system" fn compile_shader(shader: u32) {
+  with_real(|gl| gl.CompileShader(shader));
+  record("glCompileShader", vec![shader.into()], None);
+}
+
+extern "system" fn delete_shader(shader: u32) {
+  with_real(|gl| gl.DeleteShader(shader));
+  record("glDeleteShader", vec![shader.into()], None);
+}
+
+extern "system" fn create_program() -> u32 {
+  let program = with_real(|gl| gl.CreateProgram());
+  record("glCreateProgram", vec![], Some(program.into()));
+  program
+}
+
+unsafe extern "system" fn create_shader_program_v(
+  ty: GLenum, count: i32, strings: *const *const u8,
+) -> u32 {
+  let program = with_real(|gl| gl.CreateShaderProgramv(ty, count, strings));
+  let src = strings_arg(count, strings, core::ptr::null());
+  record(
+    "glCreateShaderProgramv",
+    vec![ty.into(), count.into(), src],
+    Some(program.into()),
+  );
+  program
+}
+
+extern "system" fn attach_shader(program: u32, shader: u32) {
+  with_real(|gl| gl.AttachShader(program, shader));
+  record("glAttachShader", vec![program.into(), shader.into()], None);
+}
+
+unsafe extern "system" fn detach_shader(program: u32, shader: u32) {
+  with_real(|gl| gl.DetachShader(program, shader));
+  record("glDetachShader", vec![program.into(), shader.into()], None);
+}
+
+extern "system" fn link_program(program: u32) {
+  with_real(|gl| gl.LinkProgram(program));
+  record("glLinkProgram", vec![program.into()], None);
+}
+
+extern "system" fn use_program(program: u32) {
+  with_real(|gl| gl.UseProgram(program));
+  record("glUseProgram", vec![program.into()], None);
+}
+
+extern "system" fn delete_program(program: u32) {
+  with_real(|gl| gl.DeleteProgram(program));
+  record("glDeleteProgram", vec![program.into()], None);
+}
+
+unsafe extern "system" fn program_parameter_i(
+  program: u32, pname: GLenum, value: i32,
+) {
+  with_real(|gl| gl.ProgramParameteri(program, pname, value));
+  record(
+    "glProgramParameteri",
+    vec![program.into(), pname.into(), value.into()],
+    None,
+  );
+}
+
+unsafe extern "system" fn get_uniform_location(
+  program: u32, name: *const u8,
+) -> i32 {
+  let location = with_real(|gl| gl.GetUniformLocation(program, name));
+  let name = min_alloc_lossy_into_string(gather_null_terminated_bytes(name));
+  record(
+    "glGetUniformLocation",
+    vec![program.into(), TraceArg::Str(name)],
+    Some(location.into()),
+  );
+  location
+}
+
+unsafe extern "system" fn get_program_resource_location(
+  program: u32, interface: GLenum, name: *const u8,
+) -> i32 {
+  let location =
+    with_real(|gl| gl.GetProgramResourceLocation(program, interface, name));
+  let name = min_alloc_lossy_into_string(gather_null_terminated_bytes(name));
+  record(
+    "glGetProgramResourceLocation",
+    vec![program.into(), interface.into(), TraceArg::Str(name)],
+    Some(location.into()),
+  );
+  location
+}
+
+/// A query, but uniform locations found this way (eg: by
+/// [`get_program_reflection`](GlFnsRusty::get_program_reflection)) are
+/// recorded as a `glGetProgramResourceLocation`, so that a replay can map
+/// them.
+#[allow(clippy::too_many_arguments)]
+unsafe extern "system" fn get_program_resource_iv(
+  program: u32, interface: GLenum, index: u32, prop_count: i32,
+  props: *const GLenum, count: i32, length: *mut i32, params: *mut i32,
+) {
+  let found = with_real(|gl| {
+    gl.GetProgramResourceiv(
+      program, interface, index, prop_count, props, count, length, params,
+    );
+    if interface != GL_UNIFORM || props.is_null() || params.is_null() {
+      return None;
+    }
+    let written =
+      if length.is_null() { prop_count.min(count) } else { *length };
+    let props = core::slice::from_raw_parts(props, prop_count.max(0) as usize);
+    let location = match props.iter().position(|&p| p == GL_LOCATION) {
+      Some(i) if (i as i32) < written => *params.add(i),
+      _ => return None,
+    };
+    if location < 0 {
+      return None;
+    }
+    let mut max_length = 0;
+    gl.GetProgramInterfaceiv(
+      program,
+      interface,
+      GL_MAX_NAME_LENGTH,
+      &mut max_length,
+    );
+    let mut name = vec![0_u8; max_length.max(1) as usize];
+    let mut name_length = 0;
+    gl.GetProgramResourceName(
+      program,
+      interface,
+      index,
+      max_length.max(1),
+      &mut name_length,
+      name.as_mut_ptr(),
+    );
+    name.truncate(name_length.max(0) as usize);
+    Some((location, name))
+  });
+  let (location, name) = match found {
+    Some(found) => found,
+    None => return,
+  };
+  record(
+    "glGetProgramResourceLocation",
+    vec![
+      program.into(),
+      interface.into(),
+      TraceArg::Str(min_alloc_lossy_into_string(name)),
+    ],
+    Some(location.into()),
+  );
+}
+
+unsafe extern "system" fn create_buffers(n: i32, buffers: *mut u32) {
+  with_real(|gl| gl.CreateBuffers(n, buffers));
+  let names = slice_arg(buffers, n.max(0) as usize);
+  record("glCreateBuffers", vec![n.into()], Some(names));
+}
+
+unsafe extern "system" fn delete_buffers(n: i32, buffers: *const u32) {
+  with_real(|gl| gl.DeleteBuffers(n, buffers));
+  let names = slice_arg(buffers, n.max(0) as usize);
+  record("glDeleteBuffers", vec![n.into(), names], None);
+}
+
+unsafe extern "system" fn named_buffer_storage(
+  buffer: u32, size: isize, data: *const c_void, flags: GLbitfield,
+) {
+  with_real(|gl| gl.NamedBufferStorage(buffer, size, data, flags));
+  let data = blob_arg(data, size);
+  record(
+    "glNamedBufferStorage",
+    vec![buffer.into(), size.into(), data, flags.into()],
+    None,
+  );
+}
+
+unsafe extern "system" fn named_buffer_data(
+  buffer: u32, size: isize, data: *const c_void, usage: GLenum,
+) {
+  with_real(|gl| gl.NamedBufferData(buffer, size, data, usage));
+  let data = blob_arg(data, size);
+  record(
+    "glNamedBufferData",
+    vec![buffer.into(), size.into(), data, usage.into()],
+    None,
+  );
+}
+
+unsafe extern "system" fn named_buffer_sub_data(
+  buffer: u32, offset: isize, size: isize, data: *const c_void,
+) {
+  with_real(|gl| gl.NamedBufferSubData(buffer, offset, size, data));
+  let data = blob_arg(data, size);
+  record(
+    "glNamedBufferSubData",
+    vec![buffer.into(), offset.into(), size.into(), data],
+    None,
+  );
+}
+
+unsafe extern "system" fn bind_buffer_base(
+  target: GLenum, index: u32, buffer: u32,
+) {
+  with_real(|gl| gl.BindBufferBase(target, index, buffer));
+  record(
+    "glBindBufferBase",
+    vec![target.into(), index.into(), buffer.into()],
+    None,
+  );
+}
+
+unsafe extern "system" fn bind_buffer_range(
+  target: GLenum, index: u32, buffer: u32, offset: isize, size: isize,
+) {
+  with_real(|gl| gl.BindBufferRange(target, index, buffer, offset, size));
+  record(
+    "glBindBufferRange",
+    vec![
+      target.into(),
+      index.into(),
+      buffer.into(),
+      offset.into(),
+      size.into(),
+    ],
+    None,
+  );
+}
+
+// Writes through a mapped pointer can't be seen, so a map that can write
+// marks the trace as incomplete.
+unsafe extern "system" fn map_buffer(
+  target: GLenum, access: GLenum,
+) -> *mut c_void {
+  if access != GL_READ_ONLY {
+    record_untraced("glMapBuffer");
+  }
+  with_real(|gl| gl.MapBuffer(target, access))
+}
+
+unsafe extern "system" fn map_buffer_range(
+  target: GLenum, offset: isize, length: isize, access: GLbitfield,
+) -> *mut c_void {
+  if access.0 & GL_MAP_WRITE_BIT.0 != 0 {
+    record_untraced("glMapBufferRange");
+  }
+  with_real(|gl| gl.MapBufferRange(target, offset, length, access))
+}
+
+unsafe extern "system" fn map_named_buffer(
+  buffer: u32, access: GLenum,
+) -> *mut c_void {
+  if access != GL_READ_ONLY {
+    record_untraced("glMapNamedBuffer");
+  }
+  with_real(|gl| gl.MapNamedBuffer(buffer, access))
+}
+
+unsafe extern "system" fn map_named_buffer_range(
+  buffer: u32, offset: isize, length: isize, access: GLbitfield,
+) -> *mut c_void {
+  if access.0 & GL_MAP_WRITE_BIT.0 != 0 {
+    record_untraced("glMapNamedBufferRange");
+  }
+  with_real(|gl| gl.MapNamedBufferRange(buffer, offset, length, access))
+}
+
+unsafe extern "system" fn create_vertex_arrays(n: i32, arrays: *mut u32) {
+  with_real(|gl| gl.CreateVertexArrays(n, arrays));
+  let names = slice_arg(arrays, n.max(0) as usize);
+  record("glCreateVertexArrays", vec![n.into()], Some(names));
+}
+
+unsafe extern "system" fn delete_vertex_arrays(n: i32, arrays: *const u32) {
+  with_real(|gl| gl.DeleteVertexArrays(n, arrays));
+  let names = slice_arg(arrays, n.max(0) as usize);
+  record("glDeleteVertexArrays", vec![n.into(), names], None);
+}
+
+extern "system" fn bind_vertex_array(array: u32) {
+  with_real(|gl| gl.BindVertexArray(array));
+  record("glBindVertexArray", vec![array.into()], None);
+}
+
+unsafe extern "system" fn vertex_array_vertex_buffer(
+  vao: u32, binding_index: u32, buffer: u32, offset: isize, stride: i32,
+) {
+  with_real(|gl| {
+    gl.VertexArrayVertexBuffer(vao, binding_index, buffer, offset, stride)
+  });
+  record(
+    "glVertexArrayVertexBuffer",
+    vec![
+      vao.into(),
+      binding_index.into(),
+      buffer.into(),
+      offset.into(),
+      stride.into(),
+    ],
+    None,
+  );
+}
+
+unsafe extern "system" fn vertex_array_element_buffer(vao: u32, buffer: u32) {
+  with_real(|gl| gl.VertexArrayElementBuffer(vao, buffer));
+  record("glVertexArrayElementBuffer", vec![vao.into(), buffer.into()], None);
+}
+
+unsafe extern "system" fn vertex_array_attrib_format(
+  vao: u32, attrib_index: u32, size: i32, ty: GLenum, normalized: u8,
+  relative_offset: u32,
+) {
+  with_real(|gl| {
+    gl.VertexArrayAttribFormat(
+      vao,
+      attrib_index,
+      size,
+      ty,
+      normalized,
+      relative_offset,
+    )
+  });
+  record(
+    "glVertexArrayAttribFormat",
+    vec![
+      vao.into(),
+      attrib_index.into(),
+      size.into(),
+      ty.into(),
+      normalized.into(),
+      relative_offset.into(),
+    ],
+    None,
+  );
+}
+
+unsafe extern "system" fn vertex_array_attrib_i_format(
+  vao: u32, attrib_index: u32, size: i32, ty: GLenum, relative_offset: u32,
+) {
+  with_real(|gl| {
+    gl.VertexArrayAttribIFormat(vao, attrib_index, size, ty, relative_offset)
+  });
+  record(
+    "glVertexArrayAttribIFormat",
+    vec![
+      vao.into(),
+      attrib_index.into(),
+      size.into(),
+      ty.into(),
+      relative_offset.into(),
+    ],
+    None,
+  );
+}
+
+unsafe extern "system" fn vertex_array_attrib_binding(
+  vao: u32, attrib_index: u32, binding_index: u32,
+) {
+  with_real(|gl| gl.VertexArrayAttribBinding(vao, attrib_index, binding_index));
+  record(
+    "glVertexArrayAttribBinding",
+    vec![vao.into(), attrib_index.into(), binding_index.into()],
+    None,
+  );
+}
+
+unsafe extern "system" fn enable_vertex_array_attrib(vao: u32, index: u32) {
+  with_real(|gl| gl.EnableVertexArrayAttrib(vao, index));
+  record("glEnableVertexArrayAttrib", vec![vao.into(), index.into()], None);
+}
+
+unsafe extern "system" fn vertex_attrib_4fv(index: u32, v: *const [f32; 4]) {
+  with_real(|gl| gl.VertexAttrib4fv(index, v));
+  record(
+    "glVertexAttrib4fv",
+    vec![index.into(), slice_arg(v.cast::<f32>(), 4)],
+    None,
+  );
+}
+
+unsafe extern "system" fn enable(cap: GLenum) {
+  with_real(|gl| gl.Enable(cap));
+  record("glEnable", vec![cap.into()], None);
+}
+
+unsafe extern "system" fn disable(cap: GLenum) {
+  with_real(|gl| gl.Disable(cap));
+  record("glDisable", vec![cap.into()], None);
+}
+
+// Note: the callback itself can't be recorded, only if there is one, and a
+// replay uses `println_debug_message_callback` for it.
+unsafe extern "system" fn debug_message_callback(
+  callback: GLDEBUGPROC, user_param: *const c_void,
+) {
+  with_real(|gl| gl.DebugMessageCallback(callback, user_param));
+  let has_callback = callback.is_some() as u32;
+  record("glDebugMessageCallback", vec![has_callback.into()], None);
+}
+
+unsafe extern "system" fn viewport(x: i32, y: i32, width: i32, height: i32) {
+  with_real(|gl| gl.Viewport(x, y, width, height));
+  record(
+    "glViewport",
+    vec![x.into(), y.into(), width.into(), height.into()],
+    None,
+  );
+}
+
+extern "system" fn point_size(size: f32) {
+  with_real(|gl| gl.PointSize(size));
+  record("glPointSize", vec![size.into()], None);
+}
+
+unsafe extern "system" fn polygon_mode(face: GLenum, mode: GLenum) {
+  with_real(|gl| gl.PolygonMode(face, mode));
+  record("glPolygonMode", vec![face.into(), mode.into()], None);
+}
+
+unsafe extern "system" fn patch_parameter_i(pname: GLenum, value: i32) {
+  with_real(|gl| gl.PatchParameteri(pname, value));
+  record("glPatchParameteri", vec![pname.into(), value.into()], None);
+}
+
+unsafe extern "system" fn clear_color(
+  red: f32, green: f32, blue: f32, alpha: f32,
+) {
+  with_real(|gl| gl.ClearColor(red, green, blue, alpha));
+  record(
+    "glClearColor",
+    vec![red.into(), green.into(), blue.into(), alpha.into()],
+    None,
+  );
+}
+
+unsafe extern "system" fn clear(mask: GLbitfield) {
+  with_real(|gl| gl.Clear(mask));
+  record("glClear", vec![mask.into()], None);
+}
+
+unsafe extern "system" fn clear_buffer_fv(
+  buffer: GLenum, draw_buffer: i32, value: *const f32,
+) {
+  with_real(|gl| gl.ClearBufferfv(buffer, draw_buffer, value));
+  // GL_COLOR takes 4 values and GL_DEPTH takes 1.
+  let n = if buffer == GL_COLOR { 4 } else { 1 };
+  record(
+    "glClearBufferfv",
+    vec![buffer.into(), draw_buffer.into(), slice_arg(value, n)],
+    None,
+  );
+}
+
+unsafe extern "system" fn draw_arrays(mode: GLenum, first: i32, count: i32) {
+  with_real(|gl| gl.DrawArrays(mode, first, count));
+  record("glDrawArrays", vec![mode.into(), first.into(), count.into()], None);
+}
+
+unsafe extern "system" fn draw_arrays_instanced(
+  mode: GLenum, first: i32, count: i32, instance_count: i32,
+) {
+  with_real(|gl| gl.DrawArraysInstanced(mode, first, count, instance_count));
+  record(
+    "glDrawArraysInstanced",
+    vec![mode.into(), first.into(), count.into(), instance_count.into()],
+    None,
+  );
+}
+
+/// If an element buffer is bound, so that the `indices` of a draw are an
+/// offset into it (rather than a pointer to client memory).
+unsafe fn element_buffer_bound(gl: &GlFns) -> bool {
+  let mut buffer = 0;
+  gl.GetIntegerv(GL_ELEMENT_ARRAY_BUFFER_BINDING, &mut buffer);
+  buffer != 0
+}
+
+// Note: `indices` is recorded as an offset into the element buffer, so draws
+// with index arrays in client memory are untraced.
+unsafe extern "system" fn draw_elements(
+  mode: GLenum, count: i32, ty: GLenum, indices: *const c_void,
+) {
+  let bound = with_real(|gl| {
+    gl.DrawElements(mode, count, ty, indices);
+    element_buffer_bound(gl)
+  });
+  if !bound {
+    return record_untraced("glDrawElements");
+  }
+  record(
+    "glDrawElements",
+    vec![mode.into(), count.into(), ty.into(), (indices as isize).into()],
+    None,
+  );
+}
+
+unsafe extern "system" fn draw_elements_instanced(
+  mode: GLenum, count: i32, ty: GLenum, indices: *const c_void,
+  instance_count: i32,
+) {
+  let bound = with_real(|gl| {
+    gl.DrawElementsInstanced(mode, count, ty, indices, instance_count);
+    element_buffer_bound(gl)
+  });
+  if !bound {
+    return record_untraced("glDrawElementsInstanced");
+  }
+  record(
+    "glDrawElementsInstanced",
+    vec![
+      mode.into(),
+      count.into(),
+      ty.into(),
+      (indices as isize).into(),
+      instance_count.into(),
+    ],
+    None,
+  );
+}
+
+/// The kinds of GL object names that a replay keeps track of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NameKind {
+  /// Shaders and programs share their names.
+  ShaderOrProgram,
+  Buffer,
+  VertexArray,
+}
+
+/// Replays a [`Trace`] with any context.
+///
+/// The names of created objects (and the locations of uniforms) can be
+/// different in the new context, so they're mapped from the recorded values
+/// to the new ones as the calls are made.
+///
+/// This is also an [`App`] that replays one frame per [`render`](App::render),
+/// so a trace can be played back in a window with [`run_with`].
+///
+/// A trace that [isn't complete](Trace::check_complete) still replays, but
+/// [`replay_all`](TraceReplayer::replay_all) gives an error at the end, and
+/// the `App` prints the error before the first frame.
+#[derive(Debug, Clone, Default)]
+pub struct TraceReplayer {
+  trace: Trace,
+  next_event: usize,
+  names: HashMap<(NameKind, u32), u32>,
+  /// `(new program, recorded location)` to new location.
+  locations: HashMap<(u32, i32), i32>,
+}
+impl TraceReplayer {
+  pub fn new(trace: Trace) -> Self {
+    Self { trace, ..Self::default() }
+  }
+
+  /// If every event in the trace has been replayed.
+  pub fn is_finished(&self) -> bool {
+    self.next_event >= self.trace.events.len()
+  }
+
+  /// Replays the calls up to (and including) the next end of frame.
+  ///
+  /// If a call fails, you get the error and the replay continues after
+  /// that call next time.
+  pub fn replay_frame(&mut self, gl: &GlFns) -> Result<(), String> {
+    while let Some(event) = self.trace.events.get(self.next_event) {
+      self.next_event += 1;
+      match event {
+        TraceEvent::EndFrame => break,
+        TraceEvent::Call(call) => {
+          let call = call.clone();
+          self
+            .replay_call(gl, &call)
+            .map_err(|e| format!("event {}: {}", self.next_event - 1, e))?;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Replays all the rest of the trace.
+  ///
+  /// ## Failure
+  /// * The first call that fails.
+  /// * If the trace isn't complete (once all of it is replayed).
+  pub fn replay_all(&mut self, gl: &GlFns) -> Result<(), String> {
+    while !self.is_finished() {
+      self.replay_frame(gl)?;
+    }
+    self.trace.check_complete()
+  }
+
+  fn name(
+    &self, kind: NameKind, call: &TraceCall, i: usize,
+  ) -> Result<u32, String> {
+    let recorded = call.u32(i)?;
+    if recorded == 0 {
+      return Ok(0);
+    }
+    self.names.get(&(kind, recorded)).copied().ok_or_else(|| {
+      format!(
+        "{} uses {:?} {}, which was never created.",
+        call.func, kind, recorded
+      )
+    })
+  }
+
+  fn add_name(&mut self, kind: NameKind, recorded: i64, new: u32) {
+    if let (Ok(recorded), true) = (recorded.try_into(), recorded != 0) {
+      self.names.insert((kind, recorded), new);
+    }
+  }
+
+  fn location(
+    &self, program: u32, call: &TraceCall, i: usize,
+  ) -> Result<i32, String> {
+    let recorded = call.i32(i)?;
+    // explicit locations never go through glGetUniformLocation.
+    Ok(self.locations.get(&(program, recorded)).copied().unwrap_or(recorded))
+  }
+
+  fn blob(
+    &self, call: &TraceCall, i: usize, size: isize,
+  ) -> Result<*const c_void, String> {
+    match call.arg(i)? {
+      TraceArg::Null => Ok(core::ptr::null()),
+      TraceArg::Blob(hash) => match self.trace.blobs.get(hash) {
+        Some(data) if data.len() as isize == size => Ok(data.as_ptr().cast()),
+        Some(_) => Err(format!("{} has a blob of the wrong size.", call.func)),
+        None => Err(format!(
+          "{} uses blob {:016x}, which isn't in the trace.",
+          call.func, hash
+        )),
+      },
+      _ => Err(call.bad_arg(i)),
+    }
+  }
+
+  fn create_names(
+    &mut self, kind: NameKind, call: &TraceCall,
+    create: impl FnOnce(i32, *mut u32),
+  ) -> Result<(), String> {
+    let recorded = call.ret_names()?;
+    let n = call.i32(0)?;
+    if recorded.len() != n.max(0) as usize {
+      return Err(format!("{} has the wrong number of names.", call.func));
+    }
+    let mut new = vec![0; recorded.len()];
+    create(n, new.as_mut_ptr());
+    for (&r, &n) in recorded.iter().zip(new.iter()) {
+      self.add_name(kind, r as i64, n);
+    }
+    Ok(())
+  }
+
+  fn delete_names(
+    &mut self, kind: NameKind, call: &TraceCall,
+    delete: impl FnOnce(i32, *const u32),
+  ) -> Result<(), String> {
+    let recorded = call.nums(1)?;
+    let mut new = Vec::with_capacity(recorded.len());
+    for &r in recorded {
+      let r = r as u32;
+      new.push(if r == 0 {
+        0
+      } else {
+        self.names.remove(&(kind, r)).unwrap_or(0)
+      });
+    }
+    delete(new.len().try_into().unwrap(), new.as_ptr());
+    Ok(())
+  }
+
+  fn replay_call(
+    &mut self, gl: &GlFns, call: &TraceCall,
+  ) -> Result<(), String> {
+    use NameKind::*;
+    // Safety: every pointer given to GL here points to as much data as the
+    // call will read, which is checked against the recorded sizes. The
+    // `indices` of the element draws are offsets, which are only given to GL
+    // while an element buffer is bound (so they're never read as pointers).
+    unsafe {
+      match call.func.as_str() {
+        "glCreateShader" => {
+          let shader = gl.CreateShader(call.enum_(0)?);
+          self.add_name(ShaderOrProgram, call.ret_int()?, shader);
+        }
+        "glShaderSource" => {
+          let shader = self.name(ShaderOrProgram, call, 0)?;
+          let src = call.str(2)?;
+          gl.ShaderSource(
+            shader,
+            1,
+            [src.as_ptr()].as_ptr(),
+            [src.len().try_into().unwrap()].as_ptr(),
+          );
+        }
+        "glCompileShader" => {
+          gl.CompileShader(self.name(ShaderOrProgram, call, 0)?)
+        }
+        "glDeleteShader" => {
+          gl.DeleteShader(self.name(ShaderOrProgram, call, 0)?);
+          self.names.remove(&(ShaderOrProgram, call.u32(0)?));
+        }
+        "glCreateProgram" => {
+          let program = gl.CreateProgram();
+          self.add_name(ShaderOrProgram, call.ret_int()?, program);
+        }
+        "glCreateShaderProgramv" => {
+          let mut src = call.str(2)?.as_bytes().to_vec();
+          src.push(0);
+          let program =
+            gl.CreateShaderProgramv(call.enum_(0)?, 1, [src.as_ptr()].as_ptr());
+          self.add_name(ShaderOrProgram, call.ret_int()?, program);
+        }
+        "glAttachShader" => gl.AttachShader(
+          self.name(ShaderOrProgram, call, 0)?,
+          self.name(ShaderOrProgram, call, 1)?,
+        ),
+        "glDetachShader" => gl.DetachShader(
+          self.name(ShaderOrProgram, call, 0)?,
+          self.name(ShaderOrProgram, call, 1)?,
+        ),
+        "glLinkProgram" => {
+          gl.LinkProgram(self.name(ShaderOrProgram, call, 0)?)
+        }
+        "glUseProgram" => gl.UseProgram(self.name(ShaderOrProgram, call, 0)?),
+        "glDeleteProgram" => {
+          gl.DeleteProgram(self.name(ShaderOrProgram, call, 0)?);
+          self.names.remove(&(ShaderOrProgram, call.u32(0)?));
+        }
+        "glGetUniformLocation" => {
+          let program = self.name(ShaderOrProgram, call, 0)?;
+          let mut name = call.str(1)?.as_bytes().to_vec();
+          name.push(0);
+          let location = gl.GetUniformLocation(program, name.as_ptr());
+          let recorded: i32 =
+            call.ret_int()?.try_into().map_err(|_| call.bad_arg(1))?;
+          self.locations.insert((program, recorded), location);
+        }
+        "glGetProgramResourceLocation" => {
+          let program = self.name(ShaderOrProgram, call, 0)?;
+          let mut name = call.str(2)?.as_bytes().to_vec();
+          name.push(0);
+          let location = gl.GetProgramResourceLocation(
+            program,
+            call.enum_(1)?,
+            name.as_ptr(),
+          );
+          let recorded: i32 =
+            call.ret_int()?.try_into().map_err(|_| call.bad_arg(2))?;
+          self.locations.insert((program, recorded), location);
+        }
+        "glProgramParameteri" => gl.ProgramParameteri(
+          self.name(ShaderOrProgram, call, 0)?,
+          call.enum_(1)?,
+          call.i32(2)?,
+        ),
+        "glCreateBuffers" => {
+          self.create_names(Buffer, call, |n, p| gl.CreateBuffers(n, p))?
+        }
+        "glDeleteBuffers" => {
+          self.delete_names(Buffer, call, |n, p| gl.DeleteBuffers(n, p))?
+        }
+        "glNamedBufferStorage" => {
+          let size = call.isize(1)?;
+          gl.NamedBufferStorage(
+            self.name(Buffer, call, 0)?,
+            size,
+            self.blob(call, 2, size)?,
+            GLbitfield(call.u32(3)?),
+          )
+        }
+        "glNamedBufferData" => {
+          let size = call.isize(1)?;
+          gl.NamedBufferData(
+            self.name(Buffer, call, 0)?,
+            size,
+            self.blob(call, 2, size)?,
+            call.enum_(3)?,
+          )
+        }
+        "glNamedBufferSubData" => {
+          let size = call.isize(2)?;
+          gl.NamedBufferSubData(
+            self.name(Buffer, call, 0)?,
+            call.isize(1)?,
+            size,
+            self.blob(call, 3, size)?,
+          )
+        }
+        "glBindBufferBase" => gl.BindBufferBase(
+          call.enum_(0)?,
+          call.u32(1)?,
+          self.name(Buffer, call, 2)?,
+        ),
+        "glBindBufferRange" => gl.BindBufferRange(
+          call.enum_(0)?,
+          call.u32(1)?,
+          self.name(Buffer, call, 2)?,
+          call.isize(3)?,
+          call.isize(4)?,
+        ),
+        "glCreateVertexArrays" => {
+          self.create_names(VertexArray, call, |n, p| {
+            gl.CreateVertexArrays(n, p)
+          })?
+        }
+        "glDeleteVertexArrays" => {
+          self.delete_names(VertexArray, call, |n, p| {
+            gl.DeleteVertexArrays(n, p)
+          })?
+        }
+        "glBindVertexArray" => {
+          gl.BindVertexArray(self.name(VertexArray, call, 0)?)
+        }
+        "glVertexArrayVertexBuffer" => gl.VertexArrayVertexBuffer(
+          self.name(VertexArray, call, 0)?,
+          call.u32(1)?,
+          self.name(Buffer, call, 2)?,
+          call.isize(3)?,
+          call.i32(4)?,
+        ),
+        "glVertexArrayElementBuffer" => gl.VertexArrayElementBuffer(
+          self.name(VertexArray, call, 0)?,
+          self.name(Buffer, call, 1)?,
+        ),
+        "glVertexArrayAttribFormat" => gl.VertexArrayAttribFormat(
+          self.name(VertexArray, call, 0)?,
+          call.u32(1)?,
+          call.i32(2)?,
+          call.enum_(3)?,
+          call.u8(4)?,
+          call.u32(5)?,
+        ),
+        "glVertexArrayAttribIFormat" => gl.VertexArrayAttribIFormat(
+          self.name(VertexArray, call, 0)?,
+          call.u32(1)?,
+          call.i32(2)?,
+          call.enum_(3)?,
+          call.u32(4)?,
+        ),
+        "glVertexArrayAttribBinding" => gl.VertexArrayAttribBinding(
+          self.name(VertexArray, call, 0)?,
+          call.u32(1)?,
+          call.u32(2)?,
+        ),
+        "glEnableVertexArrayAttrib" => gl.EnableVertexArrayAttrib(
+          self.name(VertexArray, call, 0)?,
+          call.u32(1)?,
+        ),
+        "glVertexAttrib4fv" => {
+          let v: [f32; 4] = match call.nums(1)? {
+            &[x, y, z, w] => [x as f32, y as f32, z as f32, w as f32],
+            _ => return Err(call.bad_arg(1)),
+          };
+          gl.VertexAttrib4fv(call.u32(0)?, &v)
+        }
+        "glEnable" => gl.Enable(call.enum_(0)?),
+        "glDisable" => gl.Disable(call.enum_(0)?),
+        "glDebugMessageCallback" => {
+          let callback: GLDEBUGPROC = match call.u32(0)? {
+            0 => None,
+            _ => Some(println_debug_message_callback),
+          };
+          gl.DebugMessageCallback(callback, core::ptr::null())
+        }
+        "glViewport" => {
+          gl.Viewport(call.i32(0)?, call.i32(1)?, call.i32(2)?, call.i32(3)?)
+        }
+        "glPointSize" => gl.PointSize(call.f32(0)?),
+        "glPolygonMode" => gl.PolygonMode(call.enum_(0)?, call.enum_(1)?),
+        "glPatchParameteri" => gl.PatchParameteri(call.enum_(0)?, call.i32(1)?),
+        "glClearColor" => {
+          gl.ClearColor(call.f32(0)?, call.f32(1)?, call.f32(2)?, call.f32(3)?)
+        }
+        "glClear" => gl.Clear(GLbitfield(call.u32(0)?)),
+        "glClearBufferfv" => {
+          let buffer = call.enum_(0)?;
+          let v: Vec<f32> = call.nums(2)?.iter().map(|&x| x as f32).collect();
+          if v.len() != if buffer == GL_COLOR { 4 } else { 1 } {
+            return Err(call.bad_arg(2));
+          }
+          gl.ClearBufferfv(buffer, call.i32(1)?, v.as_ptr())
+        }
+        "glDrawArrays" => {
+          gl.DrawArrays(call.enum_(0)?, call.i32(1)?, call.i32(2)?)
+        }
+        "glDrawArraysInstanced" => gl.DrawArraysInstanced(
+          call.enum_(0)?,
+          call.i32(1)?,
+          call.i32(2)?,
+          call.i32(3)?,
+        ),
+        "glDrawElements" | "glDrawElementsInstanced"
+          if !element_buffer_bound(gl) =>
+        {
+          return Err(format!("{} needs an element buffer bound.", call.func))
+        }
+        "glDrawElements" => gl.DrawElements(
+          call.enum_(0)?,
+          call.i32(1)?,
+          call.enum_(2)?,
+          call.isize(3)? as *const c_void,
+        ),
+        "glDrawElementsInstanced" => gl.DrawElementsInstanced(
+          call.enum_(0)?,
+          call.i32(1)?,
+          call.enum_(2)?,
+          call.isize(3)? as *const c_void,
+          call.i32(4)?,
+        ),
+        func if !func.starts_with("glProgramUniform") => {
+          return Err(format!("{} can't be replayed.", func))
+        }
+        func => {
+          let program = self.name(ShaderOrProgram, call, 0)?;
+          let location = self.location(program, call, 1)?;
+          let count = call.i32(2)?;
+          let replayed = if func.starts_with("glProgramUniformMatrix") {
+            replay_uniform_matrix(
+              gl,
+              func,
+              program,
+              location,
+              count,
+              call.u8(3)?,
+              call.nums(4)?,
+            )
+          } else {
+            replay_uniform(gl, func, program, location, count, call.nums(3)?)
+          };
+          replayed
+            .unwrap_or_else(|| Err(format!("{} can't be replayed.", func)))?
+        }
+      }
+    }
+    Ok(())
+  }
+}
+impl App for TraceReplayer {
+  /// An empty replay. Use [`run_with`] to replay an actual trace.
+  fn startup(_gl: &GlFnsRusty) -> Self {
+    Self::default()
+  }
+
+  fn render(&mut self, gl: &GlFnsRusty, _current_time: f64) {
+    if self.next_event == 0 {
+      if let Err(e) = self.trace.check_complete() {
+        eprintln!("{}", e);
+      }
+    }
+    if let Err(e) = self.replay_frame(gl) {
+      eprintln!("Replay error: {}", e);
+    }
+  }
+
+  /// Replays anything after the last frame (usually cleanup).
+  fn shutdown(&mut self, gl: &GlFnsRusty) {
+    while !self.is_finished() {
+      if let Err(e) = self.replay_frame(gl) {
+        eprintln!("Replay error: {}", e);
+      }
+    }
+  }
+}
+
+/// A writer that can still be read after it's given away.
+#[cfg(test)]
+#[derive(Clone, Default)]
+struct SharedBuf(std::rc::Rc<RefCell<Vec<u8>>>);
+#[cfg(test)]
+impl Write for SharedBuf {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.0.borrow_mut().extend_from_slice(buf);
+    Ok(buf.len())
+  }
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_Trace_parse() {
+  let text = r#"{"call":"glCreateShader","args":[35633],"ret":1}
+    {"call":"glShaderSource","args":[1,1,"a\"b\\c\ndé😀"]}
+
+    {"blob":"af63bd4c8601b7df","data":"00"}
+    {"call":"glNamedBufferSubData","args":[2,0,1,{"blob":"af63bd4c8601b7df"}]}
+    {"call":"glClearBufferfv","args":[6144,0,[0.5,"NaN",-1e3,1]]}
+    {"untraced":"glBindTexture"}
+    {"end_frame":0}
+  "#;
+  let trace = Trace::parse(text).unwrap();
+  assert_eq!(trace.frame_count(), 1);
+  assert_eq!(trace.events.len(), 5);
+  assert_eq!(trace.untraced, ["glBindTexture"]);
+  assert!(trace.check_complete().unwrap_err().contains("glBindTexture"));
+  assert_eq!(trace.blobs[&fnv1a_64(&[0])], [0]);
+  let calls: Vec<&TraceCall> = trace
+    .events
+    .iter()
+    .filter_map(|e| match e {
+      TraceEvent::Call(call) => Some(call),
+      TraceEvent::EndFrame => None,
+    })
+    .collect();
+  assert_eq!(calls[0].ret, Some(TraceArg::Num(1.0)));
+  assert_eq!(calls[1].str(2).unwrap(), "a\"b\\c\nd\u{e9}\u{1F600}");
+  assert_eq!(calls[2].args[3], TraceArg::Blob(0xaf63_bd4c_8601_b7df));
+  let clear = calls[3].nums(2).unwrap();
+  assert!(clear[1].is_nan());
+  assert_eq!(clear[2], -1000.0);
+
+  // written strings and numbers parse back the same.
+  let mut line = String::from("{\"call\":\"x\",\"args\":[");
+  write_json_str(&mut line, "tab\tnul\0quote\"");
+  line.push(',');
+  write_trace_arg(&mut line, &TraceArg::Nums(vec![0.1, f64::INFINITY, 3.0]));
+  line.push_str("]}");
+  let trace = Trace::parse(&line).unwrap();
+  assert_eq!(
+    trace.events[0],
+    TraceEvent::Call(TraceCall {
+      func: String::from("x"),
+      args: vec![
+        TraceArg::Str(String::from("tab\tnul\0quote\"")),
+        TraceArg::Nums(vec![0.1, f64::INFINITY, 3.0]),
+      ],
+      ret: None,
+    })
+  );
+
+  for bad in [
+    "[1]",
+    "{\"call\":1}",
+    "{\"call\":\"x\",\"args\":[}",
+    "{\"call\":\"x\"} extra",
+    "{\"blob\":\"0000000000000000\",\"data\":\"00\"}",
+    "{\"blob\":\"af63bd4c8601b7df\",\"data\":\"0\"}",
+    "{\"what\":0}",
+    "{\"untraced\":1}",
+    "{\"call\":\"x\",\"args\":[\"unterminated]}",
+  ] {
+    assert!(Trace::parse(bad).is_err(), "{}", bad);
+  }
+  let err = Trace::parse("{\"end_frame\":0}\n{\"what\":0}").unwrap_err();
+  assert!(err.starts_with("line 2:"), "{}", err);
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn test_GlTracer_and_TraceReplayer_with_mock() {
+  let (_mock, _) = MockGl::new();
+  let out = SharedBuf::default();
+  let (tracer, gl) =
+    unsafe { GlTracer::load_from(&MockGl::proc_address, out.clone()) }.unwrap();
+  let vertex = gl.create_compiled_shader(ShaderEnum::Vertex, "vs").unwrap();
+  let fragment = gl.create_compiled_shader(ShaderEnum::Fragment, "fs").unwrap();
+  let program = gl.create_linked_program(&[vertex, fragment]).unwrap();
+  gl.use_program(program);
+  gl.viewport(0, 0, 32, 16);
+  gl.clear_color_draw_buffer(0, [0.25, 0.5, 0.75, 1.0]);
+  // queries go straight to the driver.
+  assert!(gl.get_shader_info_log(vertex).is_empty());
+  tracer.end_frame().unwrap();
+  gl.point_size(2.0);
+  tracer.end_frame().unwrap();
+  gl.delete_program(program);
+  tracer.finish().unwrap();
+
+  let text = String::from_utf8(out.0.borrow().clone()).unwrap();
+  let trace = Trace::parse(&text).unwrap();
+  assert_eq!(trace.frame_count(), 2);
+  let names: Vec<&str> = trace
+    .events
+    .iter()
+    .map(|e| match e {
+      TraceEvent::Call(call) => call.func.as_str(),
+      TraceEvent::EndFrame => "end_frame",
+    })
+    .collect();
+  assert_eq!(
+    names,
+    [
+      "glCreateShader",
+      "glShaderSource",
+      "glCompileShader",
+      "glCreateShader",
+      "glShaderSource",
+      "glCompileShader",
+      "glCreateProgram",
+      "glAttachShader",
+      "glAttachShader",
+      "glLinkProgram",
+      "glUseProgram",
+      "glViewport",
+      "glClearBufferfv",
+      "end_frame",
+      "glPointSize",
+      "end_frame",
+      "glDeleteProgram",
+    ]
+  );
+
+  // in the replay the names are different, since something else was made
+  // first.
+  let (mock, gl) = MockGl::new();
+  gl.create_program().unwrap();
+  mock.clear_calls();
+  let mut replayer = TraceReplayer::new(trace);
+  replayer.replay_frame(&gl).unwrap();
+  assert!(!replayer.is_finished());
+  let calls = mock.calls();
+  assert_eq!(calls.len(), 13);
+  assert_eq!(calls[1].args[2], MockArg::Str(String::from("vs")));
+  assert_eq!(calls[7].args, [MockArg::Int(4), MockArg::Int(2)]);
+  assert_eq!(calls[8].args, [MockArg::Int(4), MockArg::Int(3)]);
+  assert_eq!(calls[10].args, [MockArg::Int(4)]);
+  assert_eq!(calls[12].args[2], MockArg::Floats(vec![0.25, 0.5, 0.75, 1.0]));
+  mock.clear_calls();
+  replayer.replay_all(&gl).unwrap();
+  assert!(replayer.is_finished());
+  assert_eq!(mock.call_names(), ["glPointSize", "glDeleteProgram"]);
+  assert_eq!(mock.calls()[1].args, [MockArg::Int(4)]);
+
+  // names that were never created are an error.
+  let bad = Trace::parse("{\"call\":\"glUseProgram\",\"args\":[7]}").unwrap();
+  assert!(TraceReplayer::new(bad).replay_all(&gl).is_err());
+  let bad = Trace::parse("{\"call\":\"glFinish\",\"args\":[]}").unwrap();
+  assert!(TraceReplayer::new(bad).replay_all(&gl).is_err());
+
+  // untraced calls go to the driver, and are noted once.
+  let out = SharedBuf::default();
+  let (tracer, traced) =
+    unsafe { GlTracer::load_from(&MockGl::proc_address, out.clone()) }.unwrap();
+  mock.clear_calls();
+  unsafe { traced.PixelStorei(GL_UNPACK_ALIGNMENT, 1) };
+  unsafe { traced.PixelStorei(GL_UNPACK_ALIGNMENT, 4) };
+  traced.point_size(1.0);
+  // every setter is either traced or noted, not just the well known ones.
+  unsafe { traced.PolygonOffset(1.0, 2.0) };
+  tracer.finish().unwrap();
+  assert_eq!(
+    mock.call_names(),
+    ["glPixelStorei", "glPixelStorei", "glPointSize", "glPolygonOffset"]
+  );
+  let text = String::from_utf8(out.0.borrow().clone()).unwrap();
+  let trace = Trace::parse(&text).unwrap();
+  assert_eq!(trace.untraced, ["glPixelStorei", "glPolygonOffset"]);
+  assert_eq!(trace.events.len(), 1);
+  // the rest still replays, but the replay reports that it's incomplete.
+  mock.clear_calls();
+  let err = TraceReplayer::new(trace).replay_all(&gl).unwrap_err();
+  assert!(err.contains("glPixelStorei"), "{}", err);
+  assert_eq!(mock.call_names(), ["glPointSize"]);
+
+  // element draws are only replayed with an element buffer bound, since
+  // otherwise their offset would be read as a pointer.
+  let draw =
+    Trace::parse("{\"call\":\"glDrawElements\",\"args\":[4,3,5125,0]}")
+      .unwrap();
+  mock.clear_calls();
+  let err = TraceReplayer::new(draw).replay_all(&gl).unwrap_err();
+  assert!(err.contains("element buffer"), "{}", err);
+  assert_eq!(mock.call_names(), ["glGetIntegerv"]);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_trace_and_replay_a_sample() {
+  let ctx = match HeadlessContext::new(32, 32) {
+    Ok(ctx) => ctx,
+    Err(e) => {
+      println!("skipping, no headless EGL available: {}", e);
+      return;
+    }
+  };
+  let path = std::env::temp_dir()
+    .join(format!("superbible7_trace_test_{}.jsonl", std::process::id()));
+  let config = AppConfig {
+    frame_limit: Some(3),
+    trace_path: Some(path.clone()),
+    ..AppConfig::default()
+  };
+  run_with_context(&ctx, &config, samples::Ch03::startup, Vec::new).unwrap();
+  drop(ctx);
+  let trace = Trace::load(&path).unwrap();
+  std::fs::remove_file(&path).unwrap();
+  assert_eq!(trace.frame_count(), 3);
+  let draws = trace
+    .events
+    .iter()
+    .filter(|e| matches!(e, TraceEvent::Call(c) if c.func == "glDrawArrays"))
+    .count();
+  assert_eq!(draws, 3);
+
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let mut replayer = TraceReplayer::new(trace);
+  replayer.replay_all(&gl).unwrap();
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_trace_maps_reflected_uniform_locations() {
+  let (ctx, _) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let out = SharedBuf::default();
+  let (tracer, gl) = GlTracer::load(&ctx, out.clone()).unwrap();
+  let program = gl
+    .create_shader_program(
+      ShaderEnum::Fragment,
+      "#version 450
+      layout(location = 0) out vec4 color;
+      uniform vec4 tint;
+      uniform float weights[3];
+      void main() { color = tint * weights[2]; }",
+    )
+    .unwrap();
+  let reflection = gl.get_program_reflection(program);
+  tracer.finish().unwrap();
+  let expected: Vec<(String, i64)> = reflection
+    .uniforms
+    .iter()
+    .map(|u| (u.name.clone(), u.location.into()))
+    .collect();
+  assert_eq!(expected.len(), 2);
+
+  let text = String::from_utf8(out.0.borrow().clone()).unwrap();
+  let trace = Trace::parse(&text).unwrap();
+  let recorded: Vec<(String, i64)> = trace
+    .events
+    .iter()
+    .filter_map(|e| match e {
+      TraceEvent::Call(c) if c.func == "glGetProgramResourceLocation" => {
+        Some((c.str(2).unwrap().to_owned(), c.ret_int().unwrap()))
+      }
+      _ => None,
+    })
+    .collect();
+  assert_eq!(recorded, expected);
+
+  // the replayed program has a different name, and its locations are mapped.
+  let gl = ctx.load_gl_functions().unwrap();
+  gl.create_program().unwrap();
+  let mut replayer = TraceReplayer::new(trace);
+  replayer.replay_all(&gl).unwrap();
+  let new = replayer.names[&(NameKind::ShaderOrProgram, program.0.get())];
+  assert_ne!(new, program.0.get());
+  for (_, location) in expected {
+    let location = location as i32;
+    assert_eq!(replayer.locations[&(new, location)], location);
+  }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_trace_notes_element_draws_without_an_element_buffer() {
+  let (ctx, _) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let out = SharedBuf::default();
+  let (tracer, gl) = GlTracer::load(&ctx, out.clone()).unwrap();
+  unsafe {
+    let mut vao = 0;
+    gl.CreateVertexArrays(1, &mut vao);
+    gl.BindVertexArray(vao);
+    // a client memory index array (of nothing, so that it's harmless).
+    let indices = [0_u32; 3];
+    gl.DrawElements(GL_TRIANGLES, 0, GL_UNSIGNED_INT, indices.as_ptr().cast());
+  }
+  tracer.finish().unwrap();
+  let text = String::from_utf8(out.0.borrow().clone()).unwrap();
+  let trace = Trace::parse(&text).unwrap();
+  assert_eq!(trace.untraced, ["glDrawElements"]);
+  assert!(!trace
+    .events
+    .iter()
+    .any(|e| matches!(e, TraceEvent::Call(c) if c.func == "glDrawElements")));
+}
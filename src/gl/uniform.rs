@@ -0,0 +1,487 @@
+use super::*;
+use std::collections::HashMap;
+
+/// The GLSL types of uniforms and other program variables (as reported by
+/// program introspection).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniformTypeEnum {
+  Float,
+  Vec2,
+  Vec3,
+  Vec4,
+  Int,
+  IVec2,
+  IVec3,
+  IVec4,
+  UInt,
+  UVec2,
+  UVec3,
+  UVec4,
+  Bool,
+  BVec2,
+  BVec3,
+  BVec4,
+  Mat2,
+  Mat3,
+  Mat4,
+  Mat2x3,
+  Mat2x4,
+  Mat3x2,
+  Mat3x4,
+  Mat4x2,
+  Mat4x3,
+  /// Any of the `sampler` types, which are set with an `int` texture unit.
+  Sampler(u32),
+  /// Any of the `image` types, which are set with an `int` image unit.
+  Image(u32),
+  /// Some other type (eg: doubles), which there are no setters for.
+  Other(u32),
+}
+impl UniformTypeEnum {
+  /// Converts a type value from `glGetActiveUniform`.
+  #[allow(non_upper_case_globals)]
+  pub fn from_enum(ty: GLenum) -> Self {
+    match ty {
+      GL_FLOAT => Self::Float,
+      GL_FLOAT_VEC2 => Self::Vec2,
+      GL_FLOAT_VEC3 => Self::Vec3,
+      GL_FLOAT_VEC4 => Self::Vec4,
+      GL_INT => Self::Int,
+      GL_INT_VEC2 => Self::IVec2,
+      GL_INT_VEC3 => Self::IVec3,
+      GL_INT_VEC4 => Self::IVec4,
+      GL_UNSIGNED_INT => Self::UInt,
+      GL_UNSIGNED_INT_VEC2 => Self::UVec2,
+      GL_UNSIGNED_INT_VEC3 => Self::UVec3,
+      GL_UNSIGNED_INT_VEC4 => Self::UVec4,
+      GL_BOOL => Self::Bool,
+      GL_BOOL_VEC2 => Self::BVec2,
+      GL_BOOL_VEC3 => Self::BVec3,
+      GL_BOOL_VEC4 => Self::BVec4,
+      GL_FLOAT_MAT2 => Self::Mat2,
+      GL_FLOAT_MAT3 => Self::Mat3,
+      GL_FLOAT_MAT4 => Self::Mat4,
+      GL_FLOAT_MAT2x3 => Self::Mat2x3,
+      GL_FLOAT_MAT2x4 => Self::Mat2x4,
+      GL_FLOAT_MAT3x2 => Self::Mat3x2,
+      GL_FLOAT_MAT3x4 => Self::Mat3x4,
+      GL_FLOAT_MAT4x2 => Self::Mat4x2,
+      GL_FLOAT_MAT4x3 => Self::Mat4x3,
+      GL_SAMPLER_1D
+      | GL_SAMPLER_2D
+      | GL_SAMPLER_3D
+      | GL_SAMPLER_CUBE
+      | GL_SAMPLER_1D_SHADOW
+      | GL_SAMPLER_2D_SHADOW
+      | GL_SAMPLER_1D_ARRAY
+      | GL_SAMPLER_2D_ARRAY
+      | GL_SAMPLER_1D_ARRAY_SHADOW
+      | GL_SAMPLER_2D_ARRAY_SHADOW
+      | GL_SAMPLER_2D_MULTISAMPLE
+      | GL_SAMPLER_2D_MULTISAMPLE_ARRAY
+      | GL_SAMPLER_CUBE_SHADOW
+      | GL_SAMPLER_BUFFER
+      | GL_SAMPLER_2D_RECT
+      | GL_SAMPLER_2D_RECT_SHADOW
+      | GL_SAMPLER_CUBE_MAP_ARRAY
+      | GL_SAMPLER_CUBE_MAP_ARRAY_SHADOW
+      | GL_INT_SAMPLER_1D
+      | GL_INT_SAMPLER_2D
+      | GL_INT_SAMPLER_3D
+      | GL_INT_SAMPLER_CUBE
+      | GL_INT_SAMPLER_1D_ARRAY
+      | GL_INT_SAMPLER_2D_ARRAY
+      | GL_INT_SAMPLER_2D_MULTISAMPLE
+      | GL_INT_SAMPLER_2D_MULTISAMPLE_ARRAY
+      | GL_INT_SAMPLER_BUFFER
+      | GL_INT_SAMPLER_2D_RECT
+      | GL_INT_SAMPLER_CUBE_MAP_ARRAY
+      | GL_UNSIGNED_INT_SAMPLER_1D
+      | GL_UNSIGNED_INT_SAMPLER_2D
+      | GL_UNSIGNED_INT_SAMPLER_3D
+      | GL_UNSIGNED_INT_SAMPLER_CUBE
+      | GL_UNSIGNED_INT_SAMPLER_1D_ARRAY
+      | GL_UNSIGNED_INT_SAMPLER_2D_ARRAY
+      | GL_UNSIGNED_INT_SAMPLER_2D_MULTISAMPLE
+      | GL_UNSIGNED_INT_SAMPLER_2D_MULTISAMPLE_ARRAY
+      | GL_UNSIGNED_INT_SAMPLER_BUFFER
+      | GL_UNSIGNED_INT_SAMPLER_2D_RECT
+      | GL_UNSIGNED_INT_SAMPLER_CUBE_MAP_ARRAY => Self::Sampler(ty.0),
+      GL_IMAGE_1D
+      | GL_IMAGE_2D
+      | GL_IMAGE_3D
+      | GL_IMAGE_2D_RECT
+      | GL_IMAGE_CUBE
+      | GL_IMAGE_BUFFER
+      | GL_IMAGE_1D_ARRAY
+      | GL_IMAGE_2D_ARRAY
+      | GL_IMAGE_CUBE_MAP_ARRAY
+      | GL_IMAGE_2D_MULTISAMPLE
+      | GL_IMAGE_2D_MULTISAMPLE_ARRAY
+      | GL_INT_IMAGE_1D
+      | GL_INT_IMAGE_2D
+      | GL_INT_IMAGE_3D
+      | GL_INT_IMAGE_2D_RECT
+      | GL_INT_IMAGE_CUBE
+      | GL_INT_IMAGE_BUFFER
+      | GL_INT_IMAGE_1D_ARRAY
+      | GL_INT_IMAGE_2D_ARRAY
+      | GL_INT_IMAGE_CUBE_MAP_ARRAY
+      | GL_INT_IMAGE_2D_MULTISAMPLE
+      | GL_INT_IMAGE_2D_MULTISAMPLE_ARRAY
+      | GL_UNSIGNED_INT_IMAGE_1D
+      | GL_UNSIGNED_INT_IMAGE_2D
+      | GL_UNSIGNED_INT_IMAGE_3D
+      | GL_UNSIGNED_INT_IMAGE_2D_RECT
+      | GL_UNSIGNED_INT_IMAGE_CUBE
+      | GL_UNSIGNED_INT_IMAGE_BUFFER
+      | GL_UNSIGNED_INT_IMAGE_1D_ARRAY
+      | GL_UNSIGNED_INT_IMAGE_2D_ARRAY
+      | GL_UNSIGNED_INT_IMAGE_CUBE_MAP_ARRAY
+      | GL_UNSIGNED_INT_IMAGE_2D_MULTISAMPLE
+      | GL_UNSIGNED_INT_IMAGE_2D_MULTISAMPLE_ARRAY => Self::Image(ty.0),
+      GLenum(other) => Self::Other(other),
+    }
+  }
+}
+
+/// A Rust type that can be assigned to uniforms of some GLSL types.
+///
+/// The implementations are:
+/// * `f32`, `i32`, `u32`, `bool`, and arrays of 2 to 4 of them, for the
+///   scalar and vector types. Any of these can also set a `bool` type of the
+///   same size.
+/// * `i32` also sets `sampler` and `image` types (to a unit index).
+/// * `[[f32; R]; C]` for a `matCxR` (column-major), with `C` and `R` from 2
+///   to 4.
+pub trait UniformValue: Copy {
+  /// If values of this type can be assigned to the GLSL type given.
+  fn accepts(ty: UniformTypeEnum) -> bool;
+
+  /// Assigns `values` to consecutive elements of the uniform at `location`.
+  ///
+  /// ## Safety
+  /// * `location` must be a uniform of `program` that [accepts](Self::accepts)
+  ///   this type, with at least `values.len()` elements from there on.
+  unsafe fn program_uniform(
+    gl: &GlFns, program: u32, location: i32, values: &[Self],
+  );
+}
+macro_rules! impl_uniform_value {
+  ($t:ty, $f:ident, [$($ty:pat),+]) => {
+    impl UniformValue for $t {
+      fn accepts(ty: UniformTypeEnum) -> bool {
+        use UniformTypeEnum::*;
+        matches!(ty, $($ty)|+)
+      }
+      unsafe fn program_uniform(
+        gl: &GlFns, program: u32, location: i32, values: &[Self],
+      ) {
+        gl.$f(
+          program,
+          location,
+          values.len().try_into().unwrap(),
+          values.as_ptr().cast(),
+        )
+      }
+    }
+  };
+}
+impl_uniform_value!(f32, ProgramUniform1fv, [Float, Bool]);
+impl_uniform_value!([f32; 2], ProgramUniform2fv, [Vec2, BVec2]);
+impl_uniform_value!([f32; 3], ProgramUniform3fv, [Vec3, BVec3]);
+impl_uniform_value!([f32; 4], ProgramUniform4fv, [Vec4, BVec4]);
+impl_uniform_value!(i32, ProgramUniform1iv, [Int, Bool, Sampler(_), Image(_)]);
+impl_uniform_value!([i32; 2], ProgramUniform2iv, [IVec2, BVec2]);
+impl_uniform_value!([i32; 3], ProgramUniform3iv, [IVec3, BVec3]);
+impl_uniform_value!([i32; 4], ProgramUniform4iv, [IVec4, BVec4]);
+impl_uniform_value!(u32, ProgramUniform1uiv, [UInt, Bool]);
+impl_uniform_value!([u32; 2], ProgramUniform2uiv, [UVec2, BVec2]);
+impl_uniform_value!([u32; 3], ProgramUniform3uiv, [UVec3, BVec3]);
+impl_uniform_value!([u32; 4], ProgramUniform4uiv, [UVec4, BVec4]);
+macro_rules! impl_uniform_value_bool {
+  ($t:ty, $n:literal, $f:ident, $ty:ident) => {
+    impl UniformValue for $t {
+      fn accepts(ty: UniformTypeEnum) -> bool {
+        ty == UniformTypeEnum::$ty
+      }
+      unsafe fn program_uniform(
+        gl: &GlFns, program: u32, location: i32, values: &[Self],
+      ) {
+        // Note: GL has no bool setters, so we widen to `u32` first.
+        let bools: &[bool] = core::slice::from_raw_parts(
+          values.as_ptr().cast(),
+          values.len() * $n,
+        );
+        let widened: Vec<u32> = bools.iter().map(|&b| b as u32).collect();
+        gl.$f(
+          program,
+          location,
+          values.len().try_into().unwrap(),
+          widened.as_ptr(),
+        )
+      }
+    }
+  };
+}
+impl_uniform_value_bool!(bool, 1, ProgramUniform1uiv, Bool);
+impl_uniform_value_bool!([bool; 2], 2, ProgramUniform2uiv, BVec2);
+impl_uniform_value_bool!([bool; 3], 3, ProgramUniform3uiv, BVec3);
+impl_uniform_value_bool!([bool; 4], 4, ProgramUniform4uiv, BVec4);
+macro_rules! impl_uniform_value_matrix {
+  ($cols:literal, $rows:literal, $f:ident, $ty:ident) => {
+    impl UniformValue for [[f32; $rows]; $cols] {
+      fn accepts(ty: UniformTypeEnum) -> bool {
+        ty == UniformTypeEnum::$ty
+      }
+      unsafe fn program_uniform(
+        gl: &GlFns, program: u32, location: i32, values: &[Self],
+      ) {
+        gl.$f(
+          program,
+          location,
+          values.len().try_into().unwrap(),
+          0,
+          values.as_ptr().cast(),
+        )
+      }
+    }
+  };
+}
+impl_uniform_value_matrix!(2, 2, ProgramUniformMatrix2fv, Mat2);
+impl_uniform_value_matrix!(3, 3, ProgramUniformMatrix3fv, Mat3);
+impl_uniform_value_matrix!(4, 4, ProgramUniformMatrix4fv, Mat4);
+impl_uniform_value_matrix!(2, 3, ProgramUniformMatrix2x3fv, Mat2x3);
+impl_uniform_value_matrix!(2, 4, ProgramUniformMatrix2x4fv, Mat2x4);
+impl_uniform_value_matrix!(3, 2, ProgramUniformMatrix3x2fv, Mat3x2);
+impl_uniform_value_matrix!(3, 4, ProgramUniformMatrix3x4fv, Mat3x4);
+impl_uniform_value_matrix!(4, 2, ProgramUniformMatrix4x2fv, Mat4x2);
+impl_uniform_value_matrix!(4, 3, ProgramUniformMatrix4x3fv, Mat4x3);
+
+/// One active uniform of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniformInfo {
+  /// The location of the uniform (or of element 0, for arrays).
+  pub location: i32,
+  /// The GLSL type of the uniform.
+  pub ty: UniformTypeEnum,
+  /// The number of array elements (1 if it's not an array).
+  pub array_len: i32,
+}
+
+/// The active uniforms of a program, looked up by name.
+///
+/// Get this with [`get_program_uniforms`](GlFnsRusty::get_program_uniforms)
+/// after the program is linked, and keep it around so that the
+/// `program_uniform` functions don't need to ask GL for locations every time.
+/// Only default-block uniforms are listed (not those in uniform blocks).
+///
+/// Arrays are listed by their plain name (eg: `lights`, not `lights[0]`).
+#[derive(Debug, Clone)]
+pub struct ProgramUniforms {
+  program: ProgramID,
+  uniforms: HashMap<String, UniformInfo>,
+}
+impl ProgramUniforms {
+  /// The program these uniforms are from.
+  pub fn program(&self) -> ProgramID {
+    self.program
+  }
+
+  /// Gets the info of a uniform, if the program has an active uniform with
+  /// that name.
+  pub fn get(&self, name: &str) -> Option<UniformInfo> {
+    self.uniforms.get(name).copied()
+  }
+
+  /// Iterates over all the uniforms, in no particular order.
+  pub fn iter(&self) -> impl Iterator<Item = (&str, UniformInfo)> + '_ {
+    self.uniforms.iter().map(|(name, info)| (name.as_str(), *info))
+  }
+
+  /// Finds the uniform and checks that `T` can be assigned `count` times.
+  fn check<T: UniformValue>(
+    &self, name: &str, count: usize,
+  ) -> Result<UniformInfo, String> {
+    let info = self
+      .get(name)
+      .ok_or_else(|| format!("There's no active uniform named `{}`.", name))?;
+    if !T::accepts(info.ty) {
+      return Err(format!(
+        "Uniform `{}` is a {:?}, which can't be set from a `{}`.",
+        name,
+        info.ty,
+        core::any::type_name::<T>()
+      ));
+    }
+    if count > info.array_len as usize {
+      return Err(format!(
+        "Uniform `{}` has {} elements, but {} values were given.",
+        name, info.array_len, count
+      ));
+    }
+    Ok(info)
+  }
+}
+
+impl GlFnsRusty {
+  /// Introspects the active uniforms of a linked program.
+  pub fn get_program_uniforms(&self, program: ProgramID) -> ProgramUniforms {
+    let mut uniforms = HashMap::new();
+    let mut count = 0;
+    let mut max_name_len = 0;
+    unsafe {
+      self.GetProgramiv(program.0.get(), GL_ACTIVE_UNIFORMS, &mut count);
+      self.GetProgramiv(
+        program.0.get(),
+        GL_ACTIVE_UNIFORM_MAX_LENGTH,
+        &mut max_name_len,
+      );
+    }
+    let mut name_buf: Vec<u8> = vec![0; max_name_len.max(1) as usize];
+    for index in 0..(count as u32) {
+      let mut name_len = 0;
+      let mut array_len = 0;
+      let mut ty = GLenum(0);
+      unsafe {
+        self.GetActiveUniform(
+          program.0.get(),
+          index,
+          name_buf.len().try_into().unwrap(),
+          &mut name_len,
+          &mut array_len,
+          &mut ty,
+          name_buf.as_mut_ptr(),
+        )
+      };
+      let name_bytes = &name_buf[..name_len as usize];
+      let location = {
+        let mut name_null = name_bytes.to_vec();
+        name_null.push(0);
+        unsafe { self.GetUniformLocation(program.0.get(), name_null.as_ptr()) }
+      };
+      if location < 0 {
+        // it's in a uniform block, or it's an atomic counter.
+        continue;
+      }
+      let mut name = String::from_utf8_lossy(name_bytes).into_owned();
+      if name.ends_with("[0]") {
+        name.truncate(name.len() - 3);
+      }
+      let ty = UniformTypeEnum::from_enum(ty);
+      uniforms.insert(name, UniformInfo { location, ty, array_len });
+    }
+    ProgramUniforms { program, uniforms }
+  }
+
+  /// Assigns a value to a uniform of a program.
+  ///
+  /// For array uniforms this sets element 0.
+  ///
+  /// ## Failure
+  /// * If the uniform isn't active, or the type of the value doesn't match the
+  ///   GLSL type (see [`UniformValue`]), you get an error and GL isn't called.
+  pub fn program_uniform<T: UniformValue>(
+    &self, uniforms: &ProgramUniforms, name: &str, value: T,
+  ) -> Result<(), String> {
+    self.program_uniform_array(uniforms, name, &[value])
+  }
+
+  /// Assigns values to the first elements of an array uniform of a program.
+  ///
+  /// ## Failure
+  /// * As [`program_uniform`](Self::program_uniform), and also if there are
+  ///   more values than the array has elements.
+  pub fn program_uniform_array<T: UniformValue>(
+    &self, uniforms: &ProgramUniforms, name: &str, values: &[T],
+  ) -> Result<(), String> {
+    let info = uniforms.check::<T>(name, values.len())?;
+    if !values.is_empty() {
+      unsafe {
+        T::program_uniform(
+          &self.0,
+          uniforms.program.0.get(),
+          info.location,
+          values,
+        )
+      };
+    }
+    Ok(())
+  }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_program_uniform_type_checking() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let vertex_shader = gl
+    .create_compiled_shader(
+      ShaderEnum::Vertex,
+      "#version 450 core
+      uniform mat4 mvp;
+      uniform vec2 offsets[3];
+      uniform bool flip;
+      void main() {
+        vec2 o = offsets[0] + offsets[1] + offsets[2];
+        gl_Position = mvp * vec4(o, flip ? -1.0 : 1.0, 1.0);
+      }",
+    )
+    .unwrap();
+  let fragment_shader = gl
+    .create_compiled_shader(
+      ShaderEnum::Fragment,
+      "#version 450 core
+      uniform sampler2D tex;
+      uniform uint mode;
+      out vec4 color;
+      void main() {
+        color = texture(tex, vec2(0.5)) * float(mode);
+      }",
+    )
+    .unwrap();
+  let program =
+    gl.create_linked_program(&[vertex_shader, fragment_shader]).unwrap();
+  let uniforms = gl.get_program_uniforms(program);
+
+  assert_eq!(uniforms.get("mvp").unwrap().ty, UniformTypeEnum::Mat4);
+  assert_eq!(uniforms.get("offsets").unwrap().array_len, 3);
+  assert!(matches!(
+    uniforms.get("tex").unwrap().ty,
+    UniformTypeEnum::Sampler(_)
+  ));
+
+  let identity = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0_f32],
+  ];
+  gl.program_uniform(&uniforms, "mvp", identity).unwrap();
+  gl.program_uniform_array(&uniforms, "offsets", &[[1.0_f32, 2.0]; 3]).unwrap();
+  gl.program_uniform(&uniforms, "flip", true).unwrap();
+  gl.program_uniform(&uniforms, "tex", 3_i32).unwrap();
+  gl.program_uniform(&uniforms, "mode", 2_u32).unwrap();
+
+  let mut out = [0.0_f32; 2];
+  let location = uniforms.get("offsets").unwrap().location;
+  unsafe {
+    gl.GetnUniformfv(program.0.get(), location + 2, 8, out.as_mut_ptr())
+  };
+  assert_eq!(out, [1.0, 2.0]);
+
+  assert!(gl.program_uniform(&uniforms, "mvp", 1.0_f32).is_err());
+  assert!(gl.program_uniform(&uniforms, "mode", 2_i32).is_err());
+  assert!(gl.program_uniform(&uniforms, "tex", 3_u32).is_err());
+  assert!(gl
+    .program_uniform_array(&uniforms, "offsets", &[[0.0_f32; 2]; 4])
+    .is_err());
+  assert!(gl.program_uniform(&uniforms, "not_there", 1.0_f32).is_err());
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+
+  gl.delete_program(program);
+  gl.delete_shader(vertex_shader);
+  gl.delete_shader(fragment_shader);
+}
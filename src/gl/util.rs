@@ -1,5 +1,9 @@
 use super::*;
 
+/// A `glDebugMessageCallback` that prints each message with `println!`.
+///
+/// ## Safety
+/// * `message` must point to at least `length` bytes (GL guarantees this).
 pub unsafe extern "system" fn println_debug_message_callback(
   source: GLenum, type_: GLenum, id: u32, severity: GLenum, length: i32,
   message: *const u8, _user_param: *const c_void,
@@ -92,6 +96,25 @@ impl GlFnsRusty {
     unsafe { self.PolygonMode(GL_FRONT_AND_BACK, mode.as_enum()) }
   }
 
+  /// Gets the name of the renderer (usually the GPU and driver).
+  pub fn get_renderer_string(&self) -> String {
+    self.get_string(GL_RENDERER)
+  }
+
+  /// Gets the version string of the current GL context.
+  pub fn get_version_string(&self) -> String {
+    self.get_string(GL_VERSION)
+  }
+
+  fn get_string(&self, name: GLenum) -> String {
+    let p = unsafe { self.GetString(name) };
+    if p.is_null() {
+      String::new()
+    } else {
+      min_alloc_lossy_into_string(unsafe { gather_null_terminated_bytes(p) })
+    }
+  }
+
   /// Gets a vector of all the extensions supported by the current GL context.
   pub fn get_all_extension_strings(&self) -> Vec<String> {
     let mut num_extensions = 0;
@@ -106,3 +129,30 @@ impl GlFnsRusty {
     v
   }
 }
+
+#[test]
+fn test_get_all_extension_strings_mock() {
+  let (mock, gl) = MockGl::new();
+  assert!(gl.get_all_extension_strings().is_empty());
+  mock.set_extensions(&["GL_ARB_gl_spirv", "GL_KHR_debug"]);
+  mock.clear_calls();
+  assert_eq!(
+    gl.get_all_extension_strings(),
+    ["GL_ARB_gl_spirv", "GL_KHR_debug"]
+  );
+  let calls = mock.calls();
+  assert_eq!(calls[0].args[0], MockArg::Enum(GL_NUM_EXTENSIONS));
+  assert_eq!(calls[2].args, [MockArg::Enum(GL_EXTENSIONS), MockArg::Int(1)]);
+  assert_eq!(calls.len(), 3);
+
+  gl.point_size(2.5);
+  gl.polygon_mode(PolygonEnum::Line);
+  let calls = mock.calls();
+  assert_eq!(calls[3].args, [MockArg::Float(2.5)]);
+  assert_eq!(
+    calls[4].args,
+    [MockArg::Enum(GL_FRONT_AND_BACK), MockArg::Enum(GL_LINE)]
+  );
+  assert_eq!(gl.get_version_string(), "4.6 MockGl");
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
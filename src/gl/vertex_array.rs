@@ -30,6 +30,8 @@ impl GlFnsRusty {
 
   /// Binds the named vertex array (`Some`), or clears the binding (`None`).
   pub fn bind_vertex_array(&self, opt_array_id: Option<VertexArrayID>) {
-    self.BindVertexArray(unsafe { core::mem::transmute(opt_array_id) })
+    self.BindVertexArray(unsafe {
+      core::mem::transmute::<Option<VertexArrayID>, u32>(opt_array_id)
+    })
   }
 }
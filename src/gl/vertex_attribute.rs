@@ -1,4 +1,5 @@
 use super::*;
+use bytemuck::Pod;
 
 impl GlFnsRusty {
   /// Specifies the value of a generic vertex attribute.
@@ -16,3 +17,419 @@ impl GlFnsRusty {
     unsafe { self.VertexAttrib4fv(index, &v) }
   }
 }
+
+/// The component types that a vertex attribute can be stored as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AttribTypeEnum {
+  I8 = GL_BYTE.0,
+  U8 = GL_UNSIGNED_BYTE.0,
+  I16 = GL_SHORT.0,
+  U16 = GL_UNSIGNED_SHORT.0,
+  I32 = GL_INT.0,
+  U32 = GL_UNSIGNED_INT.0,
+  F32 = GL_FLOAT.0,
+}
+impl AttribTypeEnum {
+  pub fn as_enum(self) -> GLenum {
+    GLenum(self as _)
+  }
+
+  /// The size of one component, in bytes.
+  pub const fn size(self) -> u32 {
+    match self {
+      Self::I8 | Self::U8 => 1,
+      Self::I16 | Self::U16 => 2,
+      Self::I32 | Self::U32 | Self::F32 => 4,
+    }
+  }
+}
+
+/// A type that can be used as a vertex struct field.
+///
+/// This is implemented for the scalar types GL understands, and for arrays
+/// of 1 to 4 of them.
+pub trait AttribValue: Pod {
+  /// How many components (1 to 4) the attribute has.
+  const COMPONENTS: i32;
+  /// The type of each component.
+  const COMPONENT_TYPE: AttribTypeEnum;
+}
+macro_rules! impl_attrib_value {
+  ($t:ty, $attrib_type:expr) => {
+    impl AttribValue for $t {
+      const COMPONENTS: i32 = 1;
+      const COMPONENT_TYPE: AttribTypeEnum = $attrib_type;
+    }
+    impl AttribValue for [$t; 1] {
+      const COMPONENTS: i32 = 1;
+      const COMPONENT_TYPE: AttribTypeEnum = $attrib_type;
+    }
+    impl AttribValue for [$t; 2] {
+      const COMPONENTS: i32 = 2;
+      const COMPONENT_TYPE: AttribTypeEnum = $attrib_type;
+    }
+    impl AttribValue for [$t; 3] {
+      const COMPONENTS: i32 = 3;
+      const COMPONENT_TYPE: AttribTypeEnum = $attrib_type;
+    }
+    impl AttribValue for [$t; 4] {
+      const COMPONENTS: i32 = 4;
+      const COMPONENT_TYPE: AttribTypeEnum = $attrib_type;
+    }
+  };
+}
+impl_attrib_value!(i8, AttribTypeEnum::I8);
+impl_attrib_value!(u8, AttribTypeEnum::U8);
+impl_attrib_value!(i16, AttribTypeEnum::I16);
+impl_attrib_value!(u16, AttribTypeEnum::U16);
+impl_attrib_value!(i32, AttribTypeEnum::I32);
+impl_attrib_value!(u32, AttribTypeEnum::U32);
+impl_attrib_value!(f32, AttribTypeEnum::F32);
+
+/// How the shader sees an attribute's data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttribModeEnum {
+  /// Converted to float directly (eg: `255_u8` becomes `255.0`).
+  Float,
+  /// Converted to float and normalized (eg: `255_u8` becomes `1.0`).
+  Normalized,
+  /// Kept as integers, for `int`/`uint` shader inputs.
+  Integer,
+}
+
+/// One attribute within a [`VertexFormat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertexAttribute {
+  /// The shader input location.
+  pub location: u32,
+  /// How many components (1 to 4) the attribute has.
+  pub components: i32,
+  /// The type of each component.
+  pub component_type: AttribTypeEnum,
+  /// How the data is converted for the shader.
+  pub mode: AttribModeEnum,
+  /// The byte offset of the attribute within the vertex.
+  pub relative_offset: u32,
+}
+impl VertexAttribute {
+  /// Describes an attribute of type `A` at the location and offset given.
+  ///
+  /// ## Panics
+  /// * If `relative_offset` doesn't fit in a `u32` (when used in a constant,
+  ///   as [`impl_vertex_format!`] does, that's a compile error instead).
+  pub const fn new<A: AttribValue>(
+    location: u32, relative_offset: usize, mode: AttribModeEnum,
+  ) -> Self {
+    // Note: `u32::try_from` can't be used in a `const fn`.
+    assert!(
+      relative_offset <= u32::MAX as usize,
+      "The relative offset doesn't fit in a u32."
+    );
+    Self {
+      location,
+      components: A::COMPONENTS,
+      component_type: A::COMPONENT_TYPE,
+      mode,
+      relative_offset: relative_offset as u32,
+    }
+  }
+
+  /// The size of the attribute's data, in bytes.
+  pub const fn size(&self) -> u32 {
+    self.components as u32 * self.component_type.size()
+  }
+}
+
+/// A vertex struct that can describe its own attributes.
+///
+/// Usually you'd implement this with [`impl_vertex_format!`], which computes
+/// the attribute offsets for you.
+pub trait VertexFormat: Pod {
+  /// The attributes of the vertex.
+  const ATTRIBUTES: &'static [VertexAttribute];
+}
+
+/// Implements [`VertexFormat`] for a `#[repr(C)]` struct.
+///
+/// List each field that's an attribute, along with its type and location.
+/// Fields are converted to float unless they're marked `normalized` or
+/// `integer`. Fields that aren't listed are skipped.
+///
+/// ```
+/// # use superbible7::*;
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct Vertex {
+///   position: [f32; 3],
+///   color: [u8; 4],
+/// }
+/// unsafe impl bytemuck::Zeroable for Vertex {}
+/// unsafe impl bytemuck::Pod for Vertex {}
+/// impl_vertex_format!(Vertex {
+///   position: [f32; 3] => 0,
+///   color: [u8; 4] => 1 normalized,
+/// });
+/// assert_eq!(Vertex::ATTRIBUTES[1].relative_offset, 12);
+/// ```
+#[macro_export]
+macro_rules! impl_vertex_format {
+  ($t:ty {
+    $($field:ident : $field_ty:ty => $location:literal $($mode:ident)?),*
+    $(,)?
+  }) => {
+    impl $crate::VertexFormat for $t {
+      const ATTRIBUTES: &'static [$crate::VertexAttribute] = &[$(
+        {
+          // Makes sure the listed type is the field's real type.
+          const _: fn(&$t) -> &$field_ty = |v| &v.$field;
+          $crate::VertexAttribute::new::<$field_ty>(
+            $location,
+            ::core::mem::offset_of!($t, $field),
+            $crate::impl_vertex_format!(@mode $($mode)?),
+          )
+        }
+      ),*];
+    }
+  };
+  (@mode) => { $crate::AttribModeEnum::Float };
+  (@mode normalized) => { $crate::AttribModeEnum::Normalized };
+  (@mode integer) => { $crate::AttribModeEnum::Integer };
+}
+
+/// Checks that the attributes of `V` make sense.
+///
+/// ## Failure
+/// * An attribute doesn't fit within `V`.
+/// * Two attributes have the same location.
+/// * An attribute has other than 1 to 4 components.
+/// * A float attribute is marked as `Integer`.
+pub fn validate_vertex_format<V: VertexFormat>() -> Result<(), String> {
+  let vertex_size = core::mem::size_of::<V>() as u32;
+  for (i, attr) in V::ATTRIBUTES.iter().enumerate() {
+    if !(1..=4).contains(&attr.components) {
+      return Err(format!(
+        "Attribute {} has {} components.",
+        attr.location, attr.components
+      ));
+    }
+    let end = attr.relative_offset.checked_add(attr.size());
+    if end.is_none_or(|end| end > vertex_size) {
+      return Err(format!(
+        "Attribute {} is at bytes {}..{}, but the vertex is only {} bytes.",
+        attr.location,
+        attr.relative_offset,
+        u64::from(attr.relative_offset) + u64::from(attr.size()),
+        vertex_size
+      ));
+    }
+    if attr.mode == AttribModeEnum::Integer
+      && attr.component_type == AttribTypeEnum::F32
+    {
+      return Err(format!(
+        "Attribute {} is float data used as an integer attribute.",
+        attr.location
+      ));
+    }
+    if V::ATTRIBUTES[..i].iter().any(|a| a.location == attr.location) {
+      return Err(format!("Location {} is used twice.", attr.location));
+    }
+  }
+  Ok(())
+}
+
+impl GlFnsRusty {
+  /// Sets up a vertex array to read `V` vertices from a buffer.
+  ///
+  /// * `binding`: The vertex buffer binding index to use. All of the
+  ///   attributes of `V` read through this binding.
+  /// * `buffer`: The buffer of tightly packed `V` values, starting at offset
+  ///   0.
+  ///
+  /// Each attribute gets its format set, is pointed at the binding, and is
+  /// enabled.
+  ///
+  /// ## Failure
+  /// * If [`validate_vertex_format`] fails, or an attribute's offset is past
+  ///   `GL_MAX_VERTEX_ATTRIB_RELATIVE_OFFSET` (at least 2047), then you get
+  ///   that error and GL isn't called to set up the vertex array.
+  pub fn vertex_array_from_layout<V: VertexFormat>(
+    &self, vao: VertexArrayID, binding: u32, buffer: BufferID,
+  ) -> Result<(), String> {
+    validate_vertex_format::<V>()?;
+    let mut max_offset = 0;
+    unsafe {
+      self.GetIntegerv(GL_MAX_VERTEX_ATTRIB_RELATIVE_OFFSET, &mut max_offset)
+    };
+    let max_offset = max_offset.max(2047) as u32;
+    if let Some(attr) =
+      V::ATTRIBUTES.iter().find(|a| a.relative_offset > max_offset)
+    {
+      return Err(format!(
+        "Attribute {} is at offset {}, but GL allows at most {}.",
+        attr.location, attr.relative_offset, max_offset
+      ));
+    }
+    let vaobj = vao.0.get();
+    for attr in V::ATTRIBUTES {
+      unsafe {
+        match attr.mode {
+          AttribModeEnum::Integer => self.VertexArrayAttribIFormat(
+            vaobj,
+            attr.location,
+            attr.components,
+            attr.component_type.as_enum(),
+            attr.relative_offset,
+          ),
+          _ => self.VertexArrayAttribFormat(
+            vaobj,
+            attr.location,
+            attr.components,
+            attr.component_type.as_enum(),
+            (attr.mode == AttribModeEnum::Normalized) as _,
+            attr.relative_offset,
+          ),
+        }
+        self.VertexArrayAttribBinding(vaobj, attr.location, binding);
+        self.EnableVertexArrayAttrib(vaobj, attr.location);
+      }
+    }
+    unsafe {
+      self.VertexArrayVertexBuffer(
+        vaobj,
+        binding,
+        buffer.0.get(),
+        0,
+        core::mem::size_of::<V>().try_into().unwrap(),
+      )
+    };
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct TestVertex {
+  position: [f32; 3],
+  color: [u8; 4],
+  _padding: u16,
+  id: u16,
+  uv: [f32; 2],
+}
+#[cfg(test)]
+unsafe impl bytemuck::Zeroable for TestVertex {}
+#[cfg(test)]
+unsafe impl Pod for TestVertex {}
+#[cfg(test)]
+impl_vertex_format!(TestVertex {
+  position: [f32; 3] => 0,
+  color: [u8; 4] => 1 normalized,
+  id: u16 => 2 integer,
+  uv: [f32; 2] => 3,
+});
+
+#[test]
+fn test_impl_vertex_format_offsets() {
+  let attrs = TestVertex::ATTRIBUTES;
+  let offsets: Vec<u32> = attrs.iter().map(|a| a.relative_offset).collect();
+  assert_eq!(offsets, vec![0, 12, 18, 20]);
+  assert_eq!(attrs[0].components, 3);
+  assert_eq!(attrs[1].component_type, AttribTypeEnum::U8);
+  assert_eq!(attrs[1].mode, AttribModeEnum::Normalized);
+  assert_eq!(attrs[2].mode, AttribModeEnum::Integer);
+  assert_eq!(attrs[3].mode, AttribModeEnum::Float);
+  assert_eq!(attrs[3].size(), 8);
+  assert_eq!(validate_vertex_format::<TestVertex>(), Ok(()));
+}
+
+#[test]
+fn test_validate_vertex_format_errors() {
+  #[derive(Clone, Copy)]
+  #[repr(C)]
+  struct Bad([f32; 2]);
+  unsafe impl bytemuck::Zeroable for Bad {}
+  unsafe impl Pod for Bad {}
+  impl VertexFormat for Bad {
+    const ATTRIBUTES: &'static [VertexAttribute] = &[
+      VertexAttribute::new::<[f32; 2]>(0, 0, AttribModeEnum::Float),
+      VertexAttribute::new::<[f32; 2]>(1, 4, AttribModeEnum::Float),
+    ];
+  }
+  assert!(validate_vertex_format::<Bad>().unwrap_err().contains("bytes 4..12"));
+
+  // an offset so big that the end overflows is still just out of bounds.
+  #[derive(Clone, Copy)]
+  #[repr(C)]
+  struct Overflow(f32);
+  unsafe impl bytemuck::Zeroable for Overflow {}
+  unsafe impl Pod for Overflow {}
+  impl VertexFormat for Overflow {
+    const ATTRIBUTES: &'static [VertexAttribute] =
+      &[VertexAttribute::new::<f32>(
+        0,
+        u32::MAX as usize,
+        AttribModeEnum::Float,
+      )];
+  }
+  assert!(validate_vertex_format::<Overflow>().is_err());
+}
+
+#[test]
+fn test_vertex_array_from_layout_checks_the_max_relative_offset() {
+  #[derive(Clone, Copy)]
+  #[repr(C)]
+  struct Far {
+    _skipped: [u8; 4096],
+    x: f32,
+  }
+  unsafe impl bytemuck::Zeroable for Far {}
+  unsafe impl Pod for Far {}
+  impl_vertex_format!(Far { x: f32 => 0 });
+  let (mock, gl) = MockGl::new();
+  let one = NonZeroU32::new(1).unwrap();
+  let err = gl
+    .vertex_array_from_layout::<Far>(VertexArrayID(one), 0, BufferID(one))
+    .unwrap_err();
+  assert!(err.contains("at most 2047"), "{}", err);
+  assert_eq!(mock.call_names(), ["glGetIntegerv"]);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_vertex_array_from_layout() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let vao = gl.create_vertex_arrays::<1>()[0].unwrap();
+  let buffer = gl.create_buffers::<1>()[0].unwrap();
+  let vertices = [TestVertex {
+    position: [0.0; 3],
+    color: [255; 4],
+    _padding: 0,
+    id: 7,
+    uv: [0.5; 2],
+  }; 3];
+  gl.named_buffer_storage(buffer, &vertices, BufferStorageFlags::empty());
+  gl.vertex_array_from_layout::<TestVertex>(vao, 2, buffer).unwrap();
+
+  let get = |index: u32, pname: GLenum| {
+    let mut out = 0;
+    unsafe { gl.GetVertexArrayIndexediv(vao.0.get(), index, pname, &mut out) };
+    out
+  };
+  assert_eq!(get(2, GL_VERTEX_ATTRIB_RELATIVE_OFFSET), 18);
+  assert_eq!(get(2, GL_VERTEX_ATTRIB_ARRAY_INTEGER), 1);
+  assert_eq!(get(1, GL_VERTEX_ATTRIB_ARRAY_NORMALIZED), 1);
+  assert_eq!(get(3, GL_VERTEX_ATTRIB_ARRAY_ENABLED), 1);
+  assert_eq!(get(3, GL_VERTEX_ATTRIB_BINDING), 2);
+  assert_eq!(
+    get(2, GL_VERTEX_BINDING_STRIDE),
+    core::mem::size_of::<TestVertex>() as i32
+  );
+
+  gl.delete_vertex_arrays([Some(vao)]);
+  gl.delete_buffers([Some(buffer)]);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
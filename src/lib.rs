@@ -1,14 +1,32 @@
 use core::{convert::TryInto, num::NonZeroU32};
 
+// Re-exported the same as on Windows (where it also comes from `win32`).
+pub use core::ffi::c_void;
+
 use gl46::*;
 
+#[allow(unused_macros)]
 macro_rules! c_str {
   ($text:expr) => {{
     concat!($text, '\0').as_bytes()
   }};
 }
 
+pub mod string_utils;
+pub use string_utils::*;
+
+pub mod context;
+pub use context::*;
+
+pub mod app;
+pub use app::*;
+
+pub mod math;
+pub use math::*;
+
+#[cfg(windows)]
 pub use win32::*;
+#[cfg(windows)]
 pub mod win32 {
   #![allow(bad_style)]
   //! Win32 API bindings.
@@ -49,8 +67,7 @@ pub mod win32 {
   pub mod opengl32;
   pub use opengl32::*;
 
-  pub mod string_utils;
-  pub use string_utils::*;
+  pub use crate::string_utils::*;
 
   pub mod structures;
   pub use structures::*;
@@ -60,6 +77,41 @@ pub mod win32 {
 
   pub mod user32;
   pub use user32::*;
+
+  pub mod wgl_window;
+  pub use wgl_window::*;
+}
+
+#[cfg(target_os = "linux")]
+pub use egl::*;
+#[cfg(target_os = "linux")]
+pub mod egl {
+  #![allow(bad_style)]
+  //! EGL bindings, for headless GL contexts.
+
+  use super::*;
+  use core::{
+    ffi::{c_int, c_uint},
+    ptr::{null, null_mut, NonNull},
+  };
+
+  pub mod constants;
+  pub use constants::*;
+
+  pub mod dl;
+  pub use dl::*;
+
+  pub mod egl_fns;
+  pub use egl_fns::*;
+
+  pub mod error_code;
+  pub use error_code::*;
+
+  pub mod headless;
+  pub use headless::*;
+
+  pub mod types;
+  pub use types::*;
 }
 
 pub use gl::*;
@@ -75,18 +127,91 @@ pub mod gl {
     }
   }
 
+  #[macro_use]
+  mod gl_signatures;
+
   mod shader;
   pub use shader::*;
 
+  mod shader_error;
+  pub use shader_error::*;
+
+  #[cfg(feature = "naga")]
+  mod glsl_validation;
+  #[cfg(feature = "naga")]
+  pub use glsl_validation::*;
+
+  mod preprocessor;
+  pub use preprocessor::*;
+
   mod program;
   pub use program::*;
 
+  mod program_cache;
+  pub use program_cache::*;
+
+  mod shader_watcher;
+  pub use shader_watcher::*;
+
   mod vertex_array;
   pub use vertex_array::*;
 
+  #[macro_use]
+  mod buffer;
+  pub use buffer::*;
+
+  mod texture;
+  pub use texture::*;
+
+  mod ktx;
+  pub use ktx::*;
+
+  mod sampler;
+  pub use sampler::*;
+
+  mod framebuffer;
+  pub use framebuffer::*;
+
   mod vertex_attribute;
   pub use vertex_attribute::*;
 
+  mod sbm;
+  pub use sbm::*;
+
+  mod uniform;
+  pub use uniform::*;
+
+  mod reflection;
+  pub use reflection::*;
+
+  mod block_layout;
+  pub use block_layout::*;
+
+  mod pipeline;
+  pub use pipeline::*;
+
   mod util;
   pub use util::*;
+
+  mod trace;
+  pub use trace::*;
+
+  #[cfg(test)]
+  mod mock_gl;
+  #[cfg(test)]
+  pub use mock_gl::*;
+}
+
+pub mod samples {
+  //! The book's samples, each as an [`App`].
+  use super::*;
+
+  mod ch02;
+  pub use ch02::*;
+
+  mod ch03;
+  pub use ch03::*;
+
+  mod registry;
+  pub use registry::*;
 }
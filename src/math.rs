@@ -0,0 +1,884 @@
+//! Vectors, matrices, and quaternions, in the style of the book's `vmath`.
+//!
+//! Matrices are column-major like GL expects, and vectors are column vectors
+//! (so `projection * view * model * position`). All the types are
+//! `#[repr(C)]` and [`Pod`], and the vector and square matrix types can be
+//! given directly to the uniform setters, to block layouts, and used as
+//! vertex fields.
+//!
+//! Like `vmath`, angles are given in degrees.
+
+use super::*;
+use bytemuck::{Pod, Zeroable};
+use core::ops::{
+  Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Sub, SubAssign,
+};
+
+macro_rules! impl_vector {
+  ($t:ident, $n:literal, [$($field:ident),+]) => {
+    unsafe impl Zeroable for $t {}
+    unsafe impl Pod for $t {}
+    impl $t {
+      /// Makes a vector from its components.
+      pub const fn new($($field: f32),+) -> Self {
+        Self { $($field),+ }
+      }
+
+      /// Makes a vector with every component set to `v`.
+      pub const fn splat(v: f32) -> Self {
+        Self { $($field: v),+ }
+      }
+
+      /// The sum of the products of each pair of components.
+      pub fn dot(self, other: Self) -> f32 {
+        0.0 $(+ self.$field * other.$field)+
+      }
+
+      pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+      }
+
+      /// This vector scaled to a length of 1.
+      ///
+      /// A zero vector gives NaN components.
+      pub fn normalize(self) -> Self {
+        self / self.length()
+      }
+
+      /// Linear interpolation from `self` (at `t = 0.0`) to `other` (at
+      /// `t = 1.0`).
+      pub fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+      }
+    }
+    impl From<[f32; $n]> for $t {
+      fn from([$($field),+]: [f32; $n]) -> Self {
+        Self { $($field),+ }
+      }
+    }
+    impl From<$t> for [f32; $n] {
+      fn from(v: $t) -> Self {
+        [$(v.$field),+]
+      }
+    }
+    impl Index<usize> for $t {
+      type Output = f32;
+      fn index(&self, i: usize) -> &f32 {
+        &bytemuck::cast_ref::<$t, [f32; $n]>(self)[i]
+      }
+    }
+    impl IndexMut<usize> for $t {
+      fn index_mut(&mut self, i: usize) -> &mut f32 {
+        &mut bytemuck::cast_mut::<$t, [f32; $n]>(self)[i]
+      }
+    }
+    impl Add for $t {
+      type Output = Self;
+      fn add(self, other: Self) -> Self {
+        Self { $($field: self.$field + other.$field),+ }
+      }
+    }
+    impl AddAssign for $t {
+      fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+      }
+    }
+    impl Sub for $t {
+      type Output = Self;
+      fn sub(self, other: Self) -> Self {
+        Self { $($field: self.$field - other.$field),+ }
+      }
+    }
+    impl SubAssign for $t {
+      fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+      }
+    }
+    impl Neg for $t {
+      type Output = Self;
+      fn neg(self) -> Self {
+        Self { $($field: -self.$field),+ }
+      }
+    }
+    /// Component-wise multiplication.
+    impl Mul for $t {
+      type Output = Self;
+      fn mul(self, other: Self) -> Self {
+        Self { $($field: self.$field * other.$field),+ }
+      }
+    }
+    impl Mul<f32> for $t {
+      type Output = Self;
+      fn mul(self, s: f32) -> Self {
+        Self { $($field: self.$field * s),+ }
+      }
+    }
+    impl Div<f32> for $t {
+      type Output = Self;
+      fn div(self, s: f32) -> Self {
+        Self { $($field: self.$field / s),+ }
+      }
+    }
+    impl AttribValue for $t {
+      const COMPONENTS: i32 = $n;
+      const COMPONENT_TYPE: AttribTypeEnum = AttribTypeEnum::F32;
+    }
+    impl UniformValue for $t {
+      fn accepts(ty: UniformTypeEnum) -> bool {
+        <[f32; $n]>::accepts(ty)
+      }
+      unsafe fn program_uniform(
+        gl: &GlFns, program: u32, location: i32, values: &[Self],
+      ) {
+        <[f32; $n]>::program_uniform(
+          gl,
+          program,
+          location,
+          bytemuck::cast_slice(values),
+        )
+      }
+    }
+    impl BlockValue for $t {
+      const COLUMN_SIZE: usize = <[f32; $n]>::COLUMN_SIZE;
+      fn write_block_bytes(
+        &self, out: &mut [u8], ty: UniformTypeEnum, matrix_stride: usize,
+      ) {
+        bytemuck::cast_ref::<$t, [f32; $n]>(self)
+          .write_block_bytes(out, ty, matrix_stride)
+      }
+    }
+  };
+}
+
+/// A 2 component vector (`vec2`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[repr(C)]
+pub struct Vec2 {
+  pub x: f32,
+  pub y: f32,
+}
+impl_vector!(Vec2, 2, [x, y]);
+
+/// A 3 component vector (`vec3`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[repr(C)]
+pub struct Vec3 {
+  pub x: f32,
+  pub y: f32,
+  pub z: f32,
+}
+impl_vector!(Vec3, 3, [x, y, z]);
+impl Vec3 {
+  pub const X: Self = Self::new(1.0, 0.0, 0.0);
+  pub const Y: Self = Self::new(0.0, 1.0, 0.0);
+  pub const Z: Self = Self::new(0.0, 0.0, 1.0);
+
+  /// The cross product, which is perpendicular to both vectors.
+  pub fn cross(self, other: Self) -> Self {
+    Self::new(
+      self.y * other.z - self.z * other.y,
+      self.z * other.x - self.x * other.z,
+      self.x * other.y - self.y * other.x,
+    )
+  }
+
+  /// Adds a `w` component.
+  pub const fn extend(self, w: f32) -> Vec4 {
+    Vec4::new(self.x, self.y, self.z, w)
+  }
+}
+
+/// A 4 component vector (`vec4`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[repr(C)]
+pub struct Vec4 {
+  pub x: f32,
+  pub y: f32,
+  pub z: f32,
+  pub w: f32,
+}
+impl_vector!(Vec4, 4, [x, y, z, w]);
+impl Vec4 {
+  /// Drops the `w` component.
+  pub const fn truncate(self) -> Vec3 {
+    Vec3::new(self.x, self.y, self.z)
+  }
+}
+
+macro_rules! impl_matrix {
+  ($t:ident, $v:ident, $n:literal) => {
+    unsafe impl Zeroable for $t {}
+    unsafe impl Pod for $t {}
+    impl $t {
+      /// Makes a matrix from its columns.
+      pub const fn from_cols(cols: [$v; $n]) -> Self {
+        Self { cols }
+      }
+
+      /// The element at a column and row.
+      pub fn get(&self, col: usize, row: usize) -> f32 {
+        self.cols[col][row]
+      }
+
+      /// Swaps the rows and columns.
+      pub fn transpose(&self) -> Self {
+        let mut out = Self::IDENTITY;
+        for col in 0..$n {
+          for row in 0..$n {
+            out.cols[col][row] = self.cols[row][col];
+          }
+        }
+        out
+      }
+
+      /// The inverse, or `None` if the determinant is 0.
+      pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det == 0.0 {
+          return None;
+        }
+        let mut out = self.adjugate();
+        for col in out.cols.iter_mut() {
+          *col = *col / det;
+        }
+        Some(out)
+      }
+    }
+    impl Default for $t {
+      fn default() -> Self {
+        Self::IDENTITY
+      }
+    }
+    impl From<[[f32; $n]; $n]> for $t {
+      fn from(cols: [[f32; $n]; $n]) -> Self {
+        bytemuck::cast(cols)
+      }
+    }
+    impl From<$t> for [[f32; $n]; $n] {
+      fn from(m: $t) -> Self {
+        bytemuck::cast(m)
+      }
+    }
+    impl Mul for $t {
+      type Output = Self;
+      fn mul(self, other: Self) -> Self {
+        let mut cols = other.cols;
+        for col in cols.iter_mut() {
+          *col = self * *col;
+        }
+        Self { cols }
+      }
+    }
+    impl Mul<$v> for $t {
+      type Output = $v;
+      fn mul(self, v: $v) -> $v {
+        let mut out = $v::splat(0.0);
+        for (i, col) in self.cols.iter().enumerate() {
+          out += *col * v[i];
+        }
+        out
+      }
+    }
+    impl UniformValue for $t {
+      fn accepts(ty: UniformTypeEnum) -> bool {
+        <[[f32; $n]; $n]>::accepts(ty)
+      }
+      unsafe fn program_uniform(
+        gl: &GlFns, program: u32, location: i32, values: &[Self],
+      ) {
+        <[[f32; $n]; $n]>::program_uniform(
+          gl,
+          program,
+          location,
+          bytemuck::cast_slice(values),
+        )
+      }
+    }
+    impl BlockValue for $t {
+      const COLUMN_SIZE: usize = <[[f32; $n]; $n]>::COLUMN_SIZE;
+      fn write_block_bytes(
+        &self, out: &mut [u8], ty: UniformTypeEnum, matrix_stride: usize,
+      ) {
+        bytemuck::cast_ref::<$t, [[f32; $n]; $n]>(self).write_block_bytes(
+          out,
+          ty,
+          matrix_stride,
+        )
+      }
+    }
+  };
+}
+
+/// A 3x3 column-major matrix (`mat3`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Mat3 {
+  pub cols: [Vec3; 3],
+}
+impl_matrix!(Mat3, Vec3, 3);
+impl Mat3 {
+  pub const IDENTITY: Self = Self::from_cols([Vec3::X, Vec3::Y, Vec3::Z]);
+
+  pub fn determinant(&self) -> f32 {
+    let [a, b, c] = self.cols;
+    a.dot(b.cross(c))
+  }
+
+  /// The transpose of the cofactor matrix.
+  fn adjugate(&self) -> Self {
+    let [a, b, c] = self.cols;
+    Self::from_cols([b.cross(c), c.cross(a), a.cross(b)]).transpose()
+  }
+}
+
+/// A 4x4 column-major matrix (`mat4`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Mat4 {
+  pub cols: [Vec4; 4],
+}
+impl_matrix!(Mat4, Vec4, 4);
+impl Mat4 {
+  pub const IDENTITY: Self = Self::from_cols([
+    Vec4::new(1.0, 0.0, 0.0, 0.0),
+    Vec4::new(0.0, 1.0, 0.0, 0.0),
+    Vec4::new(0.0, 0.0, 1.0, 0.0),
+    Vec4::new(0.0, 0.0, 0.0, 1.0),
+  ]);
+
+  pub fn determinant(&self) -> f32 {
+    let adj = self.adjugate();
+    (0..4).map(|i| self.cols[0][i] * adj.cols[i][0]).sum()
+  }
+
+  /// The transpose of the cofactor matrix.
+  fn adjugate(&self) -> Self {
+    let mut out = Self::IDENTITY;
+    for col in 0..4 {
+      for row in 0..4 {
+        // the minor of (row, col), which goes at (col, row).
+        let mut minor = Mat3::IDENTITY;
+        for (c, src_col) in (0..4).filter(|&c| c != col).enumerate() {
+          for (r, src_row) in (0..4).filter(|&r| r != row).enumerate() {
+            minor.cols[c][r] = self.cols[src_col][src_row];
+          }
+        }
+        let sign = if (col + row) % 2 == 0 { 1.0 } else { -1.0 };
+        out.cols[row][col] = sign * minor.determinant();
+      }
+    }
+    out
+  }
+
+  /// The upper left 3x3 of the matrix.
+  ///
+  /// For a model-view matrix `m`, the normal matrix is
+  /// `m.to_mat3().inverse().unwrap().transpose()`.
+  pub fn to_mat3(&self) -> Mat3 {
+    let [a, b, c, _] = self.cols;
+    Mat3::from_cols([a.truncate(), b.truncate(), c.truncate()])
+  }
+
+  /// A translation by `v`.
+  pub fn translate(v: Vec3) -> Self {
+    let mut out = Self::IDENTITY;
+    out.cols[3] = v.extend(1.0);
+    out
+  }
+
+  /// A scale by the amount on each axis.
+  pub fn scale(v: Vec3) -> Self {
+    let mut out = Self::IDENTITY;
+    out.cols[0].x = v.x;
+    out.cols[1].y = v.y;
+    out.cols[2].z = v.z;
+    out
+  }
+
+  /// A rotation of `angle_degrees` counter-clockwise around `axis` (when
+  /// looking from the tip of the axis towards the origin).
+  ///
+  /// The axis doesn't need to be normalized.
+  pub fn rotate(angle_degrees: f32, axis: Vec3) -> Self {
+    Mat3::rotate(angle_degrees, axis).into()
+  }
+
+  /// Rotations around the X, then Y, then Z axes, like `vmath`'s three
+  /// angle `rotate`.
+  pub fn rotate_xyz(x_degrees: f32, y_degrees: f32, z_degrees: f32) -> Self {
+    Self::rotate(z_degrees, Vec3::Z)
+      * Self::rotate(y_degrees, Vec3::Y)
+      * Self::rotate(x_degrees, Vec3::X)
+  }
+
+  /// A perspective projection, like `gluPerspective`.
+  ///
+  /// * `fovy_degrees`: The vertical field of view.
+  /// * `aspect`: The width divided by the height.
+  /// * `near` and `far`: The distances to the clip planes (both positive).
+  pub fn perspective(
+    fovy_degrees: f32, aspect: f32, near: f32, far: f32,
+  ) -> Self {
+    let f = 1.0 / (fovy_degrees.to_radians() / 2.0).tan();
+    Self::from_cols([
+      Vec4::new(f / aspect, 0.0, 0.0, 0.0),
+      Vec4::new(0.0, f, 0.0, 0.0),
+      Vec4::new(0.0, 0.0, (far + near) / (near - far), -1.0),
+      Vec4::new(0.0, 0.0, 2.0 * far * near / (near - far), 0.0),
+    ])
+  }
+
+  /// A perspective projection of the box given, like `glFrustum`.
+  ///
+  /// The box edges are at the near plane, and `near` and `far` are distances
+  /// (both positive).
+  pub fn frustum(
+    left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32,
+  ) -> Self {
+    let [w, h, d] = [right - left, top - bottom, far - near];
+    Self::from_cols([
+      Vec4::new(2.0 * near / w, 0.0, 0.0, 0.0),
+      Vec4::new(0.0, 2.0 * near / h, 0.0, 0.0),
+      Vec4::new(
+        (right + left) / w,
+        (top + bottom) / h,
+        -(far + near) / d,
+        -1.0,
+      ),
+      Vec4::new(0.0, 0.0, -2.0 * far * near / d, 0.0),
+    ])
+  }
+
+  /// An orthographic projection of the box given, like `glOrtho`.
+  pub fn ortho(
+    left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32,
+  ) -> Self {
+    let [w, h, d] = [right - left, top - bottom, far - near];
+    Self::from_cols([
+      Vec4::new(2.0 / w, 0.0, 0.0, 0.0),
+      Vec4::new(0.0, 2.0 / h, 0.0, 0.0),
+      Vec4::new(0.0, 0.0, -2.0 / d, 0.0),
+      Vec4::new(
+        -(right + left) / w,
+        -(top + bottom) / h,
+        -(far + near) / d,
+        1.0,
+      ),
+    ])
+  }
+
+  /// A view matrix for a camera at `eye` looking at `center`, like
+  /// `gluLookAt`.
+  ///
+  /// `up` is roughly the camera's up direction, and must not be parallel to
+  /// the view direction.
+  pub fn look_at(eye: Vec3, center: Vec3, up: Vec3) -> Self {
+    let f = (center - eye).normalize();
+    let s = f.cross(up).normalize();
+    let u = s.cross(f);
+    Self::from_cols([
+      Vec4::new(s.x, u.x, -f.x, 0.0),
+      Vec4::new(s.y, u.y, -f.y, 0.0),
+      Vec4::new(s.z, u.z, -f.z, 0.0),
+      Vec4::new(-s.dot(eye), -u.dot(eye), f.dot(eye), 1.0),
+    ])
+  }
+}
+impl Mat3 {
+  /// A rotation, see [`Mat4::rotate`].
+  pub fn rotate(angle_degrees: f32, axis: Vec3) -> Self {
+    let Vec3 { x, y, z } = axis.normalize();
+    let (s, c) = angle_degrees.to_radians().sin_cos();
+    let t = 1.0 - c;
+    Self::from_cols([
+      Vec3::new(t * x * x + c, t * x * y + s * z, t * x * z - s * y),
+      Vec3::new(t * x * y - s * z, t * y * y + c, t * y * z + s * x),
+      Vec3::new(t * x * z + s * y, t * y * z - s * x, t * z * z + c),
+    ])
+  }
+}
+impl From<Mat3> for Mat4 {
+  /// Puts the matrix in the upper left, with the rest as the identity.
+  fn from(m: Mat3) -> Self {
+    let [a, b, c] = m.cols;
+    Self::from_cols([
+      a.extend(0.0),
+      b.extend(0.0),
+      c.extend(0.0),
+      Vec4::new(0.0, 0.0, 0.0, 1.0),
+    ])
+  }
+}
+
+/// A quaternion, for rotations.
+///
+/// Rotation quaternions have a length of 1. Multiplying `a * b` gives the
+/// rotation of `b` followed by `a`, the same as with matrices.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Quat {
+  pub x: f32,
+  pub y: f32,
+  pub z: f32,
+  pub w: f32,
+}
+unsafe impl Zeroable for Quat {}
+unsafe impl Pod for Quat {}
+impl Default for Quat {
+  fn default() -> Self {
+    Self::IDENTITY
+  }
+}
+impl Quat {
+  /// No rotation.
+  pub const IDENTITY: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+
+  pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+    Self { x, y, z, w }
+  }
+
+  /// A rotation of `angle_degrees` around `axis`, matching
+  /// [`Mat4::rotate`].
+  pub fn from_axis_angle(axis: Vec3, angle_degrees: f32) -> Self {
+    let (s, c) = (angle_degrees.to_radians() / 2.0).sin_cos();
+    let v = axis.normalize() * s;
+    Self::new(v.x, v.y, v.z, c)
+  }
+
+  fn to_vec4(self) -> Vec4 {
+    bytemuck::cast(self)
+  }
+
+  fn from_vec4(v: Vec4) -> Self {
+    bytemuck::cast(v)
+  }
+
+  pub fn dot(self, other: Self) -> f32 {
+    self.to_vec4().dot(other.to_vec4())
+  }
+
+  pub fn length(self) -> f32 {
+    self.to_vec4().length()
+  }
+
+  /// This quaternion scaled to a length of 1.
+  pub fn normalize(self) -> Self {
+    Self::from_vec4(self.to_vec4().normalize())
+  }
+
+  /// The opposite rotation (for a quaternion with a length of 1).
+  pub fn conjugate(self) -> Self {
+    Self::new(-self.x, -self.y, -self.z, self.w)
+  }
+
+  /// Spherical linear interpolation from `self` (at `t = 0.0`) to `other`
+  /// (at `t = 1.0`), taking the shorter way around.
+  pub fn slerp(self, other: Self, t: f32) -> Self {
+    let mut other = other.to_vec4();
+    let mut cos = self.dot(Self::from_vec4(other));
+    if cos < 0.0 {
+      other = -other;
+      cos = -cos;
+    }
+    let from = self.to_vec4();
+    // very close together, where the sine below gets too small to divide by.
+    if cos > 0.9995 {
+      return Self::from_vec4(from.lerp(other, t)).normalize();
+    }
+    let angle = cos.acos();
+    let sin = angle.sin();
+    let a = ((1.0 - t) * angle).sin() / sin;
+    let b = (t * angle).sin() / sin;
+    Self::from_vec4(from * a + other * b)
+  }
+
+  /// The rotation as a matrix.
+  pub fn to_mat3(self) -> Mat3 {
+    let Self { x, y, z, w } = self;
+    Mat3::from_cols([
+      Vec3::new(
+        1.0 - 2.0 * (y * y + z * z),
+        2.0 * (x * y + z * w),
+        2.0 * (x * z - y * w),
+      ),
+      Vec3::new(
+        2.0 * (x * y - z * w),
+        1.0 - 2.0 * (x * x + z * z),
+        2.0 * (y * z + x * w),
+      ),
+      Vec3::new(
+        2.0 * (x * z + y * w),
+        2.0 * (y * z - x * w),
+        1.0 - 2.0 * (x * x + y * y),
+      ),
+    ])
+  }
+
+  /// The rotation as a matrix.
+  pub fn to_mat4(self) -> Mat4 {
+    self.to_mat3().into()
+  }
+}
+impl Mul for Quat {
+  type Output = Self;
+  fn mul(self, o: Self) -> Self {
+    Self::new(
+      self.w * o.x + self.x * o.w + self.y * o.z - self.z * o.y,
+      self.w * o.y - self.x * o.z + self.y * o.w + self.z * o.x,
+      self.w * o.z + self.x * o.y - self.y * o.x + self.z * o.w,
+      self.w * o.w - self.x * o.x - self.y * o.y - self.z * o.z,
+    )
+  }
+}
+impl Mul<Vec3> for Quat {
+  type Output = Vec3;
+  /// Rotates the vector.
+  fn mul(self, v: Vec3) -> Vec3 {
+    let q = self * Self::new(v.x, v.y, v.z, 0.0) * self.conjugate();
+    Vec3::new(q.x, q.y, q.z)
+  }
+}
+
+/// Checks that every element is within `1e-5` of the expected value.
+#[cfg(test)]
+fn assert_mat4_eq(actual: Mat4, expected: [[f32; 4]; 4]) {
+  let actual: [[f32; 4]; 4] = actual.into();
+  let close = actual
+    .iter()
+    .flatten()
+    .zip(expected.iter().flatten())
+    .all(|(a, e)| (a - e).abs() < 1e-5);
+  assert!(close, "\n{:?}\n!=\n{:?}", actual, expected);
+}
+
+#[test]
+fn test_vector_ops() {
+  let a = Vec3::new(1.0, 2.0, 3.0);
+  let b = Vec3::new(4.0, 5.0, 6.0);
+  assert_eq!(a + b, Vec3::new(5.0, 7.0, 9.0));
+  assert_eq!(b - a, Vec3::splat(3.0));
+  assert_eq!(a * 2.0, Vec3::new(2.0, 4.0, 6.0));
+  assert_eq!(a * b, Vec3::new(4.0, 10.0, 18.0));
+  assert_eq!(-a, Vec3::new(-1.0, -2.0, -3.0));
+  assert_eq!(a.dot(b), 32.0);
+  assert_eq!(Vec3::X.cross(Vec3::Y), Vec3::Z);
+  assert_eq!(a.cross(b), Vec3::new(-3.0, 6.0, -3.0));
+  assert_eq!(Vec2::new(3.0, 4.0).length(), 5.0);
+  assert_eq!(Vec2::new(3.0, 4.0).normalize(), Vec2::new(0.6, 0.8));
+  assert_eq!(a.extend(1.0).truncate(), a);
+  assert_eq!(a[2], 3.0);
+  assert_eq!(<[f32; 4]>::from(Vec4::from([1.0, 2.0, 3.0, 4.0]))[3], 4.0);
+  assert_eq!(Vec4::splat(0.0).lerp(Vec4::splat(2.0), 0.25), Vec4::splat(0.5));
+  assert_eq!(core::mem::size_of::<Vec3>(), 12);
+  assert_eq!(core::mem::size_of::<Mat4>(), 64);
+}
+
+#[test]
+fn test_matrix_transforms() {
+  let v = Vec4::new(1.0, 2.0, 3.0, 1.0);
+  let t = Mat4::translate(Vec3::new(10.0, 20.0, 30.0));
+  assert_eq!(t * v, Vec4::new(11.0, 22.0, 33.0, 1.0));
+  // directions aren't translated.
+  assert_eq!(t * Vec4::new(1.0, 2.0, 3.0, 0.0), Vec4::new(1.0, 2.0, 3.0, 0.0));
+  let s = Mat4::scale(Vec3::new(2.0, 3.0, 4.0));
+  assert_eq!(s * v, Vec4::new(2.0, 6.0, 12.0, 1.0));
+  // scale first, then translate.
+  assert_eq!((t * s) * v, Vec4::new(12.0, 26.0, 42.0, 1.0));
+
+  #[rustfmt::skip]
+  assert_mat4_eq(Mat4::rotate(90.0, Vec3::Z), [
+    [0.0, 1.0, 0.0, 0.0],
+    [-1.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+  ]);
+  // the axis is normalized.
+  assert_mat4_eq(
+    Mat4::rotate(90.0, Vec3::new(0.0, 5.0, 0.0)),
+    Mat4::rotate(90.0, Vec3::Y).into(),
+  );
+  // 120 degrees around the diagonal cycles the axes.
+  let r = Mat4::rotate(120.0, Vec3::splat(1.0));
+  assert_mat4_eq(
+    r,
+    [
+      [0.0, 1.0, 0.0, 0.0],
+      [0.0, 0.0, 1.0, 0.0],
+      [1.0, 0.0, 0.0, 0.0],
+      [0.0, 0.0, 0.0, 1.0],
+    ],
+  );
+  assert_mat4_eq(
+    Mat4::rotate_xyz(90.0, 0.0, 90.0),
+    (Mat4::rotate(90.0, Vec3::Z) * Mat4::rotate(90.0, Vec3::X)).into(),
+  );
+}
+
+#[test]
+fn test_projections() {
+  // gluPerspective(90, 2, 1, 3)
+  #[rustfmt::skip]
+  assert_mat4_eq(Mat4::perspective(90.0, 2.0, 1.0, 3.0), [
+    [0.5, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, -2.0, -1.0],
+    [0.0, 0.0, -3.0, 0.0],
+  ]);
+  // a symmetric frustum is the same as a perspective.
+  assert_mat4_eq(
+    Mat4::frustum(-2.0, 2.0, -1.0, 1.0, 1.0, 3.0),
+    Mat4::perspective(90.0, 2.0, 1.0, 3.0).into(),
+  );
+  #[rustfmt::skip]
+  assert_mat4_eq(Mat4::frustum(0.0, 2.0, 0.0, 1.0, 1.0, 3.0), [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 2.0, 0.0, 0.0],
+    [1.0, 1.0, -2.0, -1.0],
+    [0.0, 0.0, -3.0, 0.0],
+  ]);
+  #[rustfmt::skip]
+  assert_mat4_eq(Mat4::ortho(0.0, 800.0, 0.0, 600.0, -1.0, 1.0), [
+    [2.0 / 800.0, 0.0, 0.0, 0.0],
+    [0.0, 2.0 / 600.0, 0.0, 0.0],
+    [0.0, 0.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0, 1.0],
+  ]);
+  // the near plane maps to -1 and the far plane to 1.
+  let p = Mat4::perspective(60.0, 1.0, 0.5, 10.0);
+  let near = p * Vec4::new(0.0, 0.0, -0.5, 1.0);
+  let far = p * Vec4::new(0.0, 0.0, -10.0, 1.0);
+  assert!((near.z / near.w + 1.0).abs() < 1e-5);
+  assert!((far.z / far.w - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_look_at() {
+  // the default camera.
+  assert_mat4_eq(
+    Mat4::look_at(Vec3::splat(0.0), -Vec3::Z, Vec3::Y),
+    Mat4::IDENTITY.into(),
+  );
+  // from +X, looking at the origin.
+  let view = Mat4::look_at(Vec3::new(5.0, 0.0, 0.0), Vec3::splat(0.0), Vec3::Y);
+  #[rustfmt::skip]
+  assert_mat4_eq(view, [
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [-1.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, -5.0, 1.0],
+  ]);
+  // the origin is 5 units in front of the camera.
+  assert_eq!(
+    view * Vec4::new(0.0, 0.0, 0.0, 1.0),
+    Vec4::new(0.0, 0.0, -5.0, 1.0)
+  );
+}
+
+#[test]
+fn test_inverse_transpose_determinant() {
+  #[rustfmt::skip]
+  let m = Mat4::from([
+    [2.0, 0.0, 0.0, 1.0],
+    [1.0, 3.0, 0.0, 0.0],
+    [0.0, 1.0, 4.0, 0.0],
+    [0.0, 0.0, 1.0, 5.0],
+  ]);
+  assert_eq!(m.transpose().get(0, 3), m.get(3, 0));
+  assert_eq!(m.transpose().transpose(), m);
+  assert_eq!(m.determinant(), 119.0);
+  assert_eq!(m.transpose().determinant(), 119.0);
+  assert_mat4_eq(m * m.inverse().unwrap(), Mat4::IDENTITY.into());
+  assert_mat4_eq(m.inverse().unwrap() * m, Mat4::IDENTITY.into());
+  assert_eq!(Mat4::scale(Vec3::new(1.0, 0.0, 1.0)).inverse(), None);
+
+  let t = Mat4::translate(Vec3::new(1.0, 2.0, 3.0));
+  assert_mat4_eq(
+    t.inverse().unwrap(),
+    Mat4::translate(Vec3::new(-1.0, -2.0, -3.0)).into(),
+  );
+
+  let m3 = Mat3::from([[1.0, 2.0, 3.0], [0.0, 1.0, 4.0], [5.0, 6.0, 0.0]]);
+  assert_eq!(m3.determinant(), 1.0);
+  assert_eq!(
+    m3.inverse().unwrap(),
+    Mat3::from([[-24.0, 18.0, 5.0], [20.0, -15.0, -4.0], [-5.0, 4.0, 1.0]])
+  );
+  assert_eq!(Mat4::IDENTITY.to_mat3(), Mat3::IDENTITY);
+}
+
+#[test]
+fn test_quaternions() {
+  let q = Quat::from_axis_angle(Vec3::new(1.0, 1.0, 0.0), 70.0);
+  assert_mat4_eq(
+    q.to_mat4(),
+    Mat4::rotate(70.0, Vec3::new(1.0, 1.0, 0.0)).into(),
+  );
+  let v = q * Vec3::new(0.0, 0.0, 1.0);
+  let expected = Mat3::rotate(70.0, Vec3::new(1.0, 1.0, 0.0)) * Vec3::Z;
+  assert!((v - expected).length() < 1e-5);
+
+  // combining matches the matrices.
+  let a = Quat::from_axis_angle(Vec3::X, 30.0);
+  let b = Quat::from_axis_angle(Vec3::Y, 45.0);
+  assert_mat4_eq((a * b).to_mat4(), (a.to_mat4() * b.to_mat4()).into());
+  assert_mat4_eq((a * a.conjugate()).to_mat4(), Mat4::IDENTITY.into());
+
+  let start = Quat::IDENTITY;
+  let end = Quat::from_axis_angle(Vec3::Z, 90.0);
+  let half = start.slerp(end, 0.5);
+  assert_mat4_eq(half.to_mat4(), Mat4::rotate(45.0, Vec3::Z).into());
+  assert!((half.length() - 1.0).abs() < 1e-6);
+  assert_eq!(start.slerp(end, 0.0), start);
+  assert!((start.slerp(end, 1.0).dot(end) - 1.0).abs() < 1e-6);
+  // the short way around, even from the negated quaternion.
+  let negated = Quat::new(-end.x, -end.y, -end.z, -end.w);
+  assert_mat4_eq(
+    start.slerp(negated, 0.5).to_mat4(),
+    Mat4::rotate(45.0, Vec3::Z).into(),
+  );
+  // nearly equal quaternions fall back to a normalized lerp.
+  let tiny = Quat::from_axis_angle(Vec3::Z, 0.01);
+  assert!((start.slerp(tiny, 0.5).length() - 1.0).abs() < 1e-6);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_math_types_as_uniforms() {
+  let (_ctx, gl) = match test_context() {
+    Some(it) => it,
+    None => return,
+  };
+  let program = gl
+    .create_shader_program(
+      ShaderEnum::Vertex,
+      "#version 450 core
+      uniform mat4 mvp;
+      uniform mat3 normal_matrix;
+      uniform vec3 light;
+      void main() {
+        gl_Position = mvp * vec4(normal_matrix * light, 1.0);
+      }",
+    )
+    .unwrap();
+  let uniforms = gl.get_program_uniforms(program);
+  let mvp = Mat4::perspective(45.0, 1.5, 0.1, 100.0)
+    * Mat4::translate(Vec3::new(1.0, 2.0, 3.0));
+  gl.program_uniform(&uniforms, "mvp", mvp).unwrap();
+  gl.program_uniform(&uniforms, "normal_matrix", Mat3::IDENTITY).unwrap();
+  gl.program_uniform(&uniforms, "light", Vec3::new(0.0, 1.0, 0.0)).unwrap();
+  assert!(gl.program_uniform(&uniforms, "light", Vec4::splat(0.0)).is_err());
+
+  let location = uniforms.get("mvp").unwrap().location;
+  let mut out = [[0.0_f32; 4]; 4];
+  unsafe {
+    gl.GetnUniformfv(
+      program.0.get(),
+      location,
+      core::mem::size_of_val(&out) as i32,
+      out.as_mut_ptr().cast(),
+    )
+  };
+  assert_eq!(out, <[[f32; 4]; 4]>::from(mvp));
+  gl.delete_program(program);
+  assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR);
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+const VERTEX_SHADER: &str = "#version 450 core
+  void main(void)
+  {
+    // Declare a hard-coded array of positions
+    const vec4 vertices[3] = vec4[3](
+      vec4(0.25, -0.25, 0.5, 1.0),
+      vec4(-0.25, -0.25, 0.5, 1.0),
+      vec4(0.25, 0.25, 0.5, 1.0));
+    // Index into our array using gl_VertexID
+    gl_Position = vertices[gl_VertexID];
+  }";
+
+const FRAGMENT_SHADER: &str = "#version 450 core
+  out vec4 color;
+  void main(void) {
+    color = vec4(0.0, 0.8, 1.0, 1.0);
+  }";
+
+/// Chapter 2: A single triangle, with positions hard-coded in the vertex
+/// shader.
+pub struct Ch02 {
+  the_vao: Option<VertexArrayID>,
+  the_program: ProgramID,
+}
+
+impl Ch02 {
+  /// The shaders used by this sample.
+  pub const SHADERS: &'static [(ShaderEnum, &'static str)] = &[
+    (ShaderEnum::Vertex, VERTEX_SHADER),
+    (ShaderEnum::Fragment, FRAGMENT_SHADER),
+  ];
+}
+
+impl App for Ch02 {
+  fn startup(gl: &GlFnsRusty) -> Self {
+    let the_vao = gl.create_vertex_arrays::<1>()[0];
+    assert!(the_vao.is_some());
+    gl.bind_vertex_array(the_vao);
+
+    let vertex_shader =
+      gl.create_compiled_shader(ShaderEnum::Vertex, VERTEX_SHADER).unwrap();
+
+    let frag_shader =
+      gl.create_compiled_shader(ShaderEnum::Fragment, FRAGMENT_SHADER).unwrap();
+
+    let the_program =
+      gl.create_linked_program(&[vertex_shader, frag_shader]).unwrap();
+    gl.delete_shader(vertex_shader);
+    gl.delete_shader(frag_shader);
+
+    gl.use_program(the_program);
+    gl.point_size(20.0);
+
+    Self { the_vao, the_program }
+  }
+
+  fn render(&mut self, gl: &GlFnsRusty, current_time: f64) {
+    let secs_f32 = current_time as f32;
+    let color = [secs_f32.sin() * 0.5, secs_f32.cos() * 0.5, 0.0, 1.0];
+    gl.clear_color_draw_buffer(0, color);
+
+    unsafe { gl.DrawArrays(GL_TRIANGLES, 0, 3) };
+  }
+
+  fn shutdown(&mut self, gl: &GlFnsRusty) {
+    gl.delete_program(self.the_program);
+    gl.delete_vertex_arrays([self.the_vao]);
+  }
+}
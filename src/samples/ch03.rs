@@ -0,0 +1,107 @@
+use super::*;
+
+const VERTEX_SHADER: &str = "#version 450 core
+  layout (location = 0) in vec4 offset;
+  layout (location = 1) in vec4 color;
+  out VS_OUT {
+    vec4 color;
+  } vs_out;
+  void main(void)
+  {
+    // Declare a hard-coded array of positions
+    const vec4 vertices[3] = vec4[3](
+      vec4(0.25, -0.25, 0.5, 1.0),
+      vec4(-0.25, -0.25, 0.5, 1.0),
+      vec4(0.25, 0.25, 0.5, 1.0));
+    // Index into our array using gl_VertexID
+    gl_Position = vertices[gl_VertexID] + offset;
+    // transfer the color input to the next shader stage.
+    vs_out.color = color;
+  }";
+
+const FRAGMENT_SHADER: &str = "#version 450 core
+  in VS_OUT {
+    vec4 color;
+  } fs_in;
+  out vec4 color;
+  void main(void) {
+    color = vec4(
+      sin(gl_FragCoord.x *0.25) * 0.5 + 0.5,
+      cos(gl_FragCoord.y *0.25) * 0.5 + 0.5,
+      cos(gl_FragCoord.x *0.15) * cos(gl_FragCoord.y * 0.15),
+      1.0
+    );
+  }";
+
+/// Chapter 3: A moving triangle, using vertex attributes for its offset and
+/// color, with a fragment shader pattern based on `gl_FragCoord`.
+pub struct Ch03 {
+  the_vao: Option<VertexArrayID>,
+  the_program: ProgramID,
+}
+
+impl Ch03 {
+  /// The shaders used by this sample.
+  pub const SHADERS: &'static [(ShaderEnum, &'static str)] = &[
+    (ShaderEnum::Vertex, VERTEX_SHADER),
+    (ShaderEnum::Fragment, FRAGMENT_SHADER),
+  ];
+}
+
+impl App for Ch03 {
+  fn startup(gl: &GlFnsRusty) -> Self {
+    let the_vao = gl.create_vertex_arrays::<1>()[0];
+    assert!(the_vao.is_some());
+    gl.bind_vertex_array(the_vao);
+
+    let vertex_shader =
+      gl.create_compiled_shader(ShaderEnum::Vertex, VERTEX_SHADER).unwrap();
+
+    let frag_shader =
+      gl.create_compiled_shader(ShaderEnum::Fragment, FRAGMENT_SHADER).unwrap();
+
+    let the_program = gl
+      .create_linked_program(&[
+        vertex_shader,
+        //tess_control,
+        //tess_eval,
+        frag_shader,
+      ])
+      .unwrap();
+    gl.delete_shader(vertex_shader);
+    gl.delete_shader(frag_shader);
+
+    gl.use_program(the_program);
+
+    gl.point_size(20.0);
+
+    //gl.polygon_mode(PolygonEnum::Line);
+
+    //unsafe {
+    //  // this is actually the default anyway, it's just a how-to reminder
+    //  gl.PatchParameteri(GL_PATCH_VERTICES, 3);
+    //}
+
+    Self { the_vao, the_program }
+  }
+
+  fn render(&mut self, gl: &GlFnsRusty, current_time: f64) {
+    let secs_f32 = current_time as f32;
+
+    let color = [secs_f32.sin() * 0.5, secs_f32.cos() * 0.5, 0.0, 1.0];
+    gl.clear_color_draw_buffer(0, color);
+
+    let offset = [secs_f32.sin() * 0.5, secs_f32.cos() * 0.6, 0.0, 0.0];
+    gl.vertex_attrib_4fv(0, offset);
+
+    let vs_color = [0.3, secs_f32.sin() * 0.5, secs_f32.cos() * 0.6, 1.0];
+    gl.vertex_attrib_4fv(1, vs_color);
+
+    unsafe { gl.DrawArrays(GL_TRIANGLES, 0, 3) };
+  }
+
+  fn shutdown(&mut self, gl: &GlFnsRusty) {
+    gl.delete_program(self.the_program);
+    gl.delete_vertex_arrays([self.the_vao]);
+  }
+}
@@ -0,0 +1,172 @@
+use super::*;
+
+/// An entry in the list of samples.
+#[derive(Clone, Copy)]
+pub struct Sample {
+  /// The short name used to pick the sample on the command line.
+  pub name: &'static str,
+  /// A one-line description of what the sample shows.
+  pub description: &'static str,
+  /// The GLSL source of each shader that the sample compiles.
+  pub shaders: &'static [(ShaderEnum, &'static str)],
+  /// Starts the sample.
+  pub start: fn(&GlFnsRusty) -> Box<dyn DynApp>,
+}
+impl Sample {
+  /// Makes an entry for the [`App`] given.
+  pub const fn new<A: App + 'static>(
+    name: &'static str, description: &'static str,
+    shaders: &'static [(ShaderEnum, &'static str)],
+  ) -> Self {
+    Self { name, description, shaders, start: start_boxed_app::<A> }
+  }
+}
+impl core::fmt::Debug for Sample {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("Sample")
+      .field("name", &self.name)
+      .field("description", &self.description)
+      .finish()
+  }
+}
+
+/// All the samples, in chapter order.
+pub const SAMPLES: &[Sample] = &[
+  Sample::new::<Ch02>(
+    "ch02",
+    "A triangle with hard-coded vertex positions.",
+    Ch02::SHADERS,
+  ),
+  Sample::new::<Ch03>(
+    "ch03",
+    "A moving triangle, with vertex attributes for offset and color.",
+    Ch03::SHADERS,
+  ),
+];
+
+/// Looks up a sample's index in [`SAMPLES`] by name.
+pub fn find_sample(name: &str) -> Option<usize> {
+  SAMPLES.iter().position(|sample| sample.name == name)
+}
+
+/// Runs the samples, one at a time.
+///
+/// Page Down switches to the next sample and Page Up switches to the
+/// previous one. The `current_time` that each sample sees starts over from 0
+/// whenever it's switched to.
+pub struct SampleBrowser {
+  index: usize,
+  current: Box<dyn DynApp>,
+  opt_sample_start: Option<f64>,
+}
+impl SampleBrowser {
+  /// Starts the browser on the sample at `index` within [`SAMPLES`].
+  ///
+  /// ## Panics
+  /// * If the index is out of bounds.
+  pub fn new(gl: &GlFnsRusty, index: usize) -> Self {
+    let current = Self::start_sample(gl, index);
+    Self { index, current, opt_sample_start: None }
+  }
+
+  /// The index of the currently running sample.
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  /// Shuts down the current sample and starts the one at `index`.
+  ///
+  /// The index wraps around the length of [`SAMPLES`].
+  pub fn switch_to(&mut self, gl: &GlFnsRusty, index: usize) {
+    self.current.shutdown(gl);
+    self.index = index % SAMPLES.len();
+    self.current = Self::start_sample(gl, self.index);
+    self.opt_sample_start = None;
+  }
+
+  fn start_sample(gl: &GlFnsRusty, index: usize) -> Box<dyn DynApp> {
+    let sample = &SAMPLES[index];
+    println!("Running `{}`: {}", sample.name, sample.description);
+    (sample.start)(gl)
+  }
+}
+impl App for SampleBrowser {
+  /// Starts on the first sample.
+  fn startup(gl: &GlFnsRusty) -> Self {
+    Self::new(gl, 0)
+  }
+
+  fn render(&mut self, gl: &GlFnsRusty, current_time: f64) {
+    let sample_start = *self.opt_sample_start.get_or_insert(current_time);
+    self.current.render(gl, current_time - sample_start);
+  }
+
+  fn shutdown(&mut self, gl: &GlFnsRusty) {
+    self.current.shutdown(gl);
+  }
+
+  fn on_resize(&mut self, gl: &GlFnsRusty, width: i32, height: i32) {
+    self.current.on_resize(gl, width, height);
+  }
+
+  fn on_key(&mut self, gl: &GlFnsRusty, key: Key, pressed: bool) {
+    match key {
+      Key::PageDown if pressed => self.switch_to(gl, self.index + 1),
+      Key::PageUp if pressed => {
+        self.switch_to(gl, self.index + SAMPLES.len() - 1)
+      }
+      _ => self.current.on_key(gl, key, pressed),
+    }
+  }
+}
+
+#[test]
+fn test_sample_names_are_unique() {
+  for (i, sample) in SAMPLES.iter().enumerate() {
+    assert_eq!(find_sample(sample.name), Some(i), "{:?}", sample);
+  }
+}
+
+#[cfg(feature = "naga")]
+#[test]
+fn test_sample_shaders_are_valid_glsl() {
+  for sample in SAMPLES.iter() {
+    for (t, src) in sample.shaders.iter() {
+      match validate_glsl(*t, src) {
+        Ok(GlslValidation::Validated) => (),
+        Ok(GlslValidation::Skipped) => {
+          println!("{} {:?} shader: skipped by naga", sample.name, t)
+        }
+        Err(diagnostics) => {
+          panic!("{} {:?} shader: {:#?}", sample.name, t, diagnostics)
+        }
+      }
+    }
+  }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+#[allow(non_snake_case)]
+fn test_SampleBrowser_runs_every_sample() {
+  let ctx = match HeadlessContext::new(64, 64) {
+    Ok(ctx) => ctx,
+    Err(e) => {
+      println!("skipping, no headless EGL available: {}", e);
+      return;
+    }
+  };
+  let gl = ctx.load_gl_functions().unwrap();
+  let mut browser = SampleBrowser::new(&gl, 0);
+  for (i, sample) in SAMPLES.iter().enumerate() {
+    assert_eq!(browser.index(), i);
+    browser.render(&gl, 0.5);
+    assert_eq!(unsafe { gl.GetError() }, GL_NO_ERROR, "{:?}", sample);
+    browser.on_key(&gl, Key::PageDown, true);
+    browser.on_key(&gl, Key::PageDown, false);
+  }
+  assert_eq!(browser.index(), 0);
+  browser.on_key(&gl, Key::PageUp, true);
+  assert_eq!(browser.index(), SAMPLES.len() - 1);
+  browser.shutdown(&gl);
+}
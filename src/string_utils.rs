@@ -26,3 +26,16 @@ pub fn min_alloc_lossy_into_string(bytes: Vec<u8>) -> String {
     Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
   }
 }
+
+#[test]
+fn test_gather_null_terminated_bytes() {
+  let bytes = b"GL_ARB_gl_spirv\0junk";
+  let v = unsafe { gather_null_terminated_bytes(bytes.as_ptr()) };
+  assert_eq!(&v[..], b"GL_ARB_gl_spirv");
+}
+
+#[test]
+fn test_min_alloc_lossy_into_string() {
+  assert_eq!(min_alloc_lossy_into_string(b"abc".to_vec()), "abc");
+  assert_eq!(min_alloc_lossy_into_string(vec![b'a', 0xFF, b'c']), "a�c");
+}
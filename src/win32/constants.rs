@@ -187,6 +187,45 @@ pub const WM_CREATE_HALT_CREATION: LRESULT = -1 as _;
 /// See [WM_SIZE](https://docs.microsoft.com/en-us/windows/win32/winmsg/wm-size)
 pub const WM_SIZE: u32 = 0x0005;
 
+/// Posted to the window with the keyboard focus when a nonsystem key is
+/// pressed (a key pressed while the ALT key is *not* pressed).
+///
+/// * `w_param`: The virtual-key code of the key (eg: [`VK_ESCAPE`]).
+/// * `l_param`: The repeat count, scan code, and other flags. Bit 30 is set if
+///   the key was already down (meaning that this is an auto-repeat).
+/// * **Return:** if you process this message return 0.
+///
+/// See [WM_KEYDOWN](https://docs.microsoft.com/en-us/windows/win32/inputdev/wm-keydown)
+pub const WM_KEYDOWN: u32 = 0x0100;
+
+/// Posted to the window with the keyboard focus when a nonsystem key is
+/// released.
+///
+/// * `w_param`: The virtual-key code of the key (eg: [`VK_ESCAPE`]).
+/// * `l_param`: The repeat count, scan code, and other flags.
+/// * **Return:** if you process this message return 0.
+///
+/// See [WM_KEYUP](https://docs.microsoft.com/en-us/windows/win32/inputdev/wm-keyup)
+pub const WM_KEYUP: u32 = 0x0101;
+
+// Virtual-Key Codes, see
+// https://docs.microsoft.com/en-us/windows/win32/inputdev/virtual-key-codes
+//
+// Note: The `0`-`9` and `A`-`Z` keys use the same codes as ASCII.
+
+pub const VK_TAB: WPARAM = 0x09;
+pub const VK_RETURN: WPARAM = 0x0D;
+pub const VK_ESCAPE: WPARAM = 0x1B;
+pub const VK_SPACE: WPARAM = 0x20;
+pub const VK_PRIOR: WPARAM = 0x21;
+pub const VK_NEXT: WPARAM = 0x22;
+pub const VK_LEFT: WPARAM = 0x25;
+pub const VK_UP: WPARAM = 0x26;
+pub const VK_RIGHT: WPARAM = 0x27;
+pub const VK_DOWN: WPARAM = 0x28;
+pub const VK_F1: WPARAM = 0x70;
+pub const VK_F24: WPARAM = 0x87;
+
 /// Message is sent to all pop-up windows when some other window is maximized.
 pub const SIZE_MAXHIDE: WPARAM = 4;
 /// The window has been maximized.
@@ -23,6 +23,13 @@ extern "system" {
     hWndParent: HWND, hMenu: HMENU, hInstance: HINSTANCE, lpParam: LPVOID,
   ) -> HWND;
 
+  /// [`AdjustWindowRectEx`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-adjustwindowrectex)
+  ///
+  /// Grows a client area rect into the window rect needed for it.
+  pub fn AdjustWindowRectEx(
+    lpRect: &mut RECT, dwStyle: DWORD, bMenu: BOOL, dwExStyle: DWORD,
+  ) -> BOOL;
+
   /// [`ShowWindow`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-showwindow)
   pub fn ShowWindow(hWnd: HWND, nCmdShow: c_int) -> BOOL;
 
@@ -0,0 +1,334 @@
+use super::*;
+use crate::{GlContext, Key, WindowEvent};
+use utf16_lit::utf16_null;
+
+const WGL_WINDOW_CLASS_NAME: &[u16] = &utf16_null!("SuperBible7WglWindowClass");
+
+/// The part of a [`WglWindow`] that the window procedure writes to.
+struct WglWindowState {
+  events: Vec<WindowEvent>,
+  width: i32,
+  height: i32,
+}
+
+/// A Win32 window with a GL 4.6 core context attached.
+///
+/// This does the full WGL bootstrap: a dummy context to get the
+/// [`WglExtFns`], then window class registration, `choose_pixel_format_arb`,
+/// and `create_context_attribs_arb`. The new context is made current in the
+/// thread that created the window.
+///
+/// The window's message queue is only pumped when you call
+/// [`poll_events`](Self::poll_events), which you should do once per frame.
+///
+/// All resources are released on drop. Any
+/// [`GlFnsRusty`](crate::GlFnsRusty) loaded from this window must not be used
+/// after that.
+pub struct WglWindow {
+  wgl: WglExtFns,
+  hinstance: HINSTANCE,
+  atom: ATOM,
+  hwnd: HWND,
+  hdc: HDC,
+  hglrc: HGLRC,
+  opengl32: HMODULE,
+  state: *mut WglWindowState,
+}
+
+impl WglWindow {
+  /// Opens a new window with the title and client size given, and makes a GL
+  /// context for it current in this thread.
+  ///
+  /// In debug builds the context is also a debug context.
+  pub fn new(title: &str, width: i32, height: i32) -> Win32Result<Self> {
+    let wgl = WglExtFns::new()?;
+    let hinstance = HINSTANCE(unsafe { GetModuleHandleW(null()).0 });
+    // Note: if there's an error part way through, the `Drop` impl will clean
+    // up whatever we've made so far.
+    let mut out = Self {
+      wgl,
+      hinstance,
+      atom: 0,
+      hwnd: HWND::null(),
+      hdc: HDC::null(),
+      hglrc: HGLRC::null(),
+      opengl32: HMODULE::null(),
+      state: Box::into_raw(Box::new(WglWindowState {
+        events: Vec::new(),
+        width,
+        height,
+      })),
+    };
+
+    let wc = WNDCLASSEXW {
+      hInstance: hinstance,
+      lpszClassName: WGL_WINDOW_CLASS_NAME.as_ptr(),
+      lpfnWndProc: Some(wgl_window_procedure),
+      hCursor: unsafe { LoadCursorW(HINSTANCE::null(), IDC_ARROW) },
+      style: CS_OWNDC | CS_HREDRAW | CS_VREDRAW,
+      ..WNDCLASSEXW::default()
+    };
+    out.atom = unsafe { RegisterClassExW(&wc) };
+    if out.atom == 0 {
+      return Err(get_last_error());
+    }
+
+    // `CreateWindowExW` takes the outer size, so we grow the client size by
+    // the borders and title bar of the style we use.
+    let ex_style = WS_EX_APPWINDOW | WS_EX_OVERLAPPEDWINDOW;
+    let style =
+      WS_VISIBLE | WS_OVERLAPPEDWINDOW | WS_CLIPCHILDREN | WS_CLIPSIBLINGS;
+    let mut rect = RECT { left: 0, top: 0, right: width, bottom: height };
+    if !bool::from(unsafe {
+      AdjustWindowRectEx(&mut rect, style, FALSE, ex_style)
+    }) {
+      return Err(get_last_error());
+    }
+    let title_null = wide_null(title);
+    out.hwnd = unsafe {
+      CreateWindowExW(
+        ex_style,
+        out.atom as LPCWSTR,
+        title_null.as_ptr(),
+        style,
+        50,
+        50,
+        rect.right - rect.left,
+        rect.bottom - rect.top,
+        HWND::null(),
+        HMENU::null(),
+        hinstance,
+        out.state.cast(),
+      )
+    };
+    if out.hwnd.is_null() {
+      return Err(get_last_error());
+    }
+    out.hdc = unsafe { get_dc(out.hwnd) }.ok_or_else(get_last_error)?;
+
+    let ext_string =
+      out.wgl.get_extensions_string_arb(out.hdc).unwrap_or_default();
+
+    // base criteria
+    let mut int_attribs = vec![
+      [WGL_DRAW_TO_WINDOW_ARB, true as _],
+      [WGL_SUPPORT_OPENGL_ARB, true as _],
+      [WGL_DOUBLE_BUFFER_ARB, true as _],
+      [WGL_PIXEL_TYPE_ARB, WGL_TYPE_RGBA_ARB],
+      [WGL_COLOR_BITS_ARB, 32],
+      [WGL_DEPTH_BITS_ARB, 24],
+      [WGL_STENCIL_BITS_ARB, 8],
+    ];
+    if ext_string.contains("WGL_EXT_framebuffer_sRGB") {
+      int_attribs.push([WGL_FRAMEBUFFER_SRGB_CAPABLE_EXT, true as _]);
+    };
+    if ext_string.contains("WGL_ARB_multisample") {
+      int_attribs.push([WGL_SAMPLE_BUFFERS_ARB, 1]);
+    };
+    int_attribs.push([0, 0]);
+    let mut pf_index_buffer = [0_i32];
+    let pf_index = *out
+      .wgl
+      .choose_pixel_format_arb(
+        out.hdc,
+        &int_attribs,
+        &[],
+        &mut pf_index_buffer,
+      )?
+      .first()
+      .ok_or(Win32Error::APP)?;
+    let pfd = describe_pixel_format(out.hdc, pf_index)?;
+    set_pixel_format(out.hdc, pf_index, &pfd)?;
+
+    let flags =
+      if cfg!(debug_assertions) { WGL_CONTEXT_DEBUG_BIT_ARB } else { 0 };
+    let context_attribs_list = &[
+      [WGL_CONTEXT_MAJOR_VERSION_ARB, 4],
+      [WGL_CONTEXT_MINOR_VERSION_ARB, 6],
+      [WGL_CONTEXT_PROFILE_MASK_ARB, WGL_CONTEXT_CORE_PROFILE_BIT_ARB],
+      [WGL_CONTEXT_FLAGS_ARB, flags],
+      [0, 0],
+    ];
+    out.hglrc = out.wgl.create_context_attribs_arb(
+      out.hdc,
+      HGLRC::null(),
+      context_attribs_list,
+    )?;
+    unsafe { wgl_make_current(out.hdc, out.hglrc) }?;
+    out.opengl32 = load_library("opengl32.dll")?;
+    Ok(out)
+  }
+
+  /// Pumps the window's message queue, returning all the events that
+  /// happened since the last call.
+  pub fn poll_events(&self) -> Vec<WindowEvent> {
+    let mut msg = MSG::default();
+    while unsafe {
+      PeekMessageW(&mut msg, HWND::null(), 0, 0, PM_REMOVE).into()
+    } {
+      unsafe {
+        TranslateMessage(&msg);
+        DispatchMessageW(&msg);
+      }
+    }
+    // Safety: the window procedure only runs during message dispatch, and
+    // we're done with that.
+    core::mem::take(unsafe { &mut (*self.state).events })
+  }
+
+  /// The WGL extension functions used to make this window's context.
+  pub fn wgl(&self) -> &WglExtFns {
+    &self.wgl
+  }
+
+  /// The window handle.
+  pub fn hwnd(&self) -> HWND {
+    self.hwnd
+  }
+
+  /// The device context of the window.
+  pub fn hdc(&self) -> HDC {
+    self.hdc
+  }
+}
+
+impl GlContext for WglWindow {
+  type Error = Win32Error;
+
+  fn make_current(&self) -> Win32Result<()> {
+    unsafe { wgl_make_current(self.hdc, self.hglrc) }
+  }
+
+  fn swap_buffers(&self) -> Win32Result<()> {
+    if unsafe { SwapBuffers(self.hdc) }.into() {
+      Ok(())
+    } else {
+      Err(get_last_error())
+    }
+  }
+
+  /// Uses `wglGetProcAddress`, falling back to `GetProcAddress` on
+  /// "opengl32.dll" for the GL 1.1 functions.
+  fn get_proc_address(&self, name: &[u8]) -> Option<NonNull<c_void>> {
+    match name.last() {
+      Some(0) => wgl_get_proc_address(name).ok().or_else(|| {
+        NonNull::new(unsafe { GetProcAddress(self.opengl32, name.as_ptr()) })
+      }),
+      _ => None,
+    }
+  }
+
+  fn size(&self) -> (i32, i32) {
+    let state = unsafe { &*self.state };
+    (state.width, state.height)
+  }
+
+  /// ## Failure
+  /// * Needs `WGL_EXT_swap_control` (and `WGL_EXT_swap_control_tear` for
+  ///   negative intervals).
+  fn set_swap_interval(&self, interval: i32) -> Win32Result<()> {
+    // Safety: our context is current whenever the window is in use.
+    unsafe { self.wgl.set_swap_interval(interval) }
+  }
+}
+
+impl Drop for WglWindow {
+  fn drop(&mut self) {
+    if self.hglrc.is_not_null() {
+      let _i_dont_care =
+        unsafe { wgl_make_current(HDC::null(), HGLRC::null()) };
+      let _i_dont_care = unsafe { wgl_delete_context(self.hglrc) };
+    }
+    if self.hdc.is_not_null() {
+      let _i_dont_care = unsafe { release_dc(self.hwnd, self.hdc) };
+    }
+    if self.hwnd.is_not_null() {
+      let _i_dont_care = unsafe { destroy_window(self.hwnd) };
+    }
+    if self.atom != 0 {
+      let _i_dont_care =
+        unsafe { unregister_class_by_atom(self.atom, self.hinstance) };
+    }
+    if self.opengl32.is_not_null() {
+      unsafe { FreeLibrary(self.opengl32) };
+    }
+    // Safety: the window is destroyed, so the window procedure can't see the
+    // state any more.
+    drop(unsafe { Box::from_raw(self.state) });
+  }
+}
+
+/// The window procedure of every [`WglWindow`].
+///
+/// The userdata pointer of the window is the window's `WglWindowState`.
+unsafe extern "system" fn wgl_window_procedure(
+  hwnd: HWND, msg: UINT, w_param: WPARAM, l_param: LPARAM,
+) -> LRESULT {
+  match msg {
+    WM_NCCREATE => {
+      let createstruct = l_param as *mut CREATESTRUCTW;
+      if createstruct.is_null() {
+        return WM_NCCREATE_HALT_CREATION;
+      }
+      let ptr = (*createstruct).lpCreateParams as *mut WglWindowState;
+      if set_window_userdata::<WglWindowState>(hwnd, ptr).is_err() {
+        return WM_NCCREATE_HALT_CREATION;
+      }
+      return DefWindowProcW(hwnd, msg, w_param, l_param);
+    }
+    WM_SIZE => match get_window_userdata::<WglWindowState>(hwnd) {
+      Ok(ptr) if !ptr.is_null() => {
+        let width = i32::from(loword(l_param as _));
+        let height = i32::from(hiword(l_param as _));
+        (*ptr).width = width;
+        (*ptr).height = height;
+        (*ptr).events.push(WindowEvent::Resized(width, height));
+      }
+      _otherwise => (),
+    },
+    WM_KEYDOWN | WM_KEYUP => {
+      match get_window_userdata::<WglWindowState>(hwnd) {
+        Ok(ptr) if !ptr.is_null() => {
+          let key = key_from_virtual_key_code(w_param);
+          (*ptr).events.push(if msg == WM_KEYDOWN {
+            WindowEvent::KeyDown(key)
+          } else {
+            WindowEvent::KeyUp(key)
+          });
+        }
+        _otherwise => (),
+      }
+    }
+    WM_PAINT => {
+      // We draw every frame anyway, so just mark the window as painted.
+      ValidateRect(hwnd, None);
+    }
+    WM_CLOSE => match get_window_userdata::<WglWindowState>(hwnd) {
+      Ok(ptr) if !ptr.is_null() => {
+        (*ptr).events.push(WindowEvent::CloseRequested);
+      }
+      _otherwise => (),
+    },
+    _ => return DefWindowProcW(hwnd, msg, w_param, l_param),
+  }
+  0
+}
+
+/// Converts a Win32 virtual-key code into a [`Key`].
+fn key_from_virtual_key_code(vk: WPARAM) -> Key {
+  match vk {
+    0x30..=0x39 | 0x41..=0x5A => Key::Char(vk as u8 as char),
+    VK_F1..=VK_F24 => Key::F((vk - VK_F1 + 1) as u8),
+    VK_ESCAPE => Key::Escape,
+    VK_SPACE => Key::Space,
+    VK_RETURN => Key::Enter,
+    VK_TAB => Key::Tab,
+    VK_LEFT => Key::Left,
+    VK_RIGHT => Key::Right,
+    VK_UP => Key::Up,
+    VK_DOWN => Key::Down,
+    VK_PRIOR => Key::PageUp,
+    VK_NEXT => Key::PageDown,
+    other => Key::Other(other as u32),
+  }
+}